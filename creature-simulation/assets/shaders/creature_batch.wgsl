@@ -0,0 +1,45 @@
+// Creatures drawn in per-species batches (see creature_batch.rs): a unit quad instanced once per
+// creature, with its axes, position, tint and the layer of the frame texture array it shows
+#import bevy_sprite::mesh2d_view_bindings::view
+
+#ifdef TONEMAP_IN_SHADER
+#import bevy_core_pipeline::tonemapping
+#endif
+
+@group(1) @binding(0) var frames: texture_2d_array<f32>;
+@group(1) @binding(1) var frames_sampler: sampler;
+
+struct Vertex {
+    @location(0) position: vec3<f32>, // Corner of the unit quad
+    @location(2) uv: vec2<f32>,
+    @location(3) axes: vec4<f32>, // World x axis in xy, y axis in zw, scaled by the creature's size
+    @location(4) translation: vec4<f32>, // w is the frame
+    @location(5) tint: vec4<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) clip_position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+    @location(1) tint: vec4<f32>,
+    @location(2) @interpolate(flat) frame: u32,
+};
+
+@vertex
+fn vertex(vertex: Vertex) -> VertexOutput {
+    var out: VertexOutput;
+    let world_xy = vertex.translation.xy + vertex.axes.xy * vertex.position.x + vertex.axes.zw * vertex.position.y;
+    out.clip_position = view.clip_from_world * vec4<f32>(world_xy, vertex.translation.z, 1.0);
+    out.uv = vertex.uv;
+    out.tint = vertex.tint;
+    out.frame = u32(vertex.translation.w);
+    return out;
+}
+
+@fragment
+fn fragment(in: VertexOutput) -> @location(0) vec4<f32> {
+    var color = textureSample(frames, frames_sampler, in.uv, in.frame) * in.tint;
+#ifdef TONEMAP_IN_SHADER
+    color = tonemapping::tone_mapping(color, view.color_grading);
+#endif
+    return color;
+}
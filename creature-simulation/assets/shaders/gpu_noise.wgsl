@@ -0,0 +1,247 @@
+// Climate noise of world tiles, a port of WorldGenerator::sample_climate and the Perlin noise of
+// the noise crate (see gpu_noise.rs). Each invocation fills the elevation, temperature and
+// moisture of one tile.
+
+struct Params {
+    elevation_scale: f32,
+    temperature_scale: f32,
+    moisture_scale: f32,
+    sea_level_shift: f32, // SEA_LEVEL - GenerationParams::sea_level
+    world_size: f32,
+    topology: u32, // 0 = flat, 1 = cylinder, 2 = torus
+    count: u32,
+    _padding: u32,
+}
+
+const ELEVATION: u32 = 0u;
+const TEMPERATURE: u32 = 1u;
+const MOISTURE: u32 = 2u;
+const TAU: f32 = 6.283185307179586;
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> permutations: array<u32>; // 256 entries per noise, in the order above
+@group(0) @binding(2) var<storage, read> tiles: array<vec2<f32>>;
+@group(0) @binding(3) var<storage, read_write> climate: array<f32>; // Three per tile
+
+fn perm(noise: u32, index: u32) -> u32 {
+    return permutations[noise * 256u + (index & 255u)];
+}
+
+fn wrap(corner: i32) -> u32 {
+    return bitcast<u32>(corner) & 255u;
+}
+
+fn quintic(t: f32) -> f32 {
+    let x = clamp(t, 0.0, 1.0);
+    return x * x * x * (x * (x * 6.0 - 15.0) + 10.0);
+}
+
+fn lerp(a: f32, b: f32, alpha: f32) -> f32 {
+    return b * alpha + a * (1.0 - alpha);
+}
+
+fn gradient2(hash: u32, p: vec2<f32>) -> f32 {
+    switch hash & 3u {
+        case 0u: { return p.x + p.y; }
+        case 1u: { return -p.x + p.y; }
+        case 2u: { return p.x - p.y; }
+        default: { return -p.x - p.y; }
+    }
+}
+
+fn gradient3(hash: u32, p: vec3<f32>) -> f32 {
+    switch hash & 15u {
+        case 0u, 12u: { return p.x + p.y; }
+        case 1u, 13u: { return -p.x + p.y; }
+        case 2u: { return p.x - p.y; }
+        case 3u: { return -p.x - p.y; }
+        case 4u: { return p.x + p.z; }
+        case 5u: { return -p.x + p.z; }
+        case 6u: { return p.x - p.z; }
+        case 7u: { return -p.x - p.z; }
+        case 8u: { return p.y + p.z; }
+        case 9u, 14u: { return -p.y + p.z; }
+        case 10u: { return p.y - p.z; }
+        default: { return -p.y - p.z; }
+    }
+}
+
+// Same table as the noise crate, including its repeated entries
+fn gradient4(hash: u32, p: vec4<f32>) -> f32 {
+    switch hash & 31u {
+        case 0u, 28u: { return p.x + p.y + p.z; }
+        case 1u: { return -p.x + p.y + p.z; }
+        case 2u: { return p.x - p.y + p.z; }
+        case 3u: { return p.x + p.y - p.z; }
+        case 4u: { return -p.x + p.y - p.z; }
+        case 5u, 6u: { return p.x - p.y - p.z; }
+        case 7u, 29u: { return p.x + p.y + p.w; }
+        case 8u: { return -p.x + p.y + p.w; }
+        case 9u: { return p.x - p.y + p.w; }
+        case 10u, 11u, 12u: { return p.x + p.y - p.w; }
+        case 13u: { return -p.x - p.y - p.w; }
+        case 14u, 30u: { return p.x + p.z + p.w; }
+        case 15u: { return -p.x + p.z + p.w; }
+        case 16u: { return p.x - p.z + p.w; }
+        case 17u, 18u, 19u: { return p.x + p.z - p.w; }
+        case 20u: { return -p.x - p.z - p.w; }
+        case 21u, 31u: { return p.y + p.z + p.w; }
+        case 22u: { return -p.y + p.z + p.w; }
+        case 23u: { return p.y - p.z + p.w; }
+        case 24u, 26u: { return p.y - p.z - p.w; }
+        default: { return -p.y - p.z - p.w; }
+    }
+}
+
+fn perlin2(noise: u32, point: vec2<f32>) -> f32 {
+    let floored = floor(point);
+    let corner = vec2<i32>(floored);
+    let d = point - floored;
+    let x0 = perm(noise, wrap(corner.x));
+    let x1 = perm(noise, wrap(corner.x + 1));
+    let y0 = wrap(corner.y);
+    let y1 = wrap(corner.y + 1);
+
+    let g00 = gradient2(perm(noise, x0 ^ y0), d);
+    let g10 = gradient2(perm(noise, x1 ^ y0), d - vec2(1.0, 0.0));
+    let g01 = gradient2(perm(noise, x0 ^ y1), d - vec2(0.0, 1.0));
+    let g11 = gradient2(perm(noise, x1 ^ y1), d - vec2(1.0, 1.0));
+
+    let cx = quintic(d.x);
+    let cy = quintic(d.y);
+    let result = lerp(lerp(g00, g01, cy), lerp(g10, g11, cy), cx) * 1.4142135623730951;
+    return clamp(result, -1.0, 1.0);
+}
+
+fn hash3(noise: u32, corner: vec3<i32>) -> u32 {
+    return perm(noise, perm(noise, perm(noise, wrap(corner.x)) ^ wrap(corner.y)) ^ wrap(corner.z));
+}
+
+fn corner3(noise: u32, corner: vec3<i32>, d: vec3<f32>, offset: vec3<i32>) -> f32 {
+    return gradient3(hash3(noise, corner + offset), d - vec3<f32>(offset));
+}
+
+fn perlin3(noise: u32, point: vec3<f32>) -> f32 {
+    let floored = floor(point);
+    let c = vec3<i32>(floored);
+    let d = point - floored;
+
+    let g000 = corner3(noise, c, d, vec3(0, 0, 0));
+    let g100 = corner3(noise, c, d, vec3(1, 0, 0));
+    let g010 = corner3(noise, c, d, vec3(0, 1, 0));
+    let g110 = corner3(noise, c, d, vec3(1, 1, 0));
+    let g001 = corner3(noise, c, d, vec3(0, 0, 1));
+    let g101 = corner3(noise, c, d, vec3(1, 0, 1));
+    let g011 = corner3(noise, c, d, vec3(0, 1, 1));
+    let g111 = corner3(noise, c, d, vec3(1, 1, 1));
+
+    let cx = quintic(d.x);
+    let cy = quintic(d.y);
+    let cz = quintic(d.z);
+    let result = lerp(
+        lerp(lerp(g000, g001, cz), lerp(g010, g011, cz), cy),
+        lerp(lerp(g100, g101, cz), lerp(g110, g111, cz), cy),
+        cx,
+    ) * 1.1547005383792515;
+    return clamp(result, -1.0, 1.0);
+}
+
+fn hash4(noise: u32, corner: vec4<i32>) -> u32 {
+    let xyz = perm(noise, perm(noise, perm(noise, wrap(corner.x)) ^ wrap(corner.y)) ^ wrap(corner.z));
+    return perm(noise, xyz ^ wrap(corner.w));
+}
+
+fn corner4(noise: u32, corner: vec4<i32>, d: vec4<f32>, offset: vec4<i32>) -> f32 {
+    return gradient4(hash4(noise, corner + offset), d - vec4<f32>(offset));
+}
+
+fn perlin4(noise: u32, point: vec4<f32>) -> f32 {
+    let floored = floor(point);
+    let c = vec4<i32>(floored);
+    let d = point - floored;
+
+    let g0000 = corner4(noise, c, d, vec4(0, 0, 0, 0));
+    let g1000 = corner4(noise, c, d, vec4(1, 0, 0, 0));
+    let g0100 = corner4(noise, c, d, vec4(0, 1, 0, 0));
+    let g1100 = corner4(noise, c, d, vec4(1, 1, 0, 0));
+    let g0010 = corner4(noise, c, d, vec4(0, 0, 1, 0));
+    let g1010 = corner4(noise, c, d, vec4(1, 0, 1, 0));
+    let g0110 = corner4(noise, c, d, vec4(0, 1, 1, 0));
+    let g1110 = corner4(noise, c, d, vec4(1, 1, 1, 0));
+    let g0001 = corner4(noise, c, d, vec4(0, 0, 0, 1));
+    let g1001 = corner4(noise, c, d, vec4(1, 0, 0, 1));
+    let g0101 = corner4(noise, c, d, vec4(0, 1, 0, 1));
+    let g1101 = corner4(noise, c, d, vec4(1, 1, 0, 1));
+    let g0011 = corner4(noise, c, d, vec4(0, 0, 1, 1));
+    let g1011 = corner4(noise, c, d, vec4(1, 0, 1, 1));
+    let g0111 = corner4(noise, c, d, vec4(0, 1, 1, 1));
+    let g1111 = corner4(noise, c, d, vec4(1, 1, 1, 1));
+
+    let cx = quintic(d.x);
+    let cy = quintic(d.y);
+    let cz = quintic(d.z);
+    let cw = quintic(d.w);
+    let result = lerp(
+        lerp(
+            lerp(lerp(g0000, g0001, cw), lerp(g0010, g0011, cw), cz),
+            lerp(lerp(g0100, g0101, cw), lerp(g0110, g0111, cw), cz),
+            cy,
+        ),
+        lerp(
+            lerp(lerp(g1000, g1001, cw), lerp(g1010, g1011, cw), cz),
+            lerp(lerp(g1100, g1101, cw), lerp(g1110, g1111, cw), cz),
+            cy,
+        ),
+        cx,
+    );
+    return clamp(result, -1.0, 1.0);
+}
+
+// A wrapped axis is sampled around a circle as long as the flat map is wide (see sample_noise)
+fn around(t: f32, radius: f32) -> vec2<f32> {
+    let angle = t / params.world_size * TAU;
+    return vec2(radius * cos(angle), radius * sin(angle));
+}
+
+fn sample(noise: u32, tile: vec2<f32>, frequency: f32) -> f32 {
+    let radius = params.world_size * frequency / TAU;
+    switch params.topology {
+        case 1u: {
+            return perlin3(noise, vec3(around(tile.x, radius), tile.y * frequency));
+        }
+        case 2u: {
+            return perlin4(noise, vec4(around(tile.x, radius), around(tile.y, radius)));
+        }
+        default: {
+            return perlin2(noise, tile * frequency);
+        }
+    }
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let index = id.x;
+    if index >= params.count {
+        return;
+    }
+    let tile = tiles[index];
+
+    var elevation = 0.0;
+    var amplitude = 1.0;
+    var frequency = params.elevation_scale;
+    for (var octave = 0; octave < 2; octave++) {
+        elevation += sample(ELEVATION, tile, frequency) * amplitude;
+        amplitude *= 0.5;
+        frequency *= 2.0;
+    }
+    elevation = clamp((elevation + 1.0) / 2.0 + params.sea_level_shift, 0.0, 1.0);
+
+    let latitude = (tile.y + 0.5) / params.world_size * 2.0 - 1.0;
+    let temperature = clamp(1.0 - abs(latitude) + sample(TEMPERATURE, tile, params.temperature_scale) * 0.3, 0.0, 1.0);
+
+    let moisture = (sample(MOISTURE, tile, params.moisture_scale) + 1.0) / 2.0;
+
+    climate[index * 3u] = elevation;
+    climate[index * 3u + 1u] = temperature;
+    climate[index * 3u + 2u] = moisture;
+}
@@ -0,0 +1,157 @@
+use bevy::prelude::*;
+use std::time::Instant;
+use crate::creature::{move_creatures, update_needs, Creature};
+use crate::optimization::LODLevel;
+use crate::settings::Settings;
+use crate::world::WorldMap;
+
+pub const DISTANT_LOD: u8 = 3; // Creatures at this LOD level or beyond run the reduced AI
+pub const DISTANT_UPDATE_INTERVAL: u64 = 10; // Fixed ticks between updates of a distant creature
+const COST_SMOOTHING: f64 = 0.2; // Weight of the newest tick in the per-creature cost estimate
+
+pub struct AiLodPlugin;
+
+impl Plugin for AiLodPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<AiTick>()
+            .add_systems(First, start_ai_frame)
+            .add_systems(FixedFirst, (advance_ai_tick, schedule_ai_buckets).chain().run_if(resource_exists::<WorldMap>))
+            .add_systems(FixedUpdate, (
+                start_ai_clock.before(update_needs),
+                stop_ai_clock.after(move_creatures),
+            ).run_if(resource_exists::<WorldMap>));
+    }
+}
+
+// === AI LEVEL OF DETAIL ===
+// Fixed-update ticks so far; distant creatures are spread over DISTANT_UPDATE_INTERVAL ticks
+// by entity index so their updates don't all land on the same frame.
+//
+// Nearby creatures are split into ai_max_interval buckets by entity index, each remembering how
+// many ticks it has waited. Every tick the buckets that have waited ai_max_interval ticks run
+// whatever it costs, then the others take turns, round-robin, for as long as the estimated cost
+// keeps the frame's AI time within ai_budget_ms. The estimate is the time the AI systems took per
+// creature updated, measured over the last ticks. With time to spare every bucket runs every tick.
+#[derive(Resource)]
+pub struct AiTick {
+    pub tick: u64,
+    waited: Vec<u32>, // Ticks since each bucket last ran
+    due: Vec<u32>, // Ticks each bucket catches up on this tick; 0 = sits it out
+    cursor: usize, // Next bucket in line for spare budget
+    updated: usize, // Creatures updated this tick, distant ones included
+    ms_per_creature: f64,
+    frame_ms: f64, // AI time spent so far this frame
+    started: Option<Instant>,
+}
+
+impl Default for AiTick {
+    fn default() -> Self {
+        Self {
+            tick: 0,
+            waited: vec![0],
+            due: vec![1],
+            cursor: 0,
+            updated: 0,
+            ms_per_creature: 0.0,
+            frame_ms: 0.0,
+            started: None,
+        }
+    }
+}
+
+impl AiTick {
+    // Simulated time a creature should process this tick, or None if it sits this one out.
+    // Creatures catch up on all the time they skipped in one step.
+    pub fn time_step(&self, entity: Entity, lod: &LODLevel, dt: f32) -> Option<f32> {
+        if !is_distant(lod) {
+            let due = self.due[entity.index() as usize % self.due.len()];
+            return (due > 0).then_some(dt * due as f32);
+        }
+        self.is_distant_due(entity).then_some(dt * DISTANT_UPDATE_INTERVAL as f32)
+    }
+
+    fn is_distant_due(&self, entity: Entity) -> bool {
+        (self.tick + entity.index() as u64).is_multiple_of(DISTANT_UPDATE_INTERVAL)
+    }
+
+    fn bucket(&self, entity: Entity) -> usize {
+        entity.index() as usize % self.waited.len()
+    }
+}
+
+pub fn is_distant(lod: &LODLevel) -> bool {
+    lod.0 >= DISTANT_LOD
+}
+
+fn advance_ai_tick(mut tick: ResMut<AiTick>) {
+    tick.tick += 1;
+}
+
+fn start_ai_frame(mut tick: ResMut<AiTick>) {
+    tick.frame_ms = 0.0;
+}
+
+fn schedule_ai_buckets(settings: Res<Settings>, mut tick: ResMut<AiTick>, creatures: Query<(Entity, &LODLevel), With<Creature>>) {
+    let tick = &mut *tick;
+    let buckets = settings.ai_max_interval.max(1) as usize;
+    if tick.waited.len() != buckets {
+        tick.waited = vec![0; buckets];
+        tick.due = vec![0; buckets];
+        tick.cursor = 0;
+    }
+
+    let mut counts = vec![0usize; buckets];
+    let mut distant_due = 0;
+    for (entity, lod) in creatures.iter() {
+        if !is_distant(lod) {
+            counts[tick.bucket(entity)] += 1;
+        } else if tick.is_distant_due(entity) {
+            distant_due += 1;
+        }
+    }
+
+    // Overdue buckets first, whatever the budget says
+    tick.updated = distant_due;
+    let mut cost_ms = tick.frame_ms + distant_due as f64 * tick.ms_per_creature;
+    for (bucket, &count) in counts.iter().enumerate() {
+        tick.waited[bucket] += 1;
+        tick.due[bucket] = 0;
+        if tick.waited[bucket] >= buckets as u32 {
+            tick.due[bucket] = std::mem::take(&mut tick.waited[bucket]);
+            tick.updated += count;
+            cost_ms += count as f64 * tick.ms_per_creature;
+        }
+    }
+
+    // Then the rest in turn, stopping at the first that does not fit
+    let budget_ms = settings.ai_budget_ms as f64;
+    for offset in 0..buckets {
+        let bucket = (tick.cursor + offset) % buckets;
+        if tick.due[bucket] > 0 {
+            continue;
+        }
+        let bucket_ms = counts[bucket] as f64 * tick.ms_per_creature;
+        if budget_ms > 0.0 && cost_ms + bucket_ms > budget_ms {
+            tick.cursor = bucket;
+            return;
+        }
+        tick.due[bucket] = std::mem::take(&mut tick.waited[bucket]);
+        tick.updated += counts[bucket];
+        cost_ms += bucket_ms;
+    }
+}
+
+fn start_ai_clock(mut tick: ResMut<AiTick>) {
+    tick.started = Some(Instant::now());
+}
+
+fn stop_ai_clock(mut tick: ResMut<AiTick>) {
+    let Some(started) = tick.started.take() else { return };
+    let ms = started.elapsed().as_secs_f64() * 1000.0;
+    tick.frame_ms += ms;
+    if tick.updated > 0 {
+        let sample = ms / tick.updated as f64;
+        tick.ms_per_creature += (sample - tick.ms_per_creature) * COST_SMOOTHING;
+    }
+}
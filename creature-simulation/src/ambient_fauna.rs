@@ -0,0 +1,193 @@
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+use crate::biome::BiomeType;
+use crate::camera::MainCamera;
+use crate::coords::world_to_tile;
+use crate::creature::Needs;
+use crate::creature_lod::{CreatureDetail, CreatureRenderLod};
+use crate::fisheries::Fisheries;
+use crate::settings::{motion_enabled, Settings};
+use crate::simulation::SimClock;
+use crate::world::WorldMap;
+
+const CELL_SIZE: f32 = 24.0; // World units; each cell may hold one butterfly, flock or ripple
+const MAX_CELLS: i32 = 40; // Cells across the view at most, beyond which nothing is drawn
+const LIFETIME: f32 = 12.0; // Seconds one flourish lasts before its cell rolls again
+const FADE: f32 = 1.5; // Seconds to fade in and out
+const LIVELY_POPULATION: f32 = 30.0; // Healthy creatures in view at which the fauna is at its fullest
+const MIN_VITALITY: f32 = 0.2; // Share of the fauna left where nothing lives
+const BUTTERFLY_SIZE: f32 = 1.2;
+const BIRD_SIZE: f32 = 2.0;
+const FLOCK_SPEED: f32 = 18.0; // World units per second
+const RIPPLE_RADIUS: f32 = 3.0;
+
+// Drawn as gizmo lines, batched into one draw call like the stat bars
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct AmbientGizmos;
+
+pub struct AmbientFaunaPlugin;
+
+impl Plugin for AmbientFaunaPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_gizmo_config(AmbientGizmos, GizmoConfig {
+                line_width: 1.5,
+                ..default()
+            })
+            .add_systems(Update, draw_ambient_fauna
+                .run_if(motion_enabled)
+                .run_if(|lod: Res<CreatureRenderLod>| lod.detail == CreatureDetail::Sprite)
+                .run_if(resource_exists::<WorldMap>));
+    }
+}
+
+// === AMBIENT FAUNA ===
+// Butterflies over meadows and forests, birds flocking overhead and rising fish rippling the water,
+// around the camera only. None of it exists in the simulation: the view is cut into cells, and
+// each cell decides from a hash of its coordinates and the current LIFETIME period whether it holds
+// something and what it does, so nothing is spawned or stored. How busy a cell is follows its biome,
+// the health of the creatures in view, and for ripples the fish stock of its water body.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Flourish {
+    Butterfly,
+    Flock,
+    Ripple,
+}
+
+impl Flourish {
+    // Chance a cell of the biome holds one, at full vitality
+    fn density(&self, biome: BiomeType) -> f32 {
+        match (self, biome) {
+            (Flourish::Butterfly, BiomeType::Grasslands | BiomeType::TropicalRainforest) => 0.35,
+            (Flourish::Butterfly, BiomeType::Forest | BiomeType::Savanna | BiomeType::Wetlands) => 0.2,
+            (Flourish::Butterfly, BiomeType::Alpine) => 0.05,
+            (Flourish::Flock, BiomeType::Caves) => 0.0,
+            (Flourish::Flock, BiomeType::Coastal | BiomeType::Wetlands) => 0.06,
+            (Flourish::Flock, _) => 0.03,
+            (Flourish::Ripple, BiomeType::Ocean | BiomeType::Coastal | BiomeType::Wetlands | BiomeType::Tundra) => 0.3,
+            _ => 0.0,
+        }
+    }
+
+    // Butterflies and birds keep to the day; fish rise at any hour
+    fn by_day(&self) -> bool {
+        *self != Flourish::Ripple
+    }
+}
+
+// Pseudo-random value in 0.0..1.0 from a cell, period and salt
+fn cell_random(cell: IVec2, period: i64, salt: u32) -> f32 {
+    let mut hash = (cell.x as u32).wrapping_mul(0x9E37_79B1)
+        ^ (cell.y as u32).wrapping_mul(0x85EB_CA77)
+        ^ (period as u32).wrapping_mul(0xC2B2_AE3D)
+        ^ salt.wrapping_mul(0x27D4_EB2F);
+    hash ^= hash >> 15;
+    hash = hash.wrapping_mul(0x2C1B_3C6D);
+    hash ^= hash >> 12;
+    hash = hash.wrapping_mul(0x297A_2D39);
+    hash ^= hash >> 15;
+    hash as f32 / u32::MAX as f32
+}
+
+// Health of the creatures in view summed, as a share of a lively population
+fn vitality(centre: Vec2, reach: f32, creatures: &Query<(&Transform, &Needs)>) -> f32 {
+    let health: f32 = creatures.iter()
+        .filter(|(transform, _)| transform.translation.truncate().distance_squared(centre) < reach * reach)
+        .map(|(_, needs)| needs.health)
+        .sum();
+    (health / LIVELY_POPULATION).clamp(MIN_VITALITY, 1.0)
+}
+
+fn draw_ambient_fauna(
+    mut gizmos: Gizmos<AmbientGizmos>,
+    time: Res<Time>,
+    (settings, clock, world_map, fisheries): (Res<Settings>, Res<SimClock>, Res<WorldMap>, Option<Res<Fisheries>>),
+    windows: Query<&Window>,
+    cameras: Query<(&Transform, &OrthographicProjection), With<MainCamera>>,
+    creatures: Query<(&Transform, &Needs)>,
+) {
+    let (Ok(window), Ok((camera, projection))) = (windows.get_single(), cameras.get_single()) else { return };
+    let centre = camera.translation.truncate();
+    // Covers the view whichever way the camera is turned
+    let reach = window.size().length() / 2.0 * projection.scale * camera.scale.max_element();
+    let cells = (reach / CELL_SIZE).ceil() as i32;
+    if cells * 2 > MAX_CELLS {
+        return;
+    }
+
+    let vitality = vitality(centre, reach, &creatures);
+    let (across, up) = (settings.view.screen_to_world(Vec2::X), settings.view.screen_to_world(Vec2::Y));
+    let night = clock.is_night();
+    let now = time.elapsed_seconds_wrapped();
+    let origin = (centre / CELL_SIZE).floor().as_ivec2();
+    for dx in -cells..=cells {
+        for dy in -cells..=cells {
+            let cell = origin + IVec2::new(dx, dy);
+            // Cells start their periods at different times, so flourishes do not all change at once
+            let offset = cell_random(cell, 0, 0) * LIFETIME;
+            let period = ((now + offset) / LIFETIME).floor() as i64;
+            let age = now + offset - period as f32 * LIFETIME;
+            let fade = (age / FADE).min((LIFETIME - age) / FADE).clamp(0.0, 1.0);
+
+            let anchor = (cell.as_vec2() + Vec2::new(cell_random(cell, period, 1), cell_random(cell, period, 2))) * CELL_SIZE;
+            let Some((x, y)) = world_to_tile(anchor) else { continue };
+            let tile = &world_map.tiles[x][y];
+            let roll = cell_random(cell, period, 3);
+            let Some(flourish) = [Flourish::Butterfly, Flourish::Flock, Flourish::Ripple].into_iter()
+                .filter(|flourish| !(night && flourish.by_day()))
+                .scan(0.0, |sum, flourish| {
+                    let mut density = flourish.density(tile.biome) * vitality;
+                    if flourish == Flourish::Ripple {
+                        density *= fisheries.as_ref().and_then(|fisheries| fisheries.stock_at(x, y))
+                            .filter(|stock| !stock.collapsed)
+                            .map_or(0.0, |stock| stock.stock / stock.capacity);
+                    }
+                    *sum += density;
+                    Some((flourish, *sum))
+                })
+                .find(|(_, sum)| roll < *sum)
+                .map(|(flourish, _)| flourish)
+            else { continue };
+
+            let phase = cell_random(cell, period, 4) * TAU;
+            match flourish {
+                // Fluttering around a loose figure of eight
+                Flourish::Butterfly => {
+                    let hue = cell_random(cell, period, 5) * 360.0;
+                    let color = Color::hsla(hue, 0.8, 0.75, fade);
+                    let position = anchor + Vec2::new((age * 0.7 + phase).sin(), (age * 1.4 + phase).sin() * 0.5) * CELL_SIZE * 0.2;
+                    let flap = (age * 14.0 + phase).sin() * 0.5 + 0.6;
+                    for side in [-1.0, 1.0] {
+                        let tip = position + (across * side * flap + up) * BUTTERFLY_SIZE;
+                        gizmos.line_2d(position, tip, color);
+                    }
+                }
+                // A small V of birds crossing the cell in a straight line
+                Flourish::Flock => {
+                    let color = Color::srgba(0.15, 0.15, 0.2, 0.8 * fade);
+                    let heading = Vec2::from_angle(phase);
+                    let lead = anchor + heading * FLOCK_SPEED * (age - LIFETIME / 2.0);
+                    let birds = 3 + (cell_random(cell, period, 6) * 5.0) as usize;
+                    for bird in 0..birds {
+                        let rank = bird.div_ceil(2);
+                        let side = if bird % 2 == 0 { 1.0 } else { -1.0 };
+                        let position = lead - heading * rank as f32 * BIRD_SIZE * 1.5 + heading.perp() * side * rank as f32 * BIRD_SIZE;
+                        let flap = (age * 6.0 + bird as f32).sin() * 0.4;
+                        for wing in [-1.0, 1.0] {
+                            let tip = position + (across * wing + up * (0.4 + flap)) * BIRD_SIZE / 2.0;
+                            gizmos.line_2d(position, tip, color);
+                        }
+                    }
+                }
+                // Rings spreading from where a fish rose, a few times over the period
+                Flourish::Ripple => {
+                    for ring in 0..2 {
+                        let spread = (age / 3.0 + ring as f32 * 0.3 + phase) % 1.0;
+                        let color = Color::srgba(0.85, 0.95, 1.0, 0.6 * (1.0 - spread) * fade);
+                        gizmos.circle_2d(anchor, RIPPLE_RADIUS * spread, color);
+                    }
+                }
+            }
+        }
+    }
+}
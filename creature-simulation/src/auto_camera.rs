@@ -0,0 +1,186 @@
+use bevy::prelude::*;
+use crate::keybindings::{Action, Controls};
+use crate::camera::{CameraPan, CameraZoom, MainCamera};
+use crate::events::NotableEvent;
+use crate::localization::Localization;
+use crate::save::SaveViewExt;
+use crate::settings::Settings;
+use crate::text_input::text_input_inactive;
+
+const AUTO_CAMERA_ZOOM: f32 = 0.6; // Orthographic scale while following the action
+const ZOOM_SPEED: f32 = 2.0; // Exponential approach rate (per second)
+const CLUSTER_RADIUS: f32 = 60.0; // Events this close together are merged into one hot spot
+const CANDIDATE_DECAY_SECONDS: f32 = 30.0; // Stale hot spots lose importance over time
+const MAX_CANDIDATES: usize = 64;
+
+pub struct AutoCameraPlugin;
+
+impl Plugin for AutoCameraPlugin {
+    fn build(&self, app: &mut App) {
+        let enabled = app.world().resource::<Settings>().auto_camera;
+
+        app
+            .insert_resource(AutoCamera { enabled, ..default() })
+            .save_view::<bool>("auto_camera",
+                |world| Some(world.resource::<AutoCamera>().enabled),
+                |world, enabled| world.resource_mut::<AutoCamera>().enabled = enabled)
+            .add_systems(Startup, spawn_auto_camera_caption)
+            .add_systems(Update, (
+                toggle_auto_camera.run_if(text_input_inactive),
+                collect_notable_events,
+                drive_auto_camera,
+                apply_auto_camera_zoom,
+                update_auto_camera_caption,
+            ).chain());
+    }
+}
+
+// === AUTO CAMERA ===
+// Cinematic "follow the action" mode: hops between the most important recent hot spots,
+// dwelling on each for `Settings::auto_camera_dwell` seconds.
+#[derive(Resource, Default)]
+pub struct AutoCamera {
+    pub enabled: bool,
+    pub candidates: Vec<NotableEvent>,
+    pub dwell_remaining: f32,
+    pub current_label: Option<&'static str>,
+}
+
+#[derive(Component)]
+pub struct AutoCameraCaption;
+
+fn toggle_auto_camera(controls: Controls, mut auto_camera: ResMut<AutoCamera>) {
+    let manual_movement = [Action::PanUp, Action::PanDown, Action::PanLeft, Action::PanRight]
+        .into_iter()
+        .any(|action| controls.pressed(action));
+
+    if controls.just_pressed(Action::ToggleAutoCamera) {
+        auto_camera.enabled = !auto_camera.enabled;
+        info!("🎬 Auto camera {}", if auto_camera.enabled { "on" } else { "off" });
+    } else if manual_movement && auto_camera.enabled {
+        // Taking the controls hands the camera back to the user
+        auto_camera.enabled = false;
+        info!("🎬 Auto camera off");
+    }
+
+    if auto_camera.is_changed() && !auto_camera.enabled {
+        auto_camera.candidates.clear();
+        auto_camera.current_label = None;
+        auto_camera.dwell_remaining = 0.0;
+    }
+}
+
+fn collect_notable_events(mut events: EventReader<NotableEvent>, mut auto_camera: ResMut<AutoCamera>) {
+    if !auto_camera.enabled {
+        events.clear();
+        return;
+    }
+
+    for event in events.read() {
+        match auto_camera.candidates.iter_mut().find(|c| c.position.distance(event.position) < CLUSTER_RADIUS) {
+            Some(cluster) => cluster.importance += event.importance,
+            None => auto_camera.candidates.push(event.clone()),
+        }
+    }
+
+    if auto_camera.candidates.len() > MAX_CANDIDATES {
+        auto_camera.candidates.sort_by(|a, b| b.importance.total_cmp(&a.importance));
+        auto_camera.candidates.truncate(MAX_CANDIDATES);
+    }
+}
+
+fn drive_auto_camera(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut auto_camera: ResMut<AutoCamera>,
+    mut pan: ResMut<CameraPan>,
+) {
+    if !auto_camera.enabled {
+        return;
+    }
+
+    let dt = time.delta_seconds();
+    let decay = (-dt / CANDIDATE_DECAY_SECONDS).exp();
+    for candidate in auto_camera.candidates.iter_mut() {
+        candidate.importance *= decay;
+    }
+
+    auto_camera.dwell_remaining -= dt;
+    if auto_camera.dwell_remaining > 0.0 {
+        return;
+    }
+
+    let best = auto_camera.candidates.iter().enumerate()
+        .max_by(|a, b| a.1.importance.total_cmp(&b.1.importance))
+        .map(|(index, _)| index);
+    let Some(index) = best else { return };
+
+    let next = auto_camera.candidates.swap_remove(index);
+    pan.go_to(next.position);
+    auto_camera.current_label = Some(next.label_key);
+    auto_camera.dwell_remaining = settings.auto_camera_dwell;
+}
+
+fn apply_auto_camera_zoom(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    auto_camera: Res<AutoCamera>,
+    zoom: Res<CameraZoom>,
+    mut projections: Query<&mut OrthographicProjection, With<MainCamera>>,
+) {
+    let target = if auto_camera.enabled { AUTO_CAMERA_ZOOM } else { zoom.scale };
+    for mut projection in projections.iter_mut() {
+        if (projection.scale - target).abs() < 0.001 {
+            continue;
+        }
+        projection.scale = if settings.reduced_motion {
+            target
+        } else {
+            projection.scale.lerp(target, 1.0 - (-ZOOM_SPEED * time.delta_seconds()).exp())
+        };
+    }
+}
+
+fn spawn_auto_camera_caption(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 20.0,
+                color: Color::srgb(0.95, 0.95, 0.95),
+                ..default()
+            },
+        ).with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(20.0),
+            right: Val::Px(20.0),
+            padding: UiRect::all(Val::Px(8.0)),
+            ..default()
+        }).with_background_color(Color::srgba(0.05, 0.05, 0.1, 0.8)),
+        Visibility::Hidden,
+        AutoCameraCaption,
+    ));
+}
+
+fn update_auto_camera_caption(
+    auto_camera: Res<AutoCamera>,
+    localization: Res<Localization>,
+    mut captions: Query<(&mut Text, &mut Visibility), With<AutoCameraCaption>>,
+) {
+    if !auto_camera.is_changed() {
+        return;
+    }
+    for (mut text, mut visibility) in captions.iter_mut() {
+        match (auto_camera.enabled, auto_camera.current_label) {
+            (true, Some(label)) => {
+                text.sections[0].value = format!("🎬 {}", localization.get(label));
+                *visibility = Visibility::Visible;
+            }
+            (true, None) => {
+                text.sections[0].value = localization.get("autocam.waiting").to_string();
+                *visibility = Visibility::Visible;
+            }
+            (false, _) => *visibility = Visibility::Hidden,
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use bevy::prelude::*;
+use crate::creature::{Behavior, Creature, Needs, Wander, SEEK_THRESHOLD};
+use crate::creature_tracking::{display_name, CreatureName, SelectedCreature};
+use crate::learning::Learned;
+use crate::localization::Localization;
+use crate::simulation::{SimClock, SECONDS_PER_DAY};
+use crate::species::SpeciesRegistry;
+use crate::telemetry::RingBuffer;
+
+const HISTORY_LENGTH: usize = 10; // Decisions kept for the selected creature
+
+pub struct BehaviorInspectorPlugin;
+
+impl Plugin for BehaviorInspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(DecisionHistory { entity: None, decisions: RingBuffer::new(HISTORY_LENGTH) })
+            .add_systems(Startup, spawn_behavior_panel)
+            .add_systems(Update, (record_decisions, update_behavior_panel).chain());
+    }
+}
+
+// === BEHAVIOR INSPECTOR ===
+// While a creature is selected, a panel shows what it is doing, the need levels that drive its
+// choices against the threshold at which it starts seeking, and its last few decisions.
+pub struct Decision {
+    pub behavior: Behavior,
+    pub time: f64, // SimClock seconds
+    pub hunger: f32,
+    pub thirst: f32,
+}
+
+#[derive(Resource)]
+pub struct DecisionHistory {
+    pub entity: Option<Entity>, // Creature the history belongs to
+    pub decisions: RingBuffer<Decision>,
+}
+
+#[derive(Component)]
+pub struct BehaviorPanel;
+
+// A new entry whenever the selected creature's behavior changes; selecting another creature starts over
+fn record_decisions(
+    clock: Res<SimClock>,
+    selected: Res<SelectedCreature>,
+    creatures: Query<(&Wander, &Needs)>,
+    mut history: ResMut<DecisionHistory>,
+) {
+    if history.entity != selected.0 {
+        history.entity = selected.0;
+        history.decisions = RingBuffer::new(HISTORY_LENGTH);
+    }
+    let Some((wander, needs)) = selected.0.and_then(|entity| creatures.get(entity).ok()) else { return };
+    if history.decisions.iter().last().is_some_and(|decision| decision.behavior == wander.behavior) {
+        return;
+    }
+    history.decisions.push(Decision {
+        behavior: wander.behavior,
+        time: clock.elapsed,
+        hunger: needs.hunger,
+        thirst: needs.thirst,
+    });
+}
+
+fn spawn_behavior_panel(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 14.0,
+                color: Color::srgb(0.9, 0.9, 0.9),
+                ..default()
+            },
+        ).with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(80.0),
+            left: Val::Percent(35.0),
+            padding: UiRect::all(Val::Px(6.0)),
+            ..default()
+        }).with_background_color(Color::srgba(0.05, 0.05, 0.1, 0.7)),
+        Visibility::Hidden,
+        BehaviorPanel,
+    ));
+}
+
+// "day 3 14:20" style time stamps for the history
+fn sim_time(elapsed: f64) -> String {
+    let minutes = ((elapsed % SECONDS_PER_DAY) / SECONDS_PER_DAY * 24.0 * 60.0) as u32;
+    format!("{} {:02}:{:02}", (elapsed / SECONDS_PER_DAY) as u32, minutes / 60, minutes % 60)
+}
+
+type InspectedCreature = (&'static Creature, &'static Needs, &'static Wander, Option<&'static CreatureName>, Option<&'static Learned>);
+
+fn update_behavior_panel(
+    localization: Res<Localization>,
+    registry: Res<SpeciesRegistry>,
+    history: Res<DecisionHistory>,
+    creatures: Query<InspectedCreature>,
+    mut panels: Query<(&mut Text, &mut Visibility), With<BehaviorPanel>>,
+) {
+    let Ok((mut text, mut visibility)) = panels.get_single_mut() else { return };
+    let Some((entity, (creature, needs, wander, name, learned))) = history.entity
+        .and_then(|entity| creatures.get(entity).ok().map(|components| (entity, components))) else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+    *visibility = Visibility::Visible;
+
+    let percent = |value: f32| format!("{:.0}", value * 100.0);
+    let mut lines = vec![
+        localization.format("behavior.title", &[
+            ("name", display_name(name, &registry.get(creature.species).name, entity)),
+            ("behavior", localization.get(wander.behavior.label_key()).to_string()),
+        ]),
+        localization.format("behavior.needs", &[
+            ("hunger", percent(needs.hunger)),
+            ("thirst", percent(needs.thirst)),
+            ("threshold", percent(SEEK_THRESHOLD)),
+        ]),
+    ];
+    if let Some(learned) = learned {
+        lines.push(localization.format("behavior.learned", &[
+            ("hunt_drive", percent(learned.hunt_drive)),
+            ("caution", percent(learned.caution)),
+        ]));
+    }
+    lines.push(localization.get("behavior.history").to_string());
+    // Newest first
+    for decision in history.decisions.iter().collect::<Vec<_>>().into_iter().rev() {
+        lines.push(localization.format("behavior.decision", &[
+            ("time", sim_time(decision.time)),
+            ("behavior", localization.get(decision.behavior.label_key()).to_string()),
+            ("hunger", percent(decision.hunger)),
+            ("thirst", percent(decision.thirst)),
+        ]));
+    }
+    text.sections[0].value = lines.join("\n");
+}
@@ -0,0 +1,208 @@
+use bevy::app::AppExit;
+use bevy::ecs::entity::Entities;
+use bevy::prelude::*;
+use bevy::winit::WinitSettings;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::time::Instant;
+use crate::creature::Creature;
+use crate::optimized_systems::DEFAULT_WORLD_SEED;
+use crate::population_pools::PopulationPools;
+use crate::settings::Settings;
+use crate::simulation::{BackgroundMode, SimClock};
+use crate::species::SpeciesRegistry;
+use crate::stress_test::percentile;
+use crate::world::{WorldGenerator, WorldMap};
+
+pub const BENCH_REPORT_PATH: &str = "bench_report.json";
+const WORLDGEN_BUDGET_MS: f64 = 20_000.0; // 95th percentile generation time above this fails the run
+const SOAK_FRAME_BUDGET_MS: f64 = 50.0; // 95th percentile frame time above this fails the run
+const PEAK_MEMORY_BUDGET_MB: f64 = 4096.0; // Peak resident memory above this fails either run
+const SOAK_WARMUP_SECONDS: f64 = 10.0; // Simulated; frames before this are not recorded
+
+// === BENCHMARK AND SOAK RUNS ===
+// Unattended runs for CI and regression hunting, started from the command line instead of the
+// app: "--bench-worldgen N" generates N worlds in a row without opening a window, "--soak-hours H"
+// simulates the default world for H hours of simulated time. Either prints a one-line JSON summary
+// to stdout, writes it in full to BENCH_REPORT_PATH, and exits with status 1 if a budget was
+// exceeded. Peak memory is read from /proc and only checked where the platform reports it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BenchRun {
+    Worldgen { runs: u32 },
+    Soak { hours: f64 },
+}
+
+#[derive(Serialize)]
+struct WorldgenReport {
+    runs: u32,
+    seeds: Vec<u32>,
+    timings_ms: Vec<f64>,
+    mean_ms: f64,
+    p50_ms: f64,
+    p95_ms: f64,
+    max_ms: f64,
+    failures: Vec<String>,
+    peak_memory_mb: Option<f64>,
+    worldgen_budget_ms: f64,
+    peak_memory_budget_mb: f64,
+    passed: bool,
+}
+
+#[derive(Serialize)]
+struct SoakReport {
+    seed: u32,
+    simulated_hours: f64,
+    real_seconds: f64,
+    frames: usize,
+    p50_ms: f64,
+    p95_ms: f64,
+    p99_ms: f64,
+    max_ms: f64,
+    peak_entities: usize,
+    peak_memory_mb: Option<f64>,
+    populations: BTreeMap<String, usize>, // Creatures alive at the end by species, pooled ones included
+    frame_budget_ms: f64,
+    peak_memory_budget_mb: f64,
+    passed: bool,
+}
+
+// Peak resident memory of the process, where the platform reports it
+fn peak_memory_mb() -> Option<f64> {
+    let status = fs::read_to_string("/proc/self/status").ok()?;
+    let kilobytes: f64 = status.lines()
+        .find_map(|line| line.strip_prefix("VmHWM:"))?
+        .trim().trim_end_matches("kB").trim()
+        .parse().ok()?;
+    Some(kilobytes / 1024.0)
+}
+
+fn within_memory_budget(peak_memory_mb: Option<f64>) -> bool {
+    peak_memory_mb.is_none_or(|peak| peak <= PEAK_MEMORY_BUDGET_MB)
+}
+
+// One line on stdout for scripts, the indented report on disk for people
+fn publish<T: Serialize>(report: &T, passed: bool) -> AppExit {
+    match serde_json::to_string(report) {
+        Ok(json) => println!("{}", json),
+        Err(err) => eprintln!("Failed to serialize the bench report: {}", err),
+    }
+    if let Err(err) = serde_json::to_string_pretty(report).map_err(|err| err.to_string())
+        .and_then(|json| fs::write(BENCH_REPORT_PATH, json).map_err(|err| err.to_string())) {
+        eprintln!("Failed to write {}: {}", BENCH_REPORT_PATH, err);
+    }
+    if passed { AppExit::Success } else { AppExit::from_code(1) }
+}
+
+// Runs before the app is built; consecutive seeds from the default one, with the configured
+// generation parameters and no chunk cache, so every world is generated in full
+pub fn bench_worldgen(settings: &Settings, runs: u32) -> AppExit {
+    let mut seeds = Vec::new();
+    let mut timings_ms = Vec::new();
+    let mut failures = Vec::new();
+    for run in 0..runs {
+        let seed = DEFAULT_WORLD_SEED.wrapping_add(run);
+        let start = Instant::now();
+        let result = WorldGenerator::new(Some(seed)).with_params(settings.generation).generate_world();
+        let ms = start.elapsed().as_secs_f64() * 1000.0;
+        match result {
+            Ok(_) => {
+                eprintln!("World {}/{} (seed {}) generated in {:.0} ms", run + 1, runs, seed, ms);
+                seeds.push(seed);
+                timings_ms.push(ms);
+            }
+            Err(err) => failures.push(format!("seed {}: {}", seed, err)),
+        }
+    }
+
+    let mut sorted = timings_ms.clone();
+    sorted.sort_by(f64::total_cmp);
+    let p95_ms = percentile(&sorted, 0.95);
+    let peak_memory_mb = peak_memory_mb();
+    let passed = failures.is_empty() && p95_ms <= WORLDGEN_BUDGET_MS && within_memory_budget(peak_memory_mb);
+    publish(&WorldgenReport {
+        runs,
+        seeds,
+        mean_ms: sorted.iter().sum::<f64>() / sorted.len().max(1) as f64,
+        p50_ms: percentile(&sorted, 0.5),
+        p95_ms,
+        max_ms: sorted.last().copied().unwrap_or(0.0),
+        timings_ms,
+        failures,
+        peak_memory_mb,
+        worldgen_budget_ms: WORLDGEN_BUDGET_MS,
+        peak_memory_budget_mb: PEAK_MEMORY_BUDGET_MB,
+        passed,
+    }, passed)
+}
+
+pub struct SoakPlugin;
+
+impl Plugin for SoakPlugin {
+    fn build(&self, app: &mut App) {
+        let Some(BenchRun::Soak { hours }) = app.world().resource::<Settings>().bench else { return };
+        // Nobody is watching the window, so it must not throttle the run when unfocused
+        app.world_mut().resource_mut::<Settings>().background_mode = BackgroundMode::Run;
+        app
+            .insert_resource(WinitSettings::game())
+            .insert_resource(SoakRun { hours, started: Instant::now(), frame_times_ms: Vec::new(), peak_entities: 0 })
+            .add_systems(Last, record_soak_run.run_if(resource_exists::<WorldMap>));
+    }
+}
+
+#[derive(Resource)]
+struct SoakRun {
+    hours: f64,
+    started: Instant,
+    frame_times_ms: Vec<f64>,
+    peak_entities: usize,
+}
+
+fn record_soak_run(
+    time: Res<Time<Real>>,
+    entities: &Entities,
+    (clock, world_map, registry, pools): (Res<SimClock>, Res<WorldMap>, Res<SpeciesRegistry>, Res<PopulationPools>),
+    mut run: ResMut<SoakRun>,
+    creatures: Query<&Creature>,
+    mut exit: EventWriter<AppExit>,
+    mut finished: Local<bool>,
+) {
+    if *finished || clock.elapsed < SOAK_WARMUP_SECONDS {
+        return;
+    }
+    run.frame_times_ms.push(time.delta_seconds_f64() * 1000.0);
+    run.peak_entities = run.peak_entities.max(entities.len() as usize);
+    if clock.elapsed < run.hours * 3600.0 {
+        return;
+    }
+    *finished = true;
+
+    let mut populations = BTreeMap::new();
+    let species = creatures.iter().map(|creature| creature.species)
+        .chain(pools.chunks.values().flatten().map(|pooled| pooled.species));
+    for species in species {
+        *populations.entry(registry.get(species).name.clone()).or_insert(0) += 1;
+    }
+    let mut sorted = std::mem::take(&mut run.frame_times_ms);
+    sorted.sort_by(f64::total_cmp);
+    let p95_ms = percentile(&sorted, 0.95);
+    let peak_memory_mb = peak_memory_mb();
+    let passed = p95_ms <= SOAK_FRAME_BUDGET_MS && within_memory_budget(peak_memory_mb);
+    info!("⏳ Soak run finished after {:.1} simulated hours: {}", run.hours, if passed { "passed" } else { "FAILED" });
+    exit.send(publish(&SoakReport {
+        seed: world_map.seed,
+        simulated_hours: clock.elapsed / 3600.0,
+        real_seconds: run.started.elapsed().as_secs_f64(),
+        frames: sorted.len(),
+        p50_ms: percentile(&sorted, 0.5),
+        p95_ms,
+        p99_ms: percentile(&sorted, 0.99),
+        max_ms: sorted.last().copied().unwrap_or(0.0),
+        peak_entities: run.peak_entities,
+        peak_memory_mb,
+        populations,
+        frame_budget_ms: SOAK_FRAME_BUDGET_MS,
+        peak_memory_budget_mb: PEAK_MEMORY_BUDGET_MB,
+        passed,
+    }, passed));
+}
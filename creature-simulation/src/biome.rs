@@ -1,6 +1,7 @@
 use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum BiomeType {
     Ocean,
     Coastal,
@@ -18,7 +19,7 @@ pub enum BiomeType {
     Badlands,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum ResourceType {
     Water,
     Wood,
@@ -32,9 +33,80 @@ pub enum ResourceType {
     Mushrooms,
     Clay,
     Sulfur,
+    Shellfish, // Only gatherable when the tide is out
+}
+
+impl ResourceType {
+    pub const ALL: [ResourceType; 13] = [
+        ResourceType::Water,
+        ResourceType::Wood,
+        ResourceType::Stone,
+        ResourceType::Fish,
+        ResourceType::Berries,
+        ResourceType::Herbs,
+        ResourceType::Minerals,
+        ResourceType::Salt,
+        ResourceType::Ice,
+        ResourceType::Mushrooms,
+        ResourceType::Clay,
+        ResourceType::Sulfur,
+        ResourceType::Shellfish,
+    ];
+
+    pub fn name_key(&self) -> String {
+        format!("resource.{:?}", self)
+    }
+}
+
+// How hard the ground of a biome is to walk on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Footing {
+    Firm,
+    DeepSnow,
+    Mud,
+    Undergrowth, // Dense rainforest
+}
+
+impl Footing {
+    // Share of their normal speed walkers keep
+    pub fn speed(&self) -> f32 {
+        match self {
+            Footing::Firm => 1.0,
+            Footing::DeepSnow => 0.6,
+            Footing::Mud => 0.5,
+            Footing::Undergrowth => 0.7,
+        }
+    }
 }
 
 impl BiomeType {
+    pub const ALL: [BiomeType; 14] = [
+        BiomeType::Ocean,
+        BiomeType::Coastal,
+        BiomeType::Desert,
+        BiomeType::Savanna,
+        BiomeType::Grasslands,
+        BiomeType::Forest,
+        BiomeType::TropicalRainforest,
+        BiomeType::Mountain,
+        BiomeType::Alpine,
+        BiomeType::Tundra,
+        BiomeType::Wetlands,
+        BiomeType::Caves,
+        BiomeType::Volcanic,
+        BiomeType::Badlands,
+    ];
+
+    // Localization key for the biome's display name
+    pub fn name_key(&self) -> String {
+        format!("biome.{:?}", self)
+    }
+
+    // Case-insensitive lookup by variant name, e.g. "forest" or "TropicalRainforest"
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|biome| format!("{:?}", biome).eq_ignore_ascii_case(name))
+    }
+
     pub fn get_color(&self) -> Color {
         match self {
             BiomeType::Ocean => Color::srgb(0.0, 0.3, 0.8),
@@ -54,10 +126,20 @@ impl BiomeType {
         }
     }
 
+    // Ground underfoot for walkers (see gait.rs)
+    pub fn footing(&self) -> Footing {
+        match self {
+            BiomeType::Tundra | BiomeType::Alpine => Footing::DeepSnow,
+            BiomeType::Wetlands => Footing::Mud,
+            BiomeType::TropicalRainforest => Footing::Undergrowth,
+            _ => Footing::Firm,
+        }
+    }
+
     pub fn get_resources(&self) -> Vec<ResourceType> {
         match self {
             BiomeType::Ocean => vec![ResourceType::Water, ResourceType::Fish, ResourceType::Salt],
-            BiomeType::Coastal => vec![ResourceType::Water, ResourceType::Fish, ResourceType::Salt, ResourceType::Clay],
+            BiomeType::Coastal => vec![ResourceType::Water, ResourceType::Shellfish, ResourceType::Fish, ResourceType::Salt, ResourceType::Clay],
             BiomeType::Desert => vec![ResourceType::Stone, ResourceType::Minerals, ResourceType::Salt],
             BiomeType::Savanna => vec![ResourceType::Herbs, ResourceType::Stone],
             BiomeType::Grasslands => vec![ResourceType::Herbs, ResourceType::Berries],
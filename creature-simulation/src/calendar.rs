@@ -0,0 +1,267 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::localization::Localization;
+use crate::simulation::{SimClock, DAYS_PER_YEAR};
+use crate::world::{latitude, GenerationParams, WorldMap};
+
+pub const DAYS_PER_SEASON: u32 = DAYS_PER_YEAR / 4;
+pub const LUNAR_CYCLE_DAYS: u32 = 8; // New moon to new moon, one phase a day
+const SEASONAL_SWING: f32 = 0.2; // Tile temperature added at midsummer at the poles, with Earth's tilt
+const EARTH_TILT: f32 = 23.5; // Degrees
+
+pub struct CalendarPlugin;
+
+impl Plugin for CalendarPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Calendar>()
+            .init_resource::<Scheduler>()
+            .add_event::<ScheduledEventFired>()
+            .add_systems(Startup, spawn_date_label)
+            .add_systems(FixedPreUpdate, (update_calendar, fire_scheduled_events).chain()
+                .run_if(resource_exists::<WorldMap>))
+            .add_systems(Update, (
+                log_scheduled_events,
+                update_date_label.run_if(resource_changed::<Calendar>),
+            ));
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Season {
+    #[default]
+    Spring,
+    Summer,
+    Autumn,
+    Winter,
+}
+
+impl Season {
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            Season::Spring => "season.spring",
+            Season::Summer => "season.summer",
+            Season::Autumn => "season.autumn",
+            Season::Winter => "season.winter",
+        }
+    }
+
+    // Six months away, as on the other half of the map
+    pub fn opposite(&self) -> Season {
+        match self {
+            Season::Spring => Season::Autumn,
+            Season::Summer => Season::Winter,
+            Season::Autumn => Season::Spring,
+            Season::Winter => Season::Summer,
+        }
+    }
+}
+
+// Season at a tile row: the calendar's in the northern half, the opposite one in the southern half
+pub fn local_season(day: u32, y: usize) -> Season {
+    let season = Calendar::from_day(day).season;
+    if latitude(y) < 0.0 { season.opposite() } else { season }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MoonPhase {
+    #[default]
+    New,
+    WaxingCrescent,
+    FirstQuarter,
+    WaxingGibbous,
+    Full,
+    WaningGibbous,
+    LastQuarter,
+    WaningCrescent,
+}
+
+impl MoonPhase {
+    pub const ALL: [MoonPhase; 8] = [
+        MoonPhase::New, MoonPhase::WaxingCrescent, MoonPhase::FirstQuarter, MoonPhase::WaxingGibbous,
+        MoonPhase::Full, MoonPhase::WaningGibbous, MoonPhase::LastQuarter, MoonPhase::WaningCrescent,
+    ];
+
+    pub fn from_day(day: u32) -> Self {
+        Self::ALL[(day % LUNAR_CYCLE_DAYS * Self::ALL.len() as u32 / LUNAR_CYCLE_DAYS) as usize]
+    }
+
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            MoonPhase::New => "moon.new",
+            MoonPhase::WaxingCrescent => "moon.waxing_crescent",
+            MoonPhase::FirstQuarter => "moon.first_quarter",
+            MoonPhase::WaxingGibbous => "moon.waxing_gibbous",
+            MoonPhase::Full => "moon.full",
+            MoonPhase::WaningGibbous => "moon.waning_gibbous",
+            MoonPhase::LastQuarter => "moon.last_quarter",
+            MoonPhase::WaningCrescent => "moon.waning_crescent",
+        }
+    }
+
+    pub fn symbol(&self) -> &'static str {
+        ["🌑", "🌒", "🌓", "🌔", "🌕", "🌖", "🌗", "🌘"][*self as usize]
+    }
+}
+
+// Share of the moon that is lit: 0.0 at new moon, 1.0 at full moon
+pub fn moonlight(day: u32) -> f32 {
+    let phase = (day % LUNAR_CYCLE_DAYS) as f32 / LUNAR_CYCLE_DAYS as f32;
+    (1.0 - (phase * std::f32::consts::TAU).cos()) / 2.0
+}
+
+// === CALENDAR ===
+// In-game date derived from SimClock; only updated when the day changes
+#[derive(Resource, Default, Debug, Clone, Copy)]
+pub struct Calendar {
+    pub day: u32, // Days since the world was created
+    pub year: u32, // Starting at 1
+    pub season: Season,
+    pub day_of_season: u32, // Starting at 1
+    pub moon: MoonPhase,
+}
+
+impl Calendar {
+    pub fn from_day(day: u32) -> Self {
+        let day_of_year = day % DAYS_PER_YEAR;
+        let season = match day_of_year / DAYS_PER_SEASON {
+            0 => Season::Spring,
+            1 => Season::Summer,
+            2 => Season::Autumn,
+            _ => Season::Winter,
+        };
+        Self {
+            day,
+            year: day / DAYS_PER_YEAR + 1,
+            season,
+            day_of_season: day_of_year % DAYS_PER_SEASON + 1,
+            moon: MoonPhase::from_day(day),
+        }
+    }
+}
+
+// Warmest in the middle of summer, coldest in the middle of winter. Season names follow the
+// northern half of the map; the southern half has the opposite season. The swing grows with the
+// axial tilt and towards the poles, so the equator stays warm all year.
+pub fn seasonal_temperature_offset(day: u32, y: usize, params: &GenerationParams) -> f32 {
+    let midsummer = DAYS_PER_SEASON as f32 * 1.5;
+    let phase = ((day % DAYS_PER_YEAR) as f32 + 0.5 - midsummer) / DAYS_PER_YEAR as f32;
+    SEASONAL_SWING * params.axial_tilt / EARTH_TILT * latitude(y) * (phase * std::f32::consts::TAU).cos()
+}
+
+// === SCHEDULER ===
+// Named future events. Interested systems read ScheduledEventFired and match on the name;
+// the queue is stored in save games.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledEvent {
+    pub name: String,
+    pub day: u32, // Fires at the start of this day
+    #[serde(default)]
+    pub repeat_days: Option<u32>, // Re-schedules itself this many days later
+}
+
+#[derive(Resource, Default)]
+pub struct Scheduler {
+    pub events: Vec<ScheduledEvent>,
+}
+
+impl Scheduler {
+    pub fn schedule(&mut self, name: impl Into<String>, day: u32, repeat_days: Option<u32>) {
+        self.events.push(ScheduledEvent { name: name.into(), day, repeat_days: repeat_days.filter(|days| *days > 0) });
+    }
+
+    pub fn cancel(&mut self, name: &str) {
+        self.events.retain(|event| event.name != name);
+    }
+
+    // Pending events, soonest first
+    pub fn upcoming(&self) -> Vec<&ScheduledEvent> {
+        let mut events: Vec<&ScheduledEvent> = self.events.iter().collect();
+        events.sort_by_key(|event| event.day);
+        events
+    }
+}
+
+#[derive(Event)]
+pub struct ScheduledEventFired {
+    pub name: String,
+    pub day: u32,
+}
+
+fn update_calendar(clock: Res<SimClock>, mut calendar: ResMut<Calendar>) {
+    let day = clock.day();
+    if calendar.day != day || calendar.year == 0 {
+        *calendar = Calendar::from_day(day);
+    }
+}
+
+fn fire_scheduled_events(
+    calendar: Res<Calendar>,
+    mut scheduler: ResMut<Scheduler>,
+    mut fired: EventWriter<ScheduledEventFired>,
+) {
+    if !calendar.is_changed() {
+        return;
+    }
+    let day = calendar.day;
+    scheduler.events.retain_mut(|event| {
+        if event.day > day {
+            return true;
+        }
+        fired.send(ScheduledEventFired { name: event.name.clone(), day });
+        match event.repeat_days {
+            Some(repeat) => {
+                // Skip any repeats that fell into a gap (e.g. a loaded save)
+                while event.day <= day {
+                    event.day += repeat;
+                }
+                true
+            }
+            None => false,
+        }
+    });
+}
+
+fn log_scheduled_events(mut fired: EventReader<ScheduledEventFired>) {
+    for event in fired.read() {
+        info!("📅 Day {}: scheduled event '{}'", event.day, event.name);
+    }
+}
+
+#[derive(Component)]
+pub struct DateLabel;
+
+fn spawn_date_label(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 16.0,
+                color: Color::srgb(0.9, 0.9, 0.9),
+                ..default()
+            },
+        ).with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            left: Val::Percent(45.0),
+            padding: UiRect::all(Val::Px(6.0)),
+            ..default()
+        }).with_background_color(Color::srgba(0.05, 0.05, 0.1, 0.7)),
+        DateLabel,
+    ));
+}
+
+fn update_date_label(
+    calendar: Res<Calendar>,
+    localization: Res<Localization>,
+    mut labels: Query<&mut Text, With<DateLabel>>,
+) {
+    for mut text in labels.iter_mut() {
+        text.sections[0].value = localization.format("calendar.date", &[
+            ("year", calendar.year.to_string()),
+            ("season", localization.get(calendar.season.label_key()).to_string()),
+            ("day", calendar.day_of_season.to_string()),
+            ("moon", format!("{} {}", calendar.moon.symbol(), localization.get(calendar.moon.label_key()))),
+        ]);
+    }
+}
@@ -0,0 +1,213 @@
+use bevy::audio::Volume;
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::HashMap;
+use crate::camera::MainCamera;
+use crate::creature::{move_creatures, Behavior, Creature, Needs, Wander, SEEK_THRESHOLD};
+use crate::genetics::Genome;
+use crate::predation::{hunt_prey, FLEE_DISTANCE};
+use crate::profiling::budgeted;
+use crate::reproduction::ready_to_breed;
+use crate::settings::Settings;
+use crate::species::SpeciesRegistry;
+use crate::speciation::can_interbreed;
+use crate::simulation::SimClock;
+use crate::taming::Tamed;
+use crate::world::WorldMap;
+use crate::rng_streams::{RngStream, RngStreams};
+
+const CALL_COOLDOWN: f64 = 10.0; // Simulated seconds before a creature calls again
+const MATING_CALL_CHANCE: f32 = 1.0 / 60.0; // Per second, while ready to breed
+const RING_SECONDS: f32 = 1.0; // How long a call's ring takes to spread out
+const HEARING_DISTANCE: f32 = 250.0; // World units from the camera within which calls are played
+const SOUND_GAP: f32 = 0.3; // Real seconds between two played calls
+
+pub struct CallsPlugin;
+
+impl Plugin for CallsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<CreatureCall>()
+            .add_systems(FixedUpdate, (emit_calls, budgeted(answer_calls, 1.0))
+                .chain()
+                .after(hunt_prey)
+                .before(move_creatures)
+                .run_if(resource_exists::<WorldMap>))
+            .add_systems(Update, (draw_call_rings, play_call_sounds.run_if(call_sounds_enabled)));
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CallKind {
+    Alarm, // A hunter is on the caller: run
+    Mating, // Ready to breed: come over
+    Rally, // Found food or water here: come over
+}
+
+impl CallKind {
+    // World units the call carries
+    pub fn radius(&self) -> f32 {
+        match self {
+            CallKind::Alarm => 60.0,
+            CallKind::Mating => 100.0,
+            CallKind::Rally => 80.0,
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            CallKind::Alarm => Color::srgb(1.0, 0.35, 0.3),
+            CallKind::Mating => Color::srgb(1.0, 0.5, 0.85),
+            CallKind::Rally => Color::srgb(0.5, 0.9, 0.5),
+        }
+    }
+
+    // Optional sound, looked up under assets/; no sounds ship with the game
+    fn sound_path(&self) -> &'static str {
+        match self {
+            CallKind::Alarm => "sounds/call_alarm.ogg",
+            CallKind::Mating => "sounds/call_mating.ogg",
+            CallKind::Rally => "sounds/call_rally.ogg",
+        }
+    }
+}
+
+type CallerQueryData<'a> = (Entity, &'a Transform, &'a Creature, &'a Needs, &'a Genome, &'a Wander);
+type ListenerQueryData<'a> = (Entity, &'a Transform, &'a Creature, &'a Needs, &'a Genome, &'a mut Wander, Option<&'a Tamed>);
+
+// === CALLS ===
+// Creatures call out to their own species: an alarm when a hunter is after them, a mating call
+// when ready to breed, and a rally call on reaching food or water. Conspecifics within earshot
+// react in answer_calls, before move_creatures steers them. Each creature calls at most once per
+// CALL_COOLDOWN, and answering never makes another call, so calls do not cascade. Mating calls
+// are only answered by creatures close enough in traits to interbreed (see speciation.rs).
+#[derive(Event, Clone, Copy)]
+pub struct CreatureCall {
+    pub caller: Entity,
+    pub species: usize,
+    pub kind: CallKind,
+    pub position: Vec2,
+    pub genome: Genome, // Of the caller
+}
+
+fn emit_calls(
+    time: Res<Time>,
+    clock: Res<SimClock>,
+    mut last_called: Local<HashMap<Entity, f64>>,
+    creatures: Query<CallerQueryData, Without<Tamed>>,
+    mut calls: EventWriter<CreatureCall>,
+    mut streams: ResMut<RngStreams>,
+) {
+    let rng = streams.get(RngStream::Creatures);
+    let now = clock.elapsed;
+    last_called.retain(|_, at| now - *at < CALL_COOLDOWN);
+
+    for (entity, transform, creature, needs, genome, wander) in creatures.iter() {
+        if last_called.contains_key(&entity) {
+            continue;
+        }
+        let kind = match wander.behavior {
+            Behavior::Flee => CallKind::Alarm,
+            Behavior::Arrive => CallKind::Rally,
+            _ if ready_to_breed(creature, needs) && rng.gen_bool((MATING_CALL_CHANCE * time.delta_seconds()) as f64) => CallKind::Mating,
+            _ => continue,
+        };
+        last_called.insert(entity, now);
+        calls.send(CreatureCall {
+            caller: entity,
+            species: creature.species,
+            kind,
+            position: transform.translation.truncate(),
+            genome: *genome,
+        });
+    }
+}
+
+fn answer_calls(
+    (settings, registry): (Res<Settings>, Res<SpeciesRegistry>),
+    mut calls: EventReader<CreatureCall>,
+    mut creatures: Query<ListenerQueryData>,
+) {
+    for call in calls.read() {
+        for (entity, transform, creature, needs, genome, mut wander, tamed) in creatures.iter_mut() {
+            let position = transform.translation.truncate();
+            // Mating calls carry across species that split recently, in hybrid zones
+            let heard = match call.kind {
+                CallKind::Mating => can_interbreed((creature.species, genome), (call.species, &call.genome), &registry, &settings),
+                _ => creature.species == call.species,
+            };
+            if entity == call.caller || !heard || Tamed::lead(tamed).is_some()
+                || position.distance(call.position) > call.kind.radius() {
+                continue;
+            }
+            match call.kind {
+                // Run the same way as the caller, whatever they were doing; tamed creatures stay calm
+                CallKind::Alarm if tamed.is_none() && wander.behavior != Behavior::Flee => {
+                    let away = (position - call.position).normalize_or_zero();
+                    wander.target = Some(position + away * FLEE_DISTANCE);
+                    wander.behavior = Behavior::Flee;
+                    wander.search_cooldown = 2.0;
+                }
+                CallKind::Mating if wander.target.is_none() && ready_to_breed(creature, needs) => {
+                    wander.target = Some(call.position);
+                    wander.behavior = Behavior::AnswerCall;
+                }
+                CallKind::Rally if wander.target.is_none() && (needs.hunger > SEEK_THRESHOLD || needs.thirst > SEEK_THRESHOLD) => {
+                    wander.target = Some(call.position);
+                    wander.behavior = Behavior::AnswerCall;
+                    wander.search_cooldown = 2.0; // Trust the caller over its own search for a while
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+// Each call spreads as a fading ring out to the distance it carries
+fn draw_call_rings(
+    time: Res<Time>,
+    mut calls: EventReader<CreatureCall>,
+    mut rings: Local<Vec<(CreatureCall, f32)>>,
+    mut gizmos: Gizmos,
+) {
+    rings.extend(calls.read().map(|call| (*call, 0.0)));
+    rings.retain_mut(|(_, age)| {
+        *age += time.delta_seconds();
+        *age < RING_SECONDS
+    });
+    for (call, age) in rings.iter() {
+        let progress = age / RING_SECONDS;
+        gizmos.circle_2d(call.position, call.kind.radius() * progress, call.kind.color().with_alpha(0.6 * (1.0 - progress)));
+    }
+}
+
+fn call_sounds_enabled(settings: Res<Settings>) -> bool {
+    settings.call_sounds
+}
+
+// Calls near the camera are played, quieter with distance
+fn play_call_sounds(
+    mut commands: Commands,
+    time: Res<Time>,
+    asset_server: Res<AssetServer>,
+    cameras: Query<&Transform, With<MainCamera>>,
+    mut calls: EventReader<CreatureCall>,
+    mut since_last: Local<f32>,
+) {
+    *since_last += time.delta_seconds();
+    let Ok(camera) = cameras.get_single() else { return };
+    let listener = camera.translation.truncate();
+    let nearest = calls.read()
+        .map(|call| (call.kind, call.position.distance(listener)))
+        .filter(|(_, distance)| *distance < HEARING_DISTANCE)
+        .min_by(|a, b| a.1.total_cmp(&b.1));
+    let Some((kind, distance)) = nearest else { return };
+    if *since_last < SOUND_GAP {
+        return;
+    }
+    *since_last = 0.0;
+    commands.spawn(AudioBundle {
+        source: asset_server.load(kind.sound_path()),
+        settings: PlaybackSettings::DESPAWN.with_volume(Volume::new(1.0 - distance / HEARING_DISTANCE)),
+    });
+}
@@ -0,0 +1,268 @@
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::coords::{tile_to_world, wrap_position, CHUNK_SIZE, TILE_SIZE, WORLD_EXTENT};
+use crate::inset_camera::InsetCamera;
+use crate::keybindings::{Action, Controls};
+use crate::save::SaveViewExt;
+use crate::settings::Settings;
+use crate::text_input::{text_input_inactive, PromptKind, PromptSubmitted, TextPrompt};
+use crate::world::{Topology, WorldMap, WORLD_SIZE};
+
+const CAMERA_SPEED: f32 = 300.0;
+const CAMERA_SPRINT_MULTIPLIER: f32 = 3.0; // While Action::PanFast (Shift) is held
+const CAMERA_ACCELERATION: f32 = 8.0; // Exponential approach rate towards the target velocity (per second)
+const EDGE_SCROLL_MARGIN: f32 = 12.0; // Pixels from the window edge that trigger edge scrolling
+const PAN_SPEED: f32 = 6.0; // Exponential approach rate of animated pans (per second)
+const PAN_ARRIVE_DISTANCE: f32 = 0.5; // World units
+pub const ZOOM_STEP: f32 = 1.15; // Scale factor per mouse wheel notch
+pub const MIN_ZOOM: f32 = 0.25; // Orthographic scale limits
+pub const MAX_ZOOM: f32 = 12.0;
+
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<CameraBookmarks>()
+            .init_resource::<CameraPan>()
+            .init_resource::<CameraZoom>()
+            .save_view("camera", save_camera, load_camera)
+            .add_systems(Update, (
+                (handle_camera_bookmarks, open_go_to_prompt).run_if(text_input_inactive),
+                handle_camera_movement.run_if(text_input_inactive),
+                zoom_with_mouse_wheel,
+                go_to_submitted_location,
+                apply_camera_pan,
+            ).chain())
+            .add_systems(PostUpdate, clamp_camera_to_world.before(TransformSystem::TransformPropagate));
+    }
+}
+
+// The camera the user steers; others, like the inset view, only follow along
+#[derive(Component)]
+pub struct MainCamera;
+
+// For turning cursor positions into world positions
+pub type MainCameraQuery<'w, 's> = Query<'w, 's, (&'static Camera, &'static GlobalTransform), With<MainCamera>>;
+
+// Saved camera positions, indexed by number key
+#[derive(Resource, Default)]
+pub struct CameraBookmarks {
+    pub slots: [Option<Vec2>; 10],
+}
+
+// Pending animated pan; manual camera movement cancels it
+#[derive(Resource, Default)]
+pub struct CameraPan {
+    pub target: Option<Vec2>,
+}
+
+impl CameraPan {
+    // Animated unless smooth_camera is off or reduced motion is on, in which case the camera jumps
+    pub fn go_to(&mut self, target: Vec2) {
+        self.target = Some(target);
+    }
+}
+
+// Where the user was looking, for save files
+#[derive(Serialize, Deserialize)]
+struct SavedCamera {
+    position: (f32, f32),
+    zoom: f32,
+    bookmarks: Vec<Option<(f32, f32)>>,
+}
+
+fn save_camera(world: &mut World) -> Option<SavedCamera> {
+    let transform = world.query_filtered::<&Transform, With<MainCamera>>().get_single(world).ok()?;
+    let position = transform.translation.truncate().into();
+    Some(SavedCamera {
+        position,
+        zoom: world.resource::<CameraZoom>().scale,
+        bookmarks: world.resource::<CameraBookmarks>().slots.iter().map(|slot| slot.map(Into::into)).collect(),
+    })
+}
+
+// Jumps straight there, cancelling any pan under way
+fn load_camera(world: &mut World, saved: SavedCamera) {
+    if let Ok(mut transform) = world.query_filtered::<&mut Transform, With<MainCamera>>().get_single_mut(world) {
+        transform.translation = Vec2::from(saved.position).extend(transform.translation.z);
+    }
+    world.resource_mut::<CameraPan>().target = None;
+    world.resource_mut::<CameraZoom>().scale = saved.zoom.clamp(MIN_ZOOM, MAX_ZOOM);
+    let mut bookmarks = world.resource_mut::<CameraBookmarks>();
+    for (slot, saved) in bookmarks.slots.iter_mut().zip(saved.bookmarks) {
+        *slot = saved.map(Vec2::from);
+    }
+}
+
+// The bindings list one key per slot
+fn handle_camera_bookmarks(
+    controls: Controls,
+    cameras: Query<&Transform, With<MainCamera>>,
+    mut bookmarks: ResMut<CameraBookmarks>,
+    mut pan: ResMut<CameraPan>,
+) {
+    let Ok(camera_transform) = cameras.get_single() else { return };
+    if let Some(slot) = controls.just_pressed_index(Action::StoreBookmark).filter(|slot| *slot < bookmarks.slots.len()) {
+        bookmarks.slots[slot] = Some(camera_transform.translation.truncate());
+        info!("🔖 Camera bookmark {} set", slot);
+    }
+    if let Some(position) = controls.just_pressed_index(Action::RecallBookmark).and_then(|slot| bookmarks.slots.get(slot).copied().flatten()) {
+        pan.go_to(position);
+    }
+}
+
+fn open_go_to_prompt(controls: Controls, mut prompt: ResMut<TextPrompt>) {
+    if controls.just_pressed(Action::GoToLocation) {
+        prompt.open(PromptKind::GoToLocation, String::new());
+    }
+}
+
+// Accepts tile coordinates as "x,y" or "x y"
+pub fn parse_tile_coordinates(text: &str) -> Option<(usize, usize)> {
+    let mut parts = text.split(|c: char| c == ',' || c.is_whitespace()).filter(|part| !part.is_empty());
+    let x = parts.next()?.parse().ok()?;
+    let y = parts.next()?.parse().ok()?;
+    if parts.next().is_some() || x >= WORLD_SIZE || y >= WORLD_SIZE {
+        return None;
+    }
+    Some((x, y))
+}
+
+fn go_to_submitted_location(mut submitted: EventReader<PromptSubmitted>, mut pan: ResMut<CameraPan>) {
+    for event in submitted.read() {
+        if event.kind != PromptKind::GoToLocation {
+            continue;
+        }
+        match parse_tile_coordinates(&event.text) {
+            Some((x, y)) => pan.go_to(tile_to_world(x, y)),
+            None => warn!("Go to expects tile coordinates \"x,y\" between 0 and {}, got \"{}\"", WORLD_SIZE - 1, event.text),
+        }
+    }
+}
+
+// Orthographic scale the user asked for; applied (eased) in auto_camera.rs, which overrides it
+// while following the action
+#[derive(Resource)]
+pub struct CameraZoom {
+    pub scale: f32,
+}
+
+impl Default for CameraZoom {
+    fn default() -> Self {
+        Self { scale: 1.0 }
+    }
+}
+
+// Left to the inset camera while the cursor is over it
+fn zoom_with_mouse_wheel(mut wheel: EventReader<MouseWheel>, inset: Res<InsetCamera>, mut zoom: ResMut<CameraZoom>) {
+    let notches: f32 = wheel.read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / 40.0,
+        })
+        .sum();
+    if notches != 0.0 && !inset.hovered {
+        zoom.scale = (zoom.scale * ZOOM_STEP.powf(-notches)).clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+}
+
+fn apply_camera_pan(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut pan: ResMut<CameraPan>,
+    mut cameras: Query<&mut Transform, With<MainCamera>>,
+) {
+    let Some(target) = pan.target else { return };
+    let Ok(mut camera_transform) = cameras.get_single_mut() else { return };
+
+    let current = camera_transform.translation.truncate();
+    let next = if settings.smooth_camera && !settings.reduced_motion {
+        current.lerp(target, 1.0 - (-PAN_SPEED * time.delta_seconds()).exp())
+    } else {
+        target
+    };
+
+    camera_transform.translation.x = next.x;
+    camera_transform.translation.y = next.y;
+    if next.distance(target) < PAN_ARRIVE_DISTANCE {
+        camera_transform.translation.x = target.x;
+        camera_transform.translation.y = target.y;
+        pan.target = None;
+    }
+}
+
+// Keyboard/edge-scroll panning with eased acceleration and deceleration
+fn handle_camera_movement(
+    mut camera_query: Query<(&mut Transform, &OrthographicProjection), With<MainCamera>>,
+    controls: Controls,
+    windows: Query<&Window>,
+    settings: Res<Settings>,
+    time: Res<Time>,
+    mut pan: ResMut<CameraPan>,
+    mut velocity: Local<Vec2>,
+) {
+    if let Ok((mut camera_transform, projection)) = camera_query.get_single_mut() {
+        let mut direction = Vec2::ZERO;
+
+        if controls.pressed(Action::PanUp) {
+            direction.y += 1.0;
+        }
+        if controls.pressed(Action::PanDown) {
+            direction.y -= 1.0;
+        }
+        if controls.pressed(Action::PanLeft) {
+            direction.x -= 1.0;
+        }
+        if controls.pressed(Action::PanRight) {
+            direction.x += 1.0;
+        }
+
+        if settings.edge_scrolling {
+            if let Some((window, cursor)) = windows.get_single().ok()
+                .and_then(|window| window.cursor_position().map(|cursor| (window, cursor))) {
+                // Window y runs top-down
+                if cursor.x < EDGE_SCROLL_MARGIN { direction.x -= 1.0; }
+                if cursor.x > window.width() - EDGE_SCROLL_MARGIN { direction.x += 1.0; }
+                if cursor.y < EDGE_SCROLL_MARGIN { direction.y += 1.0; }
+                if cursor.y > window.height() - EDGE_SCROLL_MARGIN { direction.y -= 1.0; }
+            }
+        }
+
+        let sprint = controls.pressed(Action::PanFast);
+        let speed = if sprint { CAMERA_SPEED * CAMERA_SPRINT_MULTIPLIER } else { CAMERA_SPEED };
+        // Same on-screen speed at every zoom level, along the screen whichever way the view turns
+        let desired = settings.view.screen_to_world(direction.normalize_or_zero()) * speed * projection.scale;
+
+        if desired != Vec2::ZERO {
+            pan.target = None; // Manual movement takes over from any animated pan
+        }
+
+        *velocity = if settings.reduced_motion {
+            desired
+        } else {
+            velocity.lerp(desired, 1.0 - (-CAMERA_ACCELERATION * time.delta_seconds()).exp())
+        };
+        if desired == Vec2::ZERO && velocity.length() < 1.0 {
+            *velocity = Vec2::ZERO;
+        }
+
+        camera_transform.translation += velocity.extend(0.0) * time.delta_seconds();
+    }
+}
+
+// Keep the camera centre within one chunk of the world edge, whatever moved it. Across a wrapped
+// edge it comes back in on the other side instead.
+fn clamp_camera_to_world(world_map: Option<Res<WorldMap>>, mut camera_query: Query<&mut Transform, With<MainCamera>>) {
+    let topology = world_map.map_or(Topology::Flat, |world_map| world_map.params.topology);
+    let half_extent = WORLD_EXTENT / 2.0 + CHUNK_SIZE as f32 * TILE_SIZE;
+    for mut camera_transform in camera_query.iter_mut() {
+        let clamped = wrap_position(camera_transform.translation.truncate(), topology)
+            .clamp(Vec2::splat(-half_extent), Vec2::splat(half_extent));
+        if clamped != camera_transform.translation.truncate() {
+            camera_transform.translation.x = clamped.x;
+            camera_transform.translation.y = clamped.y;
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::time::common_conditions::on_timer;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use crate::coords::{tile_to_chunk, tile_to_world, world_to_tile, CHUNKS_PER_SIDE, CHUNK_SIZE, TILE_SIZE};
+use crate::creature::Creature;
+use crate::keybindings::{Action, Controls};
+use crate::save::SaveViewExt;
+use crate::species::SpeciesRegistry;
+use crate::text_input::text_input_inactive;
+use crate::world::WorldMap;
+
+const CENSUS_SIDE: usize = CHUNKS_PER_SIDE as usize; // One cell per render chunk
+const CENSUS_REFRESH_SECONDS: f32 = 3.0;
+const CENSUS_Z: f32 = 4.0; // Under the pheromone and death overlays
+
+pub struct CensusPlugin;
+
+impl Plugin for CensusPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<CensusMode>()
+            .save_view_resource::<CensusMode>("census")
+            .add_systems(Startup, spawn_census_overlay)
+            .add_systems(Update, (
+                cycle_census_mode.run_if(text_input_inactive),
+                show_census_overlay.run_if(resource_changed::<CensusMode>),
+                update_census_image.run_if(resource_changed::<CensusMode>
+                    .or_else(on_timer(Duration::from_secs_f32(CENSUS_REFRESH_SECONDS)))),
+            ).chain().run_if(resource_exists::<WorldMap>));
+    }
+}
+
+// === SPECIES CENSUS ===
+// Colors each render chunk by the species with most members in it, to show where ranges meet,
+// or by how many species live there, to show diversity hotspots. Creatures are counted per chunk
+// on a slow timer while the overlay is shown; the key cycles dominant species, richness and off.
+#[derive(Resource, Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CensusMode {
+    #[default]
+    Off,
+    Dominant,
+    Richness,
+}
+
+impl CensusMode {
+    fn next(self) -> Self {
+        match self {
+            CensusMode::Off => CensusMode::Dominant,
+            CensusMode::Dominant => CensusMode::Richness,
+            CensusMode::Richness => CensusMode::Off,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct CensusOverlay {
+    pub image: Handle<Image>,
+}
+
+fn spawn_census_overlay(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let image = Image::new_fill(
+        Extent3d { width: CENSUS_SIDE as u32, height: CENSUS_SIDE as u32, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    let image = images.add(image);
+
+    // The last chunks are cut short, so the overlay reaches past the map's high edges
+    let extent = (CENSUS_SIDE * CHUNK_SIZE) as f32 * TILE_SIZE;
+    let centre = tile_to_world(0, 0) - TILE_SIZE / 2.0 + extent / 2.0;
+    commands.spawn((
+        SpriteBundle {
+            texture: image.clone(),
+            sprite: Sprite {
+                custom_size: Some(Vec2::splat(extent)),
+                ..default()
+            },
+            transform: Transform::from_translation(centre.extend(CENSUS_Z)),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        CensusOverlay { image },
+    ));
+}
+
+fn cycle_census_mode(controls: Controls, mut mode: ResMut<CensusMode>) {
+    if controls.just_pressed(Action::CycleCensus) {
+        *mode = mode.next();
+        info!("🗺️ Species census: {:?}", *mode);
+    }
+}
+
+// Also when a save sets the mode
+fn show_census_overlay(mode: Res<CensusMode>, mut overlay: Query<&mut Visibility, With<CensusOverlay>>) {
+    for mut visibility in overlay.iter_mut() {
+        *visibility = if *mode == CensusMode::Off { Visibility::Hidden } else { Visibility::Visible };
+    }
+}
+
+fn update_census_image(
+    mode: Res<CensusMode>,
+    registry: Res<SpeciesRegistry>,
+    creatures: Query<(&Transform, &Creature)>,
+    overlay: Query<&CensusOverlay>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    if *mode == CensusMode::Off {
+        return;
+    }
+    let Ok(overlay) = overlay.get_single() else { return };
+    let Some(image) = images.get_mut(&overlay.image) else { return };
+
+    let species_count = registry.species.len();
+    let mut counts = vec![0u32; CENSUS_SIDE * CENSUS_SIDE * species_count];
+    for (transform, creature) in creatures.iter() {
+        let Some((x, y)) = world_to_tile(transform.translation.truncate()) else { continue };
+        let (cx, cy) = tile_to_chunk(x, y);
+        let cell = cx as usize * CENSUS_SIDE + cy as usize;
+        if let Some(count) = counts.get_mut(cell * species_count + creature.species) {
+            *count += 1;
+        }
+    }
+
+    let richest = counts.chunks(species_count.max(1))
+        .map(|cell| cell.iter().filter(|count| **count > 0).count())
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    for cx in 0..CENSUS_SIDE {
+        for cy in 0..CENSUS_SIDE {
+            let cell = cx * CENSUS_SIDE + cy;
+            let cell_counts = &counts[cell * species_count..(cell + 1) * species_count];
+            let color = match *mode {
+                CensusMode::Dominant => cell_counts.iter().enumerate()
+                    .filter(|(_, count)| **count > 0)
+                    .max_by_key(|(_, count)| **count)
+                    .map_or([0, 0, 0, 0], |(species, _)| {
+                        let (r, g, b) = registry.get(species).color;
+                        [(r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8, 150]
+                    }),
+                // Deep blue for a single species, through to yellow for the richest chunk
+                _ => match cell_counts.iter().filter(|count| **count > 0).count() {
+                    0 => [0, 0, 0, 0],
+                    richness => {
+                        let share = richness as f32 / richest as f32;
+                        [(255.0 * share) as u8, (60.0 + 180.0 * share) as u8, (200.0 * (1.0 - share)) as u8, 150]
+                    }
+                },
+            };
+            // Image rows run top-down while world y runs bottom-up
+            let pixel = ((CENSUS_SIDE - 1 - cy) * CENSUS_SIDE + cx) * 4;
+            image.data[pixel..pixel + 4].copy_from_slice(&color);
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use bevy::prelude::*;
+use std::cmp::Reverse;
+use std::fs;
+use std::path::PathBuf;
+use std::time::SystemTime;
+use crate::biome::{BiomeType, ResourceType};
+use crate::world::{GenerationParams, Tile};
+
+pub const CHUNK_CACHE_DIR: &str = "chunk_cache";
+const CACHE_VERSION: u32 = 1; // Bump when generation or the file layout changes
+const LAST_USED_FILE: &str = "last_used";
+
+// === CHUNK CACHE ===
+// Generated tiles of each generation chunk, written to CHUNK_CACHE_DIR so the same world loads
+// from disk next time instead of being sampled again. Every world gets its own directory, named
+// by a hash of CACHE_VERSION, the seed and all generation parameters, so changing any setting
+// simply misses the cache. Past the size cap (Settings::chunk_cache_mb) the least recently used
+// worlds are deleted. Best effort throughout: an unreadable file is regenerated, a failed write
+// only logged.
+#[derive(Clone)]
+pub struct ChunkCache {
+    dir: PathBuf,
+    cap_bytes: u64,
+}
+
+// FNV-1a, stable across builds unlike std's hasher
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| (hash ^ *byte as u64).wrapping_mul(0x0100_0000_01b3))
+}
+
+impl ChunkCache {
+    pub fn new(seed: u32, params: &GenerationParams, cap_mb: u32) -> Self {
+        let params = ron::to_string(params).unwrap_or_default();
+        let key = fnv1a(format!("{}|{}|{}", CACHE_VERSION, seed, params).as_bytes());
+        Self {
+            dir: PathBuf::from(CHUNK_CACHE_DIR).join(format!("{:016x}", key)),
+            cap_bytes: cap_mb as u64 * 1024 * 1024,
+        }
+    }
+
+    fn chunk_path(&self, chunk_x: usize, chunk_y: usize) -> PathBuf {
+        self.dir.join(format!("{}_{}.chunk", chunk_x, chunk_y))
+    }
+
+    // The chunk's tiles in generation order, if cached and intact
+    pub fn load(&self, chunk_x: usize, chunk_y: usize, tile_count: usize) -> Option<Vec<Tile>> {
+        let bytes = fs::read(self.chunk_path(chunk_x, chunk_y)).ok()?;
+        let tiles = decode_tiles(&bytes)?;
+        (tiles.len() == tile_count).then_some(tiles)
+    }
+
+    pub fn store(&self, chunk_x: usize, chunk_y: usize, tiles: &[Tile]) {
+        let path = self.chunk_path(chunk_x, chunk_y);
+        if let Err(err) = fs::create_dir_all(&self.dir).and_then(|()| fs::write(&path, encode_tiles(tiles))) {
+            debug!("Could not cache chunk {}: {}", path.display(), err);
+        }
+    }
+
+    // Marks this world as just used, then deletes the least recently used others past the cap
+    pub fn finish(&self) {
+        if fs::create_dir_all(&self.dir).and_then(|()| fs::write(self.dir.join(LAST_USED_FILE), [])).is_err() {
+            return;
+        }
+        let Ok(entries) = fs::read_dir(CHUNK_CACHE_DIR) else { return };
+        let mut worlds: Vec<(SystemTime, u64, PathBuf)> = entries
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.is_dir())
+            .map(|path| {
+                let used = fs::metadata(path.join(LAST_USED_FILE)).and_then(|meta| meta.modified())
+                    .unwrap_or(SystemTime::UNIX_EPOCH);
+                let size = fs::read_dir(&path).into_iter().flatten()
+                    .filter_map(|file| file.ok()?.metadata().ok())
+                    .map(|meta| meta.len())
+                    .sum();
+                (used, size, path)
+            })
+            .collect();
+        worlds.sort_by_key(|world| Reverse(world.0)); // Most recent first
+
+        let mut total = 0;
+        for (_, size, path) in worlds {
+            total += size;
+            if total > self.cap_bytes && path != self.dir {
+                match fs::remove_dir_all(&path) {
+                    Ok(()) => debug!("Evicted cached world {}", path.display()),
+                    Err(err) => debug!("Could not evict cached world {}: {}", path.display(), err),
+                }
+                total -= size;
+            }
+        }
+    }
+}
+
+// Per tile: biome, elevation, temperature, moisture, resource count, resources; indices into ALL
+fn encode_tiles(tiles: &[Tile]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(tiles.len() * 16);
+    for tile in tiles {
+        bytes.push(BiomeType::ALL.iter().position(|biome| *biome == tile.biome).unwrap_or(0) as u8);
+        for value in [tile.elevation, tile.temperature, tile.moisture] {
+            bytes.extend_from_slice(&value.to_le_bytes());
+        }
+        bytes.push(tile.resources.len() as u8);
+        bytes.extend(tile.resources.iter()
+            .map(|resource| ResourceType::ALL.iter().position(|r| r == resource).unwrap_or(0) as u8));
+    }
+    bytes
+}
+
+fn decode_tiles(mut bytes: &[u8]) -> Option<Vec<Tile>> {
+    let mut tiles = Vec::new();
+    let mut take = |count: usize| {
+        let (head, rest) = bytes.split_at_checked(count)?;
+        bytes = rest;
+        Some(head)
+    };
+    let float = |slice: &[u8]| f32::from_le_bytes(slice.try_into().unwrap_or_default());
+    while let Some(biome) = take(1) {
+        let biome = *BiomeType::ALL.get(biome[0] as usize)?;
+        let climate = take(12)?;
+        let count = take(1)?[0] as usize;
+        let resources = take(count)?.iter()
+            .map(|index| ResourceType::ALL.get(*index as usize).copied())
+            .collect::<Option<Vec<_>>>()?;
+        tiles.push(Tile {
+            biome,
+            elevation: float(&climate[0..4]),
+            temperature: float(&climate[4..8]),
+            moisture: float(&climate[8..12]),
+            resources,
+        });
+    }
+    Some(tiles)
+}
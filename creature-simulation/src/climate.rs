@@ -0,0 +1,186 @@
+use bevy::prelude::*;
+use std::collections::{HashMap, VecDeque};
+use crate::coords::{tile_to_chunk, tile_to_world};
+use crate::biome::BiomeType;
+use crate::calendar::Calendar;
+use crate::events::NotableEvent;
+use crate::hydrology::Hydrology;
+use crate::optimization::ChunkManager;
+use crate::simulation::DAYS_PER_YEAR;
+use crate::world::{WorldGenerator, WorldMap, WORLD_SIZE};
+
+pub const SHIFT_YEARS: u8 = 3; // Years outside its biome's envelope before a tile changes biome
+const DEFAULT_WARMING: f32 = 0.005; // Tile temperature gained per year
+const AVERAGE_DAYS: f32 = DAYS_PER_YEAR as f32; // Time constant of the running climate means
+
+pub struct ClimatePlugin;
+
+impl Plugin for ClimatePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ClimateTrend>()
+            .add_systems(Update, init_climate_record.run_if(resource_added::<WorldMap>))
+            .add_systems(FixedUpdate, (drift_climate, shift_biomes)
+                .chain()
+                .run_if(resource_exists::<ClimateRecord>)
+                .run_if(resource_changed::<Calendar>));
+    }
+}
+
+// === CLIMATE CHANGE ===
+// A slow global trend: every day tiles warm and (optionally) dry by a share of the yearly rate,
+// so all systems reading tile temperature and moisture feel it. Set with the "climate" console
+// command.
+#[derive(Resource)]
+pub struct ClimateTrend {
+    pub warming_per_year: f32,
+    pub drying_per_year: f32, // Generated moisture lost per year, applied through hydrology
+}
+
+impl Default for ClimateTrend {
+    fn default() -> Self {
+        Self { warming_per_year: DEFAULT_WARMING, drying_per_year: 0.0 }
+    }
+}
+
+// Running yearly means of every tile's climate. Once a year each land tile's means are classified
+// like world generation does; a tile whose climate has left both its current biome and the one it
+// was generated for for SHIFT_YEARS years in a row moves one step towards the new biome along
+// BiomeType::can_transition_to, so a forest turns to grassland before savanna and desert.
+#[derive(Resource)]
+pub struct ClimateRecord {
+    pub mean_temperature: Vec<f32>, // Indexed [x * WORLD_SIZE + y]
+    pub mean_moisture: Vec<f32>,
+    baseline: Vec<BiomeType>, // Biome of the generated climate, so edited tiles are left alone
+    years_outside: Vec<u8>,
+    pub shifted_tiles: usize, // Since the world was created
+}
+
+impl ClimateRecord {
+    pub fn from_world_map(world_map: &WorldMap) -> Self {
+        let tiles = || world_map.tiles.iter().flatten();
+        Self {
+            mean_temperature: tiles().map(|tile| tile.temperature).collect(),
+            mean_moisture: tiles().map(|tile| tile.moisture).collect(),
+            baseline: tiles()
+                .map(|tile| WorldGenerator::determine_biome_fast(tile.elevation, tile.temperature, tile.moisture, &world_map.params))
+                .collect(),
+            years_outside: vec![0; WORLD_SIZE * WORLD_SIZE],
+            shifted_tiles: 0,
+        }
+    }
+}
+
+// First step on the shortest chain of allowed transitions from one biome to another
+fn next_step(from: BiomeType, to: BiomeType) -> Option<BiomeType> {
+    let mut came_from: HashMap<BiomeType, BiomeType> = HashMap::new();
+    let mut queue = VecDeque::from([from]);
+    while let Some(biome) = queue.pop_front() {
+        if biome == to {
+            let mut step = to;
+            while came_from[&step] != from {
+                step = came_from[&step];
+            }
+            return Some(step);
+        }
+        for next in BiomeType::ALL {
+            if next != from && biome.can_transition_to(&next) && !came_from.contains_key(&next) {
+                came_from.insert(next, biome);
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+fn init_climate_record(mut commands: Commands, world_map: Res<WorldMap>) {
+    commands.insert_resource(ClimateRecord::from_world_map(&world_map));
+}
+
+// Once per day: apply the trend, then fold today's climate into the means
+fn drift_climate(
+    trend: Res<ClimateTrend>,
+    mut world_map: ResMut<WorldMap>,
+    mut hydrology: Option<ResMut<Hydrology>>,
+    mut record: ResMut<ClimateRecord>,
+) {
+    let warming = trend.warming_per_year / DAYS_PER_YEAR as f32;
+    let drying = trend.drying_per_year / DAYS_PER_YEAR as f32;
+    if let (Some(hydrology), true) = (hydrology.as_mut(), drying != 0.0) {
+        for moisture in hydrology.base_moisture.iter_mut() {
+            *moisture = (*moisture - drying).clamp(0.0, 1.0);
+        }
+    }
+
+    // Like hydrology's moisture updates, not a structural change
+    let world_map = world_map.bypass_change_detection();
+    let record = &mut *record;
+    for (x, column) in world_map.tiles.iter_mut().enumerate() {
+        for (y, tile) in column.iter_mut().enumerate() {
+            tile.temperature = (tile.temperature + warming).clamp(0.0, 1.0);
+            let index = x * WORLD_SIZE + y;
+            // Generated moisture as dried by the trend; rain and river levels are weather, not climate
+            let moisture = hydrology.as_ref().map_or(tile.moisture, |hydrology| hydrology.base_moisture[index]);
+            record.mean_temperature[index] += (tile.temperature - record.mean_temperature[index]) / AVERAGE_DAYS;
+            record.mean_moisture[index] += (moisture - record.mean_moisture[index]) / AVERAGE_DAYS;
+        }
+    }
+}
+
+fn shift_biomes(
+    calendar: Res<Calendar>,
+    mut world_map: ResMut<WorldMap>,
+    mut record: ResMut<ClimateRecord>,
+    mut chunk_manager: ResMut<ChunkManager>,
+    mut notable: EventWriter<NotableEvent>,
+) {
+    if calendar.day == 0 || !calendar.day.is_multiple_of(DAYS_PER_YEAR) {
+        return;
+    }
+
+    // Re-rendered chunk by chunk like editor strokes, without a full world re-render
+    let world_map = world_map.bypass_change_detection();
+    let record = &mut *record;
+    let mut changes: HashMap<(BiomeType, BiomeType), usize> = HashMap::new();
+    let mut chunks: HashMap<(i32, i32), (usize, usize)> = HashMap::new(); // First shifted tile per chunk
+    for x in 0..WORLD_SIZE {
+        for y in 0..WORLD_SIZE {
+            let index = x * WORLD_SIZE + y;
+            let tile = &mut world_map.tiles[x][y];
+            let climate = WorldGenerator::determine_biome_fast(
+                tile.elevation, record.mean_temperature[index], record.mean_moisture[index], &world_map.params,
+            );
+            if climate == tile.biome || climate == record.baseline[index] {
+                record.years_outside[index] = 0;
+                continue;
+            }
+            record.years_outside[index] += 1;
+            if record.years_outside[index] < SHIFT_YEARS {
+                continue;
+            }
+            record.years_outside[index] = 0;
+            let Some(biome) = next_step(tile.biome, climate) else { continue };
+
+            *changes.entry((tile.biome, biome)).or_default() += 1;
+            tile.biome = biome;
+            tile.resources = WorldGenerator::generate_resources_fast(&biome, world_map.seed, x, y);
+            let chunk = tile_to_chunk(x, y);
+            chunk_manager.dirty_chunks.insert(chunk);
+            chunks.entry(chunk).or_insert((x, y));
+        }
+    }
+
+    if changes.is_empty() {
+        return;
+    }
+    let shifted: usize = changes.values().sum();
+    record.shifted_tiles += shifted;
+    info!("🌡️ Year {}: the changing climate turned {} tiles into other biomes ({} so far)",
+        calendar.year, shifted, record.shifted_tiles);
+    for ((from, to), count) in &changes {
+        debug!("    {:?} -> {:?}: {}", from, to, count);
+    }
+    for (x, y) in chunks.into_values() {
+        notable.send(NotableEvent { position: tile_to_world(x, y), importance: 1.5, label_key: "event.biome_shift" });
+    }
+}
@@ -0,0 +1,107 @@
+use bevy::prelude::*;
+use crate::world::{Topology, WORLD_SIZE};
+
+pub const TILE_SIZE: f32 = 4.0; // World units per tile
+pub const WORLD_EXTENT: f32 = WORLD_SIZE as f32 * TILE_SIZE; // World units across the map
+pub const MAP_CENTER: Vec2 = Vec2::splat(-TILE_SIZE / 2.0); // See below
+pub const CHUNK_SIZE: usize = 32; // Tiles per side of a render chunk
+pub const CHUNKS_PER_SIDE: i32 = WORLD_SIZE.div_ceil(CHUNK_SIZE) as i32; // The last one is cut short
+
+// === COORDINATES ===
+// Three spaces: tiles (0..WORLD_SIZE on both axes), render chunks of CHUNK_SIZE² tiles, and world
+// units. The map is centred on the origin with every tile centre on a multiple of TILE_SIZE, so it
+// reaches half a tile further on the low side and its true centre is MAP_CENTER. All conversions
+// between the spaces go through here; the free functions at the bottom are untyped shorthands.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TilePos {
+    pub x: usize,
+    pub y: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ChunkPos {
+    pub x: i32,
+    pub y: i32,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WorldPos(pub Vec2);
+
+impl TilePos {
+    pub fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    pub fn center(self) -> WorldPos {
+        let axis = |tile: usize| (tile as f32 - WORLD_SIZE as f32 / 2.0) * TILE_SIZE;
+        WorldPos(Vec2::new(axis(self.x), axis(self.y)))
+    }
+
+    pub fn chunk(self) -> ChunkPos {
+        ChunkPos { x: (self.x / CHUNK_SIZE) as i32, y: (self.y / CHUNK_SIZE) as i32 }
+    }
+}
+
+impl ChunkPos {
+    // Tiles of the chunk as (start_x, start_y, end_x, end_y), end exclusive and clipped to the map
+    pub fn tile_bounds(self) -> (usize, usize, usize, usize) {
+        let clip = |tile: i32| tile.clamp(0, WORLD_SIZE as i32) as usize;
+        let size = CHUNK_SIZE as i32;
+        (clip(self.x * size), clip(self.y * size), clip((self.x + 1) * size), clip((self.y + 1) * size))
+    }
+}
+
+impl WorldPos {
+    // Grid coordinates of the nearest tile centre, which can lie off the map
+    pub fn grid(self) -> IVec2 {
+        (self.0 / TILE_SIZE + WORLD_SIZE as f32 / 2.0).round().as_ivec2()
+    }
+
+    // Tile containing the position, or None outside the map
+    pub fn tile(self) -> Option<TilePos> {
+        let grid = self.grid();
+        let on_map = |value: i32| (0..WORLD_SIZE as i32).contains(&value);
+        (on_map(grid.x) && on_map(grid.y)).then(|| TilePos::new(grid.x as usize, grid.y as usize))
+    }
+
+    // Moved back onto the map across wrapped edges
+    pub fn wrapped(self, topology: Topology) -> Self {
+        let low = -(WORLD_SIZE as f32 / 2.0 + 0.5) * TILE_SIZE; // Outer edge of tile 0
+        let wrap = |value: f32, wraps: bool| if wraps { (value - low).rem_euclid(WORLD_EXTENT) + low } else { value };
+        Self(Vec2::new(wrap(self.0.x, topology.wraps_x()), wrap(self.0.y, topology.wraps_y())))
+    }
+
+    // Shortest offset to another position, which may lead across a wrapped edge
+    pub fn offset_to(self, to: WorldPos, topology: Topology) -> Vec2 {
+        let shortest = |delta: f32, wraps: bool| if wraps { delta - WORLD_EXTENT * (delta / WORLD_EXTENT).round() } else { delta };
+        let delta = to.0 - self.0;
+        Vec2::new(shortest(delta.x, topology.wraps_x()), shortest(delta.y, topology.wraps_y()))
+    }
+}
+
+// Centre of a tile in world space
+pub fn tile_to_world(x: usize, y: usize) -> Vec2 {
+    TilePos::new(x, y).center().0
+}
+
+// Tile containing a world-space position, or None outside the map
+pub fn world_to_tile(position: Vec2) -> Option<(usize, usize)> {
+    WorldPos(position).tile().map(|tile| (tile.x, tile.y))
+}
+
+pub fn tile_to_chunk(x: usize, y: usize) -> (i32, i32) {
+    let chunk = TilePos::new(x, y).chunk();
+    (chunk.x, chunk.y)
+}
+
+pub fn chunk_tile_bounds(chunk_x: i32, chunk_y: i32) -> (usize, usize, usize, usize) {
+    ChunkPos { x: chunk_x, y: chunk_y }.tile_bounds()
+}
+
+pub fn wrap_position(position: Vec2, topology: Topology) -> Vec2 {
+    WorldPos(position).wrapped(topology).0
+}
+
+pub fn world_offset(from: Vec2, to: Vec2, topology: Topology) -> Vec2 {
+    WorldPos(from).offset_to(WorldPos(to), topology)
+}
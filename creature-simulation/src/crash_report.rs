@@ -0,0 +1,283 @@
+use bevy::prelude::*;
+use std::backtrace::Backtrace;
+use std::collections::VecDeque;
+use std::fmt::Write as _;
+use std::fs;
+use std::panic::{self, PanicHookInfo};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::errors::{panic_message, SimFailure};
+use crate::events::NotableEvent;
+use crate::localization::Localization;
+use crate::profiling::SystemBudgets;
+use crate::save::SAVE_PATH;
+use crate::settings::Settings;
+use crate::simulation::SimClock;
+use crate::world::WorldMap;
+
+pub const CRASH_DIR: &str = "crashes";
+const PENDING_PATH: &str = "crashes/pending"; // Path of the newest report, until it has been shown
+const RECENT_EVENTS: usize = 50;
+const FRAME_SMOOTHING: f64 = 0.05; // Weight of the newest frame in the average frame time
+
+// What the panic hook writes out, kept current each frame by the main thread
+static CONTEXT: Mutex<CrashContext> = Mutex::new(CrashContext::new());
+// Sections whose panics are caught and turned into errors (see Recovering)
+static RECOVERING: AtomicUsize = AtomicUsize::new(0);
+
+pub struct CrashReportPlugin;
+
+impl Plugin for CrashReportPlugin {
+    fn build(&self, app: &mut App) {
+        install_panic_hook();
+        app
+            .add_systems(Startup, show_previous_crash)
+            .add_systems(Update, dismiss_crash_dialog)
+            .add_systems(Last, record_crash_context);
+    }
+}
+
+// === CRASH REPORTS ===
+// A panic anywhere takes the app down, so a hook writes what is known about the moment to a
+// timestamped report in CRASH_DIR first: the panic and where it happened, the recent notable
+// events and failures, the timings of the budgeted systems, the world seed and the settings. The
+// last save is copied next to it, as the closest snapshot of the world there is. The hook cannot
+// reach the ECS, so the main thread keeps a copy of all this up to date at the end of each frame.
+// On the next launch a dialog says where the report was written.
+struct CrashContext {
+    seed: Option<u32>,
+    sim_elapsed: f64,
+    frame_ms: f64, // Smoothed
+    settings: String, // As RON
+    events: VecDeque<String>,
+    timings: Vec<String>,
+}
+
+impl CrashContext {
+    const fn new() -> Self {
+        Self {
+            seed: None,
+            sim_elapsed: 0.0,
+            frame_ms: 0.0,
+            settings: String::new(),
+            events: VecDeque::new(),
+            timings: Vec::new(),
+        }
+    }
+
+    fn push_event(&mut self, event: String) {
+        if self.events.len() == RECENT_EVENTS {
+            self.events.pop_front();
+        }
+        self.events.push_back(event);
+    }
+}
+
+// Held around code that catches its own panics, like the world generator's worker threads, so
+// those do not leave a crash report behind. Reports are skipped for every thread meanwhile.
+pub struct Recovering;
+
+impl Recovering {
+    pub fn enter() -> Self {
+        RECOVERING.fetch_add(1, Ordering::SeqCst);
+        Self
+    }
+}
+
+impl Drop for Recovering {
+    fn drop(&mut self) {
+        RECOVERING.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+// Chained in front of the default hook, which still prints the panic
+fn install_panic_hook() {
+    let previous = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        previous(info);
+        if RECOVERING.load(Ordering::SeqCst) == 0 {
+            write_crash_report(info);
+        }
+    }));
+}
+
+fn write_crash_report(info: &PanicHookInfo) {
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |time| time.as_secs());
+    let path = format!("{}/crash-{}.txt", CRASH_DIR, timestamp);
+    let mut report = String::new();
+    let _ = writeln!(report, "Creature simulation {} crash report", env!("CARGO_PKG_VERSION"));
+    let _ = writeln!(report, "Time: {} (Unix seconds)", timestamp);
+    let _ = writeln!(report, "Thread: {}", std::thread::current().name().unwrap_or("unnamed"));
+    let _ = writeln!(report, "Panic: {}", panic_message(info.payload()));
+    if let Some(location) = info.location() {
+        let _ = writeln!(report, "At: {}", location);
+    }
+
+    // The panicking thread may hold the lock itself
+    match CONTEXT.try_lock() {
+        Ok(context) => {
+            let seed = context.seed.map_or("no world".to_string(), |seed| seed.to_string());
+            let _ = writeln!(report, "Seed: {}", seed);
+            let _ = writeln!(report, "Simulated time: {:.0} s", context.sim_elapsed);
+            let _ = writeln!(report, "Frame time: {:.1} ms", context.frame_ms);
+            let _ = writeln!(report, "\n== Recent events ==");
+            for event in &context.events {
+                let _ = writeln!(report, "{}", event);
+            }
+            let _ = writeln!(report, "\n== System timings (ms last frame / budget, frames over) ==");
+            for timing in &context.timings {
+                let _ = writeln!(report, "{}", timing);
+            }
+            let _ = writeln!(report, "\n== Settings ==\n{}", context.settings);
+        }
+        Err(_) => {
+            let _ = writeln!(report, "\nThe crash context was unavailable");
+        }
+    }
+
+    let snapshot = format!("{}/crash-{}-save.ron", CRASH_DIR, timestamp);
+    let _ = fs::create_dir_all(CRASH_DIR);
+    let _ = match fs::copy(SAVE_PATH, &snapshot) {
+        Ok(_) => writeln!(report, "\n== Snapshot ==\nLast save copied to {}", snapshot),
+        Err(err) => writeln!(report, "\n== Snapshot ==\nNo save to copy ({})", err),
+    };
+    let _ = writeln!(report, "\n== Backtrace ==\n{}", Backtrace::force_capture());
+
+    match fs::write(&path, report) {
+        Ok(()) => {
+            let _ = fs::write(PENDING_PATH, &path);
+            eprintln!("Crash report written to {}", path);
+        }
+        Err(err) => eprintln!("Could not write the crash report to {}: {}", path, err),
+    }
+}
+
+fn record_crash_context(
+    time: Res<Time<Real>>,
+    (settings, localization, budgets): (Res<Settings>, Res<Localization>, Res<SystemBudgets>),
+    (world_map, clock): (Option<Res<WorldMap>>, Res<SimClock>),
+    mut events: EventReader<NotableEvent>,
+    mut failures: EventReader<SimFailure>,
+) {
+    let Ok(mut context) = CONTEXT.lock() else { return };
+    context.seed = world_map.map(|world_map| world_map.seed);
+    context.sim_elapsed = clock.elapsed;
+    let frame_ms = time.delta_seconds_f64() * 1000.0;
+    context.frame_ms += (frame_ms - context.frame_ms) * FRAME_SMOOTHING;
+    if settings.is_changed() || context.settings.is_empty() {
+        context.settings = ron::ser::to_string_pretty(&*settings, ron::ser::PrettyConfig::default())
+            .unwrap_or_else(|err| format!("Could not serialize the settings: {}", err));
+    }
+    for event in events.read() {
+        context.push_event(format!("[{:.0} s] {} at ({:.0}, {:.0})",
+            clock.elapsed, localization.get(event.label_key), event.position.x, event.position.y));
+    }
+    for failure in failures.read() {
+        context.push_event(format!("[{:.0} s] Failure: {}", clock.elapsed, failure.error));
+    }
+    if budgets.is_changed() {
+        context.timings = budgets.timings().into_iter()
+            .map(|(system, ms, budget, frames_over)| format!("{}: {:.2} / {:.1}, {}", system, ms, budget, frames_over))
+            .collect();
+    }
+}
+
+#[derive(Component)]
+struct CrashDialog;
+
+#[derive(Component)]
+struct DismissCrashButton;
+
+// Shown once per report: the pending marker goes as soon as the dialog is up
+fn show_previous_crash(mut commands: Commands, localization: Res<Localization>) {
+    let Ok(report) = fs::read_to_string(PENDING_PATH) else { return };
+    let _ = fs::remove_file(PENDING_PATH);
+    let report = report.trim();
+    if !Path::new(report).exists() {
+        return;
+    }
+    warn!(report, "The last run crashed");
+
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            // Above the loading screen, which is up when the dialog opens
+            z_index: ZIndex::Global(110),
+            ..default()
+        },
+        CrashDialog,
+    )).with_children(|parent| {
+        parent.spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                padding: UiRect::all(Val::Px(20.0)),
+                max_width: Val::Px(600.0),
+                ..default()
+            },
+            background_color: Color::srgba(0.1, 0.05, 0.08, 0.95).into(),
+            ..default()
+        }).with_children(|dialog| {
+            dialog.spawn(TextBundle::from_section(
+                localization.get("crash.title"),
+                TextStyle {
+                    font_size: 28.0,
+                    color: Color::srgb(0.95, 0.6, 0.55),
+                    ..default()
+                },
+            ));
+            dialog.spawn(TextBundle::from_section(
+                localization.format("crash.body", &[("path", report.to_string())]),
+                TextStyle {
+                    font_size: 16.0,
+                    color: Color::srgb(0.85, 0.85, 0.85),
+                    ..default()
+                },
+            ).with_style(Style {
+                margin: UiRect::all(Val::Px(12.0)),
+                ..default()
+            }));
+            dialog.spawn((
+                ButtonBundle {
+                    style: Style {
+                        padding: UiRect::axes(Val::Px(16.0), Val::Px(8.0)),
+                        ..default()
+                    },
+                    background_color: Color::srgb(0.3, 0.2, 0.25).into(),
+                    ..default()
+                },
+                DismissCrashButton,
+            )).with_children(|button| {
+                button.spawn(TextBundle::from_section(
+                    localization.get("crash.dismiss"),
+                    TextStyle {
+                        font_size: 18.0,
+                        color: Color::srgb(0.9, 0.9, 0.9),
+                        ..default()
+                    },
+                ));
+            });
+        });
+    });
+}
+
+fn dismiss_crash_dialog(
+    mut commands: Commands,
+    buttons: Query<&Interaction, (Changed<Interaction>, With<DismissCrashButton>)>,
+    dialogs: Query<Entity, With<CrashDialog>>,
+) {
+    if buttons.iter().any(|interaction| *interaction == Interaction::Pressed) {
+        for entity in dialogs.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,636 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use serde::Serialize;
+use std::f32::consts::{FRAC_PI_2, PI};
+use crate::coords::{tile_to_world, world_offset, world_to_tile, wrap_position, TILE_SIZE};
+use crate::ai_lod::{is_distant, AiTick};
+use crate::biome::ResourceType;
+use crate::calendar::{seasonal_temperature_offset, Calendar};
+use crate::events::NotableEvent;
+use crate::fisheries::Fisheries;
+use crate::genetics::{founder_lineage, Genome};
+use crate::hibernation::{Hibernation, TORPOR_METABOLISM};
+use crate::hydrology::Hydrology;
+use crate::ice::{IceSheet, ICE_SPEED};
+use crate::profiling::budgeted;
+use crate::rivers::{Crossing, Rivers, FORD_SPEED, LOG_SPEED};
+use crate::pheromones::PheromoneGrid;
+use crate::predation::hunt_prey;
+use crate::simulation::SimClock;
+use crate::taming::Tamed;
+use crate::species::{Diet, MovementMode, Species};
+use crate::tides::Tides;
+use crate::vegetation::Vegetation;
+use crate::optimization::LODLevel;
+use crate::species::SpeciesRegistry;
+use crate::world::{Tile, Topology, WorldMap, WORLD_SIZE};
+use crate::rng_streams::{RngStream, RngStreams};
+
+// Need rates are per simulated second
+pub const HUNGER_RATE: f32 = 0.004; // Energy burn of a reference creature in comfortable weather
+pub const THIRST_RATE: f32 = 0.006;
+pub const EAT_RATE: f32 = 0.08;
+pub const DRINK_RATE: f32 = 0.15;
+pub const STARVATION_DAMAGE: f32 = 0.02; // Health lost per second while starving or dehydrated
+pub const RECOVERY_RATE: f32 = 0.01;
+pub const SEEK_THRESHOLD: f32 = 0.5; // Start looking for food/water above this need level
+pub const SEARCH_RADIUS: i32 = 20; // Tiles scanned when looking for food or water
+pub const REFERENCE_SIZE: f32 = 3.0;
+pub const REFERENCE_SPEED: f32 = 14.0;
+pub const TEMPERATURE_COST: f32 = 4.0; // Extra burn per unit of temperature outside the comfort zone
+pub const CREATURE_Z: f32 = 2.0;
+pub const FLYING_Z: f32 = 4.0; // Above ground creatures and swarms
+
+pub struct CreaturePlugin;
+
+impl Plugin for CreaturePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<SpeciesRegistry>()
+            .add_event::<CreatureDied>()
+            .add_systems(Update, spawn_initial_creatures.run_if(resource_added::<WorldMap>))
+            .add_systems(FixedUpdate, (
+                budgeted(update_needs, 2.0),
+                budgeted(hunt_prey, 2.0),
+                budgeted(move_creatures, 4.0),
+                remove_dead_creatures,
+            ).chain().run_if(resource_exists::<WorldMap>));
+    }
+}
+
+#[derive(Component)]
+pub struct Creature {
+    pub species: usize, // Index into SpeciesRegistry
+    pub age: f32, // Simulated seconds alive
+}
+
+// All needs are 0.0..=1.0; hunger/thirst of 1.0 means starving/dehydrated
+#[derive(Component)]
+pub struct Needs {
+    pub health: f32,
+    pub hunger: f32,
+    pub thirst: f32,
+}
+
+impl Default for Needs {
+    fn default() -> Self {
+        Self { health: 1.0, hunger: 0.2, thirst: 0.2 }
+    }
+}
+
+#[derive(Event)]
+pub struct CreatureDied {
+    pub position: Vec2,
+    pub species: usize,
+    pub cause: DeathCause,
+}
+
+// Why a creature died. Hunger and thirst are read off its needs; every other cause is set with
+// Killed by whatever killed it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DeathCause {
+    Starvation,
+    Dehydration,
+    Predation,
+    OldAge,
+    Disease,
+    Fire,
+    Event, // Removed by the player or an experiment
+}
+
+impl DeathCause {
+    pub const ALL: [DeathCause; 7] = [
+        DeathCause::Starvation,
+        DeathCause::Dehydration,
+        DeathCause::Predation,
+        DeathCause::OldAge,
+        DeathCause::Disease,
+        DeathCause::Fire,
+        DeathCause::Event,
+    ];
+
+    // Notable event label
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            DeathCause::Starvation => "event.starvation",
+            DeathCause::Dehydration => "event.dehydration",
+            DeathCause::Predation => "event.predation",
+            DeathCause::OldAge => "event.old_age",
+            DeathCause::Disease => "event.disease",
+            DeathCause::Fire => "event.fire",
+            DeathCause::Event => "event.culled",
+        }
+    }
+
+    // Short name for charts and legends
+    pub fn name_key(&self) -> &'static str {
+        match self {
+            DeathCause::Starvation => "cause.starvation",
+            DeathCause::Dehydration => "cause.dehydration",
+            DeathCause::Predation => "cause.predation",
+            DeathCause::OldAge => "cause.old_age",
+            DeathCause::Disease => "cause.disease",
+            DeathCause::Fire => "cause.fire",
+            DeathCause::Event => "cause.event",
+        }
+    }
+
+    pub fn color(&self) -> Color {
+        match self {
+            DeathCause::Starvation => Color::srgb(0.9, 0.7, 0.3),
+            DeathCause::Dehydration => Color::srgb(0.4, 0.7, 1.0),
+            DeathCause::Predation => Color::srgb(0.95, 0.3, 0.3),
+            DeathCause::OldAge => Color::srgb(0.7, 0.7, 0.7),
+            DeathCause::Disease => Color::srgb(0.6, 0.9, 0.4),
+            DeathCause::Fire => Color::srgb(1.0, 0.5, 0.1),
+            DeathCause::Event => Color::srgb(0.8, 0.5, 0.9),
+        }
+    }
+}
+
+// Marks a creature as killed; remove_dead_creatures despawns it whatever its health
+#[derive(Component)]
+pub struct Killed(pub DeathCause);
+
+#[derive(Component, Default)]
+pub struct Wander {
+    pub heading: f32, // Radians
+    pub target: Option<Vec2>,
+    pub search_cooldown: f32, // Seconds until the next food/water scan is allowed
+    pub behavior: Behavior, // Latest decision, for the behavior inspector
+}
+
+// What the movement and hunting systems last decided a creature should do
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Behavior {
+    #[default]
+    Wander,
+    EscapeTide,
+    SeekRoost,
+    Roost,
+    SeekWater,
+    SeekFood,
+    Hunt,
+    Flee, // A hunter is closing in
+    Led, // Tamed and walking to its lead marker
+    AnswerCall, // Heading for a conspecific's mating or rally call
+    Arrive, // Reached its target this step
+    TurnBack, // Next step was blocked by water or the map edge
+    SkirtCliff, // Walking along a cliff it cannot climb
+    FollowRiver, // Walking along a river bank towards a crossing
+    FollowTrail, // Steering along a pheromone trail
+    SeekDen, // Heading for its winter den
+    Hibernate, // In torpor, or woken and still groggy
+    RaidNest, // Going for eggs it smells
+    FollowParent, // A juvenile catching up with its parent
+    Defend, // A parent rushing to its fleeing young
+}
+
+impl Behavior {
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            Behavior::Wander => "behavior.wander",
+            Behavior::EscapeTide => "behavior.escape_tide",
+            Behavior::SeekRoost => "behavior.seek_roost",
+            Behavior::Roost => "behavior.roost",
+            Behavior::SeekWater => "behavior.seek_water",
+            Behavior::SeekFood => "behavior.seek_food",
+            Behavior::Hunt => "behavior.hunt",
+            Behavior::Flee => "behavior.flee",
+            Behavior::Led => "behavior.led",
+            Behavior::AnswerCall => "behavior.answer_call",
+            Behavior::Arrive => "behavior.arrive",
+            Behavior::TurnBack => "behavior.turn_back",
+            Behavior::SkirtCliff => "behavior.skirt_cliff",
+            Behavior::FollowRiver => "behavior.follow_river",
+            Behavior::FollowTrail => "behavior.follow_trail",
+            Behavior::SeekDen => "behavior.seek_den",
+            Behavior::Hibernate => "behavior.hibernate",
+            Behavior::RaidNest => "behavior.raid_nest",
+            Behavior::FollowParent => "behavior.follow_parent",
+            Behavior::Defend => "behavior.defend",
+        }
+    }
+}
+
+// === SURROUNDINGS ===
+// Read-only view of everything a creature can sense about the tiles around it
+#[derive(SystemParam)]
+pub struct Surroundings<'w> {
+    world_map: Res<'w, WorldMap>,
+    tides: Res<'w, Tides>,
+    hydrology: Option<Res<'w, Hydrology>>,
+    vegetation: Option<Res<'w, Vegetation>>,
+    fisheries: Option<Res<'w, Fisheries>>,
+    ice: Option<Res<'w, IceSheet>>,
+    rivers: Option<Res<'w, Rivers>>,
+    calendar: Res<'w, Calendar>,
+}
+
+impl Surroundings<'_> {
+    pub fn tile(&self, x: usize, y: usize) -> &Tile {
+        &self.world_map.tiles[x][y]
+    }
+
+    pub fn topology(&self) -> Topology {
+        self.world_map.params.topology
+    }
+
+    // Tile temperature shifted by the season
+    pub fn temperature(&self, x: usize, y: usize) -> f32 {
+        self.tile(x, y).temperature + seasonal_temperature_offset(self.calendar.day, y, &self.world_map.params)
+    }
+
+    pub fn is_frozen(&self, x: usize, y: usize) -> bool {
+        self.ice.as_ref().is_some_and(|ice| ice.is_frozen(x, y))
+    }
+
+    // Frozen water counts as solid ground
+    pub fn is_underwater(&self, x: usize, y: usize) -> bool {
+        self.tides.is_underwater(self.tile(x, y)) && !self.is_frozen(x, y)
+    }
+
+    pub fn can_enter(&self, x: usize, y: usize, movement: MovementMode) -> bool {
+        match movement {
+            MovementMode::Walk => !self.is_underwater(x, y) && !self.is_river_barrier(x, y, movement),
+            MovementMode::Fly => true,
+        }
+    }
+
+    // River a walker has to find a ford or log to get across
+    pub fn is_river_barrier(&self, x: usize, y: usize, movement: MovementMode) -> bool {
+        movement == MovementMode::Walk && self.rivers.as_ref().is_some_and(|rivers| rivers.blocks(x, y))
+    }
+
+    // Too steep to walk from one neighbouring tile to the other
+    pub fn is_cliff(&self, from: (usize, usize), to: (usize, usize), movement: MovementMode) -> bool {
+        movement == MovementMode::Walk && from != to && self.world_map.is_cliff(from, to)
+    }
+
+    // Share of its normal speed a creature keeps on the tile
+    pub fn speed_factor(&self, x: usize, y: usize, movement: MovementMode) -> f32 {
+        match movement {
+            MovementMode::Walk if self.is_frozen(x, y) => ICE_SPEED,
+            MovementMode::Walk => self.tile(x, y).biome.footing().speed() * match self.rivers.as_ref().and_then(|rivers| rivers.crossing(x, y)) {
+                Some(Crossing::Ford) => FORD_SPEED,
+                Some(Crossing::Log) => LOG_SPEED,
+                None => 1.0,
+            },
+            _ => 1.0,
+        }
+    }
+
+    // Trees to spend the night in
+    pub fn has_roost(&self, x: usize, y: usize) -> bool {
+        self.tile(x, y).resources.contains(&ResourceType::Wood)
+    }
+
+    // Whether food on this tile can be eaten right now: shellfish are only reachable at low tide,
+    // plant food is gone from grazed-out ground and collapsed fisheries yield no fish
+    pub fn has_food(&self, x: usize, y: usize, diet: Diet) -> bool {
+        if diet == Diet::Nectarivore {
+            return self.vegetation.as_ref().is_some_and(|vegetation| vegetation.is_blooming(x, y));
+        }
+        let tile = self.tile(x, y);
+        let grazed_out = self.vegetation.as_ref().is_some_and(|vegetation| vegetation.is_grazed_out(x, y, tile.biome));
+        tile.resources.iter().any(|r| diet.edible_resources().contains(r) && match r {
+            ResourceType::Shellfish => !self.tides.is_underwater(tile),
+            ResourceType::Berries | ResourceType::Herbs | ResourceType::Mushrooms => !grazed_out,
+            ResourceType::Fish => self.fisheries.as_ref().is_none_or(|fisheries| fisheries.has_fish(x, y)),
+            _ => true,
+        })
+    }
+
+    // Water sources dry up when the hydrology model says the local water level is too low
+    pub fn has_drinkable_water(&self, x: usize, y: usize) -> bool {
+        self.tile(x, y).resources.iter().any(|r| matches!(r, ResourceType::Water | ResourceType::Ice))
+            && self.hydrology.as_ref().is_none_or(|hydrology| hydrology.water_available(x, y))
+    }
+}
+
+// === SPAWNING ===
+fn spawn_initial_creatures(
+    mut commands: Commands,
+    world_map: Res<WorldMap>,
+    registry: Res<SpeciesRegistry>,
+) {
+    let mut rng = rand::rngs::StdRng::seed_from_u64(world_map.seed as u64);
+    let mut spawned = 0;
+
+    for (species_id, species) in registry.species.iter().enumerate() {
+        for _ in 0..species.initial_population {
+            // Rejection-sample a tile in one of the species' habitats
+            let tile = (0..50).find_map(|_| {
+                let (x, y) = (rng.gen_range(0..WORLD_SIZE), rng.gen_range(0..WORLD_SIZE));
+                species.habitats.contains(&world_map.tiles[x][y].biome).then_some((x, y))
+            });
+            let Some((x, y)) = tile else { continue };
+
+            let genome = Genome::founder(&mut rng, founder_lineage(species_id, x, y));
+            spawn_creature(&mut commands, &registry, species_id, tile_to_world(x, y), rng.gen_range(0.0..2.0 * PI), genome);
+            spawned += 1;
+        }
+    }
+
+    info!("🦎 Spawned {} creatures across {} species", spawned, registry.species.len());
+}
+
+pub fn spawn_creature(
+    commands: &mut Commands,
+    registry: &SpeciesRegistry,
+    species_id: usize,
+    position: Vec2,
+    heading: f32,
+    genome: Genome,
+) -> Entity {
+    let species = registry.get(species_id);
+    commands.spawn((
+        SpriteBundle {
+            sprite: Sprite {
+                color: genome.color(species),
+                custom_size: Some(Vec2::splat(species.size * genome.size)),
+                ..default()
+            },
+            transform: Transform::from_translation(position.extend(match species.movement {
+                MovementMode::Walk => CREATURE_Z,
+                MovementMode::Fly => FLYING_Z,
+            })),
+            ..default()
+        },
+        Creature { species: species_id, age: 0.0 },
+        Needs::default(),
+        Wander { heading, ..default() },
+        genome,
+        LODLevel(0),
+    )).id()
+}
+
+// === NEEDS ===
+// Energy burn per second. Larger bodies cost more (sublinearly, like real metabolic scaling),
+// half of the burn scales with speed, and being too hot or too cold adds on top.
+pub fn metabolic_rate(species: &Species, genome: &Genome, temperature: f32) -> f32 {
+    let size = (species.size * genome.size / REFERENCE_SIZE).powf(0.75);
+    let activity = 0.5 + 0.5 * species.speed / REFERENCE_SPEED;
+    let climate = 1.0 + TEMPERATURE_COST * species.temperature_stress(temperature);
+    HUNGER_RATE * size * activity * climate
+}
+
+type NeedsQueryData = (
+    Entity,
+    &'static Transform,
+    &'static mut Creature,
+    &'static mut Needs,
+    &'static Genome,
+    &'static LODLevel,
+    Option<&'static Hibernation>,
+);
+
+pub fn update_needs(
+    time: Res<Time>,
+    ai_tick: Res<AiTick>,
+    surroundings: Surroundings,
+    registry: Res<SpeciesRegistry>,
+    mut creatures: Query<NeedsQueryData>,
+) {
+    for (entity, transform, mut creature, mut needs, genome, lod, hibernation) in creatures.iter_mut() {
+        let Some(dt) = ai_tick.time_step(entity, lod, time.delta_seconds()) else { continue };
+        creature.age += dt;
+        let species = registry.get(creature.species);
+
+        let tile = world_to_tile(transform.translation.truncate());
+        // Distant creatures don't search for food and water, so inside their habitat
+        // they are assumed to find enough of both
+        let foraging = is_distant(lod)
+            && tile.is_some_and(|(x, y)| species.habitats.contains(&surroundings.tile(x, y).biome));
+        if !foraging {
+            let temperature = tile.map_or(0.5, |(x, y)| surroundings.temperature(x, y));
+            let torpor = if hibernation.is_some_and(|hibernation| hibernation.torpid) { TORPOR_METABOLISM } else { 1.0 };
+            needs.hunger += metabolic_rate(species, genome, temperature) * torpor * dt;
+            needs.thirst += THIRST_RATE * (1.0 - species.drought_tolerance) * torpor * dt;
+        }
+
+        if let Some((x, y)) = tile {
+            if surroundings.has_food(x, y, species.diet) {
+                needs.hunger -= EAT_RATE * dt;
+            }
+            if surroundings.has_drinkable_water(x, y) {
+                needs.thirst -= DRINK_RATE * dt;
+            }
+        }
+
+        needs.hunger = needs.hunger.clamp(0.0, 1.0);
+        needs.thirst = needs.thirst.clamp(0.0, 1.0);
+
+        if needs.hunger >= 1.0 || needs.thirst >= 1.0 {
+            needs.health -= STARVATION_DAMAGE * dt;
+        } else if needs.hunger < SEEK_THRESHOLD && needs.thirst < SEEK_THRESHOLD {
+            needs.health = (needs.health + RECOVERY_RATE * dt).min(1.0);
+        }
+    }
+}
+
+// Dry tile within SEARCH_RADIUS satisfying the predicate that is quickest to reach, looking
+// across wrapped edges
+pub fn find_nearby_tile(
+    surroundings: &Surroundings,
+    origin: (usize, usize),
+    movement: MovementMode,
+    matches: impl Fn(usize, usize) -> bool,
+) -> Option<(usize, usize)> {
+    let mut best: Option<((usize, usize), f32)> = None;
+    for dx in -SEARCH_RADIUS..=SEARCH_RADIUS {
+        for dy in -SEARCH_RADIUS..=SEARCH_RADIUS {
+            let Some((x, y)) = surroundings.topology().wrap_tile(origin.0 as i32 + dx, origin.1 as i32 + dy) else { continue };
+            // Slow ground, like mud or deep snow, only adds to the distance
+            let distance = ((dx * dx + dy * dy) as f32).sqrt();
+            if best.is_some_and(|(_, cost)| cost <= distance) || surroundings.is_underwater(x, y) || !matches(x, y) {
+                continue;
+            }
+            let cost = distance / surroundings.speed_factor(x, y, movement);
+            if best.is_none_or(|(_, best_cost)| cost < best_cost) {
+                best = Some(((x, y), cost));
+            }
+        }
+    }
+    best.map(|(tile, _)| tile)
+}
+
+// === MOVEMENT ===
+type MoverQueryData = (
+    Entity,
+    &'static mut Transform,
+    &'static Creature,
+    &'static Needs,
+    &'static mut Wander,
+    &'static LODLevel,
+    Option<&'static Tamed>,
+    Option<&'static Hibernation>,
+);
+
+pub fn move_creatures(
+    time: Res<Time>,
+    (clock, pheromones): (Res<SimClock>, Res<PheromoneGrid>),
+    ai_tick: Res<AiTick>,
+    surroundings: Surroundings,
+    registry: Res<SpeciesRegistry>,
+    mut creatures: Query<MoverQueryData>,
+    mut streams: ResMut<RngStreams>,
+) {
+    let rng = streams.get(RngStream::Creatures);
+
+    for (entity, mut transform, creature, needs, mut wander, lod, tamed, hibernation) in creatures.iter_mut() {
+        let Some(dt) = ai_tick.time_step(entity, lod, time.delta_seconds()) else { continue };
+        // Hibernating in its den (see hibernation.rs)
+        if Hibernation::is_dormant(hibernation) {
+            continue;
+        }
+        let species = registry.get(creature.species);
+        let position = transform.translation.truncate();
+        wander.search_cooldown -= dt;
+
+        // A led creature goes where it is told and waits there, whatever its needs
+        let lead = Tamed::lead(tamed);
+        if let Some(lead) = lead {
+            wander.target = Some(lead);
+            wander.behavior = Behavior::Led;
+        }
+
+        // The tide came in: head for the nearest dry ground before anything else
+        let current = world_to_tile(position);
+        let current_elevation = current.map_or(f32::MIN, |(x, y)| surroundings.tile(x, y).elevation);
+        let standing_in_water = species.movement == MovementMode::Walk
+            && current.is_some_and(|(x, y)| surroundings.is_underwater(x, y));
+        if let (true, None, Some(tile)) = (standing_in_water, wander.target, current) {
+            wander.target = find_nearby_tile(&surroundings, tile, species.movement, |_, _| true).map(|(x, y)| tile_to_world(x, y));
+            if wander.target.is_some() {
+                wander.behavior = Behavior::EscapeTide;
+            }
+        }
+
+        // Flyers spend the night roosting in trees, unless night is when they are about
+        if species.movement == MovementMode::Fly && !species.nocturnal && clock.is_night() && lead.is_none() {
+            if current.is_some_and(|(x, y)| surroundings.has_roost(x, y)) {
+                wander.target = None;
+                wander.behavior = Behavior::Roost;
+                continue;
+            }
+            if let (None, true, Some(tile)) = (wander.target, wander.search_cooldown <= 0.0, current) {
+                wander.target = find_nearby_tile(&surroundings, tile, species.movement, |x, y| surroundings.has_roost(x, y))
+                    .map(|(x, y)| tile_to_world(x, y));
+                if wander.target.is_some() {
+                    wander.behavior = Behavior::SeekRoost;
+                }
+                wander.search_cooldown = 2.0;
+            }
+        }
+
+        // Pick a food or water target when a need becomes pressing; distant creatures skip the scan
+        if wander.target.is_none() && wander.search_cooldown <= 0.0 && !is_distant(lod) {
+            if let Some(tile) = world_to_tile(position) {
+                let found = if needs.thirst > SEEK_THRESHOLD && needs.thirst >= needs.hunger {
+                    find_nearby_tile(&surroundings, tile, species.movement, |x, y| surroundings.has_drinkable_water(x, y))
+                        .map(|tile| (tile, Behavior::SeekWater))
+                } else if needs.hunger > SEEK_THRESHOLD {
+                    find_nearby_tile(&surroundings, tile, species.movement, |x, y| surroundings.has_food(x, y, species.diet))
+                        .map(|tile| (tile, Behavior::SeekFood))
+                } else {
+                    None
+                };
+                if let Some((_, behavior)) = found {
+                    wander.behavior = behavior;
+                }
+                wander.target = found.map(|((x, y), _)| tile_to_world(x, y));
+            }
+            wander.search_cooldown = 2.0;
+        }
+
+        // Targets across a wrapped edge are approached the short way round
+        let topology = surroundings.topology();
+        let direction = match wander.target.map(|target| world_offset(position, target, topology)) {
+            Some(offset) if offset.length() < TILE_SIZE * 0.5 => {
+                wander.target = None;
+                wander.behavior = Behavior::Arrive;
+                continue; // Arrived - stay put and eat/drink
+            }
+            Some(offset) => offset.normalize_or_zero(),
+            None => {
+                wander.behavior = Behavior::Wander;
+                wander.heading += rng.gen_range(-0.5..0.5) * dt * 4.0;
+                // Hungry ants follow the scent of others that found food
+                let trail = (species.pheromones && PheromoneGrid::follows(needs.hunger))
+                    .then(|| pheromones.steer(position, wander.heading))
+                    .flatten();
+                if let Some(heading) = trail {
+                    wander.heading = heading;
+                    wander.behavior = Behavior::FollowTrail;
+                }
+                Vec2::from_angle(wander.heading)
+            }
+        };
+
+        let speed = species.speed * current.map_or(1.0, |(x, y)| surroundings.speed_factor(x, y, species.movement));
+        let next = wrap_position(position + direction * speed * dt, topology);
+        let climbable = |to: (usize, usize)| current.is_none_or(|from| !surroundings.is_cliff(from, to, species.movement));
+        match world_to_tile(next) {
+            // Creatures caught by the rising tide may wade uphill, but never further out
+            Some((x, y)) if climbable((x, y)) && (surroundings.can_enter(x, y, species.movement)
+                || (standing_in_water && surroundings.tile(x, y).elevation >= current_elevation)) => {
+                transform.translation.x = next.x;
+                transform.translation.y = next.y;
+            }
+            Some(tile) if !climbable(tile) || surroundings.is_river_barrier(tile.0, tile.1, species.movement) => {
+                // A cliff or river - follow it sideways, keeping the target; turn back in a dead end
+                let around = [FRAC_PI_2, -FRAC_PI_2].into_iter()
+                    .map(|turn| Vec2::from_angle(turn).rotate(direction))
+                    .find(|side| world_to_tile(wrap_position(position + *side * speed * dt, topology))
+                        .is_some_and(|(x, y)| climbable((x, y)) && surroundings.can_enter(x, y, species.movement)));
+                match around {
+                    Some(side) => {
+                        let next = wrap_position(position + side * speed * dt, topology);
+                        transform.translation.x = next.x;
+                        transform.translation.y = next.y;
+                        wander.heading = side.to_angle();
+                        wander.behavior = if climbable(tile) { Behavior::FollowRiver } else { Behavior::SkirtCliff };
+                    }
+                    None => {
+                        wander.heading += PI;
+                        wander.target = None;
+                        wander.behavior = Behavior::TurnBack;
+                    }
+                }
+            }
+            _ => {
+                // Blocked by water or the map edge - turn around and drop the target
+                wander.heading += PI;
+                wander.target = None;
+                wander.behavior = Behavior::TurnBack;
+            }
+        }
+    }
+}
+
+fn remove_dead_creatures(
+    mut commands: Commands,
+    mut deaths: EventWriter<CreatureDied>,
+    mut notable: EventWriter<NotableEvent>,
+    registry: Res<SpeciesRegistry>,
+    creatures: Query<(Entity, &Transform, &Creature, &Needs, Option<&Killed>)>,
+) {
+    for (entity, transform, creature, needs, killed) in creatures.iter() {
+        if needs.health <= 0.0 || killed.is_some() {
+            let position = transform.translation.truncate();
+            let cause = match killed {
+                Some(Killed(cause)) => *cause,
+                None if needs.thirst >= 1.0 => DeathCause::Dehydration,
+                None => DeathCause::Starvation,
+            };
+            let importance = if cause == DeathCause::Predation { 2.0 } else { 1.0 };
+            debug!("{} died ({:?}) at {:?}", registry.get(creature.species).name, cause, position);
+            deaths.send(CreatureDied { position, species: creature.species, cause });
+            notable.send(NotableEvent { position, importance, label_key: cause.label_key() });
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,442 @@
+use bevy::prelude::*;
+use bevy::core_pipeline::core_2d::Transparent2d;
+use bevy::core_pipeline::tonemapping::{DebandDither, Tonemapping};
+use bevy::ecs::query::ROQueryItem;
+use bevy::ecs::system::lifetimeless::{Read, SRes};
+use bevy::ecs::system::SystemParamItem;
+use bevy::math::FloatOrd;
+use bevy::render::mesh::{Indices, MeshVertexBufferLayoutRef, MeshVertexBufferLayouts, PrimitiveTopology};
+use bevy::render::render_asset::{RenderAssetUsages, RenderAssets};
+use bevy::render::render_phase::{
+    AddRenderCommand, DrawFunctions, PhaseItem, PhaseItemExtraIndex, RenderCommand, RenderCommandResult,
+    SetItemPipeline, TrackedRenderPass, ViewSortedRenderPhases,
+};
+use bevy::render::render_resource::binding_types::{sampler, texture_2d_array};
+use bevy::render::render_resource::{
+    BindGroup, BindGroupEntries, BindGroupLayout, BindGroupLayoutEntries, Buffer, BufferInitDescriptor,
+    BufferUsages, Extent3d, IndexFormat, PipelineCache, RawBufferVec, RenderPipelineDescriptor, SamplerBindingType,
+    ShaderStages, SpecializedMeshPipeline, SpecializedRenderPipeline, SpecializedRenderPipelines,
+    TextureDimension, TextureFormat, TextureSampleType, VertexAttribute, VertexBufferLayout, VertexFormat,
+    VertexStepMode,
+};
+use bevy::render::renderer::{RenderDevice, RenderQueue};
+use bevy::render::texture::{GpuImage, ImageSampler};
+use bevy::render::view::{ExtractedView, VisibilitySystems};
+use bevy::render::{Extract, Render, RenderApp, RenderSet};
+use bevy::sprite::{tonemapping_pipeline_key, Mesh2dPipeline, Mesh2dPipelineKey, SetMesh2dViewBindGroup};
+use crate::ai_lod::is_distant;
+use crate::creature::{Behavior, Creature, Wander};
+use crate::creature_lod::{CreatureDetail, CreatureRenderLod};
+use crate::optimization::LODLevel;
+use crate::settings::Settings;
+use crate::species::{MovementMode, SpeciesRegistry};
+
+const SHADER_PATH: &str = "shaders/creature_batch.wgsl";
+const FRAME_SIZE: u32 = 16; // Pixels across a frame
+const WALK_FRAMES: u32 = 4; // Layers 0-3 of the frame texture; the wingbeat follows
+const FLY_FRAMES: u32 = 2;
+const LAYERS: u32 = 8; // The frames, padded: the GL backend takes a texture of six square layers for a cube map
+const FRAMES_PER_SECOND: f32 = 6.0;
+
+pub struct CreatureBatchPlugin;
+
+impl Plugin for CreatureBatchPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<CreatureBatches>()
+            .add_systems(Startup, create_frame_texture)
+            .add_systems(PostUpdate, (
+                toggle_creature_batches.before(VisibilitySystems::VisibilityPropagate),
+                // Once culled, so creatures out of view are left out and the rest hidden from the sprite renderer
+                update_creature_batches.after(VisibilitySystems::CheckVisibility),
+            ));
+
+        let Some(render_app) = app.get_sub_app_mut(RenderApp) else { return };
+        render_app
+            .add_render_command::<Transparent2d, DrawCreatureBatch>()
+            .init_resource::<SpecializedRenderPipelines<CreatureBatchPipeline>>()
+            .init_resource::<CreatureBatchBuffers>()
+            .add_systems(ExtractSchedule, extract_creature_batches)
+            .add_systems(Render, (
+                prepare_creature_batches.in_set(RenderSet::PrepareResources),
+                prepare_frames_bind_group.in_set(RenderSet::PrepareBindGroups),
+                queue_creature_batches.in_set(RenderSet::Queue),
+            ));
+    }
+
+    fn finish(&self, app: &mut App) {
+        if let Some(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app.init_resource::<CreatureBatchPipeline>();
+        }
+    }
+}
+
+// === CREATURE BATCHES ===
+// From batch_creatures_from creatures on, one sprite entity per creature costs more to extract and
+// sort than it takes to draw, so the creatures of each species are drawn as one instanced draw
+// instead: a unit quad, and a per-instance buffer holding each creature's axes, position, tint and
+// animation frame, textured from a texture array of silhouette frames. The instance buffers live
+// in the render world and are rewritten in place every frame, growing only when a species does.
+// The creatures keep their Sprite and image, which systems such as the colour modes and the detail
+// levels still write to and the batch reads from; the sprite renderer skips them because they are
+// marked as not in view once their instance is written. Each species' batch entity is hidden
+// again when the count drops or the view zooms out to the density markers.
+#[derive(Resource, Default)]
+struct CreatureBatches {
+    active: bool,
+    frames: Option<Handle<Image>>,
+    entities: Vec<Entity>, // By species index, spawned on first use
+}
+
+// One creature in a species' instance buffer, uploaded as three vec4s
+#[derive(Clone, Copy)]
+struct CreatureInstance {
+    axes: Vec4, // World x and y axes of the creature's quad, scaled by its size
+    translation: Vec4, // w is the layer of the frame texture it shows
+    tint: Vec4, // Linear
+}
+
+// A species' instances, refilled every frame and copied to the render world
+#[derive(Component, Default, Clone)]
+struct CreatureInstances {
+    species: usize,
+    instances: Vec<CreatureInstance>,
+    z: f32, // Sort depth of the whole batch
+}
+
+// White silhouettes, tinted per creature: a body on four leg poses, then a body between two
+// wing poses
+fn create_frame_texture(mut batches: ResMut<CreatureBatches>, mut images: ResMut<Assets<Image>>) {
+    let mut data = Vec::with_capacity((FRAME_SIZE * FRAME_SIZE * LAYERS * 4) as usize);
+    for layer in 0..LAYERS {
+        for y in 0..FRAME_SIZE {
+            for x in 0..FRAME_SIZE {
+                let covered = layer < WALK_FRAMES + FLY_FRAMES && frame_covers(layer, x as f32 + 0.5, y as f32 + 0.5);
+                data.extend_from_slice(&[255, 255, 255, if covered { 255 } else { 0 }]);
+            }
+        }
+    }
+    let mut image = Image::new(
+        Extent3d { width: FRAME_SIZE, height: FRAME_SIZE * LAYERS, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    );
+    image.reinterpret_stacked_2d_as_array(LAYERS);
+    image.sampler = ImageSampler::nearest();
+    batches.frames = Some(images.add(image));
+}
+
+// Whether a pixel of a frame is part of the silhouette, in pixels from the top left
+fn frame_covers(layer: u32, x: f32, y: f32) -> bool {
+    let half = FRAME_SIZE as f32 / 2.0;
+    let (dx, dy) = (x - half, y - half);
+    if layer < WALK_FRAMES {
+        let body = (dx / 5.0).powi(2) + (dy / 4.0).powi(2) <= 1.0;
+        // Legs below the body, swinging forward and back over the cycle
+        let swing = [-1.5, 0.0, 1.5, 0.0][layer as usize];
+        let leg = |base: f32, swing: f32| (dx - base - swing * (dy - 3.0) / 4.0).abs() < 0.8 && (3.0..7.0).contains(&dy);
+        body || leg(-2.5, swing) || leg(2.5, -swing)
+    } else {
+        let body = (dx / 2.5).powi(2) + (dy / 3.5).powi(2) <= 1.0;
+        // Wings up, then down
+        let lift = if layer == WALK_FRAMES { -2.5 } else { 2.0 };
+        let wing = dx.abs() < 7.5 && (dy - lift * (dx.abs() / 7.5)).abs() < 1.2;
+        body || wing
+    }
+}
+
+fn toggle_creature_batches(
+    settings: Res<Settings>,
+    lod: Res<CreatureRenderLod>,
+    mut batches: ResMut<CreatureBatches>,
+    creatures: Query<(), With<Creature>>,
+    mut batch_visibility: Query<&mut Visibility, With<CreatureInstances>>,
+) {
+    let count = creatures.iter().len();
+    let active = settings.batch_creatures_from > 0 && count >= settings.batch_creatures_from
+        && lod.detail != CreatureDetail::Density;
+    if active != batches.active {
+        info!(count, "🦌 Creature batches {}", if active { "on" } else { "off" });
+        batches.active = active;
+        for entity in &batches.entities {
+            if let Ok(mut visibility) = batch_visibility.get_mut(*entity) {
+                *visibility = if active { Visibility::Inherited } else { Visibility::Hidden };
+            }
+        }
+    }
+}
+
+type BatchQueryData = (
+    Entity,
+    &'static GlobalTransform,
+    &'static Sprite,
+    &'static Creature,
+    &'static Wander,
+    &'static LODLevel,
+    &'static mut ViewVisibility,
+);
+
+// Refills the instances from the creatures' transforms and sprites, after the animation systems
+fn update_creature_batches(
+    mut commands: Commands,
+    time: Res<Time>,
+    registry: Res<SpeciesRegistry>,
+    mut batches: ResMut<CreatureBatches>,
+    mut creatures: Query<BatchQueryData>,
+    mut instances: Query<&mut CreatureInstances>,
+) {
+    if !batches.active {
+        return;
+    }
+    while batches.entities.len() < registry.species.len() {
+        let species = batches.entities.len();
+        let entity = commands.spawn((CreatureInstances { species, ..default() }, VisibilityBundle::default())).id();
+        batches.entities.push(entity);
+    }
+    for mut batch in instances.iter_mut() {
+        batch.instances.clear();
+        batch.z = f32::MIN;
+    }
+
+    for (entity, transform, sprite, creature, wander, lod, mut view_visibility) in creatures.iter_mut() {
+        if !view_visibility.get() {
+            continue;
+        }
+        let Some(mut batch) = batches.entities.get(creature.species).and_then(|entity| instances.get_mut(*entity).ok()) else { continue };
+        // The instance replaces the sprite for this frame
+        *view_visibility = ViewVisibility::HIDDEN;
+
+        let size = sprite.custom_size.unwrap_or(Vec2::ONE);
+        let affine = transform.affine();
+        let (x_axis, y_axis) = (affine.matrix3.x_axis.truncate() * size.x, affine.matrix3.y_axis.truncate() * size.y);
+
+        // Strides and wingbeats are out of step between creatures, as in gait.rs
+        let phase = ((time.elapsed_seconds() + entity.index() as f32 * 0.7) * FRAMES_PER_SECOND) as u32;
+        let still = matches!(wander.behavior, Behavior::Arrive | Behavior::Roost | Behavior::Hibernate) || is_distant(lod);
+        let frame = match registry.get(creature.species).movement {
+            MovementMode::Fly if still => WALK_FRAMES,
+            MovementMode::Fly => WALK_FRAMES + phase % FLY_FRAMES,
+            MovementMode::Walk if still => 0,
+            MovementMode::Walk => phase % WALK_FRAMES,
+        };
+        let translation = transform.translation();
+        batch.z = batch.z.max(translation.z);
+        batch.instances.push(CreatureInstance {
+            axes: Vec4::new(x_axis.x, x_axis.y, y_axis.x, y_axis.y),
+            translation: translation.extend(frame as f32),
+            tint: Vec4::from_array(sprite.color.to_linear().to_f32_array()),
+        });
+    }
+}
+
+// === RENDER WORLD ===
+
+// The pipeline and unit quad every species' draw shares
+#[derive(Resource)]
+struct CreatureBatchPipeline {
+    mesh2d_pipeline: Mesh2dPipeline, // For the view bindings and the tonemapping shader defs
+    frames_layout: BindGroupLayout,
+    shader: Handle<Shader>,
+    quad_layout: MeshVertexBufferLayoutRef,
+    quad_vertices: Buffer,
+    quad_indices: Buffer,
+}
+
+impl FromWorld for CreatureBatchPipeline {
+    fn from_world(world: &mut World) -> Self {
+        let quad = Mesh::new(PrimitiveTopology::TriangleList, RenderAssetUsages::RENDER_WORLD)
+            .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, vec![[-0.5, 0.5, 0.0], [0.5, 0.5, 0.0], [0.5, -0.5, 0.0], [-0.5, -0.5, 0.0]])
+            .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, vec![[0.0, 0.0], [1.0, 0.0], [1.0, 1.0], [0.0, 1.0]])
+            .with_inserted_indices(Indices::U32(vec![0, 2, 1, 0, 3, 2]));
+        let quad_layout = quad.get_mesh_vertex_buffer_layout(&mut world.resource_mut::<MeshVertexBufferLayouts>());
+        let device = world.resource::<RenderDevice>();
+        let quad_vertices = device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("creature_batch_quad_vertices"),
+            contents: &quad.get_vertex_buffer_data(),
+            usage: BufferUsages::VERTEX,
+        });
+        let quad_indices = device.create_buffer_with_data(&BufferInitDescriptor {
+            label: Some("creature_batch_quad_indices"),
+            contents: quad.get_index_buffer_bytes().unwrap_or_default(),
+            usage: BufferUsages::INDEX,
+        });
+        let frames_layout = device.create_bind_group_layout(
+            "creature_batch_frames_layout",
+            &BindGroupLayoutEntries::sequential(ShaderStages::FRAGMENT, (
+                texture_2d_array(TextureSampleType::Float { filterable: true }),
+                sampler(SamplerBindingType::Filtering),
+            )),
+        );
+        Self {
+            mesh2d_pipeline: world.resource::<Mesh2dPipeline>().clone(),
+            frames_layout,
+            shader: world.resource::<AssetServer>().load(SHADER_PATH),
+            quad_layout,
+            quad_vertices,
+            quad_indices,
+        }
+    }
+}
+
+impl SpecializedRenderPipeline for CreatureBatchPipeline {
+    type Key = Mesh2dPipelineKey;
+
+    fn specialize(&self, key: Self::Key) -> RenderPipelineDescriptor {
+        // The 2D mesh pipeline for the quad, with the instances as a second vertex buffer
+        let mut descriptor = self.mesh2d_pipeline.specialize(key, &self.quad_layout)
+            .expect("the quad has positions and uvs");
+        let instance_attribute = |shader_location: u32| VertexAttribute {
+            format: VertexFormat::Float32x4,
+            offset: (shader_location as u64 - 3) * 16,
+            shader_location,
+        };
+        descriptor.label = Some("creature_batch_pipeline".into());
+        descriptor.vertex.shader = self.shader.clone();
+        descriptor.vertex.buffers.push(VertexBufferLayout {
+            array_stride: size_of::<CreatureInstance>() as u64,
+            step_mode: VertexStepMode::Instance,
+            attributes: (3..6).map(instance_attribute).collect(),
+        });
+        if let Some(fragment) = descriptor.fragment.as_mut() {
+            fragment.shader = self.shader.clone();
+        }
+        descriptor.layout = vec![self.mesh2d_pipeline.view_layout.clone(), self.frames_layout.clone()];
+        descriptor
+    }
+}
+
+// Instance buffers by species, kept across frames so they are only reallocated to grow
+#[derive(Resource, Default)]
+struct CreatureBatchBuffers {
+    instances: Vec<RawBufferVec<Vec4>>, // Three per creature, see CreatureInstance
+    frames: Option<BindGroup>,
+}
+
+#[derive(Resource)]
+struct ExtractedFrames(AssetId<Image>);
+
+fn extract_creature_batches(
+    mut commands: Commands,
+    batches: Extract<Res<CreatureBatches>>,
+    instances: Extract<Query<(Entity, &CreatureInstances, &InheritedVisibility)>>,
+) {
+    if let Some(frames) = &batches.frames {
+        commands.insert_resource(ExtractedFrames(frames.id()));
+    }
+    let extracted: Vec<_> = instances.iter()
+        .filter(|(_, batch, visibility)| visibility.get() && !batch.instances.is_empty())
+        .map(|(entity, batch, _)| (entity, batch.clone()))
+        .collect();
+    commands.insert_or_spawn_batch(extracted);
+}
+
+fn prepare_creature_batches(
+    device: Res<RenderDevice>,
+    queue: Res<RenderQueue>,
+    mut buffers: ResMut<CreatureBatchBuffers>,
+    batches: Query<&CreatureInstances>,
+) {
+    for batch in batches.iter() {
+        while buffers.instances.len() <= batch.species {
+            buffers.instances.push(RawBufferVec::new(BufferUsages::VERTEX));
+        }
+        let buffer = &mut buffers.instances[batch.species];
+        buffer.clear();
+        buffer.extend(batch.instances.iter().flat_map(|instance| [instance.axes, instance.translation, instance.tint]));
+        buffer.write_buffer(&device, &queue);
+    }
+}
+
+fn prepare_frames_bind_group(
+    device: Res<RenderDevice>,
+    pipeline: Res<CreatureBatchPipeline>,
+    images: Res<RenderAssets<GpuImage>>,
+    frames: Option<Res<ExtractedFrames>>,
+    mut buffers: ResMut<CreatureBatchBuffers>,
+) {
+    if buffers.frames.is_some() {
+        return;
+    }
+    let Some(image) = frames.and_then(|frames| images.get(frames.0)) else { return };
+    buffers.frames = Some(device.create_bind_group(
+        "creature_batch_frames",
+        &pipeline.frames_layout,
+        &BindGroupEntries::sequential((&image.texture_view, &image.sampler)),
+    ));
+}
+
+type ViewQueryData = (Entity, &'static ExtractedView, Option<&'static Tonemapping>, Option<&'static DebandDither>);
+
+// One draw per species in every 2D view, sorted with the sprites by the batch's depth
+fn queue_creature_batches(
+    (draw_functions, pipeline_cache): (Res<DrawFunctions<Transparent2d>>, Res<PipelineCache>),
+    (pipeline, mut pipelines): (Res<CreatureBatchPipeline>, ResMut<SpecializedRenderPipelines<CreatureBatchPipeline>>),
+    msaa: Res<Msaa>,
+    batches: Query<(Entity, &CreatureInstances)>,
+    mut phases: ResMut<ViewSortedRenderPhases<Transparent2d>>,
+    views: Query<ViewQueryData>,
+) {
+    if batches.is_empty() {
+        return;
+    }
+    let draw_function = draw_functions.read().id::<DrawCreatureBatch>();
+    for (view_entity, view, tonemapping, dither) in views.iter() {
+        let Some(phase) = phases.get_mut(&view_entity) else { continue };
+        // Same key as the 2D mesh pipeline's own meshes
+        let mut key = Mesh2dPipelineKey::from_msaa_samples(msaa.samples())
+            | Mesh2dPipelineKey::from_hdr(view.hdr)
+            | Mesh2dPipelineKey::from_primitive_topology(PrimitiveTopology::TriangleList);
+        if !view.hdr {
+            if let Some(tonemapping) = tonemapping {
+                key |= Mesh2dPipelineKey::TONEMAP_IN_SHADER | tonemapping_pipeline_key(*tonemapping);
+            }
+            if let Some(DebandDither::Enabled) = dither {
+                key |= Mesh2dPipelineKey::DEBAND_DITHER;
+            }
+        }
+        let pipeline = pipelines.specialize(&pipeline_cache, &pipeline, key);
+        for (entity, batch) in batches.iter() {
+            phase.add(Transparent2d {
+                sort_key: FloatOrd(batch.z),
+                entity,
+                pipeline,
+                draw_function,
+                batch_range: 0..1,
+                extra_index: PhaseItemExtraIndex::NONE,
+            });
+        }
+    }
+}
+
+type DrawCreatureBatch = (SetItemPipeline, SetMesh2dViewBindGroup<0>, DrawCreatureInstances);
+
+struct DrawCreatureInstances;
+
+impl<P: PhaseItem> RenderCommand<P> for DrawCreatureInstances {
+    type Param = (SRes<CreatureBatchPipeline>, SRes<CreatureBatchBuffers>);
+    type ViewQuery = ();
+    type ItemQuery = Read<CreatureInstances>;
+
+    fn render<'w>(
+        _item: &P,
+        _view: ROQueryItem<'w, Self::ViewQuery>,
+        batch: Option<ROQueryItem<'w, Self::ItemQuery>>,
+        (pipeline, buffers): SystemParamItem<'w, '_, Self::Param>,
+        pass: &mut TrackedRenderPass<'w>,
+    ) -> RenderCommandResult {
+        let (pipeline, buffers) = (pipeline.into_inner(), buffers.into_inner());
+        let Some(batch) = batch else { return RenderCommandResult::Failure };
+        let Some(frames) = &buffers.frames else { return RenderCommandResult::Failure };
+        let Some(instances) = buffers.instances.get(batch.species).and_then(RawBufferVec::buffer) else {
+            return RenderCommandResult::Failure;
+        };
+        pass.set_bind_group(1, frames, &[]);
+        pass.set_vertex_buffer(0, pipeline.quad_vertices.slice(..));
+        pass.set_vertex_buffer(1, instances.slice(..));
+        pass.set_index_buffer(pipeline.quad_indices.slice(..), 0, IndexFormat::Uint32);
+        pass.draw_indexed(0..6, 0, 0..batch.instances.len() as u32);
+        RenderCommandResult::Success
+    }
+}
@@ -0,0 +1,127 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::camera::MainCamera;
+use crate::coords::{tile_to_chunk, world_to_tile, CHUNK_SIZE, TILE_SIZE};
+use crate::creature::Creature;
+use crate::genetics::Genome;
+use crate::population_pools::{chunk_center, PopulationPools};
+use crate::species::SpeciesRegistry;
+
+const DOT_ZOOM: f32 = 2.5; // Orthographic scale beyond which creatures shrink to dots
+const DENSITY_ZOOM: f32 = 6.0; // Beyond this they are hidden and summarised per chunk
+const DOT_PIXELS: f32 = 2.0; // Minimum on-screen size of a dot
+
+// Density markers are gizmo circles, batched into one draw call like the stat bars
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct DensityGizmos;
+
+pub struct CreatureLodPlugin;
+
+impl Plugin for CreatureLodPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<CreatureRenderLod>()
+            .insert_gizmo_config(DensityGizmos, GizmoConfig {
+                line_width: 3.0,
+                ..default()
+            })
+            .add_systems(Update, (
+                update_creature_detail,
+                apply_creature_detail,
+                draw_density_markers.run_if(|lod: Res<CreatureRenderLod>| lod.detail == CreatureDetail::Density),
+            ).chain());
+    }
+}
+
+// === CREATURE RENDER LOD ===
+// Picked from the camera zoom for the whole view: full sprites close up, dots at medium zoom,
+// and per-chunk density markers (including pooled creatures) when looking at the whole map.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CreatureDetail {
+    #[default]
+    Sprite,
+    Dot,
+    Density,
+}
+
+#[derive(Resource)]
+pub struct CreatureRenderLod {
+    pub detail: CreatureDetail,
+    pub scale: f32, // Camera orthographic scale the detail was picked for
+}
+
+impl Default for CreatureRenderLod {
+    fn default() -> Self {
+        Self { detail: CreatureDetail::Sprite, scale: 1.0 }
+    }
+}
+
+fn update_creature_detail(
+    projections: Query<&OrthographicProjection, (With<MainCamera>, Changed<OrthographicProjection>)>,
+    mut lod: ResMut<CreatureRenderLod>,
+) {
+    let Ok(projection) = projections.get_single() else { return };
+    let detail = match projection.scale {
+        scale if scale < DOT_ZOOM => CreatureDetail::Sprite,
+        scale if scale < DENSITY_ZOOM => CreatureDetail::Dot,
+        _ => CreatureDetail::Density,
+    };
+    // Dot sizes follow the zoom; the other levels only care when the level changes
+    if detail != lod.detail || detail == CreatureDetail::Dot {
+        *lod = CreatureRenderLod { detail, scale: projection.scale };
+    }
+}
+
+fn apply_creature_detail(
+    lod: Res<CreatureRenderLod>,
+    registry: Res<SpeciesRegistry>,
+    mut creatures: Query<(Ref<Creature>, &Genome, &mut Sprite, &mut Visibility)>,
+) {
+    for (creature, genome, mut sprite, mut visibility) in creatures.iter_mut() {
+        if !lod.is_changed() && !creature.is_added() {
+            continue;
+        }
+        let size = registry.get(creature.species).size * genome.size;
+        match lod.detail {
+            CreatureDetail::Sprite => sprite.custom_size = Some(Vec2::splat(size)),
+            CreatureDetail::Dot => sprite.custom_size = Some(Vec2::splat(size.max(DOT_PIXELS * lod.scale))),
+            CreatureDetail::Density => {}
+        }
+        visibility.set_if_neq(if lod.detail == CreatureDetail::Density { Visibility::Hidden } else { Visibility::Inherited });
+    }
+}
+
+// One circle per populated chunk, sized by head count and coloured by its most common species
+fn draw_density_markers(
+    mut gizmos: Gizmos<DensityGizmos>,
+    registry: Res<SpeciesRegistry>,
+    pools: Option<Res<PopulationPools>>,
+    creatures: Query<(&Transform, &Creature)>,
+) {
+    let mut chunks: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+    let mut count = |chunk: (i32, i32), species: usize| {
+        let counts = chunks.entry(chunk).or_insert_with(|| vec![0; registry.species.len()]);
+        if let Some(count) = counts.get_mut(species) {
+            *count += 1;
+        }
+    };
+    for (transform, creature) in creatures.iter() {
+        if let Some((x, y)) = world_to_tile(transform.translation.truncate()) {
+            count(tile_to_chunk(x, y), creature.species);
+        }
+    }
+    for (chunk, pooled) in pools.iter().flat_map(|pools| pools.chunks.iter()) {
+        for creature in pooled {
+            count(*chunk, creature.species);
+        }
+    }
+
+    let busiest = chunks.values().map(|counts| counts.iter().sum::<usize>()).max().unwrap_or(0).max(1);
+    let max_radius = CHUNK_SIZE as f32 * TILE_SIZE / 2.0;
+    for (chunk, counts) in &chunks {
+        let total: usize = counts.iter().sum();
+        let Some((dominant, _)) = counts.iter().enumerate().max_by_key(|(_, count)| **count) else { continue };
+        let radius = max_radius * (total as f32 / busiest as f32).sqrt();
+        gizmos.circle_2d(chunk_center(*chunk), radius.max(max_radius * 0.15), registry.get(dominant).get_color());
+    }
+}
@@ -0,0 +1,79 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::keybindings::{Action, Controls};
+use crate::creature::{Creature, Needs};
+use crate::text_input::text_input_inactive;
+use crate::optimization::LODLevel;
+use crate::settings::Settings;
+use crate::save::SaveViewExt;
+use crate::species::SpeciesRegistry;
+
+const BAR_WIDTH: f32 = 6.0;
+const BAR_SPACING: f32 = 1.5;
+const BAR_BACKGROUND: Color = Color::srgba(0.1, 0.1, 0.1, 0.8);
+
+// Stat bars are drawn as gizmo lines, which bevy batches into a single draw call
+// per frame regardless of how many creatures are visible.
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct StatBarGizmos;
+
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
+pub struct StatOverlay {
+    pub enabled: bool,
+}
+
+pub struct CreatureOverlayPlugin;
+
+impl Plugin for CreatureOverlayPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<StatOverlay>()
+            .save_view_resource::<StatOverlay>("stat_bars")
+            .insert_gizmo_config(StatBarGizmos, GizmoConfig {
+                line_width: 1.5,
+                ..default()
+            })
+            .add_systems(Update, (
+                toggle_stat_overlay.run_if(text_input_inactive),
+                draw_stat_bars.run_if(|overlay: Res<StatOverlay>| overlay.enabled),
+            ));
+    }
+}
+
+fn toggle_stat_overlay(controls: Controls, mut overlay: ResMut<StatOverlay>) {
+    if controls.just_pressed(Action::ToggleStatBars) {
+        overlay.enabled = !overlay.enabled;
+        info!("📊 Creature stat bars {}", if overlay.enabled { "shown" } else { "hidden" });
+    }
+}
+
+// Health, hunger and thirst bars above every creature close to the camera (LOD 0), level on
+// screen in either view
+fn draw_stat_bars(
+    mut gizmos: Gizmos<StatBarGizmos>,
+    settings: Res<Settings>,
+    registry: Res<SpeciesRegistry>,
+    creatures: Query<(&Transform, &Creature, &Needs, &LODLevel)>,
+) {
+    let (across, up) = (settings.view.screen_to_world(Vec2::X), settings.view.screen_to_world(Vec2::Y));
+    for (transform, creature, needs, lod) in creatures.iter() {
+        if lod.0 != 0 {
+            continue;
+        }
+
+        let size = registry.get(creature.species).size;
+        let left = transform.translation.truncate() - across * BAR_WIDTH / 2.0 + up * (size / 2.0 + 2.0);
+        let bars = [
+            (needs.health, Color::srgb(0.2, 0.9, 0.2)),
+            (1.0 - needs.hunger, Color::srgb(0.9, 0.6, 0.1)),
+            (1.0 - needs.thirst, Color::srgb(0.2, 0.5, 1.0)),
+        ];
+
+        for (row, (value, color)) in bars.into_iter().enumerate() {
+            let start = left + up * BAR_SPACING * row as f32;
+            let filled = start + across * BAR_WIDTH * value.clamp(0.0, 1.0);
+            gizmos.line_2d(start, filled, color);
+            gizmos.line_2d(filled, start + across * BAR_WIDTH, BAR_BACKGROUND);
+        }
+    }
+}
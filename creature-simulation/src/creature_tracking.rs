@@ -0,0 +1,244 @@
+use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use std::time::Duration;
+use crate::coords::world_to_tile;
+use crate::keybindings::{Action, Controls};
+use crate::camera::{CameraPan, MainCameraQuery};
+use crate::creature::{Creature, Needs};
+use crate::optimization::SpatialView;
+use crate::loading::LoadingState;
+use crate::localization::Localization;
+use crate::names::creature_name;
+use crate::species::SpeciesRegistry;
+use crate::text_input::{text_input_inactive, PromptKind, PromptSubmitted, TextPrompt};
+use crate::world::WorldMap;
+
+const SELECT_RADIUS: f32 = 8.0; // World units around the cursor that count as a hit
+const SELECT_CANDIDATES: usize = 4; // Creatures nearest the cursor considered for a click
+const PANEL_REFRESH_SECONDS: f32 = 0.5;
+
+pub struct CreatureTrackingPlugin;
+
+impl Plugin for CreatureTrackingPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<SelectedCreature>()
+            .add_systems(Update, (
+                start_naming.run_if(text_input_inactive),
+                apply_creature_name,
+                (select_creature_on_click, toggle_bookmark.run_if(text_input_inactive)).chain(),
+                draw_selection_marker,
+                jump_to_tracked_creature,
+                update_tracked_panel
+                    .run_if(|state: Res<LoadingState>| state.is_complete)
+                    .run_if(on_timer(Duration::from_secs_f32(PANEL_REFRESH_SECONDS))),
+            ));
+    }
+}
+
+// User-given name; unnamed creatures are shown as "<species> #<entity>"
+#[derive(Component, Clone)]
+pub struct CreatureName(pub String);
+
+// Pinned to the tracked creatures panel
+#[derive(Component)]
+pub struct Bookmarked;
+
+#[derive(Resource, Default)]
+pub struct SelectedCreature(pub Option<Entity>);
+
+#[derive(Component)]
+pub struct TrackedPanel;
+
+#[derive(Component)]
+pub struct TrackedEntry(pub Entity);
+
+pub fn display_name(name: Option<&CreatureName>, species: &str, entity: Entity) -> String {
+    match name {
+        Some(name) => name.0.clone(),
+        None => format!("{} #{}", species, entity.index()),
+    }
+}
+
+fn select_creature_on_click(
+    mouse: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window>,
+    cameras: MainCameraQuery,
+    creatures: Query<(Entity, &Transform), With<Creature>>,
+    (entries, spatial): (Query<&Interaction, With<TrackedEntry>>, Res<SpatialView>),
+    mut selected: ResMut<SelectedCreature>,
+) {
+    if !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+    // Clicks on the tracked panel are handled by jump_to_tracked_creature
+    if entries.iter().any(|interaction| *interaction != Interaction::None) {
+        return;
+    }
+    let Ok(window) = windows.get_single() else { return };
+    let Ok((camera, camera_transform)) = cameras.get_single() else { return };
+    let Some(cursor) = window.cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor)) else { return };
+
+    // The spatial view is a tick behind, so the few nearest are checked where they are now
+    selected.0 = spatial.nearest(cursor, SELECT_CANDIDATES).into_iter()
+        .filter_map(|(entity, _)| creatures.get(entity).ok())
+        .map(|(entity, transform)| (entity, transform.translation.truncate().distance(cursor)))
+        .filter(|(_, distance)| *distance < SELECT_RADIUS)
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity);
+}
+
+type BookmarkQueryData = (Has<Bookmarked>, Has<CreatureName>, &'static Transform);
+
+// Pinning an unnamed creature makes it notable enough for a generated name
+fn toggle_bookmark(
+    mut commands: Commands,
+    controls: Controls,
+    selected: Res<SelectedCreature>,
+    world_map: Option<Res<WorldMap>>,
+    bookmarked: Query<BookmarkQueryData, With<Creature>>,
+) {
+    if !controls.just_pressed(Action::PinCreature) {
+        return;
+    }
+    let Some(entity) = selected.0 else { return };
+    match bookmarked.get(entity) {
+        Ok((true, _, _)) => { commands.entity(entity).remove::<Bookmarked>(); }
+        Ok((false, named, transform)) => {
+            commands.entity(entity).insert(Bookmarked);
+            let biome = world_to_tile(transform.translation.truncate())
+                .zip(world_map.as_ref())
+                .map(|((x, y), world_map)| world_map.tiles[x][y].biome);
+            if let (false, Some(biome)) = (named, biome) {
+                commands.entity(entity).insert(CreatureName(creature_name(entity.to_bits(), biome)));
+            }
+        }
+        Err(_) => {}
+    }
+}
+
+fn start_naming(
+    controls: Controls,
+    selected: Res<SelectedCreature>,
+    names: Query<Option<&CreatureName>, With<Creature>>,
+    mut prompt: ResMut<TextPrompt>,
+) {
+    if !controls.just_pressed(Action::NameCreature) {
+        return;
+    }
+    let Some(entity) = selected.0 else { return };
+    let Ok(name) = names.get(entity) else { return };
+    prompt.open(PromptKind::CreatureName(entity), name.map(|name| name.0.clone()).unwrap_or_default());
+}
+
+// An empty name clears it
+fn apply_creature_name(
+    mut commands: Commands,
+    mut submitted: EventReader<PromptSubmitted>,
+    creatures: Query<(), With<Creature>>,
+) {
+    for event in submitted.read() {
+        let PromptKind::CreatureName(target) = event.kind else { continue };
+        // The creature may have died mid-edit
+        if creatures.get(target).is_err() {
+            continue;
+        }
+        if event.text.is_empty() {
+            commands.entity(target).remove::<CreatureName>();
+        } else {
+            commands.entity(target).insert(CreatureName(event.text.clone()));
+        }
+    }
+}
+
+fn draw_selection_marker(
+    mut gizmos: Gizmos,
+    mut selected: ResMut<SelectedCreature>,
+    creatures: Query<&Transform, With<Creature>>,
+) {
+    let Some(entity) = selected.0 else { return };
+    match creatures.get(entity) {
+        Ok(transform) => { gizmos.circle_2d(transform.translation.truncate(), 6.0, Color::WHITE); }
+        Err(_) => selected.0 = None, // Died or despawned
+    }
+}
+
+fn jump_to_tracked_creature(
+    entries: Query<(&Interaction, &TrackedEntry), Changed<Interaction>>,
+    creatures: Query<&Transform, With<Creature>>,
+    mut pan: ResMut<CameraPan>,
+    mut selected: ResMut<SelectedCreature>,
+) {
+    for (interaction, entry) in entries.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Ok(target) = creatures.get(entry.0) else { continue };
+        pan.go_to(target.translation.truncate());
+        selected.0 = Some(entry.0);
+    }
+}
+
+// === TRACKED CREATURES PANEL ===
+// Rebuilt on a short timer rather than every frame; the list is small but text layout is not free.
+fn update_tracked_panel(
+    mut commands: Commands,
+    localization: Res<Localization>,
+    registry: Res<SpeciesRegistry>,
+    tracked: Query<(Entity, &Creature, &Needs, Option<&CreatureName>), With<Bookmarked>>,
+    panels: Query<Entity, With<TrackedPanel>>,
+) {
+    let panel = match panels.get_single() {
+        Ok(panel) => {
+            commands.entity(panel).despawn_descendants();
+            panel
+        }
+        Err(_) => commands.spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.0),
+                    right: Val::Px(10.0),
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(8.0)),
+                    row_gap: Val::Px(4.0),
+                    ..default()
+                },
+                background_color: Color::srgba(0.05, 0.05, 0.1, 0.8).into(),
+                ..default()
+            },
+            TrackedPanel,
+        )).id(),
+    };
+
+    let text_style = TextStyle { font_size: 14.0, color: Color::srgb(0.9, 0.9, 0.9), ..default() };
+
+    commands.entity(panel).with_children(|parent| {
+        parent.spawn(TextBundle::from_section(localization.get("tracking.title"), text_style.clone()));
+
+        if tracked.is_empty() {
+            parent.spawn(TextBundle::from_section(localization.get("tracking.empty"), text_style.clone()));
+        }
+
+        for (entity, creature, needs, name) in tracked.iter() {
+            let species = &registry.get(creature.species).name;
+            let summary = localization.format("tracking.entry", &[
+                ("name", display_name(name, species, entity)),
+                ("health", format!("{:.0}", needs.health * 100.0)),
+                ("food", format!("{:.0}", (1.0 - needs.hunger) * 100.0)),
+                ("water", format!("{:.0}", (1.0 - needs.thirst) * 100.0)),
+            ]);
+            parent.spawn((
+                ButtonBundle {
+                    style: Style { padding: UiRect::axes(Val::Px(6.0), Val::Px(2.0)), ..default() },
+                    background_color: Color::srgba(0.2, 0.2, 0.3, 0.9).into(),
+                    ..default()
+                },
+                TrackedEntry(entity),
+            )).with_children(|button| {
+                button.spawn(TextBundle::from_section(summary, text_style.clone()));
+            });
+        }
+    });
+}
@@ -0,0 +1,142 @@
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use crate::coords::{world_to_tile, MAP_CENTER, WORLD_EXTENT};
+use crate::keybindings::{Action, Controls};
+use crate::creature::CreatureDied;
+use crate::save::SaveViewExt;
+use crate::text_input::text_input_inactive;
+use crate::world::WORLD_SIZE;
+
+pub const HEATMAP_CELL_TILES: usize = 8; // Each heat map cell covers 8x8 tiles
+pub const HEATMAP_SIZE: usize = WORLD_SIZE / HEATMAP_CELL_TILES;
+pub const HEATMAP_DECAY_SECONDS: f32 = 120.0; // Time constant of the exponential decay
+const HEATMAP_SATURATION: f32 = 5.0; // Heat at which a cell is drawn fully red
+const HEATMAP_Z: f32 = 5.0; // Above tiles, environment and creatures
+
+pub struct DeathHeatmapPlugin;
+
+impl Plugin for DeathHeatmapPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<DeathHeatmap>()
+            .save_visibility::<HeatmapOverlay>("heatmap")
+            .add_systems(Startup, spawn_heatmap_overlay)
+            .add_systems(FixedUpdate, decay_heatmap)
+            .add_systems(Update, (
+                record_deaths,
+                toggle_heatmap_overlay.run_if(text_input_inactive),
+                update_heatmap_image,
+            ).chain());
+    }
+}
+
+// === DEATH HEAT MAP ===
+// Coarse grid of recent deaths; each death adds 1.0 heat which decays over simulated time.
+#[derive(Resource)]
+pub struct DeathHeatmap {
+    pub cells: Vec<f32>, // Indexed [cx * HEATMAP_SIZE + cy], matching tiles[x][y]
+}
+
+impl Default for DeathHeatmap {
+    fn default() -> Self {
+        Self { cells: vec![0.0; HEATMAP_SIZE * HEATMAP_SIZE] }
+    }
+}
+
+impl DeathHeatmap {
+    pub fn add(&mut self, position: Vec2) {
+        if let Some((x, y)) = world_to_tile(position) {
+            let (cx, cy) = (x / HEATMAP_CELL_TILES, y / HEATMAP_CELL_TILES);
+            self.cells[cx * HEATMAP_SIZE + cy] += 1.0;
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct HeatmapOverlay {
+    pub image: Handle<Image>,
+}
+
+fn spawn_heatmap_overlay(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let image = Image::new_fill(
+        Extent3d { width: HEATMAP_SIZE as u32, height: HEATMAP_SIZE as u32, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    let image = images.add(image);
+
+    // Tile centres sit on the grid, so the map spans half a tile further on the low side
+    commands.spawn((
+        SpriteBundle {
+            texture: image.clone(),
+            sprite: Sprite {
+                custom_size: Some(Vec2::splat(WORLD_EXTENT)),
+                ..default()
+            },
+            transform: Transform::from_translation(MAP_CENTER.extend(HEATMAP_Z)),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        HeatmapOverlay { image },
+    ));
+}
+
+fn record_deaths(mut deaths: EventReader<CreatureDied>, mut heatmap: ResMut<DeathHeatmap>) {
+    for death in deaths.read() {
+        heatmap.add(death.position);
+    }
+}
+
+fn decay_heatmap(time: Res<Time>, mut heatmap: ResMut<DeathHeatmap>) {
+    let factor = (-time.delta_seconds() / HEATMAP_DECAY_SECONDS).exp();
+    for cell in heatmap.cells.iter_mut() {
+        *cell *= factor;
+    }
+}
+
+fn toggle_heatmap_overlay(
+    controls: Controls,
+    mut overlay: Query<&mut Visibility, With<HeatmapOverlay>>,
+) {
+    if !controls.just_pressed(Action::ToggleHeatmap) {
+        return;
+    }
+    for mut visibility in overlay.iter_mut() {
+        *visibility = match *visibility {
+            Visibility::Hidden => Visibility::Visible,
+            _ => Visibility::Hidden,
+        };
+        info!("💀 Death heat map {}", if *visibility == Visibility::Hidden { "hidden" } else { "shown" });
+    }
+}
+
+// Re-uploads the heat map texture while the overlay is visible
+fn update_heatmap_image(
+    heatmap: Res<DeathHeatmap>,
+    overlay: Query<(&HeatmapOverlay, &Visibility)>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let Ok((overlay, visibility)) = overlay.get_single() else { return };
+    if *visibility == Visibility::Hidden {
+        return;
+    }
+    let Some(image) = images.get_mut(&overlay.image) else { return };
+
+    for cx in 0..HEATMAP_SIZE {
+        for cy in 0..HEATMAP_SIZE {
+            let heat = (heatmap.cells[cx * HEATMAP_SIZE + cy] / HEATMAP_SATURATION).min(1.0);
+            // Image rows run top-down while world y runs bottom-up
+            let pixel = ((HEATMAP_SIZE - 1 - cy) * HEATMAP_SIZE + cx) * 4;
+            // Yellow for a single recent death, through to red for hot spots
+            image.data[pixel..pixel + 4].copy_from_slice(&[
+                255,
+                (255.0 * (1.0 - heat)) as u8,
+                0,
+                if heat > 0.01 { (80.0 + 150.0 * heat) as u8 } else { 0 },
+            ]);
+        }
+    }
+}
@@ -0,0 +1,221 @@
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use crate::calendar::Calendar;
+use crate::creature::DeathCause;
+use crate::events::NotableEvent;
+use crate::extreme_weather::ExtremeWeather;
+use crate::genealogy::Genealogy;
+use crate::keybindings::{Action, Controls, Keybindings};
+use crate::localization::Localization;
+use crate::save::SaveViewExt;
+use crate::text_input::text_input_inactive;
+use crate::weather::{celsius, rain_label_key, ClimateHistory};
+use crate::world::WorldMap;
+
+pub const JOURNAL_PATH: &str = "journal.md";
+
+pub struct DigestPlugin;
+
+impl Plugin for DigestPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<DayDigests>()
+            .save_view::<bool>("digest",
+                |world| world.query::<&DigestPanel>().iter(world).next().map(|panel| panel.expanded),
+                |world, expanded| world.query::<&mut DigestPanel>().iter_mut(world).for_each(|mut panel| panel.expanded = expanded))
+            .add_systems(Startup, spawn_digest_panel)
+            .add_systems(Update, (
+                reset_digests.run_if(resource_added::<WorldMap>),
+                compile_day_digest.run_if(resource_exists::<WorldMap>),
+                toggle_digest_panel.run_if(text_input_inactive),
+                refresh_digest_panel,
+            ).chain());
+    }
+}
+
+// === DAY DIGEST ===
+// Notable events are tallied through the day; when the calendar turns over they are compiled,
+// with the day's births and the weather at the camera, into a digest. The latest one is shown in
+// a panel that stays collapsed to a one-line notice until expanded, and every digest is appended
+// to JOURNAL_PATH in the current language.
+pub struct DayDigest {
+    pub day: u32,
+    pub births: usize,
+    pub deaths: BTreeMap<&'static str, usize>, // By cause, as event label keys
+    pub temperature: Option<f32>, // Mean at the camera, °C
+    pub rain_key: Option<&'static str>,
+    pub extremes: Vec<&'static str>, // Extreme weather lasting the day, as event label keys
+    pub events: BTreeMap<&'static str, usize>, // Other notable events by label key
+}
+
+impl DayDigest {
+    pub fn deaths_total(&self) -> usize {
+        self.deaths.values().sum()
+    }
+
+    pub fn lines(&self, localization: &Localization) -> Vec<String> {
+        let mut lines = vec![
+            localization.format("digest.title", &[("day", self.day.to_string())]),
+            localization.format("digest.births", &[("count", self.births.to_string())]),
+            localization.format("digest.deaths", &[("count", self.deaths_total().to_string())]),
+        ];
+        for (cause, count) in self.deaths.iter().filter(|(_, count)| **count > 0) {
+            lines.push(format!("  {}: {}", localization.get(cause), count));
+        }
+        if let (Some(temperature), Some(rain_key)) = (self.temperature, self.rain_key) {
+            lines.push(localization.format("digest.weather", &[
+                ("sky", localization.get(rain_key).to_string()),
+                ("temperature", format!("{:.0}", temperature)),
+            ]));
+        }
+        for extreme in &self.extremes {
+            lines.push(format!("  ⚠️ {}", localization.get(extreme)));
+        }
+        if !self.events.is_empty() {
+            lines.push(localization.get("digest.events").to_string());
+            for (label_key, count) in &self.events {
+                lines.push(format!("  {} ×{}", localization.get(label_key), count));
+            }
+        }
+        lines
+    }
+}
+
+#[derive(Resource)]
+pub struct DayDigests {
+    pub latest: Option<DayDigest>,
+    tally: BTreeMap<&'static str, usize>, // Notable events of the day so far
+    day: Option<u32>,
+    journal: Option<File>,
+}
+
+impl Default for DayDigests {
+    fn default() -> Self {
+        let journal = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(JOURNAL_PATH)
+            .map_err(|err| warn!("Run journal disabled, could not open {}: {}", JOURNAL_PATH, err))
+            .ok();
+        Self { latest: None, tally: BTreeMap::new(), day: None, journal }
+    }
+}
+
+#[derive(Component)]
+pub struct DigestPanel {
+    pub expanded: bool,
+}
+
+fn reset_digests(mut digests: ResMut<DayDigests>) {
+    digests.latest = None;
+    digests.tally.clear();
+    digests.day = None;
+}
+
+fn compile_day_digest(
+    calendar: Res<Calendar>,
+    localization: Res<Localization>,
+    (genealogy, history, extremes): (Res<Genealogy>, Res<ClimateHistory>, Option<Res<ExtremeWeather>>),
+    mut notable: EventReader<NotableEvent>,
+    mut digests: ResMut<DayDigests>,
+) {
+    // Only a new digest counts as a change, for the panel
+    let running = digests.bypass_change_detection();
+    for event in notable.read() {
+        *running.tally.entry(event.label_key).or_default() += 1;
+    }
+    let previous = running.day.replace(calendar.day);
+    let Some(day) = previous.filter(|day| *day != calendar.day) else { return };
+    let tally = std::mem::take(&mut running.tally);
+    // A loaded save jumps the calendar; there is no whole day to report
+    if calendar.day != day + 1 {
+        return;
+    }
+
+    let (deaths, events) = tally.into_iter().partition(|(label_key, _)| DeathCause::ALL.iter().any(|cause| cause.label_key() == *label_key));
+    let climate = history.last_day();
+    let digest = DayDigest {
+        day,
+        births: genealogy.births_on(day),
+        deaths,
+        temperature: climate.map(|climate| celsius(climate.temperature)),
+        rain_key: climate.map(|climate| rain_label_key(climate.rainfall)),
+        extremes: extremes.iter()
+            .flat_map(|extremes| extremes.events.iter())
+            .filter(|event| event.is_active(day))
+            .map(|event| event.kind.label_key())
+            .collect(),
+        events,
+    };
+    info!("📰 Day {}: {} births, {} deaths", day, digest.births, digest.deaths_total());
+
+    let lines = digest.lines(&localization);
+    if let Some(journal) = running.journal.as_mut() {
+        let entry = format!("## {}\n\n{}\n\n", lines[0], lines[1..].join("\n"));
+        if let Err(err) = journal.write_all(entry.as_bytes()) {
+            warn!("Failed to append to {}: {}", JOURNAL_PATH, err);
+        }
+    }
+    digests.latest = Some(digest);
+}
+
+fn spawn_digest_panel(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section("", TextStyle {
+            font_size: 14.0,
+            color: Color::srgb(0.9, 0.9, 0.9),
+            ..default()
+        }).with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            left: Val::Percent(35.0), // Clear of the world report and the tracked creature's card
+            padding: UiRect::all(Val::Px(8.0)),
+            ..default()
+        }).with_background_color(Color::srgba(0.05, 0.05, 0.1, 0.8)),
+        Visibility::Hidden,
+        DigestPanel { expanded: false },
+    ));
+}
+
+fn toggle_digest_panel(controls: Controls, mut panels: Query<&mut DigestPanel>) {
+    if !controls.just_pressed(Action::ToggleDayDigest) {
+        return;
+    }
+    for mut panel in panels.iter_mut() {
+        panel.expanded = !panel.expanded;
+    }
+}
+
+// Hidden until the first digest; collapsed, it only names the day and its births and deaths
+fn refresh_digest_panel(
+    digests: Res<DayDigests>,
+    localization: Res<Localization>,
+    bindings: Res<Keybindings>,
+    mut panels: Query<(Ref<DigestPanel>, &mut Text, &mut Visibility)>,
+) {
+    for (panel, mut text, mut visibility) in panels.iter_mut() {
+        if !digests.is_changed() && !panel.is_changed() {
+            continue;
+        }
+        let Some(digest) = digests.latest.as_ref() else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        *visibility = Visibility::Visible;
+        let key = bindings.label(Action::ToggleDayDigest);
+        text.sections[0].value = if panel.expanded {
+            let mut lines = digest.lines(&localization);
+            lines.push(localization.format("digest.collapse", &[("key", key)]));
+            lines.join("\n")
+        } else {
+            localization.format("digest.notice", &[
+                ("day", digest.day.to_string()),
+                ("births", digest.births.to_string()),
+                ("deaths", digest.deaths_total().to_string()),
+                ("key", key),
+            ])
+        };
+    }
+}
@@ -0,0 +1,546 @@
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use crate::camera::MainCameraQuery;
+use crate::coords::{tile_to_chunk, tile_to_world, world_to_tile, TILE_SIZE};
+use crate::keybindings::{Action, Controls};
+use crate::biome::BiomeType;
+use crate::errors::{SimError, SimFailure};
+use crate::generation_service::{GenerationId, GenerationJob, GenerationOutput, GenerationPriority, GenerationService};
+use crate::environment::{get_environment_elements, EnvironmentType};
+use crate::io_queue::IoQueue;
+use crate::localization::Localization;
+use crate::optimization::ChunkManager;
+use crate::reserves::{ReserveBrush, ReserveShape, Reserves};
+use crate::spawn_zones::{SpawnBrush, SpawnMode};
+use crate::species::SpeciesRegistry;
+use crate::stamps::StampLibrary;
+use crate::text_input::text_input_inactive;
+use crate::world::{GenerationParams, WorldMap, SEA_LEVEL, WORLD_SIZE};
+use crate::rng_streams::{RngStream, RngStreams};
+
+pub const EDITS_PATH: &str = "world_edits.ron";
+const MAX_UNDO: usize = 100; // Strokes kept on the undo stack
+const MAX_BRUSH_RADIUS: usize = 16; // Tiles
+const RAISE_RATE: f32 = 0.5; // Elevation per second at the brush centre
+const SCATTER_DENSITY: f32 = 0.2; // Chance per tile of an extra prop
+const WATER_DEPTH: f32 = 0.05; // Placed water sits this far below sea level
+
+const PAINT_BIOMES: [BiomeType; 12] = [
+    BiomeType::Grasslands, BiomeType::Forest, BiomeType::TropicalRainforest, BiomeType::Savanna,
+    BiomeType::Desert, BiomeType::Badlands, BiomeType::Wetlands, BiomeType::Tundra,
+    BiomeType::Alpine, BiomeType::Mountain, BiomeType::Volcanic, BiomeType::Coastal,
+];
+
+pub struct EditorPlugin;
+
+impl Plugin for EditorPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Editor>()
+            .init_resource::<ChunkRestores>()
+            .add_systems(Startup, spawn_editor_label)
+            .add_systems(Update, (
+                reset_editor.run_if(resource_added::<WorldMap>),
+                apply_restored_chunks.run_if(resource_exists::<WorldMap>),
+                toggle_editor.run_if(text_input_inactive),
+                (editor_hotkeys, undo_redo, save_load_edits).run_if(text_input_inactive).run_if(editor_active),
+                paint_with_brush.run_if(editor_active),
+                draw_brush.run_if(editor_active),
+                update_editor_label.run_if(resource_changed::<Editor>
+                    .or_else(resource_changed::<StampLibrary>)
+                    .or_else(resource_changed::<SpawnBrush>)
+                    .or_else(resource_changed::<ReserveBrush>)),
+            ).chain());
+    }
+}
+
+// === WORLD EDITOR ===
+// Toggled with E. Left mouse applies the current tool; each stroke (press to release) is one
+// undoable command holding the before/after state of every tile it touched. Edits write straight
+// into the WorldMap without triggering its change detection and mark their chunks dirty, so only
+// those chunks are re-rendered. Derived layers (hydrology, vegetation) keep their generated state.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum EditorTool {
+    #[default]
+    PaintBiome,
+    RaiseTerrain,
+    LowerTerrain,
+    PlaceWater,
+    ScatterProps,
+    SpawnCreatures, // Spawner brush (spawn_zones.rs)
+    Reserve, // Paint or drag out nature reserves (reserves.rs)
+    CopyRegion, // Drag a rectangle onto the clipboard stamp (stamps.rs)
+    Stamp, // Click to place the selected stamp
+}
+
+impl EditorTool {
+    fn next(self) -> Self {
+        match self {
+            EditorTool::PaintBiome => EditorTool::RaiseTerrain,
+            EditorTool::RaiseTerrain => EditorTool::LowerTerrain,
+            EditorTool::LowerTerrain => EditorTool::PlaceWater,
+            EditorTool::PlaceWater => EditorTool::ScatterProps,
+            EditorTool::ScatterProps => EditorTool::SpawnCreatures,
+            EditorTool::SpawnCreatures => EditorTool::Reserve,
+            EditorTool::Reserve => EditorTool::CopyRegion,
+            EditorTool::CopyRegion => EditorTool::Stamp,
+            EditorTool::Stamp => EditorTool::PaintBiome,
+        }
+    }
+
+    // Tools applied continuously under the circular brush while the mouse is held
+    pub fn is_brush(self) -> bool {
+        !matches!(self, EditorTool::CopyRegion | EditorTool::Stamp)
+    }
+
+    fn label_key(self) -> &'static str {
+        match self {
+            EditorTool::PaintBiome => "editor.tool.paint_biome",
+            EditorTool::RaiseTerrain => "editor.tool.raise",
+            EditorTool::LowerTerrain => "editor.tool.lower",
+            EditorTool::PlaceWater => "editor.tool.water",
+            EditorTool::ScatterProps => "editor.tool.props",
+            EditorTool::SpawnCreatures => "editor.tool.spawn",
+            EditorTool::Reserve => "editor.tool.reserve",
+            EditorTool::CopyRegion => "editor.tool.copy",
+            EditorTool::Stamp => "editor.tool.stamp",
+        }
+    }
+}
+
+// Everything about a tile the editor can change
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TileState {
+    pub biome: BiomeType,
+    pub elevation: f32,
+    pub props: Option<Vec<EnvironmentType>>, // None = the props generated for the biome
+}
+
+// One stroke: (before, after) per touched tile
+#[derive(Default)]
+pub struct EditCommand {
+    pub tiles: HashMap<(usize, usize), (TileState, TileState)>,
+}
+
+#[derive(Resource)]
+pub struct Editor {
+    pub active: bool,
+    pub tool: EditorTool,
+    pub biome: BiomeType, // Painted by EditorTool::PaintBiome
+    pub radius: usize, // Brush radius in tiles
+    undo: Vec<EditCommand>,
+    redo: Vec<EditCommand>,
+    stroke: Option<EditCommand>, // Being painted; pushed onto the undo stack on release
+    edited: HashMap<(usize, usize), TileState>, // Current state of every tile ever edited
+}
+
+impl Default for Editor {
+    fn default() -> Self {
+        Self {
+            active: false,
+            tool: EditorTool::default(),
+            biome: BiomeType::Forest,
+            radius: 3,
+            undo: Vec::new(),
+            redo: Vec::new(),
+            stroke: None,
+            edited: HashMap::new(),
+        }
+    }
+}
+
+// === EDIT LAYER FILE ===
+// The edited tiles of one generated world; only loads onto the same seed and parameters.
+#[derive(Serialize, Deserialize)]
+pub struct EditLayer {
+    pub seed: u32,
+    pub params: GenerationParams,
+    pub tiles: Vec<((usize, usize), TileState)>,
+}
+
+impl EditLayer {
+    pub fn read() -> Result<Self, SimError> {
+        let contents = fs::read_to_string(EDITS_PATH).map_err(|err| SimError::io(EDITS_PATH, err))?;
+        ron::from_str(&contents).map_err(|err| SimError::parse(EDITS_PATH, err))
+    }
+}
+
+impl Editor {
+    // Current state of every tile edited since the world was generated
+    pub fn edited_tiles(&self) -> Vec<((usize, usize), TileState)> {
+        self.edited.iter().map(|(tile, state)| (*tile, state.clone())).collect()
+    }
+
+    fn push_command(&mut self, command: EditCommand) {
+        self.undo.push(command);
+        self.redo.clear();
+        if self.undo.len() > MAX_UNDO {
+            self.undo.remove(0);
+        }
+    }
+
+    // Applies a whole command at once (loaded edits, stamps) as one undo step
+    pub fn apply(&mut self, world_map: &mut WorldMap, chunk_manager: &mut ChunkManager, command: EditCommand) {
+        let afters = command.tiles.iter().map(|(tile, (_, after))| (tile, after));
+        apply_states(world_map, chunk_manager, &mut self.edited, afters);
+        self.push_command(command);
+    }
+}
+
+// Chunks being generated again from the world's seed, to be set back to their generated state in
+// one undoable step (console "world restore")
+#[derive(Resource, Default)]
+pub struct ChunkRestores(Vec<GenerationId>);
+
+impl ChunkRestores {
+    pub fn request(&mut self, service: &mut GenerationService, world_map: &WorldMap, chunk: (i32, i32)) {
+        let job = GenerationJob::Chunk { seed: world_map.seed, params: world_map.params, chunk };
+        self.0.push(service.request(job, GenerationPriority::Normal));
+        info!("✏️ Restoring chunk {:?} as generated", chunk);
+    }
+}
+
+pub fn editor_active(editor: Res<Editor>) -> bool {
+    editor.active
+}
+
+pub fn tile_state(world_map: &WorldMap, x: usize, y: usize) -> TileState {
+    let tile = &world_map.tiles[x][y];
+    TileState { biome: tile.biome, elevation: tile.elevation, props: world_map.props.get(&(x, y)).cloned() }
+}
+
+// A command setting the tiles to the given states, skipping any off the map
+pub fn edit_command(world_map: &WorldMap, tiles: Vec<((usize, usize), TileState)>) -> EditCommand {
+    let mut command = EditCommand::default();
+    for (tile, state) in tiles {
+        if tile.0 < WORLD_SIZE && tile.1 < WORLD_SIZE {
+            command.tiles.insert(tile, (tile_state(world_map, tile.0, tile.1), state));
+        }
+    }
+    command
+}
+
+// Writes tile states into the map and queues their chunks for re-rendering
+fn apply_states<'a>(
+    world_map: &mut WorldMap,
+    chunk_manager: &mut ChunkManager,
+    edited: &mut HashMap<(usize, usize), TileState>,
+    states: impl Iterator<Item = (&'a (usize, usize), &'a TileState)>,
+) {
+    for (&(x, y), state) in states {
+        let tile = &mut world_map.tiles[x][y];
+        tile.biome = state.biome;
+        tile.elevation = state.elevation;
+        match &state.props {
+            Some(props) => world_map.props.insert((x, y), props.clone()),
+            None => world_map.props.remove(&(x, y)),
+        };
+        edited.insert((x, y), state.clone());
+        chunk_manager.dirty_chunks.insert(tile_to_chunk(x, y));
+    }
+}
+
+// A chunk generated for an older world is dropped
+fn apply_restored_chunks(
+    mut restores: ResMut<ChunkRestores>,
+    mut service: ResMut<GenerationService>,
+    mut editor: ResMut<Editor>,
+    mut world_map: ResMut<WorldMap>,
+    mut chunk_manager: ResMut<ChunkManager>,
+) {
+    let finished: Vec<_> = restores.0.iter()
+        .filter_map(|id| service.take(*id).map(|result| (*id, result)))
+        .collect();
+    for (id, result) in finished {
+        restores.0.retain(|restoring| *restoring != id);
+        let Ok(GenerationOutput::Chunk(tiles)) = result else { continue };
+        let world_map = world_map.bypass_change_detection();
+        let states = tiles.into_iter()
+            .map(|(x, y, tile)| ((x, y), TileState { biome: tile.biome, elevation: tile.elevation, props: None }))
+            .collect();
+        let command = edit_command(world_map, states);
+        editor.apply(world_map, &mut chunk_manager, command);
+    }
+}
+
+pub fn reset_editor(mut editor: ResMut<Editor>) {
+    let Editor { active, tool, biome, radius, .. } = *editor;
+    *editor = Editor { active, tool, biome, radius, ..default() };
+}
+
+fn toggle_editor(controls: Controls, mut editor: ResMut<Editor>) {
+    if controls.just_pressed(Action::ToggleEditor) {
+        editor.active = !editor.active;
+        editor.stroke = None;
+    }
+}
+
+fn editor_hotkeys(controls: Controls, mut editor: ResMut<Editor>) {
+    if controls.just_pressed(Action::NextTool) {
+        editor.tool = editor.tool.next();
+    }
+    // Other tools use the same key for their own choice: stamps (stamps.rs) and species (spawn_zones.rs)
+    if controls.just_pressed(Action::NextChoice) && editor.tool == EditorTool::PaintBiome {
+        let index = PAINT_BIOMES.iter().position(|biome| *biome == editor.biome).map_or(0, |index| index + 1);
+        editor.biome = PAINT_BIOMES[index % PAINT_BIOMES.len()];
+    }
+    if controls.just_pressed(Action::ShrinkBrush) {
+        editor.radius = editor.radius.saturating_sub(1);
+    }
+    if controls.just_pressed(Action::GrowBrush) {
+        editor.radius = (editor.radius + 1).min(MAX_BRUSH_RADIUS);
+    }
+}
+
+// Ctrl+Z undoes the last stroke, Ctrl+Y (or Ctrl+Shift+Z) redoes it
+fn undo_redo(
+    controls: Controls,
+    mut editor: ResMut<Editor>,
+    world_map: Option<ResMut<WorldMap>>,
+    mut chunk_manager: ResMut<ChunkManager>,
+) {
+    // Ctrl+Shift+Z also matches the undo chord; redo wins
+    let redo = controls.just_pressed(Action::Redo);
+    let undo = !redo && controls.just_pressed(Action::Undo);
+    let Some(mut world_map) = world_map.filter(|_| undo || redo) else { return };
+    let editor = &mut *editor;
+    let world_map = world_map.bypass_change_detection();
+
+    if undo {
+        let Some(command) = editor.undo.pop() else { return };
+        let befores = command.tiles.iter().map(|(tile, (before, _))| (tile, before));
+        apply_states(world_map, &mut chunk_manager, &mut editor.edited, befores);
+        editor.redo.push(command);
+    } else {
+        let Some(command) = editor.redo.pop() else { return };
+        let afters = command.tiles.iter().map(|(tile, (_, after))| (tile, after));
+        apply_states(world_map, &mut chunk_manager, &mut editor.edited, afters);
+        editor.undo.push(command);
+    }
+}
+
+// New state of a tile under the brush, `strength` falling from 1 at the centre to 0 at the rim
+fn brush_result(editor: &Editor, state: &TileState, x: usize, y: usize, strength: f32, dt: f32, rng: &mut impl Rng) -> TileState {
+    let mut result = state.clone();
+    match editor.tool {
+        EditorTool::PaintBiome => result.biome = editor.biome,
+        EditorTool::RaiseTerrain | EditorTool::LowerTerrain => {
+            let sign = if editor.tool == EditorTool::RaiseTerrain { 1.0 } else { -1.0 };
+            result.elevation = (state.elevation + sign * RAISE_RATE * strength * dt).clamp(0.0, 1.0);
+            // Crossing sea level turns land into ocean and back
+            if result.elevation < SEA_LEVEL {
+                result.biome = BiomeType::Ocean;
+            } else if state.biome == BiomeType::Ocean {
+                result.biome = BiomeType::Coastal;
+            }
+        }
+        EditorTool::PlaceWater => {
+            result.biome = BiomeType::Ocean;
+            result.elevation = state.elevation.min(SEA_LEVEL - WATER_DEPTH);
+        }
+        // Tiles already carrying edited props are left alone so holding the brush does not reshuffle them
+        EditorTool::ScatterProps if state.props.is_none() => {
+            let mut props = get_environment_elements(&state.biome, x, y);
+            let choices: &[EnvironmentType] = match state.biome {
+                BiomeType::Desert | BiomeType::Badlands => &[EnvironmentType::Cactus, EnvironmentType::Rock],
+                BiomeType::Tundra | BiomeType::Alpine | BiomeType::Mountain => &[EnvironmentType::Rock, EnvironmentType::DeadTree],
+                _ => &[EnvironmentType::Tree, EnvironmentType::Bush, EnvironmentType::Grass],
+            };
+            if rng.gen::<f32>() < SCATTER_DENSITY {
+                props.extend(choices.choose(rng).copied());
+            }
+            result.props = Some(props.into_vec());
+        }
+        EditorTool::ScatterProps | EditorTool::SpawnCreatures | EditorTool::Reserve | EditorTool::CopyRegion | EditorTool::Stamp => {}
+    }
+    result
+}
+
+pub fn cursor_tile(windows: &Query<&Window>, cameras: &MainCameraQuery) -> Option<(usize, usize)> {
+    let window = windows.get_single().ok()?;
+    let (camera, camera_transform) = cameras.get_single().ok()?;
+    let cursor = camera.viewport_to_world_2d(camera_transform, window.cursor_position()?)?;
+    world_to_tile(cursor)
+}
+
+fn paint_with_brush(
+    time: Res<Time>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    (windows, cameras): (Query<&Window>, MainCameraQuery),
+    interactions: Query<&Interaction>,
+    mut editor: ResMut<Editor>,
+    world_map: Option<ResMut<WorldMap>>,
+    (mut chunk_manager, reserves, mut streams): (ResMut<ChunkManager>, Res<Reserves>, ResMut<RngStreams>),
+) {
+    let Some(mut world_map) = world_map else { return };
+    if mouse.just_released(MouseButton::Left) {
+        if let Some(stroke) = editor.stroke.take().filter(|stroke| !stroke.tiles.is_empty()) {
+            editor.push_command(stroke);
+        }
+        return;
+    }
+    if !mouse.pressed(MouseButton::Left) || !editor.tool.is_brush() {
+        return;
+    }
+    // Presses that start on a UI element belong to that element
+    if mouse.just_pressed(MouseButton::Left) && interactions.iter().any(|interaction| *interaction != Interaction::None) {
+        return;
+    }
+    if mouse.just_pressed(MouseButton::Left) {
+        editor.stroke = Some(EditCommand::default());
+    }
+    let Some((cx, cy)) = cursor_tile(&windows, &cameras) else { return };
+    let editor = editor.bypass_change_detection();
+    let world_map = world_map.bypass_change_detection();
+
+    let radius = editor.radius as i32;
+    let rng = streams.get(RngStream::Environment);
+    let mut changed = Vec::new();
+    for dx in -radius..=radius {
+        for dy in -radius..=radius {
+            let (x, y) = (cx as i32 + dx, cy as i32 + dy);
+            let distance = ((dx * dx + dy * dy) as f32).sqrt();
+            if x < 0 || y < 0 || x >= WORLD_SIZE as i32 || y >= WORLD_SIZE as i32 || distance > radius as f32 + 0.5 {
+                continue;
+            }
+            let (x, y) = (x as usize, y as usize);
+            if reserves.contains(x, y) {
+                continue;
+            }
+            let strength = 1.0 - distance / (radius as f32 + 1.0);
+            let before = tile_state(world_map, x, y);
+            let after = brush_result(editor, &before, x, y, strength, time.delta_seconds(), rng);
+            if after != before {
+                changed.push(((x, y), before, after));
+            }
+        }
+    }
+
+    let Some(stroke) = editor.stroke.as_mut() else { return };
+    for (tile, before, after) in &changed {
+        stroke.tiles.entry(*tile).or_insert_with(|| (before.clone(), after.clone())).1 = after.clone();
+    }
+    apply_states(world_map, &mut chunk_manager, &mut editor.edited, changed.iter().map(|(tile, _, after)| (tile, after)));
+}
+
+fn draw_brush(
+    mut gizmos: Gizmos,
+    editor: Res<Editor>,
+    windows: Query<&Window>,
+    cameras: MainCameraQuery,
+) {
+    if !editor.tool.is_brush() {
+        return;
+    }
+    let Some((x, y)) = cursor_tile(&windows, &cameras) else { return };
+    let radius = (editor.radius as f32 + 0.5) * TILE_SIZE;
+    gizmos.circle_2d(tile_to_world(x, y), radius, Color::srgb(1.0, 1.0, 1.0));
+}
+
+// F6 writes every edited tile to EDITS_PATH; F7 loads them back onto the same world as one undoable stroke
+fn save_load_edits(
+    controls: Controls,
+    mut editor: ResMut<Editor>,
+    world_map: Option<ResMut<WorldMap>>,
+    mut chunk_manager: ResMut<ChunkManager>,
+    (mut io, mut failures): (ResMut<IoQueue>, EventWriter<SimFailure>),
+) {
+    let Some(mut world_map) = world_map else { return };
+    if controls.just_pressed(Action::SaveEdits) {
+        let layer = EditLayer {
+            seed: world_map.seed,
+            params: world_map.params,
+            tiles: editor.edited_tiles(),
+        };
+        let message = format!("✏️ Saved {} edited tiles to {}", layer.tiles.len(), EDITS_PATH);
+        io.write_ron(EDITS_PATH, layer, message, None);
+    }
+
+    if controls.just_pressed(Action::LoadEdits) {
+        if !Path::new(EDITS_PATH).exists() {
+            return warn!("No edits to load from {}", EDITS_PATH);
+        }
+        let layer = match EditLayer::read() {
+            Ok(layer) if layer.seed != world_map.seed || layer.params != world_map.params => {
+                failures.send(SimFailure::new(SimError::Incompatible {
+                    path: EDITS_PATH.to_string(),
+                    reason: format!("the edits are for seed {} with other generation parameters", layer.seed),
+                }));
+                return;
+            }
+            Ok(layer) => layer,
+            Err(err) => {
+                failures.send(SimFailure::new(err));
+                return;
+            }
+        };
+
+        let world_map = world_map.bypass_change_detection();
+        let command = edit_command(world_map, layer.tiles);
+        info!("✏️ Loaded {} edited tiles from {}", command.tiles.len(), EDITS_PATH);
+        editor.apply(world_map, &mut chunk_manager, command);
+    }
+}
+
+#[derive(Component)]
+pub struct EditorLabel;
+
+fn spawn_editor_label(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 14.0,
+                color: Color::srgb(0.9, 0.9, 0.9),
+                ..default()
+            },
+        ).with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(44.0),
+            left: Val::Percent(35.0),
+            padding: UiRect::all(Val::Px(6.0)),
+            ..default()
+        }).with_background_color(Color::srgba(0.05, 0.05, 0.1, 0.7)),
+        Visibility::Hidden,
+        EditorLabel,
+    ));
+}
+
+fn update_editor_label(
+    editor: Res<Editor>,
+    (stamps, spawn_brush, reserve_brush, registry): (Res<StampLibrary>, Res<SpawnBrush>, Res<ReserveBrush>, Res<SpeciesRegistry>),
+    localization: Res<Localization>,
+    mut labels: Query<(&mut Text, &mut Visibility), With<EditorLabel>>,
+) {
+    let tool = match (editor.tool, stamps.selected()) {
+        (EditorTool::Stamp, Some(stamp)) => localization.format("editor.tool.stamp", &[("stamp", stamp.name.clone())]),
+        (EditorTool::SpawnCreatures, _) => localization.format("editor.tool.spawn", &[
+            ("species", registry.species.get(spawn_brush.species).map(|species| species.name.clone()).unwrap_or_default()),
+            ("mode", localization.get(match spawn_brush.mode {
+                SpawnMode::Immediate => "editor.spawn.immediate",
+                SpawnMode::Zone => "editor.spawn.zone",
+            }).to_string()),
+        ]),
+        (EditorTool::Reserve, _) => localization.format("editor.tool.reserve", &[
+            ("shape", localization.get(match reserve_brush.shape {
+                ReserveShape::Brush => "editor.reserve.brush",
+                ReserveShape::Rectangle => "editor.reserve.rectangle",
+            }).to_string()),
+        ]),
+        (tool, _) => localization.get(tool.label_key()).to_string(),
+    };
+    for (mut text, mut visibility) in labels.iter_mut() {
+        *visibility = if editor.active { Visibility::Visible } else { Visibility::Hidden };
+        text.sections[0].value = localization.format("editor.status", &[
+            ("tool", tool.clone()),
+            ("biome", localization.get(&editor.biome.name_key()).to_string()),
+            ("radius", editor.radius.to_string()),
+            ("undo", editor.undo.len().to_string()),
+        ]);
+    }
+}
@@ -1,7 +1,17 @@
 use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
 use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
 use crate::biome::BiomeType;
 use crate::world::WORLD_SIZE;
+use crate::settings::{motion_enabled, Settings};
+
+pub const MIN_DENSITY: f32 = 0.25;
+pub const MAX_DENSITY: f32 = 2.0;
+const AUTO_STEP: f32 = 0.25;
+const AUTO_INTERVAL: f32 = 3.0; // Seconds of frames averaged for each auto density step
+const HEADROOM: f32 = 0.7; // Auto density rises again below this share of the target frame time
 
 #[derive(Component)]
 pub struct EnvironmentSprite {
@@ -18,7 +28,7 @@ pub struct SwayAnimation {
     pub original_rotation: f32,
 }
 
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq, Serialize, Deserialize)]
 pub enum EnvironmentType {
     Tree,
     Grass,
@@ -31,6 +41,17 @@ pub enum EnvironmentType {
 }
 
 impl EnvironmentType {
+    pub const ALL: [EnvironmentType; 8] = [
+        EnvironmentType::Tree,
+        EnvironmentType::Grass,
+        EnvironmentType::Rock,
+        EnvironmentType::Cactus,
+        EnvironmentType::Bush,
+        EnvironmentType::Flower,
+        EnvironmentType::Mushroom,
+        EnvironmentType::DeadTree,
+    ];
+
     pub fn get_color(&self) -> Color {
         match self {
             EnvironmentType::Tree => Color::srgb(0.1, 0.5, 0.1),
@@ -83,120 +104,192 @@ pub struct EnvironmentPlugin;
 
 impl Plugin for EnvironmentPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, sway_animation_system);
+        let density = VegetationDensity::from_settings(app.world().resource::<Settings>());
+        app
+            .insert_resource(density)
+            .add_systems(Update, (
+                sway_animation_system.run_if(motion_enabled),
+                auto_vegetation_density.run_if(|density: Res<VegetationDensity>| density.auto),
+            ));
     }
 }
 
-pub fn get_environment_elements(biome: &BiomeType, tile_x: usize, tile_y: usize) -> Vec<EnvironmentType> {
-    let mut elements = Vec::new();
+// === VEGETATION DENSITY ===
+// A graphics setting: multiplier on the spawn chances of environment props, applied when a chunk
+// is built (loaded chunks are rebuilt when it changes). In auto mode the density steps down by
+// AUTO_STEP whenever frames over the last AUTO_INTERVAL seconds took longer than
+// Settings::target_frame_ms on average, and back up towards the configured density once there
+// is headroom again. Set with the "density" console command.
+#[derive(Resource)]
+pub struct VegetationDensity {
+    pub configured: f32,
+    pub current: f32,
+    pub auto: bool,
+}
+
+impl VegetationDensity {
+    fn from_settings(settings: &Settings) -> Self {
+        let configured = settings.vegetation_density.clamp(MIN_DENSITY, MAX_DENSITY);
+        Self { configured, current: configured, auto: settings.auto_vegetation_density }
+    }
+
+    pub fn set(&mut self, density: f32) {
+        self.configured = density.clamp(MIN_DENSITY, MAX_DENSITY);
+        self.current = self.configured;
+        self.auto = false;
+    }
+}
+
+// Averages real frame times while the window has focus (unfocused frames are capped on purpose)
+fn auto_vegetation_density(
+    time: Res<Time<Real>>,
+    settings: Res<Settings>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut density: ResMut<VegetationDensity>,
+    mut frames: Local<(f32, u32)>,
+) {
+    if !windows.get_single().is_ok_and(|window| window.focused) {
+        *frames = (0.0, 0);
+        return;
+    }
+    frames.0 += time.delta_seconds();
+    frames.1 += 1;
+    if frames.0 < AUTO_INTERVAL {
+        return;
+    }
+    let frame_ms = frames.0 / frames.1 as f32 * 1000.0;
+    *frames = (0.0, 0);
+
+    let target = if frame_ms > settings.target_frame_ms {
+        (density.current - AUTO_STEP).max(MIN_DENSITY)
+    } else if frame_ms < settings.target_frame_ms * HEADROOM {
+        (density.current + AUTO_STEP).min(density.configured)
+    } else {
+        density.current
+    };
+    if target != density.current {
+        info!("🌿 Vegetation density {:.2} -> {:.2} ({:.1} ms frames, target {:.1} ms)",
+            density.current, target, frame_ms, settings.target_frame_ms);
+        density.current = target;
+    }
+}
+
+// A tile holds at most a few props (three generated, plus one scattered in the editor), so they
+// stay inline and building a chunk allocates nothing per tile
+pub type TileProps = SmallVec<[EnvironmentType; 4]>;
+
+pub fn get_environment_elements(biome: &BiomeType, tile_x: usize, tile_y: usize) -> TileProps {
+    environment_elements_at_density(biome, tile_x, tile_y, 1.0)
+}
+
+// Props of a tile with every spawn chance scaled by `density`. The same rolls are made at every
+// density, so a denser tile keeps all the props of a sparser one.
+pub fn environment_elements_at_density(biome: &BiomeType, tile_x: usize, tile_y: usize, density: f32) -> TileProps {
+    let mut elements = TileProps::new();
 
     // Use tile coordinates as seed for consistent generation
     let seed = ((tile_x * WORLD_SIZE + tile_y) * 12345) as u64;
     let mut seeded_rng = rand::rngs::StdRng::seed_from_u64(seed);
+    let mut roll = |chance: f32| seeded_rng.gen::<f32>() < chance * density;
 
+    // Flowers are simulated (and drawn) by vegetation.rs rather than placed here
     match biome {
         BiomeType::Forest => {
-            if seeded_rng.gen::<f32>() < 0.3 {
+            if roll(0.3) {
                 elements.push(EnvironmentType::Tree);
             }
-            if seeded_rng.gen::<f32>() < 0.4 {
+            if roll(0.4) {
                 elements.push(EnvironmentType::Bush);
             }
-            if seeded_rng.gen::<f32>() < 0.2 {
+            if roll(0.2) {
                 elements.push(EnvironmentType::Mushroom);
             }
         },
         BiomeType::TropicalRainforest => {
-            if seeded_rng.gen::<f32>() < 0.5 {
+            if roll(0.5) {
                 elements.push(EnvironmentType::Tree);
             }
-            if seeded_rng.gen::<f32>() < 0.6 {
+            if roll(0.6) {
                 elements.push(EnvironmentType::Bush);
             }
-            if seeded_rng.gen::<f32>() < 0.1 {
-                elements.push(EnvironmentType::Flower);
-            }
         },
         BiomeType::Grasslands => {
-            if seeded_rng.gen::<f32>() < 0.7 {
+            if roll(0.7) {
                 elements.push(EnvironmentType::Grass);
             }
-            if seeded_rng.gen::<f32>() < 0.1 {
-                elements.push(EnvironmentType::Flower);
-            }
-            if seeded_rng.gen::<f32>() < 0.05 {
+            if roll(0.05) {
                 elements.push(EnvironmentType::Rock);
             }
         },
         BiomeType::Savanna => {
-            if seeded_rng.gen::<f32>() < 0.5 {
+            if roll(0.5) {
                 elements.push(EnvironmentType::Grass);
             }
-            if seeded_rng.gen::<f32>() < 0.1 {
+            if roll(0.1) {
                 elements.push(EnvironmentType::Tree);
             }
-            if seeded_rng.gen::<f32>() < 0.1 {
+            if roll(0.1) {
                 elements.push(EnvironmentType::Rock);
             }
         },
         BiomeType::Desert => {
-            if seeded_rng.gen::<f32>() < 0.1 {
+            if roll(0.1) {
                 elements.push(EnvironmentType::Cactus);
             }
-            if seeded_rng.gen::<f32>() < 0.15 {
+            if roll(0.15) {
                 elements.push(EnvironmentType::Rock);
             }
-            if seeded_rng.gen::<f32>() < 0.05 {
+            if roll(0.05) {
                 elements.push(EnvironmentType::DeadTree);
             }
         },
         BiomeType::Mountain => {
-            if seeded_rng.gen::<f32>() < 0.3 {
+            if roll(0.3) {
                 elements.push(EnvironmentType::Rock);
             }
-            if seeded_rng.gen::<f32>() < 0.1 {
+            if roll(0.1) {
                 elements.push(EnvironmentType::DeadTree);
             }
         },
         BiomeType::Alpine => {
-            if seeded_rng.gen::<f32>() < 0.4 {
+            if roll(0.4) {
                 elements.push(EnvironmentType::Rock);
             }
         },
         BiomeType::Wetlands => {
-            if seeded_rng.gen::<f32>() < 0.6 {
+            if roll(0.6) {
                 elements.push(EnvironmentType::Grass);
             }
-            if seeded_rng.gen::<f32>() < 0.2 {
+            if roll(0.2) {
                 elements.push(EnvironmentType::Bush);
             }
-            if seeded_rng.gen::<f32>() < 0.1 {
+            if roll(0.1) {
                 elements.push(EnvironmentType::Mushroom);
             }
         },
         BiomeType::Tundra => {
-            if seeded_rng.gen::<f32>() < 0.1 {
+            if roll(0.1) {
                 elements.push(EnvironmentType::Rock);
             }
         },
         BiomeType::Badlands => {
-            if seeded_rng.gen::<f32>() < 0.2 {
+            if roll(0.2) {
                 elements.push(EnvironmentType::Rock);
             }
-            if seeded_rng.gen::<f32>() < 0.05 {
+            if roll(0.05) {
                 elements.push(EnvironmentType::DeadTree);
             }
         },
         BiomeType::Volcanic => {
-            if seeded_rng.gen::<f32>() < 0.25 {
+            if roll(0.25) {
                 elements.push(EnvironmentType::Rock);
             }
         },
         BiomeType::Caves => {
-            if seeded_rng.gen::<f32>() < 0.3 {
+            if roll(0.3) {
                 elements.push(EnvironmentType::Mushroom);
             }
-            if seeded_rng.gen::<f32>() < 0.4 {
+            if roll(0.4) {
                 elements.push(EnvironmentType::Rock);
             }
         },
@@ -0,0 +1,312 @@
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use std::any::Any;
+use std::fmt;
+use crate::loading::LoadingState;
+use crate::localization::Localization;
+use crate::optimized_systems::WorldGeneration;
+use crate::relationships::RelationshipTable;
+use crate::save::PersistenceRequest;
+use crate::settings::Settings;
+use crate::species::SpeciesRegistry;
+use crate::world::WorldMap;
+
+pub struct ErrorsPlugin;
+
+impl Plugin for ErrorsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_state::<AppState>()
+            .init_resource::<CurrentError>()
+            .add_event::<SimFailure>()
+            .add_systems(Startup, check_built_in_assets)
+            .add_systems(Update, (
+                raise_errors,
+                handle_error_buttons.run_if(in_state(AppState::Error)),
+            ).chain())
+            .add_systems(OnEnter(AppState::Error), spawn_error_screen)
+            .add_systems(OnExit(AppState::Error), close_error_screen);
+    }
+}
+
+// === ERRORS ===
+// Failures of world generation, save files and built-in assets. Systems that hit one send a
+// SimFailure; the first one switches to AppState::Error, which pauses the simulation and shows
+// what went wrong with buttons to retry, carry on with the current world, or quit.
+#[derive(Debug, Clone)]
+pub enum SimError {
+    Generation(String), // Invalid parameters or a panicking generator thread
+    Cancelled, // Generation stopped from the loading screen; not shown, it asks for a seed instead
+    Io { path: String, message: String },
+    Parse { path: String, message: String },
+    Incompatible { path: String, reason: String }, // A save or edit layer made for another world
+}
+
+impl SimError {
+    pub fn io(path: impl fmt::Display, err: impl fmt::Display) -> Self {
+        Self::Io { path: path.to_string(), message: err.to_string() }
+    }
+
+    pub fn parse(path: impl fmt::Display, err: impl fmt::Display) -> Self {
+        Self::Parse { path: path.to_string(), message: err.to_string() }
+    }
+
+    // Heading of the error screen
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            Self::Generation(_) => "error.generation",
+            Self::Cancelled => "error.cancelled",
+            Self::Io { .. } => "error.io",
+            Self::Parse { .. } => "error.parse",
+            Self::Incompatible { .. } => "error.incompatible",
+        }
+    }
+}
+
+impl fmt::Display for SimError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Generation(message) => write!(f, "World generation failed: {}", message),
+            Self::Cancelled => write!(f, "World generation was cancelled"),
+            Self::Io { path, message } => write!(f, "Could not access {}: {}", path, message),
+            Self::Parse { path, message } => write!(f, "Could not parse {}: {}", path, message),
+            Self::Incompatible { path, reason } => write!(f, "{} does not fit this world: {}", path, reason),
+        }
+    }
+}
+
+impl std::error::Error for SimError {}
+
+// Text of a caught panic payload
+pub fn panic_message(panic: &(dyn Any + Send)) -> String {
+    panic.downcast_ref::<&str>().map(|message| message.to_string())
+        .or_else(|| panic.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic".to_string())
+}
+
+#[derive(States, Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AppState {
+    #[default]
+    Running,
+    Error,
+}
+
+// What the error screen's retry button does again
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryAction {
+    GenerateWorld { seed: u32 },
+    Save,
+    Load,
+}
+
+#[derive(Event, Debug, Clone)]
+pub struct SimFailure {
+    pub error: SimError,
+    pub retry: Option<RetryAction>,
+}
+
+impl SimFailure {
+    pub fn new(error: SimError) -> Self {
+        Self { error, retry: None }
+    }
+
+    pub fn with_retry(error: SimError, retry: RetryAction) -> Self {
+        Self { error, retry: Some(retry) }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct CurrentError {
+    pub failure: Option<SimFailure>,
+    paused_simulation: bool, // Only undo a pause we caused ourselves
+}
+
+#[derive(Component)]
+pub struct ErrorScreen;
+
+#[derive(Component, Clone, Copy)]
+pub enum ErrorButton {
+    Retry,
+    Continue, // Back to the current world, if there is one
+    Quit,
+}
+
+// Species and their relationships are compiled in; an empty registry would otherwise only show
+// up as a world without creatures, and a broken table as creatures ignoring each other
+fn check_built_in_assets(mut failures: EventWriter<SimFailure>) {
+    match SpeciesRegistry::load() {
+        Ok(registry) => {
+            if let Err(err) = RelationshipTable::load(&registry) {
+                failures.send(SimFailure::new(err));
+            }
+        }
+        Err(err) => {
+            failures.send(SimFailure::new(err));
+        }
+    }
+}
+
+// Failures raised while the screen is already up are only logged
+fn raise_errors(
+    mut failures: EventReader<SimFailure>,
+    mut current: ResMut<CurrentError>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    for failure in failures.read() {
+        error!("{}", failure.error);
+        if current.failure.is_none() {
+            current.failure = Some(failure.clone());
+            next_state.set(AppState::Error);
+        }
+    }
+}
+
+fn spawn_error_screen(
+    mut commands: Commands,
+    localization: Res<Localization>,
+    world_map: Option<Res<WorldMap>>,
+    mut current: ResMut<CurrentError>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    let Some(failure) = current.failure.clone() else { return };
+    if !virtual_time.is_paused() {
+        virtual_time.pause();
+        current.paused_simulation = true;
+    }
+
+    let mut buttons = Vec::new();
+    if failure.retry.is_some() {
+        buttons.push((ErrorButton::Retry, "error.retry"));
+    }
+    if world_map.is_some() {
+        buttons.push((ErrorButton::Continue, "error.continue"));
+    }
+    buttons.push((ErrorButton::Quit, "error.quit"));
+
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            background_color: Color::srgba(0.1, 0.05, 0.08, 0.92).into(),
+            // Above the loading screen, which is still up when the first world fails
+            z_index: ZIndex::Global(100),
+            ..default()
+        },
+        ErrorScreen,
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section(
+            localization.get(failure.error.label_key()),
+            TextStyle {
+                font_size: 36.0,
+                color: Color::srgb(0.95, 0.6, 0.55),
+                ..default()
+            },
+        ).with_style(Style {
+            margin: UiRect::all(Val::Px(16.0)),
+            ..default()
+        }));
+
+        parent.spawn(TextBundle::from_section(
+            failure.error.to_string(),
+            TextStyle {
+                font_size: 18.0,
+                color: Color::srgb(0.85, 0.85, 0.85),
+                ..default()
+            },
+        ).with_style(Style {
+            max_width: Val::Px(700.0),
+            margin: UiRect::all(Val::Px(10.0)),
+            ..default()
+        }));
+
+        parent.spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                margin: UiRect::all(Val::Px(16.0)),
+                ..default()
+            },
+            ..default()
+        }).with_children(|row| {
+            for (button, key) in buttons {
+                row.spawn((
+                    ButtonBundle {
+                        style: Style {
+                            margin: UiRect::all(Val::Px(8.0)),
+                            padding: UiRect::axes(Val::Px(16.0), Val::Px(8.0)),
+                            ..default()
+                        },
+                        background_color: Color::srgb(0.3, 0.2, 0.25).into(),
+                        ..default()
+                    },
+                    button,
+                )).with_children(|label| {
+                    label.spawn(TextBundle::from_section(
+                        localization.get(key),
+                        TextStyle {
+                            font_size: 18.0,
+                            color: Color::srgb(0.9, 0.9, 0.9),
+                            ..default()
+                        },
+                    ));
+                });
+            }
+        });
+    });
+}
+
+fn handle_error_buttons(
+    mut generation: WorldGeneration,
+    buttons: Query<(&Interaction, &ErrorButton), Changed<Interaction>>,
+    current: Res<CurrentError>,
+    settings: Res<Settings>,
+    mut loading_state: ResMut<LoadingState>,
+    mut next_state: ResMut<NextState<AppState>>,
+    (mut persistence, mut exit): (EventWriter<PersistenceRequest>, EventWriter<AppExit>),
+) {
+    let Some((_, button)) = buttons.iter().find(|(interaction, _)| **interaction == Interaction::Pressed) else { return };
+    match button {
+        ErrorButton::Retry => {
+            match current.failure.as_ref().and_then(|failure| failure.retry) {
+                Some(RetryAction::GenerateWorld { seed }) => {
+                    info!("🌍 Retrying world generation with seed {}", seed);
+                    loading_state.restart();
+                    generation.start(&settings, seed);
+                }
+                Some(RetryAction::Save) => {
+                    persistence.send(PersistenceRequest::Save);
+                }
+                Some(RetryAction::Load) => {
+                    persistence.send(PersistenceRequest::Load);
+                }
+                None => {}
+            }
+            next_state.set(AppState::Running);
+        }
+        ErrorButton::Continue => next_state.set(AppState::Running),
+        ErrorButton::Quit => {
+            exit.send(AppExit::Success);
+        }
+    }
+}
+
+fn close_error_screen(
+    mut commands: Commands,
+    screens: Query<Entity, With<ErrorScreen>>,
+    mut current: ResMut<CurrentError>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    for entity in screens.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    if current.paused_simulation {
+        virtual_time.unpause();
+    }
+    *current = CurrentError::default();
+}
@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+
+pub struct EventsPlugin;
+
+impl Plugin for EventsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<NotableEvent>();
+    }
+}
+
+// === NOTABLE EVENT STREAM ===
+// Simulation happenings worth showing to the user, emitted by the simulation systems and
+// consumed by presentation features such as the auto camera.
+#[derive(Event, Clone)]
+pub struct NotableEvent {
+    pub position: Vec2,
+    pub importance: f32, // Relative weight; 1.0 for a single ordinary event
+    pub label_key: &'static str, // Localization key describing the event
+}
@@ -0,0 +1,482 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use crate::camera::MainCamera;
+use crate::coords::{tile_to_chunk, tile_to_world, world_to_tile, TILE_SIZE};
+use crate::keybindings::{Action, Controls};
+use crate::biome::BiomeType;
+use crate::creature::{spawn_creature, Creature, DeathCause, Killed};
+use crate::optimization::{SpatialSearch, SpatialView};
+use crate::genetics::{founder_lineage, Genome};
+use crate::names::{creature_name, region_name, species_name};
+use crate::path_debug::PathDebug;
+use crate::schedule_dump::ScheduleDump;
+use crate::reserves::Reserves;
+use crate::scenario::{ScenarioRequest, Scenarios};
+use crate::calendar::{ScheduledEventFired, Scheduler};
+use crate::climate::ClimateTrend;
+use crate::genealogy::Genealogy;
+use crate::generation_panel::GenerationPanel;
+use crate::generation_service::GenerationService;
+use crate::editor::ChunkRestores;
+use crate::environment::{VegetationDensity, MAX_DENSITY, MIN_DENSITY};
+use crate::simulation::{SimClock, DAYS_PER_YEAR};
+use crate::species::SpeciesRegistry;
+use crate::stress_test::{StressTest, DEFAULT_STRESS_SECONDS};
+use crate::telemetry::Telemetry;
+use crate::thumbnails::{request_thumbnails, Thumbnails};
+use crate::text_input::{text_input_inactive, PromptKind, PromptSubmitted, TextPrompt};
+use crate::tides::Tides;
+use crate::vegetation::Vegetation;
+use crate::world::{WorldMap, WORLD_SIZE};
+use crate::world_registry::{activate_world, compare_worlds, WorldId, WorldRegistry};
+use crate::world_stats::WorldReport;
+use crate::rng_streams::{RngStream, RngStreams};
+
+const REGION_RADIUS: f32 = 40.0 * TILE_SIZE; // Experiment region around the camera center
+const MAX_INTRODUCED: usize = 500;
+const MAX_SAMPLE_NAMES: usize = 20;
+
+pub struct ExperimentsPlugin;
+
+impl Plugin for ExperimentsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Experiments>()
+            .add_systems(Update, (
+                open_console.run_if(text_input_inactive),
+                run_console_command,
+                record_experiments.run_if(resource_exists::<WorldMap>),
+            ).chain());
+    }
+}
+
+// === ECOSYSTEM METRICS ===
+#[derive(Serialize, Clone, Default)]
+pub struct SpeciesCount {
+    pub world: usize,
+    pub region: usize,
+    pub reserves: usize, // Inside nature reserves, anywhere in the world
+}
+
+#[derive(Serialize, Clone)]
+pub struct EcosystemMetrics {
+    pub day: u32,
+    pub populations: BTreeMap<String, SpeciesCount>,
+    pub region_plant_cover: f32, // Mean cover over the region's tiles
+}
+
+// Everything needed to take a metrics snapshot
+#[derive(SystemParam)]
+pub struct EcosystemProbe<'w, 's> {
+    clock: Res<'w, SimClock>,
+    registry: Res<'w, SpeciesRegistry>,
+    vegetation: Option<Res<'w, Vegetation>>,
+    reserves: Res<'w, Reserves>,
+    spatial: Res<'w, SpatialView>,
+    creatures: Query<'w, 's, (Entity, &'static Transform, &'static Creature)>,
+}
+
+impl EcosystemProbe<'_, '_> {
+    pub fn measure(&self, center: Vec2) -> EcosystemMetrics {
+        let mut populations: BTreeMap<String, SpeciesCount> = self.registry.species.iter()
+            .map(|species| (species.name.clone(), SpeciesCount::default()))
+            .collect();
+        for (_, transform, creature) in self.creatures.iter() {
+            let count = populations.entry(self.registry.get(creature.species).name.clone()).or_default();
+            count.world += 1;
+            if self.reserves.contains_point(transform.translation.truncate()) {
+                count.reserves += 1;
+            }
+            if transform.translation.truncate().distance(center) <= REGION_RADIUS {
+                count.region += 1;
+            }
+        }
+
+        let region_plant_cover = self.vegetation.as_ref().map_or(0.0, |vegetation| {
+            let tiles = region_tiles(center).collect::<Vec<_>>();
+            tiles.iter().map(|(x, y)| vegetation.cover(*x, *y)).sum::<f32>() / tiles.len().max(1) as f32
+        });
+
+        EcosystemMetrics { day: self.clock.day(), populations, region_plant_cover }
+    }
+}
+
+fn region_tiles(center: Vec2) -> impl Iterator<Item = (usize, usize)> {
+    let radius = (REGION_RADIUS / TILE_SIZE) as usize;
+    let (cx, cy) = world_to_tile(center).unwrap_or((WORLD_SIZE / 2, WORLD_SIZE / 2));
+    (cx.saturating_sub(radius)..(cx + radius).min(WORLD_SIZE))
+        .flat_map(move |x| (cy.saturating_sub(radius)..(cy + radius).min(WORLD_SIZE)).map(move |y| (x, y)))
+        .filter(move |(x, y)| tile_to_world(*x, *y).distance(center) <= REGION_RADIUS)
+}
+
+// === EXPERIMENTS ===
+// A species removal or introduction in a region, followed by a yearly metrics snapshot written
+// to the telemetry log until the experiment has run for the requested number of years.
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Experiment {
+    pub id: usize,
+    pub action: String,
+    pub region: String, // Generated place name for the experiment region
+    pub center: (f32, f32),
+    pub years: u32,
+    pub start_day: u32,
+    pub reports: u32, // Yearly snapshots recorded so far
+}
+
+impl Experiment {
+    // Scheduler event for the yearly snapshots
+    fn schedule_name(&self) -> String {
+        format!("experiment:{}", self.id)
+    }
+}
+
+// Saved together with the scheduler queue that drives the yearly snapshots
+#[derive(Resource, Default, Clone, Serialize, Deserialize)]
+pub struct Experiments {
+    pub active: Vec<Experiment>,
+    pub started: usize,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ConsoleCommand {
+    Remove { species: usize, years: u32 },
+    Introduce { species: usize, count: usize, years: u32 },
+    Schedule { in_days: u32, name: String },
+    Agenda,
+    WorldNew { seed: u32 },
+    WorldList,
+    WorldCompare { id: u32 },
+    WorldUse { id: u32 }, // Makes a scratch world the active one
+    WorldPreview { seeds: Vec<u32> },
+    WorldRestore, // The chunk under the camera, as generated
+    Names { biome: BiomeType, count: usize },
+    Stress { seconds: f32 },
+    Paths { goal: Option<(usize, usize)> },
+    Climate { trend: Option<(f32, f32)> }, // Warming and drying per year
+    Density { value: Option<f32>, auto: bool },
+    Scenario(ScenarioRequest),
+    Genealogy,
+    Systems,
+}
+
+// "remove <years> <species>", "introduce <years> <count> <species>", "schedule <days> <name>", "agenda",
+// "world new <seed>" / "world list" / "world compare <id>" / "world use <id>" / "world preview <seeds...>"
+// for scratch worlds, generated with the generation panel's settings,
+// "world restore" to undo every edit to the chunk under the camera,
+// "names <biome> [count]" to sample the name generator, "stress [seconds]" for a performance run,
+// "paths [<x> <y>]" for the pathfinding overlay (toggled, or shown with a goal tile),
+// "climate [<warming> [drying]]" to show or set the yearly climate trend,
+// "density [<0.25-2> | auto]" to show or set the vegetation density,
+// "export <name>" / "import <name or path>" for scenario bundles, "genealogy" to export the selected
+// creature's family tree, or "systems" to dump the system schedules
+pub fn parse_console_command(text: &str, registry: &SpeciesRegistry) -> Result<ConsoleCommand, String> {
+    let mut words = text.split_whitespace();
+    let verb = words.next().unwrap_or_default().to_ascii_lowercase();
+    match verb.as_str() {
+        "agenda" => return Ok(ConsoleCommand::Agenda),
+        "systems" => return Ok(ConsoleCommand::Systems),
+        "genealogy" => return Ok(ConsoleCommand::Genealogy),
+        "stress" => {
+            let seconds = words.next().and_then(|w| w.parse().ok()).filter(|s: &f32| *s > 0.0)
+                .unwrap_or(DEFAULT_STRESS_SECONDS);
+            return Ok(ConsoleCommand::Stress { seconds });
+        }
+        "paths" => {
+            let coords = words.map(|w| w.parse::<usize>().map_err(|_| format!("invalid tile coordinate '{}'", w)))
+                .collect::<Result<Vec<_>, _>>()?;
+            return match coords[..] {
+                [] => Ok(ConsoleCommand::Paths { goal: None }),
+                [x, y] if x < WORLD_SIZE && y < WORLD_SIZE => Ok(ConsoleCommand::Paths { goal: Some((x, y)) }),
+                _ => Err(format!("expected a goal tile as '<x> <y>' below {}", WORLD_SIZE)),
+            };
+        }
+        "climate" => {
+            let rates = words.map(|w| w.parse::<f32>().ok().filter(|rate| rate.is_finite()).ok_or_else(|| format!("invalid rate '{}'", w)))
+                .collect::<Result<Vec<_>, _>>()?;
+            return match rates[..] {
+                [] => Ok(ConsoleCommand::Climate { trend: None }),
+                [warming] => Ok(ConsoleCommand::Climate { trend: Some((warming, 0.0)) }),
+                [warming, drying] => Ok(ConsoleCommand::Climate { trend: Some((warming, drying)) }),
+                _ => Err("expected '[<warming> [drying]]' per year".to_string()),
+            };
+        }
+        "density" => {
+            return match words.next() {
+                None => Ok(ConsoleCommand::Density { value: None, auto: false }),
+                Some("auto") => Ok(ConsoleCommand::Density { value: None, auto: true }),
+                Some(word) => match word.parse::<f32>() {
+                    Ok(value) if (MIN_DENSITY..=MAX_DENSITY).contains(&value) => Ok(ConsoleCommand::Density { value: Some(value), auto: false }),
+                    _ => Err(format!("expected a density from {} to {} or 'auto'", MIN_DENSITY, MAX_DENSITY)),
+                },
+            };
+        }
+        "export" | "import" => {
+            let name = words.collect::<Vec<_>>().join(" ");
+            if name.is_empty() {
+                return Err("expected a scenario name".to_string());
+            }
+            let request = if verb == "export" { ScenarioRequest::Export(name) } else { ScenarioRequest::Import(name) };
+            return Ok(ConsoleCommand::Scenario(request));
+        }
+        "names" => {
+            let name = words.next().unwrap_or_default();
+            let biome = BiomeType::from_name(name).ok_or(format!("unknown biome '{}'", name))?;
+            let count = words.next().and_then(|w| w.parse().ok()).unwrap_or(5).min(MAX_SAMPLE_NAMES);
+            return Ok(ConsoleCommand::Names { biome, count });
+        }
+        "world" => {
+            let action = words.next().unwrap_or_default().to_ascii_lowercase();
+            if action == "preview" {
+                let seeds = words.map(|w| w.parse::<u32>().map_err(|_| format!("invalid seed '{}'", w)))
+                    .collect::<Result<_, _>>()?;
+                return Ok(ConsoleCommand::WorldPreview { seeds });
+            }
+            let number = words.next().and_then(|w| w.parse::<u32>().ok());
+            return match action.as_str() {
+                "new" => Ok(ConsoleCommand::WorldNew { seed: number.ok_or("expected a seed")? }),
+                "list" => Ok(ConsoleCommand::WorldList),
+                "restore" => Ok(ConsoleCommand::WorldRestore),
+                "compare" => Ok(ConsoleCommand::WorldCompare { id: number.ok_or("expected a world id")? }),
+                "use" => Ok(ConsoleCommand::WorldUse { id: number.ok_or("expected a world id")? }),
+                _ => Err(format!("unknown world command '{}'", action)),
+            };
+        }
+        "schedule" => {
+            let in_days = words.next().and_then(|w| w.parse().ok()).ok_or("expected a number of days")?;
+            let name = words.collect::<Vec<_>>().join(" ");
+            if name.is_empty() {
+                return Err("expected an event name".to_string());
+            }
+            return Ok(ConsoleCommand::Schedule { in_days, name });
+        }
+        _ => {}
+    }
+    let years: u32 = words.next().and_then(|w| w.parse().ok()).filter(|y| *y > 0)
+        .ok_or("expected a number of years")?;
+    let count = match verb.as_str() {
+        "remove" => None,
+        "introduce" => Some(words.next().and_then(|w| w.parse::<usize>().ok())
+            .filter(|c| (1..=MAX_INTRODUCED).contains(c))
+            .ok_or(format!("expected a count between 1 and {}", MAX_INTRODUCED))?),
+        _ => return Err(format!("unknown command '{}'", verb)),
+    };
+    let name = words.collect::<Vec<_>>().join(" ");
+    let species = registry.species.iter().position(|s| s.name.eq_ignore_ascii_case(&name))
+        .ok_or(format!("unknown species '{}'", name))?;
+    Ok(match count {
+        None => ConsoleCommand::Remove { species, years },
+        Some(count) => ConsoleCommand::Introduce { species, count, years },
+    })
+}
+
+fn open_console(controls: Controls, mut prompt: ResMut<TextPrompt>) {
+    if controls.just_pressed(Action::OpenConsole) {
+        prompt.open(PromptKind::Console, String::new());
+    }
+}
+
+// Settings the console can change on the fly, and the tuned generation settings new worlds use
+type ConsoleTunables<'w> = (ResMut<'w, ClimateTrend>, ResMut<'w, VegetationDensity>, Res<'w, GenerationPanel>);
+
+// Tools and reports the console drives
+type ConsoleTools<'w> = (ResMut<'w, WorldRegistry>, Option<Res<'w, WorldReport>>, ResMut<'w, StressTest>, ResMut<'w, PathDebug>, ResMut<'w, Scenarios>, ResMut<'w, Genealogy>, ResMut<'w, ChunkRestores>);
+
+// What the console reads the map through, records to, draws random numbers from and generates with
+type ConsoleOutputs<'w> = (Res<'w, Tides>, ResMut<'w, Telemetry>, ResMut<'w, Thumbnails>, ResMut<'w, ScheduleDump>, ResMut<'w, RngStreams>, ResMut<'w, GenerationService>);
+
+fn run_console_command(
+    mut commands: Commands,
+    mut submitted: EventReader<PromptSubmitted>,
+    probe: EcosystemProbe,
+    cameras: Query<&Transform, With<MainCamera>>,
+    (world_map, mut experiments, mut scheduler, (mut climate, mut density, panel)): (Option<Res<WorldMap>>, ResMut<Experiments>, ResMut<Scheduler>, ConsoleTunables),
+    (tides, mut telemetry, mut thumbnails, mut schedule_dump, mut streams, mut generation): ConsoleOutputs,
+    (mut worlds, active_report, mut stress, mut path_debug, mut scenarios, mut genealogy, mut restores): ConsoleTools,
+) {
+    for event in submitted.read() {
+        if event.kind != PromptKind::Console {
+            continue;
+        }
+        let Some(world_map) = &world_map else { continue };
+        let Ok(camera_transform) = cameras.get_single() else { continue };
+        let center = camera_transform.translation.truncate();
+
+        let command = match parse_console_command(&event.text, &probe.registry) {
+            Ok(command) => command,
+            Err(err) => {
+                warn!("Console: {} in \"{}\"", err, event.text);
+                continue;
+            }
+        };
+
+        // Snapshot before touching anything
+        let before = probe.measure(center);
+        let (action, years) = match command {
+            ConsoleCommand::Schedule { in_days, name } => {
+                let day = before.day + in_days;
+                info!("📅 Scheduled '{}' for day {}", name, day);
+                scheduler.schedule(name, day, None);
+                continue;
+            }
+            ConsoleCommand::Agenda => {
+                for scheduled in scheduler.upcoming() {
+                    info!("📅 Day {}: {}", scheduled.day, scheduled.name);
+                }
+                continue;
+            }
+            ConsoleCommand::WorldNew { seed } => {
+                worlds.generate(&mut generation, seed, panel.params);
+                continue;
+            }
+            // Only printed, so the simulation streams are left alone
+            ConsoleCommand::Names { biome, count } => {
+                let rng = streams.get(RngStream::Ui);
+                for _ in 0..count {
+                    let seed = rng.gen();
+                    info!("🏷️ {} / {} / {}", creature_name(seed, biome), species_name(seed, biome), region_name(seed, biome));
+                }
+                continue;
+            }
+            ConsoleCommand::Stress { seconds } => {
+                stress.requested = Some(seconds);
+                continue;
+            }
+            ConsoleCommand::Scenario(request) => {
+                scenarios.requested = Some(request);
+                continue;
+            }
+            ConsoleCommand::Genealogy => {
+                genealogy.export_requested = true;
+                continue;
+            }
+            ConsoleCommand::Systems => {
+                schedule_dump.requested = true;
+                continue;
+            }
+            ConsoleCommand::Paths { goal } => {
+                path_debug.command(goal);
+                continue;
+            }
+            ConsoleCommand::Climate { trend } => {
+                if let Some((warming, drying)) = trend {
+                    climate.warming_per_year = warming;
+                    climate.drying_per_year = drying;
+                }
+                info!("🌡️ Climate trend: {:+.3} temperature and {:+.3} moisture per year",
+                    climate.warming_per_year, -climate.drying_per_year);
+                continue;
+            }
+            ConsoleCommand::Density { value, auto } => {
+                if let Some(value) = value {
+                    density.set(value);
+                } else if auto {
+                    density.auto = true;
+                }
+                info!("🌿 Vegetation density {:.2}{}", density.current, if density.auto { " (auto)" } else { "" });
+                continue;
+            }
+            ConsoleCommand::WorldPreview { seeds } => {
+                request_thumbnails(&mut generation, &mut thumbnails, &seeds, panel.params);
+                continue;
+            }
+            ConsoleCommand::WorldRestore => {
+                let Some((x, y)) = world_to_tile(center) else { continue };
+                restores.request(&mut generation, world_map, tile_to_chunk(x, y));
+                continue;
+            }
+            ConsoleCommand::WorldList => {
+                info!("🌍 World {} (seed {}, active)", worlds.active.0, world_map.seed);
+                for world in &worlds.scratch {
+                    info!("🌍 World {} (seed {})", world.id.0, world.report.seed);
+                }
+                continue;
+            }
+            ConsoleCommand::WorldCompare { id } => {
+                let (Some(world), Some(report)) = (worlds.get(WorldId(id)), &active_report) else {
+                    warn!("Console: no scratch world {}", id);
+                    continue;
+                };
+                info!("🌍 Active world vs world {}:", id);
+                for line in compare_worlds(world_map, report, world) {
+                    info!("    {}", line);
+                }
+                continue;
+            }
+            ConsoleCommand::WorldUse { id } => {
+                let id = WorldId(id);
+                commands.add(move |world: &mut World| activate_world(world, id));
+                continue;
+            }
+            ConsoleCommand::Remove { species, years } => {
+                let mut removed = 0;
+                for (entity, _) in probe.spatial.within(center, REGION_RADIUS, SpatialSearch::Quadtree) {
+                    let Ok((_, transform, creature)) = probe.creatures.get(entity) else { continue };
+                    let position = transform.translation.truncate();
+                    if creature.species == species && position.distance(center) <= REGION_RADIUS && !probe.reserves.contains_point(position) {
+                        commands.entity(entity).insert(Killed(DeathCause::Event));
+                        removed += 1;
+                    }
+                }
+                (format!("removed {} {}", removed, probe.registry.get(species).name), years)
+            }
+            ConsoleCommand::Introduce { species, count, years } => {
+                let land: Vec<(usize, usize)> = region_tiles(center)
+                    .filter(|(x, y)| !tides.is_underwater(&world_map.tiles[*x][*y]) && !probe.reserves.contains(*x, *y))
+                    .collect();
+                if land.is_empty() {
+                    warn!("Console: no dry land outside reserves around the camera to introduce {} on", probe.registry.get(species).name);
+                    continue;
+                }
+                let rng = streams.get(RngStream::Creatures);
+                for _ in 0..count {
+                    let (x, y) = land[rng.gen_range(0..land.len())];
+                    let genome = Genome::founder(rng, founder_lineage(species, x, y));
+                    let heading = rng.gen_range(0.0..std::f32::consts::TAU);
+                    spawn_creature(&mut commands, &probe.registry, species, tile_to_world(x, y), heading, genome);
+                }
+                (format!("introduced {} {}", count, probe.registry.get(species).name), years)
+            }
+        };
+
+        experiments.started += 1;
+        let (x, y) = world_to_tile(center).unwrap_or((WORLD_SIZE / 2, WORLD_SIZE / 2));
+        let experiment = Experiment {
+            id: experiments.started,
+            action,
+            region: region_name(((x as u64) << 32 | y as u64) ^ world_map.seed as u64, world_map.tiles[x][y].biome),
+            center: (center.x, center.y),
+            years,
+            start_day: before.day,
+            reports: 0,
+        };
+        info!("🧪 Experiment {} in {}: {} for {} years", experiment.id, experiment.region, experiment.action, years);
+        scheduler.schedule(experiment.schedule_name(), experiment.start_day + DAYS_PER_YEAR, Some(DAYS_PER_YEAR));
+        telemetry.record("experiment_start", &serde_json::json!({ "experiment": experiment, "metrics": before }));
+        experiments.active.push(experiment);
+    }
+}
+
+fn record_experiments(
+    probe: EcosystemProbe,
+    mut fired: EventReader<ScheduledEventFired>,
+    (mut experiments, mut scheduler): (ResMut<Experiments>, ResMut<Scheduler>),
+    mut telemetry: ResMut<Telemetry>,
+) {
+    for event in fired.read() {
+        let Some(index) = experiments.active.iter().position(|experiment| experiment.schedule_name() == event.name) else {
+            continue;
+        };
+        let experiment = &mut experiments.active[index];
+        experiment.reports += 1;
+        let metrics = probe.measure(Vec2::new(experiment.center.0, experiment.center.1));
+        telemetry.record("experiment_year", &serde_json::json!({ "experiment": experiment, "metrics": metrics }));
+        if experiment.reports >= experiment.years {
+            info!("🧪 Experiment {} ({}) finished after {} years", experiment.id, experiment.action, experiment.years);
+            scheduler.cancel(&event.name);
+            experiments.active.remove(index);
+        }
+    }
+}
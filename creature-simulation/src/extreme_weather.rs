@@ -0,0 +1,242 @@
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use serde::Serialize;
+use crate::biome::BiomeType;
+use crate::calendar::{Calendar, Season};
+use crate::coords::tile_to_world;
+use crate::events::NotableEvent;
+use crate::hydrology::{Hydrology, FORECAST_DAYS, HYDROLOGY_CELL_TILES, HYDROLOGY_SIZE};
+use crate::simulation::SimClock;
+use crate::telemetry::Telemetry;
+use crate::world::{WorldMap, WORLD_SIZE};
+
+const EVENT_CHANCE: f64 = 1.0 / 20.0; // Per day; about one event every other year
+const MIN_DAYS: u32 = 2;
+const MAX_DAYS: u32 = 5;
+const MIN_RADIUS: usize = 40; // Tiles
+const MAX_RADIUS: usize = 120;
+const HEAT_WAVE_WARMING: f32 = 0.15; // Tile temperature at the centre, about 9°C
+const COLD_SNAP_COOLING: f32 = 0.2;
+const DROUGHT_RAIN: f32 = 0.1; // Share of rainfall still reaching the region
+const HEAT_WAVE_RAIN: f32 = 0.6;
+
+pub struct ExtremeWeatherPlugin;
+
+impl Plugin for ExtremeWeatherPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, init_extreme_weather.run_if(resource_added::<WorldMap>))
+            .add_systems(FixedUpdate, update_extreme_weather
+                .run_if(resource_exists::<ExtremeWeather>)
+                .run_if(resource_exists::<Hydrology>));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtremeKind {
+    HeatWave,
+    ColdSnap,
+    Drought,
+}
+
+impl ExtremeKind {
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            ExtremeKind::HeatWave => "event.heat_wave",
+            ExtremeKind::ColdSnap => "event.cold_snap",
+            ExtremeKind::Drought => "event.regional_drought",
+        }
+    }
+
+    // Change to tile temperature at the centre of the region
+    fn warming(&self) -> f32 {
+        match self {
+            ExtremeKind::HeatWave => HEAT_WAVE_WARMING,
+            ExtremeKind::ColdSnap => -COLD_SNAP_COOLING,
+            ExtremeKind::Drought => 0.0,
+        }
+    }
+
+    // Share of the day's rainfall that still falls
+    fn rain(&self) -> f32 {
+        match self {
+            ExtremeKind::HeatWave => HEAT_WAVE_RAIN,
+            ExtremeKind::ColdSnap => 1.0,
+            ExtremeKind::Drought => DROUGHT_RAIN,
+        }
+    }
+
+    // Share of the normal plant regrowth
+    fn regrowth(&self) -> f32 {
+        match self {
+            ExtremeKind::HeatWave => 0.5,
+            ExtremeKind::ColdSnap => 0.3, // Frost
+            ExtremeKind::Drought => 0.2,
+        }
+    }
+}
+
+// === EXTREME WEATHER ===
+// Rare regional spells of heat, cold or drought lasting a few days. They are rolled FORECAST_DAYS
+// ahead so the weather panel can warn about them. While one lasts, tile temperatures in its
+// region are shifted (strongest at the centre), rain over it is cut through hydrology, so
+// moisture and rivers follow, and plants regrow slower. Temperature changes are undone when it
+// ends; the climate means keep what the region went through.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExtremeEvent {
+    pub kind: ExtremeKind,
+    pub centre: (usize, usize), // Tile
+    pub radius: usize, // Tiles
+    pub start_day: u32,
+    pub end_day: u32, // First day after the event
+    #[serde(skip)]
+    applied: Vec<(usize, f32)>, // Temperature change per tile index [x * WORLD_SIZE + y]
+}
+
+impl ExtremeEvent {
+    pub fn is_active(&self, day: u32) -> bool {
+        (self.start_day..self.end_day).contains(&day)
+    }
+
+    // 1.0 at the centre, falling to 0.0 at the radius and beyond
+    pub fn strength(&self, x: usize, y: usize) -> f32 {
+        let dx = x as f32 - self.centre.0 as f32;
+        let dy = y as f32 - self.centre.1 as f32;
+        (1.0 - (dx * dx + dy * dy) / (self.radius * self.radius) as f32).max(0.0)
+    }
+}
+
+#[derive(Resource)]
+pub struct ExtremeWeather {
+    pub events: Vec<ExtremeEvent>, // Forecast and active, by start day
+    last_day: Option<u32>,
+    rng: StdRng,
+}
+
+impl ExtremeWeather {
+    fn for_world(world_map: &WorldMap) -> Self {
+        Self { events: Vec::new(), last_day: None, rng: StdRng::seed_from_u64(world_map.seed as u64 ^ 0x4558_5452) }
+    }
+
+    // Change to this tile's temperature on a day, from every event lasting that day
+    pub fn temperature_offset(&self, x: usize, y: usize, day: u32) -> f32 {
+        self.events.iter().filter(|event| event.is_active(day))
+            .map(|event| event.kind.warming() * event.strength(x, y))
+            .sum()
+    }
+
+    // Share of the rainfall reaching this tile on a day
+    pub fn rain_factor(&self, x: usize, y: usize, day: u32) -> f32 {
+        self.events.iter().filter(|event| event.is_active(day))
+            .map(|event| 1.0 - (1.0 - event.kind.rain()) * event.strength(x, y))
+            .fold(1.0, f32::min)
+    }
+
+    // Share of the normal plant regrowth on this tile today
+    pub fn regrowth_factor(&self, x: usize, y: usize) -> f32 {
+        let Some(day) = self.last_day else { return 1.0 };
+        self.events.iter().filter(|event| event.is_active(day))
+            .map(|event| 1.0 - (1.0 - event.kind.regrowth()) * event.strength(x, y))
+            .fold(1.0, f32::min)
+    }
+
+    // Events reaching this tile that have not ended yet
+    pub fn affecting(&self, x: usize, y: usize) -> impl Iterator<Item = &ExtremeEvent> {
+        self.events.iter().filter(move |event| event.strength(x, y) > 0.0)
+    }
+
+    fn roll_event(&mut self, world_map: &WorldMap, start_day: u32) -> Option<ExtremeEvent> {
+        let kind = match Calendar::from_day(start_day).season {
+            Season::Summer => if self.rng.gen_bool(0.5) { ExtremeKind::HeatWave } else { ExtremeKind::Drought },
+            Season::Winter => ExtremeKind::ColdSnap,
+            _ => [ExtremeKind::HeatWave, ExtremeKind::ColdSnap, ExtremeKind::Drought][self.rng.gen_range(0..3)],
+        };
+        // Centred on land; a few tries before giving up on a mostly ocean world
+        let centre = (0..20)
+            .map(|_| (self.rng.gen_range(0..WORLD_SIZE), self.rng.gen_range(0..WORLD_SIZE)))
+            .find(|&(x, y)| world_map.tiles[x][y].biome != BiomeType::Ocean)?;
+        let days = self.rng.gen_range(MIN_DAYS..=MAX_DAYS);
+        Some(ExtremeEvent {
+            kind,
+            centre,
+            radius: self.rng.gen_range(MIN_RADIUS..=MAX_RADIUS),
+            start_day,
+            end_day: start_day + days,
+            applied: Vec::new(),
+        })
+    }
+}
+
+fn init_extreme_weather(mut commands: Commands, world_map: Res<WorldMap>) {
+    commands.insert_resource(ExtremeWeather::for_world(&world_map));
+}
+
+// Once a day: end finished events, start today's and roll the forecast
+fn update_extreme_weather(
+    clock: Res<SimClock>,
+    mut extremes: ResMut<ExtremeWeather>,
+    mut world_map: ResMut<WorldMap>,
+    mut hydrology: ResMut<Hydrology>,
+    mut telemetry: ResMut<Telemetry>,
+    mut notable: EventWriter<NotableEvent>,
+) {
+    let day = clock.day();
+    if extremes.last_day == Some(day) {
+        return;
+    }
+    extremes.last_day = Some(day);
+
+    // Like climate drift, not a structural change
+    let world_map = world_map.bypass_change_detection();
+    let extremes = &mut *extremes;
+    for event in extremes.events.iter_mut().filter(|event| event.end_day <= day) {
+        for &(index, change) in &event.applied {
+            let tile = &mut world_map.tiles[index / WORLD_SIZE][index % WORLD_SIZE];
+            tile.temperature = (tile.temperature - change).clamp(0.0, 1.0);
+        }
+        info!("🌡️ Day {}: the {:?} around tile {:?} is over", day, event.kind, event.centre);
+        telemetry.record("extreme_weather_end", &*event);
+    }
+    extremes.events.retain(|event| event.end_day > day);
+
+    for event in extremes.events.iter_mut().filter(|event| event.start_day == day && event.applied.is_empty()) {
+        let (cx, cy) = event.centre;
+        let warming = event.kind.warming();
+        let xs = cx.saturating_sub(event.radius)..(cx + event.radius + 1).min(WORLD_SIZE);
+        for x in xs {
+            for y in cy.saturating_sub(event.radius)..(cy + event.radius + 1).min(WORLD_SIZE) {
+                let strength = event.strength(x, y);
+                if strength <= 0.0 || warming == 0.0 {
+                    continue;
+                }
+                let tile = &mut world_map.tiles[x][y];
+                let before = tile.temperature;
+                tile.temperature = (before + warming * strength).clamp(0.0, 1.0);
+                event.applied.push((x * WORLD_SIZE + y, tile.temperature - before));
+            }
+        }
+        info!("🌡️ Day {}: {:?} around tile {:?} (radius {}) for {} days",
+            day, event.kind, event.centre, event.radius, event.end_day - event.start_day);
+        telemetry.record("extreme_weather", &*event);
+        notable.send(NotableEvent { position: tile_to_world(cx, cy), importance: 4.0, label_key: event.kind.label_key() });
+    }
+
+    // Rain over each hydrology cell follows the events lasting today
+    let centre = HYDROLOGY_CELL_TILES / 2;
+    for cx in 0..HYDROLOGY_SIZE {
+        for cy in 0..HYDROLOGY_SIZE {
+            let (x, y) = (cx * HYDROLOGY_CELL_TILES + centre, cy * HYDROLOGY_CELL_TILES + centre);
+            hydrology.regional_rain[cx * HYDROLOGY_SIZE + cy] = extremes.rain_factor(x, y, day);
+        }
+    }
+
+    let start_day = day + FORECAST_DAYS as u32;
+    if extremes.rng.gen_bool(EVENT_CHANCE) {
+        if let Some(event) = extremes.roll_event(world_map, start_day) {
+            debug!("Forecast: {:?} around tile {:?} from day {}", event.kind, event.centre, event.start_day);
+            extremes.events.push(event);
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use bevy::prelude::*;
+use crate::coords::{tile_to_world, world_to_tile};
+use crate::biome::ResourceType;
+use crate::creature::{Creature, Needs};
+use crate::events::NotableEvent;
+use crate::species::SpeciesRegistry;
+use crate::world::{WorldMap, WORLD_SIZE};
+use crate::world_stats::label_regions;
+
+pub const FISHERY_TICK_SECONDS: f32 = 10.0;
+const FISH_PER_TILE: f32 = 1.0; // Carrying capacity contributed by each tile of a water body
+const GROWTH_RATE: f32 = 0.05; // Logistic growth rate per tick
+const FISH_EATEN: f32 = 0.05; // Fish per second taken by a feeding creature
+const COLLAPSE_FRACTION: f32 = 0.1; // Below this share of capacity a fishery has collapsed
+const RECOVERY_FRACTION: f32 = 0.5; // A collapsed fishery counts as recovered above this share
+const SEED_FRACTION: f32 = 0.02; // Share of capacity that always escapes, so an emptied stock can regrow
+const REPORTED_BODY_TILES: usize = 16; // Smaller ponds collapse without a notable event
+
+pub struct FisheriesPlugin;
+
+impl Plugin for FisheriesPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, init_fisheries.run_if(resource_added::<WorldMap>))
+            .add_systems(FixedUpdate, (catch_fish, fishery_tick).chain().run_if(resource_exists::<Fisheries>));
+    }
+}
+
+pub struct FishStock {
+    pub capacity: f32,
+    pub stock: f32,
+    pub tiles: usize,
+    pub center: Vec2, // World position used for notable events
+    pub collapsed: bool,
+}
+
+// === FISHERIES ===
+// Every connected body of fish-bearing water (sea, lake, marsh) has its own fish stock that
+// grows logistically and is fished down by the creatures feeding on it.
+#[derive(Resource)]
+pub struct Fisheries {
+    pub body: Vec<Option<usize>>, // Water body per tile, indexed [x * WORLD_SIZE + y]
+    pub stocks: Vec<FishStock>,
+}
+
+impl Fisheries {
+    pub fn from_world_map(world_map: &WorldMap) -> Self {
+        let (body, sizes) = label_regions(world_map, |biome| biome.get_resources().contains(&ResourceType::Fish));
+
+        let mut centers = vec![Vec2::ZERO; sizes.len()];
+        for (index, body) in body.iter().enumerate() {
+            if let Some(body) = body {
+                centers[*body] += tile_to_world(index / WORLD_SIZE, index % WORLD_SIZE);
+            }
+        }
+
+        let stocks = sizes.iter().zip(centers).map(|(&tiles, center)| {
+            let capacity = tiles as f32 * FISH_PER_TILE;
+            FishStock { capacity, stock: capacity, tiles, center: center / tiles as f32, collapsed: false }
+        }).collect();
+        Self { body, stocks }
+    }
+
+    pub fn stock_at(&self, x: usize, y: usize) -> Option<&FishStock> {
+        self.body[x * WORLD_SIZE + y].map(|body| &self.stocks[body])
+    }
+
+    // Collapsed fisheries yield nothing until they recover
+    pub fn has_fish(&self, x: usize, y: usize) -> bool {
+        self.stock_at(x, y).is_some_and(|stock| !stock.collapsed)
+    }
+}
+
+fn init_fisheries(mut commands: Commands, world_map: Res<WorldMap>) {
+    let fisheries = Fisheries::from_world_map(&world_map);
+    info!("🐟 Found {} fish-bearing water bodies", fisheries.stocks.len());
+    commands.insert_resource(fisheries);
+}
+
+// Mirrors update_needs: anything eating on a fish tile with a fish-eating diet takes fish
+fn catch_fish(
+    time: Res<Time>,
+    world_map: Res<WorldMap>,
+    registry: Res<SpeciesRegistry>,
+    mut fisheries: ResMut<Fisheries>,
+    creatures: Query<(&Transform, &Creature, &Needs)>,
+) {
+    let dt = time.delta_seconds();
+    for (transform, creature, needs) in creatures.iter() {
+        if needs.hunger <= 0.0 || !registry.get(creature.species).diet.edible_resources().contains(&ResourceType::Fish) {
+            continue;
+        }
+        let Some((x, y)) = world_to_tile(transform.translation.truncate()) else { continue };
+        if !fisheries.has_fish(x, y) || !world_map.tiles[x][y].resources.contains(&ResourceType::Fish) {
+            continue;
+        }
+        let Some(body) = fisheries.body[x * WORLD_SIZE + y] else { continue };
+        let stock = &mut fisheries.stocks[body];
+        stock.stock = (stock.stock - FISH_EATEN * dt).max(stock.capacity * SEED_FRACTION);
+    }
+}
+
+fn fishery_tick(
+    time: Res<Time>,
+    mut tick_timer: Local<f32>,
+    mut fisheries: ResMut<Fisheries>,
+    mut notable: EventWriter<NotableEvent>,
+) {
+    *tick_timer += time.delta_seconds();
+    if *tick_timer < FISHERY_TICK_SECONDS {
+        return;
+    }
+    *tick_timer = 0.0;
+
+    for stock in fisheries.stocks.iter_mut() {
+        stock.stock += GROWTH_RATE * stock.stock * (1.0 - stock.stock / stock.capacity);
+
+        let share = stock.stock / stock.capacity;
+        if !stock.collapsed && share < COLLAPSE_FRACTION {
+            stock.collapsed = true;
+            if stock.tiles >= REPORTED_BODY_TILES {
+                info!("🎣 Fishery of {} tiles collapsed", stock.tiles);
+                notable.send(NotableEvent { position: stock.center, importance: 4.0, label_key: "event.fishery_collapse" });
+            }
+        } else if stock.collapsed && share > RECOVERY_FRACTION {
+            stock.collapsed = false;
+        }
+    }
+}
@@ -0,0 +1,264 @@
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use std::collections::{HashMap, HashSet};
+use crate::biome::{BiomeType, Footing};
+use crate::coords::{chunk_tile_bounds, tile_to_chunk, tile_to_world, world_to_tile, CHUNK_SIZE, TILE_SIZE};
+use crate::creature::{move_creatures, Creature};
+use crate::hibernation::Hibernation;
+use crate::optimization::ChunkRoot;
+use crate::species::{MovementMode, SpeciesRegistry};
+use crate::world::WorldMap;
+
+const PIXELS_PER_TILE: usize = 4; // Footprint resolution; one pixel per world unit
+const CHUNK_PIXELS: usize = CHUNK_SIZE * PIXELS_PER_TILE;
+const STRIDE: f32 = 2.5; // World units walked between two prints
+const PRINT_SPREAD: f32 = 0.6; // World units left and right feet land off the line walked
+const PRINT_FADE_SECONDS: f32 = 90.0; // Time constant of a print's fading
+const WEAR_STEP: f32 = 0.01; // Wear a tile takes per stride across it
+const WEAR_FADE_SECONDS: f32 = 2400.0; // Trails grow back over about ten days unless walked
+const TRAIL_WEAR: (f32, f32) = (0.5, 2.0); // Wear at which a trail starts to show, and is fully worn
+const FADE_INTERVAL: f32 = 1.0; // Simulated seconds between fading passes
+const REFRESH_SECONDS: f32 = 0.25; // Real seconds between texture uploads of changed chunks
+const FOOTPRINT_Z: f32 = 0.25; // Over the tiles, under cliff lines and props
+const TRAIL_COLOR: [u8; 3] = [120, 95, 60];
+
+pub struct FootprintsPlugin;
+
+impl Plugin for FootprintsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Footprints>()
+            .add_systems(Update, (
+                clear_footprints.run_if(resource_added::<WorldMap>),
+                add_strides,
+                show_chunk_footprints,
+                update_footprint_images.run_if(resource_exists::<WorldMap>),
+            ).chain())
+            .add_systems(FixedUpdate, (leave_footprints.after(move_creatures), fade_footprints)
+                .chain()
+                .run_if(resource_exists::<WorldMap>));
+    }
+}
+
+// === FOOTPRINTS AND TRAILS ===
+// Walkers leave prints on snow, sand and mud that fade over a few minutes, and wear down every
+// land tile they cross; tiles walked often enough show as a trail until they grow back. Both are
+// drawn into one texture per render chunk, a child of its ChunkRoot, so any number of prints
+// costs one sprite per chunk. Prints are only kept for chunks on screen; wear is kept everywhere.
+#[derive(Resource, Default)]
+pub struct Footprints {
+    chunks: HashMap<(i32, i32), ChunkMarks>, // By source chunk
+}
+
+#[derive(Default)]
+struct ChunkMarks {
+    wear: Vec<f32>, // Per tile [lx * CHUNK_SIZE + ly]; empty until walked on
+    shown: Option<(Handle<Image>, Vec<f32>)>, // Texture and print strength per pixel while on screen
+    dirty: bool,
+}
+
+impl Footprints {
+    // Wears the tile under a stride and leaves a print there, if the ground takes them
+    fn step(&mut self, world_map: &WorldMap, position: Vec2) {
+        let Some((x, y)) = world_to_tile(position) else { return };
+        let biome = world_map.tiles[x][y].biome;
+        if biome == BiomeType::Ocean {
+            return;
+        }
+        let chunk = tile_to_chunk(x, y);
+        let (start_x, start_y, _, _) = chunk_tile_bounds(chunk.0, chunk.1);
+        let (lx, ly) = (x - start_x, y - start_y);
+        let marks = self.chunks.entry(chunk).or_default();
+        if marks.wear.is_empty() {
+            marks.wear = vec![0.0; CHUNK_SIZE * CHUNK_SIZE];
+        }
+        let wear = &mut marks.wear[lx * CHUNK_SIZE + ly];
+        *wear += WEAR_STEP;
+        marks.dirty |= *wear >= TRAIL_WEAR.0;
+
+        let Some((_, prints)) = marks.shown.as_mut() else { return };
+        if print_color(biome).is_none() {
+            return;
+        }
+        let within = (position - tile_to_world(x, y)) / TILE_SIZE + 0.5; // 0.0..1.0 across the tile
+        let pixel = |tile: usize, within: f32| tile * PIXELS_PER_TILE + ((within * PIXELS_PER_TILE as f32) as usize).min(PIXELS_PER_TILE - 1);
+        prints[pixel(lx, within.x) * CHUNK_PIXELS + pixel(ly, within.y)] = 1.0;
+        marks.dirty = true;
+    }
+}
+
+// Color of prints left on a biome's ground, if it takes them
+fn print_color(biome: BiomeType) -> Option<[u8; 3]> {
+    match (biome, biome.footing()) {
+        (_, Footing::DeepSnow) => Some([95, 110, 140]),
+        (_, Footing::Mud) => Some([55, 40, 25]),
+        (BiomeType::Desert | BiomeType::Coastal, _) => Some([150, 120, 70]),
+        _ => None,
+    }
+}
+
+// Where the last print was left, and which foot comes next
+#[derive(Component)]
+pub struct Stride {
+    last: Vec2,
+    left: bool,
+}
+
+// Overlays go with the chunks of the old world
+fn clear_footprints(mut footprints: ResMut<Footprints>) {
+    footprints.chunks.clear();
+}
+
+// Walkers only; fliers leave no marks
+fn add_strides(
+    mut commands: Commands,
+    registry: Res<SpeciesRegistry>,
+    creatures: Query<(Entity, &Transform, &Creature), Added<Creature>>,
+) {
+    for (entity, transform, creature) in creatures.iter() {
+        if registry.get(creature.species).movement == MovementMode::Walk {
+            commands.entity(entity).insert(Stride { last: transform.translation.truncate(), left: false });
+        }
+    }
+}
+
+fn leave_footprints(
+    world_map: Res<WorldMap>,
+    mut footprints: ResMut<Footprints>,
+    mut creatures: Query<(&Transform, &mut Stride, Option<&Hibernation>)>,
+) {
+    for (transform, mut stride, hibernation) in creatures.iter_mut() {
+        let position = transform.translation.truncate();
+        let walked = position - stride.last;
+        // Jumps, like loading a save or wrapping round the map, leave no prints
+        if walked.length() < STRIDE || Hibernation::is_dormant(hibernation) {
+            continue;
+        }
+        stride.last = position;
+        if walked.length() > STRIDE * 4.0 {
+            continue;
+        }
+        stride.left = !stride.left;
+        let side = walked.normalize().perp() * if stride.left { PRINT_SPREAD } else { -PRINT_SPREAD };
+        footprints.step(&world_map, position + side);
+    }
+}
+
+// Prints fade quickly and trails slowly; chunks scrolled off screen drop their prints
+fn fade_footprints(
+    time: Res<Time>,
+    mut since_fade: Local<f32>,
+    mut footprints: ResMut<Footprints>,
+    roots: Query<&ChunkRoot>,
+) {
+    *since_fade += time.delta_seconds();
+    if *since_fade < FADE_INTERVAL {
+        return;
+    }
+    let elapsed = std::mem::take(&mut *since_fade);
+    let print_fade = (-elapsed / PRINT_FADE_SECONDS).exp();
+    let wear_fade = (-elapsed / WEAR_FADE_SECONDS).exp();
+    let on_screen: HashSet<(i32, i32)> = roots.iter().map(|root| root.source).collect();
+
+    footprints.chunks.retain(|chunk, marks| {
+        if !on_screen.contains(chunk) {
+            marks.shown = None;
+        }
+        let mut marked = false;
+        for wear in marks.wear.iter_mut() {
+            *wear = if *wear * wear_fade < 1e-3 { 0.0 } else { *wear * wear_fade };
+            marked |= *wear > 0.0;
+        }
+        if let Some((_, prints)) = marks.shown.as_mut() {
+            for print in prints.iter_mut().filter(|print| **print > 0.0) {
+                *print = if *print * print_fade < 0.05 { 0.0 } else { *print * print_fade };
+            }
+            marks.dirty = true;
+            marked = true;
+        }
+        marked
+    });
+}
+
+#[derive(Component)]
+struct FootprintOverlay;
+
+// Newly rendered chunks get a sprite showing their marks; copies past a wrapped edge share it
+fn show_chunk_footprints(
+    mut commands: Commands,
+    mut footprints: ResMut<Footprints>,
+    mut images: ResMut<Assets<Image>>,
+    roots: Query<(Entity, &ChunkRoot), Added<ChunkRoot>>,
+) {
+    for (root, chunk) in roots.iter() {
+        let marks = footprints.chunks.entry(chunk.source).or_default();
+        let (image, _) = marks.shown.get_or_insert_with(|| {
+            let image = Image::new_fill(
+                Extent3d { width: CHUNK_PIXELS as u32, height: CHUNK_PIXELS as u32, depth_or_array_layers: 1 },
+                TextureDimension::D2,
+                &[0, 0, 0, 0],
+                TextureFormat::Rgba8UnormSrgb,
+                RenderAssetUsages::default(),
+            );
+            (images.add(image), vec![0.0; CHUNK_PIXELS * CHUNK_PIXELS])
+        });
+        marks.dirty = true;
+
+        let (start_x, start_y, _, _) = chunk_tile_bounds(chunk.source.0, chunk.source.1);
+        let extent = CHUNK_SIZE as f32 * TILE_SIZE;
+        let centre = tile_to_world(start_x, start_y) - TILE_SIZE / 2.0 + extent / 2.0;
+        let overlay = commands.spawn((
+            SpriteBundle {
+                texture: image.clone(),
+                sprite: Sprite { custom_size: Some(Vec2::splat(extent)), ..default() },
+                transform: Transform::from_translation(centre.extend(FOOTPRINT_Z)),
+                ..default()
+            },
+            FootprintOverlay,
+        )).id();
+        commands.entity(root).add_child(overlay);
+    }
+}
+
+fn update_footprint_images(
+    time: Res<Time<Real>>,
+    mut since_refresh: Local<f32>,
+    world_map: Res<WorldMap>,
+    mut footprints: ResMut<Footprints>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    *since_refresh += time.delta_seconds();
+    if *since_refresh < REFRESH_SECONDS {
+        return;
+    }
+    *since_refresh = 0.0;
+
+    for (chunk, marks) in footprints.chunks.iter_mut().filter(|(_, marks)| marks.dirty) {
+        let Some((handle, prints)) = marks.shown.as_ref() else { continue };
+        let Some(image) = images.get_mut(handle) else { continue };
+        marks.dirty = false;
+        let (start_x, start_y, end_x, end_y) = chunk_tile_bounds(chunk.0, chunk.1);
+        for px in 0..CHUNK_PIXELS {
+            for py in 0..CHUNK_PIXELS {
+                let (lx, ly) = (px / PIXELS_PER_TILE, py / PIXELS_PER_TILE);
+                let (x, y) = (start_x + lx, start_y + ly);
+                let color = if x >= end_x || y >= end_y {
+                    [0, 0, 0, 0]
+                } else {
+                    let wear = marks.wear.get(lx * CHUNK_SIZE + ly).copied().unwrap_or(0.0);
+                    let trail = ((wear - TRAIL_WEAR.0) / (TRAIL_WEAR.1 - TRAIL_WEAR.0)).clamp(0.0, 1.0);
+                    let print = prints[px * CHUNK_PIXELS + py];
+                    match print_color(world_map.tiles[x][y].biome) {
+                        Some([r, g, b]) if print * 180.0 > trail * 110.0 => [r, g, b, (print * 180.0) as u8],
+                        _ if trail > 0.0 => [TRAIL_COLOR[0], TRAIL_COLOR[1], TRAIL_COLOR[2], (trail * 110.0) as u8],
+                        _ => [0, 0, 0, 0],
+                    }
+                };
+                // Image rows run top-down while world y runs bottom-up
+                let pixel = ((CHUNK_PIXELS - 1 - py) * CHUNK_PIXELS + px) * 4;
+                image.data[pixel..pixel + 4].copy_from_slice(&color);
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+use bevy::window::PresentMode;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use crate::settings::Settings;
+
+pub struct FramePacingPlugin;
+
+impl Plugin for FramePacingPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<FrameLimiter>()
+            .add_systems(Update, apply_vsync_mode.run_if(resource_changed::<Settings>))
+            .add_systems(Last, limit_frame_rate);
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VsyncMode {
+    #[default]
+    On,
+    Off,
+    Adaptive, // Vsync, but tear instead of stalling when a frame is late
+}
+
+impl VsyncMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "on" => Some(Self::On),
+            "off" => Some(Self::Off),
+            "adaptive" => Some(Self::Adaptive),
+            _ => None,
+        }
+    }
+
+    pub fn present_mode(&self) -> PresentMode {
+        match self {
+            VsyncMode::On => PresentMode::AutoVsync,
+            VsyncMode::Off => PresentMode::AutoNoVsync,
+            VsyncMode::Adaptive => PresentMode::FifoRelaxed,
+        }
+    }
+}
+
+// === MANUAL FRAME LIMITER ===
+// Sleeps at the end of the frame so that frames are at least 1/fps_cap apart.
+#[derive(Resource)]
+pub struct FrameLimiter {
+    pub frame_start: Instant,
+}
+
+impl Default for FrameLimiter {
+    fn default() -> Self {
+        Self { frame_start: Instant::now() }
+    }
+}
+
+fn limit_frame_rate(settings: Res<Settings>, mut limiter: ResMut<FrameLimiter>) {
+    if settings.fps_cap > 0.0 {
+        let target = Duration::from_secs_f32(1.0 / settings.fps_cap);
+        let elapsed = limiter.frame_start.elapsed();
+        if elapsed < target {
+            std::thread::sleep(target - elapsed);
+        }
+    }
+    limiter.frame_start = Instant::now();
+}
+
+fn apply_vsync_mode(settings: Res<Settings>, mut windows: Query<&mut Window>) {
+    let present_mode = settings.vsync.present_mode();
+    for mut window in windows.iter_mut() {
+        if window.present_mode != present_mode {
+            window.present_mode = present_mode;
+        }
+    }
+}
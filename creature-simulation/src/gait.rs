@@ -0,0 +1,102 @@
+use bevy::prelude::*;
+use crate::ai_lod::is_distant;
+use crate::biome::Footing;
+use crate::coords::world_to_tile;
+use crate::creature::{Behavior, Creature, Surroundings, Wander};
+use crate::optimization::LODLevel;
+use crate::settings::{motion_enabled, Settings};
+use crate::species::{MovementMode, SpeciesRegistry};
+use crate::world::WorldMap;
+
+pub struct GaitPlugin;
+
+impl Plugin for GaitPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (
+            update_gaits,
+            animate_gaits.run_if(motion_enabled),
+        ).chain().run_if(resource_exists::<WorldMap>));
+    }
+}
+
+// === GAITS ===
+// How a walker moves over the ground it is on: it trudges through deep snow, wades through
+// wetland mud and pushes through rainforest undergrowth, each at the speed of the biome's
+// footing (see Surroundings::speed_factor) and with its own animation while on the move.
+// Fliers, and walkers on firm ground or ice, walk normally.
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Gait {
+    #[default]
+    Walk,
+    Trudge,
+    Wade,
+    Push,
+}
+
+impl Gait {
+    fn on(footing: Footing) -> Self {
+        match footing {
+            Footing::Firm => Gait::Walk,
+            Footing::DeepSnow => Gait::Trudge,
+            Footing::Mud => Gait::Wade,
+            Footing::Undergrowth => Gait::Push,
+        }
+    }
+
+    // Rotation and scale of the sprite at a point of its stride
+    fn pose(&self, phase: f32) -> (f32, Vec2) {
+        match self {
+            Gait::Walk => (0.0, Vec2::ONE),
+            // Slow, heavy steps sinking into the snow
+            Gait::Trudge => {
+                let sink = (phase * 3.0).sin().abs();
+                (0.0, Vec2::new(1.0 + 0.05 * sink, 1.0 - 0.15 * sink))
+            }
+            // Low in the mud, rocking from side to side
+            Gait::Wade => ((phase * 2.0).sin() * 0.1, Vec2::new(1.08, 0.82)),
+            // Shouldering through the plants
+            Gait::Push => ((phase * 5.0).sin() * 0.2, Vec2::ONE),
+        }
+    }
+}
+
+fn update_gaits(
+    mut commands: Commands,
+    registry: Res<SpeciesRegistry>,
+    surroundings: Surroundings,
+    mut creatures: Query<(Entity, &Transform, &Creature, Option<&mut Gait>)>,
+) {
+    for (entity, transform, creature, gait) in creatures.iter_mut() {
+        let walking = registry.get(creature.species).movement == MovementMode::Walk;
+        let current = match world_to_tile(transform.translation.truncate()) {
+            Some((x, y)) if walking && !surroundings.is_frozen(x, y) => Gait::on(surroundings.tile(x, y).biome.footing()),
+            _ => Gait::Walk,
+        };
+        match gait {
+            Some(mut gait) => { gait.set_if_neq(current); }
+            None => { commands.entity(entity).insert(current); }
+        }
+    }
+}
+
+// Creatures standing still, or too far away to make out, keep their plain pose, upright in the
+// isometric view (see view_mode.rs)
+fn animate_gaits(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut creatures: Query<(Entity, &mut Transform, &Gait, &Wander, &LODLevel)>,
+) {
+    for (entity, mut transform, gait, wander, lod) in creatures.iter_mut() {
+        let still = matches!(wander.behavior, Behavior::Arrive | Behavior::Roost | Behavior::Hibernate);
+        let gait = if still || is_distant(lod) { Gait::Walk } else { *gait };
+        // Strides are out of step between creatures
+        let phase = time.elapsed_seconds() + entity.index() as f32 * 0.7;
+        let (rotation, scale) = gait.pose(phase);
+        let rotation = settings.view.rotation() * Quat::from_rotation_z(rotation);
+        let scale = scale.extend(1.0) * settings.view.stretch();
+        if transform.rotation != rotation || transform.scale != scale {
+            transform.rotation = rotation;
+            transform.scale = scale;
+        }
+    }
+}
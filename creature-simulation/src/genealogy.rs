@@ -0,0 +1,197 @@
+use bevy::prelude::*;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
+use std::fs;
+use crate::creature::Creature;
+use crate::creature_tracking::{display_name, CreatureName, SelectedCreature};
+use crate::errors::{SimError, SimFailure};
+use crate::genetics::Genome;
+use crate::io_queue::IoQueue;
+use crate::species::SpeciesRegistry;
+use crate::world::WorldMap;
+
+pub const GENEALOGY_DIR: &str = "genealogy";
+const MAX_RECORDS: usize = 100_000; // Births remembered; the oldest are forgotten first
+const MAX_DESCENDANTS: usize = 500; // Per export, nearest generations first
+
+pub struct GenealogyPlugin;
+
+impl Plugin for GenealogyPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Genealogy>()
+            .add_systems(Update, (
+                reset_genealogy.run_if(resource_added::<WorldMap>),
+                export_genealogy.run_if(|genealogy: Res<Genealogy>| genealogy.export_requested),
+            ));
+    }
+}
+
+// === GENEALOGY ===
+// Who gave birth to whom (see reproduction.rs), with the traits each creature was born with, so
+// family trees outlive their members. Founders enter when they first give birth. Births are keyed
+// by entity, whose generation keeps ids unique even after they are reused; creatures pooled away
+// from the camera come back as new entities and start a new tree.
+#[derive(Clone)]
+struct BirthRecord {
+    parent: Option<Entity>, // None for founders
+    species: usize,
+    genome: Genome,
+    born_day: Option<u32>, // Unknown for founders
+}
+
+#[derive(Resource, Default)]
+pub struct Genealogy {
+    records: HashMap<Entity, BirthRecord>,
+    order: VecDeque<Entity>, // Oldest record first
+    pub export_requested: bool, // Set by the console's "genealogy" command
+}
+
+impl Genealogy {
+    pub fn record_birth(&mut self, parent: (Entity, usize, Genome), child: (Entity, Genome), day: u32) {
+        let (parent, species, parent_genome) = parent;
+        if !self.records.contains_key(&parent) {
+            self.insert(parent, BirthRecord { parent: None, species, genome: parent_genome, born_day: None });
+        }
+        self.insert(child.0, BirthRecord { parent: Some(parent), species, genome: child.1, born_day: Some(day) });
+    }
+
+    // Births and hatchings recorded on a day
+    pub fn births_on(&self, day: u32) -> usize {
+        self.records.values().filter(|record| record.born_day == Some(day)).count()
+    }
+
+    fn insert(&mut self, entity: Entity, record: BirthRecord) {
+        self.records.insert(entity, record);
+        self.order.push_back(entity);
+        while self.order.len() > MAX_RECORDS {
+            if let Some(oldest) = self.order.pop_front() {
+                self.records.remove(&oldest);
+            }
+        }
+    }
+}
+
+fn reset_genealogy(mut genealogy: ResMut<Genealogy>) {
+    let export_requested = genealogy.export_requested;
+    *genealogy = Genealogy { export_requested, ..default() };
+}
+
+// One creature in an exported family tree
+#[derive(Serialize)]
+struct FamilyMember {
+    id: u64, // Entity bits
+    parent: Option<u64>,
+    name: String,
+    species: String,
+    alive: bool,
+    born_day: Option<u32>,
+    generation: i32, // Relative to the exported creature, ancestors negative
+    size: f32, // Genome multiplier on the species size
+    tint: (f32, f32, f32),
+    lineage: u32,
+    boldness: f32,
+    color: String, // "#rrggbb", as drawn
+}
+
+#[derive(Serialize)]
+struct FamilyTree {
+    subject: u64,
+    members: Vec<FamilyMember>,
+}
+
+// Its ancestors up to the founder and its descendants, written as GraphViz DOT and JSON
+fn export_genealogy(
+    mut genealogy: ResMut<Genealogy>,
+    selected: Res<SelectedCreature>,
+    registry: Res<SpeciesRegistry>,
+    creatures: Query<(&Creature, &Genome, Option<&CreatureName>)>,
+    (mut io, mut failures): (ResMut<IoQueue>, EventWriter<SimFailure>),
+) {
+    genealogy.export_requested = false;
+    let Some(subject) = selected.0 else {
+        return warn!("Select a creature to export its genealogy");
+    };
+    let live_record = |entity: Entity| creatures.get(entity).ok().map(|(creature, genome, _)| BirthRecord {
+        parent: None, species: creature.species, genome: *genome, born_day: None,
+    });
+    let record = |entity: Entity| genealogy.records.get(&entity).cloned().or_else(|| live_record(entity));
+
+    // Walk up to the founder, then breadth first down through the descendants
+    let mut family: Vec<(Entity, i32)> = vec![(subject, 0)];
+    let mut ancestor = record(subject).and_then(|record| record.parent);
+    while let Some(entity) = ancestor {
+        family.insert(0, (entity, -(family.len() as i32)));
+        ancestor = record(entity).and_then(|record| record.parent);
+    }
+    let mut children: HashMap<Entity, Vec<Entity>> = HashMap::new();
+    for (child, record) in &genealogy.records {
+        if let Some(parent) = record.parent {
+            children.entry(parent).or_default().push(*child);
+        }
+    }
+    let mut frontier = VecDeque::from([(subject, 0)]);
+    let mut descendants = 0;
+    while let Some((entity, generation)) = frontier.pop_front() {
+        let mut kids = children.get(&entity).cloned().unwrap_or_default();
+        kids.sort();
+        for child in kids.into_iter().take(MAX_DESCENDANTS - descendants) {
+            family.push((child, generation + 1));
+            frontier.push_back((child, generation + 1));
+            descendants += 1;
+        }
+    }
+
+    let members: Vec<FamilyMember> = family.iter().filter_map(|&(entity, generation)| {
+        let record = record(entity)?;
+        let species = registry.species.get(record.species)?;
+        let live = creatures.get(entity).ok();
+        let color = record.genome.color(species).to_srgba();
+        Some(FamilyMember {
+            id: entity.to_bits(),
+            parent: record.parent.map(Entity::to_bits),
+            name: display_name(live.and_then(|(_, _, name)| name), &species.name, entity),
+            species: species.name.clone(),
+            alive: live.is_some(),
+            born_day: record.born_day,
+            generation,
+            size: record.genome.size,
+            tint: record.genome.tint,
+            lineage: record.genome.lineage,
+            boldness: record.genome.boldness,
+            color: format!("#{:02x}{:02x}{:02x}", (color.red * 255.0) as u8, (color.green * 255.0) as u8, (color.blue * 255.0) as u8),
+        })
+    }).collect();
+    let tree = FamilyTree { subject: subject.to_bits(), members };
+
+    let mut dot = String::from("digraph genealogy {\n    rankdir=TB;\n    node [shape=box, style=filled, fontname=\"sans-serif\"];\n");
+    for member in &tree.members {
+        let born = member.born_day.map_or("founder".to_string(), |day| format!("born day {}", day));
+        let _ = writeln!(dot, "    n{} [label=\"{}\\n{}, {}\\nsize {:.2}, lineage {}\", fillcolor=\"{}\"{}];",
+            member.id, member.name.replace('"', "'"), member.species, born, member.size, member.lineage, member.color,
+            if member.id == tree.subject { ", penwidth=3" } else if !member.alive { ", fontcolor=\"#555555\"" } else { "" });
+    }
+    for member in &tree.members {
+        if let Some(parent) = member.parent.filter(|parent| tree.members.iter().any(|m| m.id == *parent)) {
+            let _ = writeln!(dot, "    n{} -> n{};", parent, member.id);
+        }
+    }
+    dot.push_str("}\n");
+
+    if let Err(err) = fs::create_dir_all(GENEALOGY_DIR) {
+        failures.send(SimFailure::new(SimError::io(GENEALOGY_DIR, err)));
+        return;
+    }
+    let stem = format!("{}/{}", GENEALOGY_DIR, subject);
+    let json = match serde_json::to_string_pretty(&tree) {
+        Ok(json) => json,
+        Err(err) => {
+            failures.send(SimFailure::new(SimError::io(&stem, err)));
+            return;
+        }
+    };
+    let count = tree.members.len();
+    io.write_text(&format!("{}.dot", stem), dot, format!("🌳 Exported {} relatives to {}.dot", count, stem), None);
+    io.write_text(&format!("{}.json", stem), json, format!("🌳 Exported {} relatives to {}.json", count, stem), None);
+}
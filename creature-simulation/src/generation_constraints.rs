@@ -0,0 +1,60 @@
+use serde::{Deserialize, Serialize};
+use crate::biome::BiomeType;
+use crate::world::WorldMap;
+use crate::world_stats::{region_sizes, WorldReport};
+
+// === GENERATION CONSTRAINTS ===
+// Optional requirements a generated map must meet. A failing seed is regenerated with the
+// next seed (up to `max_retries` times) and the failures are recorded in the world report.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GenerationConstraints {
+    pub min_biome_coverage: Vec<(BiomeType, f32)>, // Minimum percentage of all tiles
+    pub min_biome_region: Vec<(BiomeType, usize)>, // At least one connected region of N tiles
+    pub max_retries: u32,
+}
+
+impl Default for GenerationConstraints {
+    fn default() -> Self {
+        Self {
+            min_biome_coverage: Vec::new(),
+            min_biome_region: Vec::new(),
+            max_retries: 5,
+        }
+    }
+}
+
+impl GenerationConstraints {
+    pub fn is_empty(&self) -> bool {
+        self.min_biome_coverage.is_empty() && self.min_biome_region.is_empty()
+    }
+
+    // Returns a description of every violated constraint (empty when the map passes)
+    pub fn check(&self, world_map: &WorldMap, report: &WorldReport) -> Vec<String> {
+        let mut violations = Vec::new();
+
+        for (biome, min_percent) in &self.min_biome_coverage {
+            let percent = report.biome_coverage.iter()
+                .find(|(b, _)| b == biome)
+                .map_or(0.0, |(_, p)| *p);
+            if percent < *min_percent {
+                violations.push(format!("{:?} coverage {:.1}% < {:.1}%", biome, percent, min_percent));
+            }
+        }
+
+        for (biome, min_tiles) in &self.min_biome_region {
+            let largest = region_sizes(world_map, |b| b == *biome).into_iter().max().unwrap_or(0);
+            if largest < *min_tiles {
+                violations.push(format!("largest {:?} region {} tiles < {} tiles", biome, largest, min_tiles));
+            }
+        }
+
+        violations
+    }
+}
+
+// Parses "Biome:value" CLI arguments such as "Ocean:20" or "Forest:5000"
+pub fn parse_biome_value<T: std::str::FromStr>(arg: &str) -> Option<(BiomeType, T)> {
+    let (name, value) = arg.split_once(':')?;
+    Some((BiomeType::from_name(name)?, value.parse().ok()?))
+}
@@ -0,0 +1,328 @@
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::ui::RelativeCursorPosition;
+use crate::keybindings::{Action, Controls};
+use crate::nesting::Egg;
+use crate::creature::Creature;
+use crate::generation_service::{GenerationId, GenerationJob, GenerationOutput, GenerationPriority, GenerationService};
+use crate::localization::Localization;
+use crate::optimized_systems::WorldGeneration;
+use crate::population_pools::PopulationPools;
+use crate::settings::Settings;
+use crate::swarm::Swarm;
+use crate::world::{GenerationParams, Topology, WorldMap};
+
+const PREVIEW_SIZE: usize = 100; // Pixels per side; one sample every 10 tiles
+const PREVIEW_PIXELS: f32 = 200.0; // On-screen size of the preview
+const TRACK_WIDTH: f32 = 160.0;
+
+pub struct GenerationPanelPlugin;
+
+impl Plugin for GenerationPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<GenerationPanel>()
+            .add_systems(Startup, spawn_generation_panel)
+            .add_systems(Update, (
+                toggle_generation_panel,
+                drag_sliders,
+                press_panel_buttons,
+                update_sliders.run_if(resource_changed::<GenerationPanel>),
+                refresh_preview.run_if(resource_exists::<WorldMap>),
+                collect_preview,
+            ).chain());
+    }
+}
+
+// === GENERATION PANEL ===
+// Tuning sliders for the world generator (toggled with T). Every change re-renders a low-res
+// biome preview of the current seed in the background; "Regenerate" replaces the running world
+// with a full generation using the tuned values.
+#[derive(Resource)]
+pub struct GenerationPanel {
+    pub params: GenerationParams,
+    preview_stale: bool, // Params changed since the last preview was started
+    preview: Option<GenerationId>, // Being generated
+}
+
+impl FromWorld for GenerationPanel {
+    fn from_world(world: &mut World) -> Self {
+        Self { params: world.resource::<Settings>().generation, preview_stale: true, preview: None }
+    }
+}
+
+struct SliderSpec {
+    label_key: &'static str,
+    min: f32,
+    max: f32,
+    value: fn(&mut GenerationParams) -> &mut f32,
+}
+
+const SLIDERS: [SliderSpec; 8] = [
+    SliderSpec { label_key: "generation.sea_level", min: 0.1, max: 0.6, value: |params| &mut params.sea_level },
+    SliderSpec { label_key: "generation.elevation_scale", min: 0.002, max: 0.03, value: |params| &mut params.elevation_scale },
+    SliderSpec { label_key: "generation.temperature_scale", min: 0.001, max: 0.02, value: |params| &mut params.temperature_scale },
+    SliderSpec { label_key: "generation.moisture_scale", min: 0.002, max: 0.03, value: |params| &mut params.moisture_scale },
+    SliderSpec { label_key: "generation.mountain_elevation", min: 0.6, max: 0.95, value: |params| &mut params.mountain_elevation },
+    SliderSpec { label_key: "generation.hot_temperature", min: 0.5, max: 0.9, value: |params| &mut params.hot_temperature },
+    SliderSpec { label_key: "generation.cold_temperature", min: 0.1, max: 0.5, value: |params| &mut params.cold_temperature },
+    SliderSpec { label_key: "generation.axial_tilt", min: 0.0, max: 45.0, value: |params| &mut params.axial_tilt },
+];
+
+#[derive(Component)]
+pub struct GenerationPanelRoot;
+
+#[derive(Component)]
+pub struct SliderTrack(usize); // Index into SLIDERS
+
+#[derive(Component)]
+pub struct SliderFill(usize);
+
+#[derive(Component)]
+pub struct SliderValue(usize);
+
+#[derive(Component)]
+pub struct GenerationPreview;
+
+#[derive(Component)]
+pub enum PanelButton {
+    Regenerate,
+    Reset,
+    Topology, // Cycles through Topology::ALL
+}
+
+#[derive(Component)]
+pub struct TopologyLabel;
+
+
+fn spawn_generation_panel(mut commands: Commands, localization: Res<Localization>) {
+    let text_style = TextStyle { font_size: 14.0, color: Color::srgb(0.9, 0.9, 0.9), ..default() };
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(10.0),
+                right: Val::Px(10.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(8.0)),
+                row_gap: Val::Px(4.0),
+                ..default()
+            },
+            background_color: Color::srgba(0.05, 0.05, 0.1, 0.8).into(),
+            visibility: Visibility::Hidden,
+            // Above the loading screen, where it tunes the world picked after a cancel
+            z_index: ZIndex::Global(10),
+            ..default()
+        },
+        GenerationPanelRoot,
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section(localization.get("generation.title"), text_style.clone()));
+        parent.spawn((
+            ImageBundle {
+                style: Style { width: Val::Px(PREVIEW_PIXELS), height: Val::Px(PREVIEW_PIXELS), ..default() },
+                ..default()
+            },
+            GenerationPreview,
+        ));
+
+        for (index, slider) in SLIDERS.iter().enumerate() {
+            parent.spawn(NodeBundle {
+                style: Style { align_items: AlignItems::Center, column_gap: Val::Px(6.0), ..default() },
+                ..default()
+            }).with_children(|row| {
+                row.spawn(TextBundle::from_section(localization.get(slider.label_key), text_style.clone())
+                    .with_style(Style { width: Val::Px(130.0), ..default() }));
+                row.spawn((
+                    ButtonBundle {
+                        style: Style { width: Val::Px(TRACK_WIDTH), height: Val::Px(10.0), ..default() },
+                        background_color: Color::srgb(0.2, 0.2, 0.3).into(),
+                        ..default()
+                    },
+                    RelativeCursorPosition::default(),
+                    SliderTrack(index),
+                )).with_children(|track| {
+                    track.spawn((
+                        NodeBundle {
+                            style: Style { height: Val::Percent(100.0), ..default() },
+                            background_color: Color::srgb(0.4, 0.6, 0.9).into(),
+                            ..default()
+                        },
+                        SliderFill(index),
+                    ));
+                });
+                row.spawn((TextBundle::from_section("", text_style.clone()), SliderValue(index)));
+            });
+        }
+
+        parent.spawn((
+            ButtonBundle {
+                style: Style { padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)), ..default() },
+                background_color: Color::srgba(0.2, 0.2, 0.3, 0.9).into(),
+                ..default()
+            },
+            PanelButton::Topology,
+        )).with_children(|button| {
+            button.spawn((TextBundle::from_section("", text_style.clone()), TopologyLabel));
+        });
+
+        parent.spawn(NodeBundle {
+            style: Style { column_gap: Val::Px(6.0), ..default() },
+            ..default()
+        }).with_children(|row| {
+            for (button, key) in [(PanelButton::Regenerate, "generation.regenerate"), (PanelButton::Reset, "generation.reset")] {
+                row.spawn((
+                    ButtonBundle {
+                        style: Style { padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)), ..default() },
+                        background_color: Color::srgba(0.2, 0.2, 0.3, 0.9).into(),
+                        ..default()
+                    },
+                    button,
+                )).with_children(|button| {
+                    button.spawn(TextBundle::from_section(localization.get(key), text_style.clone()));
+                });
+            }
+        });
+    });
+}
+
+fn toggle_generation_panel(
+    controls: Controls,
+    mut panels: Query<&mut Visibility, With<GenerationPanelRoot>>,
+) {
+    if !controls.just_pressed(Action::ToggleGenerationPanel) {
+        return;
+    }
+    for mut visibility in panels.iter_mut() {
+        *visibility = match *visibility {
+            Visibility::Hidden => Visibility::Visible,
+            _ => Visibility::Hidden,
+        };
+    }
+}
+
+// A held track follows the cursor, even when dragged past either end
+fn drag_sliders(
+    tracks: Query<(&Interaction, &RelativeCursorPosition, &SliderTrack)>,
+    mut panel: ResMut<GenerationPanel>,
+) {
+    for (interaction, cursor, track) in tracks.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(position) = cursor.normalized else { continue };
+        let slider = &SLIDERS[track.0];
+        let value = slider.min + position.x.clamp(0.0, 1.0) * (slider.max - slider.min);
+        let current = (slider.value)(&mut panel.bypass_change_detection().params);
+        if *current == value {
+            continue;
+        }
+        *current = value;
+        panel.preview_stale = true;
+    }
+}
+
+fn update_sliders(
+    panel: Res<GenerationPanel>,
+    localization: Res<Localization>,
+    mut fills: Query<(&mut Style, &SliderFill)>,
+    mut values: Query<(&mut Text, &SliderValue), Without<TopologyLabel>>,
+    mut topology_labels: Query<&mut Text, With<TopologyLabel>>,
+) {
+    let mut params = panel.params;
+    for (mut style, fill) in fills.iter_mut() {
+        let slider = &SLIDERS[fill.0];
+        let fraction = (*(slider.value)(&mut params) - slider.min) / (slider.max - slider.min);
+        style.width = Val::Percent(fraction * 100.0);
+    }
+    for (mut text, value) in values.iter_mut() {
+        text.sections[0].value = format!("{:.3}", (SLIDERS[value.0].value)(&mut params));
+    }
+    for mut text in topology_labels.iter_mut() {
+        text.sections[0].value = localization.get(params.topology.label_key()).to_string();
+    }
+}
+
+// Entities tied to places in the current world
+pub type WorldInhabitant = Or<(With<Creature>, With<Swarm>, With<Egg>)>;
+
+// Regenerate swaps in a new world with the same seed; creatures belong to the old one and go with it
+fn press_panel_buttons(
+    mut commands: Commands,
+    buttons: Query<(&Interaction, &PanelButton), Changed<Interaction>>,
+    mut panel: ResMut<GenerationPanel>,
+    mut settings: ResMut<Settings>,
+    world_map: Option<Res<WorldMap>>,
+    (mut pools, mut generation): (ResMut<PopulationPools>, WorldGeneration),
+    inhabitants: Query<Entity, WorldInhabitant>,
+) {
+    for (interaction, button) in buttons.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        match button {
+            PanelButton::Reset => {
+                panel.params = GenerationParams::default();
+                panel.preview_stale = true;
+            }
+            PanelButton::Topology => {
+                let next = Topology::ALL.iter().position(|topology| *topology == panel.params.topology).map_or(0, |i| i + 1);
+                panel.params.topology = Topology::ALL[next % Topology::ALL.len()];
+                panel.preview_stale = true;
+            }
+            PanelButton::Regenerate => {
+                let Some(world_map) = &world_map else { continue };
+                settings.generation = panel.params;
+                for entity in inhabitants.iter() {
+                    commands.entity(entity).despawn();
+                }
+                pools.chunks.clear();
+                info!("🌍 Regenerating seed {} with {:?}", world_map.seed, panel.params);
+                generation.start(&settings, world_map.seed);
+            }
+        }
+    }
+}
+
+// A newer preview replaces one still being generated, which is cancelled
+fn refresh_preview(
+    mut panel: ResMut<GenerationPanel>,
+    mut service: ResMut<GenerationService>,
+    world_map: Res<WorldMap>,
+    panels: Query<&Visibility, With<GenerationPanelRoot>>,
+) {
+    let visible = panels.iter().any(|visibility| *visibility != Visibility::Hidden);
+    if !visible || !panel.preview_stale {
+        return;
+    }
+    panel.preview_stale = false;
+    if let Some(previous) = panel.preview.take() {
+        service.discard(previous);
+    }
+    let job = GenerationJob::Preview { seed: world_map.seed, params: panel.params, size: PREVIEW_SIZE };
+    panel.preview = Some(service.request(job, GenerationPriority::Normal));
+}
+
+fn collect_preview(
+    mut panel: ResMut<GenerationPanel>,
+    mut service: ResMut<GenerationService>,
+    mut images: ResMut<Assets<Image>>,
+    mut previews: Query<&mut UiImage, With<GenerationPreview>>,
+) {
+    let Some(id) = panel.preview else { return };
+    let Some(result) = service.take(id) else { return };
+    panel.preview = None;
+    if let Ok(GenerationOutput::Pixels(pixels)) = result {
+        let rgba = pixels.chunks(3).flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 255]).collect();
+        let image = images.add(Image::new(
+            Extent3d { width: PREVIEW_SIZE as u32, height: PREVIEW_SIZE as u32, depth_or_array_layers: 1 },
+            TextureDimension::D2,
+            rgba,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::RENDER_WORLD,
+        ));
+        for mut preview in previews.iter_mut() {
+            preview.texture = image.clone();
+        }
+    }
+}
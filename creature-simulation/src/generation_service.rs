@@ -0,0 +1,263 @@
+use bevy::prelude::*;
+use bevy::tasks::{AsyncComputeTaskPool, Task};
+use futures_lite::future;
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+use crate::errors::SimError;
+use crate::generation_constraints::GenerationConstraints;
+use crate::gpu_noise::GpuNoise;
+use crate::settings::Settings;
+use crate::thumbnails::{biome_pixels, load_or_render};
+use crate::world::{GenerationParams, ProgressCallback, Tile, WorldGenerator, WorldMap};
+use crate::world_stats::WorldReport;
+
+const MAX_RUNNING: usize = 2; // Jobs on the compute pool at once; a full world uses every core itself
+
+pub struct GenerationServicePlugin;
+
+impl Plugin for GenerationServicePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<GenerationService>()
+            .add_systems(PreUpdate, run_generation_service);
+    }
+}
+
+// === GENERATION SERVICE ===
+// All world generation work goes through one queue: the world being loaded, scratch worlds for
+// comparison, the generation panel's preview, seed thumbnails and single chunks. A system asks for
+// a job with a priority and gets an id back; the service starts the most urgent jobs first, at
+// most MAX_RUNNING at a time, and keeps each result until it is taken by id. Cancelling a job
+// drops it from the queue or stops a running world at its next chunk, and its result becomes
+// SimError::Cancelled; discarding one cancels it and throws away whatever it still produces.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum GenerationPriority {
+    Background, // Thumbnails and scratch worlds
+    Normal, // Something the user is looking at, like a preview
+    Urgent, // The world being loaded
+}
+
+#[derive(Clone, Debug)]
+pub enum GenerationJob {
+    // A full world and its report, retried with the next seed until the constraints pass
+    World { seed: u32, params: GenerationParams, constraints: GenerationConstraints, chunk_cache_mb: u32 },
+    Preview { seed: u32, params: GenerationParams, size: usize }, // Biome colours sampled from the noise
+    Thumbnail { seed: u32, params: GenerationParams }, // A preview cached on disk (see thumbnails.rs)
+    Chunk { seed: u32, params: GenerationParams, chunk: (i32, i32) }, // The tiles of one render chunk
+}
+
+impl GenerationJob {
+    // The world the settings ask for
+    pub fn world(settings: &Settings, seed: u32) -> Self {
+        Self::World {
+            seed,
+            params: settings.generation,
+            constraints: settings.generation_constraints.clone(),
+            chunk_cache_mb: settings.chunk_cache_mb,
+        }
+    }
+
+    // A world with the given parameters and no constraints, generated in full
+    pub fn plain_world(seed: u32, params: GenerationParams) -> Self {
+        Self::World {
+            seed,
+            params,
+            constraints: GenerationConstraints::default(),
+            chunk_cache_mb: 0,
+        }
+    }
+}
+
+pub enum GenerationOutput {
+    World(Box<(WorldMap, WorldReport)>),
+    Pixels(Vec<u8>), // RGB rows, top to bottom
+    Chunk(Vec<(usize, usize, Tile)>),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct GenerationId(u64);
+
+type JobResult = Result<GenerationOutput, SimError>;
+
+struct QueuedJob {
+    id: GenerationId,
+    job: GenerationJob,
+    priority: GenerationPriority,
+}
+
+struct RunningJob {
+    id: GenerationId,
+    task: Task<JobResult>,
+    progress: Arc<Mutex<(f32, String)>>, // Share done and the localization key of the stage
+    cancel: Arc<AtomicBool>, // Checked between chunks by the world generator
+}
+
+#[derive(Resource, Default)]
+pub struct GenerationService {
+    queue: Vec<QueuedJob>,
+    running: Vec<RunningJob>,
+    finished: HashMap<GenerationId, JobResult>,
+    discarded: HashSet<GenerationId>, // Running jobs nobody will take the result of
+    next_id: u64,
+    gpu_noise: Option<Arc<GpuNoise>>, // Shared by every job's generator when the GPU can run it (see gpu_noise.rs)
+}
+
+impl GenerationService {
+    pub fn request(&mut self, job: GenerationJob, priority: GenerationPriority) -> GenerationId {
+        let id = GenerationId(self.next_id);
+        self.next_id += 1;
+        debug!(?id, ?job, ?priority, "Generation job queued");
+        self.queue.push(QueuedJob { id, job, priority });
+        id
+    }
+
+    pub fn cancel(&mut self, id: GenerationId) {
+        if let Some(index) = self.queue.iter().position(|queued| queued.id == id) {
+            self.queue.remove(index);
+            self.finished.insert(id, Err(SimError::Cancelled));
+        } else if let Some(running) = self.running.iter().find(|running| running.id == id) {
+            running.cancel.store(true, Ordering::Relaxed);
+        }
+    }
+
+    // For requests that have been superseded
+    pub fn discard(&mut self, id: GenerationId) {
+        self.cancel(id);
+        if self.finished.remove(&id).is_none() && self.is_pending(id) {
+            self.discarded.insert(id);
+        }
+    }
+
+    // Queued or running, not yet finished
+    pub fn is_pending(&self, id: GenerationId) -> bool {
+        self.queue.iter().any(|queued| queued.id == id) || self.running.iter().any(|running| running.id == id)
+    }
+
+    // (share done, localization key of the stage) of a running job
+    pub fn progress(&self, id: GenerationId) -> Option<(f32, String)> {
+        let running = self.running.iter().find(|running| running.id == id)?;
+        running.progress.lock().ok().map(|progress| progress.clone())
+    }
+
+    // The result of a finished job, handed out once
+    pub fn take(&mut self, id: GenerationId) -> Option<JobResult> {
+        self.finished.remove(&id)
+    }
+
+    // Jobs started from now on evaluate their noise on the GPU
+    pub fn set_gpu_noise(&mut self, gpu_noise: Arc<GpuNoise>) {
+        self.gpu_noise = Some(gpu_noise);
+    }
+}
+
+fn run_generation_service(mut service: ResMut<GenerationService>) {
+    let service = &mut *service;
+    let (finished, discarded) = (&mut service.finished, &mut service.discarded);
+    service.running.retain_mut(|running| {
+        let Some(result) = future::block_on(future::poll_once(&mut running.task)) else { return true };
+        if !discarded.remove(&running.id) {
+            finished.insert(running.id, result);
+        }
+        false
+    });
+
+    while service.running.len() < MAX_RUNNING {
+        // Most urgent first, oldest first among equals
+        let Some(index) = service.queue.iter().enumerate()
+            .max_by_key(|(_, queued)| (queued.priority, std::cmp::Reverse(queued.id.0)))
+            .map(|(index, _)| index)
+        else { break };
+        let QueuedJob { id, job, .. } = service.queue.remove(index);
+        let progress = Arc::new(Mutex::new((0.0, "loading.initializing".to_string())));
+        let cancel = Arc::new(AtomicBool::new(false));
+        let (job_progress, job_cancel) = (Arc::clone(&progress), Arc::clone(&cancel));
+        let gpu_noise = service.gpu_noise.clone();
+        let task = AsyncComputeTaskPool::get().spawn(async move { run_job(job, gpu_noise, job_progress, job_cancel) });
+        service.running.push(RunningJob { id, task, progress, cancel });
+    }
+}
+
+fn run_job(
+    job: GenerationJob,
+    gpu_noise: Option<Arc<GpuNoise>>,
+    progress: Arc<Mutex<(f32, String)>>,
+    cancel: Arc<AtomicBool>,
+) -> JobResult {
+    if cancel.load(Ordering::Relaxed) {
+        return Err(SimError::Cancelled);
+    }
+    let generator = |seed, params| WorldGenerator::new(Some(seed)).with_params(params).with_gpu_noise(gpu_noise.clone());
+    match job {
+        GenerationJob::World { seed, params, constraints, chunk_cache_mb } => {
+            generate_world(seed, params, &constraints, chunk_cache_mb, gpu_noise.clone(), progress, cancel)
+                .map(|generated| GenerationOutput::World(Box::new(generated)))
+        }
+        GenerationJob::Preview { seed, params, size } => {
+            Ok(GenerationOutput::Pixels(biome_pixels(&generator(seed, params), size)))
+        }
+        GenerationJob::Thumbnail { seed, params } => Ok(GenerationOutput::Pixels(load_or_render(&generator(seed, params)))),
+        GenerationJob::Chunk { seed, params, chunk } => {
+            Ok(GenerationOutput::Chunk(generator(seed, params).generate_chunk(chunk.0, chunk.1)))
+        }
+    }
+}
+
+// Regenerates with the next seed until the constraints pass or retries run out
+fn generate_world(
+    base_seed: u32,
+    params: GenerationParams,
+    constraints: &GenerationConstraints,
+    chunk_cache_mb: u32,
+    gpu_noise: Option<Arc<GpuNoise>>,
+    progress: Arc<Mutex<(f32, String)>>,
+    cancel: Arc<AtomicBool>,
+) -> Result<(WorldMap, WorldReport), SimError> {
+    let mut constraint_failures = Vec::new();
+    let mut attempt = 0;
+
+    loop {
+        let seed = base_seed.wrapping_add(attempt);
+        let generation = info_span!("world_generation", seed, attempt);
+        let result = generation.in_scope(|| {
+            let generator = WorldGenerator::new(Some(seed))
+                .with_params(params)
+                .with_chunk_cache(chunk_cache_mb)
+                .with_gpu_noise(gpu_noise.clone());
+            let map_gen_start = Instant::now();
+
+            // Called from the generator's worker threads, outside the span
+            let tracker = Arc::clone(&progress);
+            let progress_callback: ProgressCallback = Box::new(move |done: f32, stage: &str| {
+                if let Ok(mut tracker) = tracker.lock() {
+                    *tracker = (done, stage.to_string());
+                    trace!(progress = done, stage, elapsed = ?map_gen_start.elapsed(), "World generation progress");
+                }
+            });
+
+            let world_map = generator.generate_world_with_progress(Some(progress_callback), Some(Arc::clone(&cancel)))?;
+            info!(elapsed = ?map_gen_start.elapsed(), "World map generated");
+
+            let report_start = Instant::now();
+            let report = WorldReport::from_world_map(&world_map);
+            debug!(elapsed = ?report_start.elapsed(), "World report built");
+            Ok((world_map, report))
+        });
+        let (world_map, mut report) = result?;
+
+        let violations = if constraints.is_empty() { Vec::new() } else { constraints.check(&world_map, &report) };
+        if violations.is_empty() || attempt >= constraints.max_retries {
+            if !violations.is_empty() {
+                warn!("Generation constraints still unmet after {} retries, keeping seed {}", attempt, seed);
+                constraint_failures.push(format!("seed {}: {} (retries exhausted, kept)", seed, violations.join(", ")));
+            }
+            report.constraint_failures = constraint_failures;
+            return Ok((world_map, report));
+        }
+
+        info!("🌍 Seed {} rejected by generation constraints: {}", seed, violations.join(", "));
+        constraint_failures.push(format!("seed {}: {}", seed, violations.join(", ")));
+        attempt += 1;
+    }
+}
@@ -0,0 +1,124 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use crate::keybindings::{Action, Controls};
+use crate::save::SaveViewExt;
+use crate::creature::Creature;
+use crate::species::{Species, SpeciesRegistry};
+use crate::text_input::text_input_inactive;
+
+const FOUNDER_TINT: f32 = 0.08; // Max per-channel color offset of the initial population
+const FOUNDER_SIZE: f32 = 0.15; // Max relative size deviation of the initial population
+const MUTATION_TINT: f32 = 0.03;
+const MUTATION_SIZE: f32 = 0.04;
+const FOUNDER_BOLDNESS: f32 = 0.2; // Max deviation from an even temperament in the initial population
+const MUTATION_BOLDNESS: f32 = 0.05;
+const LINEAGE_REGION: usize = 32; // Founders spawned in the same region share a lineage
+
+pub struct GeneticsPlugin;
+
+impl Plugin for GeneticsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<CreatureColorMode>()
+            .save_view_resource::<CreatureColorMode>("color_mode")
+            .add_systems(Update, (
+                toggle_color_mode.run_if(text_input_inactive),
+                apply_creature_colors,
+            ).chain());
+    }
+}
+
+// Heritable traits; offspring get a slightly mutated copy of their parent's genome
+#[derive(Component, Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Genome {
+    pub tint: (f32, f32, f32), // Per-channel offset added to the species color
+    pub size: f32, // Multiplier on the species size
+    pub lineage: u32, // Ancestry cluster, inherited unchanged
+    #[serde(default = "even_temperament")]
+    pub boldness: f32, // 0.0 = cautious, 1.0 = bold; where learned behavior starts and fades back to (see learning.rs)
+}
+
+fn even_temperament() -> f32 {
+    0.5
+}
+
+impl Genome {
+    pub fn founder(rng: &mut impl Rng, lineage: u32) -> Self {
+        let mut offset = || rng.gen_range(-FOUNDER_TINT..=FOUNDER_TINT);
+        let tint = (offset(), offset(), offset());
+        let size = 1.0 + rng.gen_range(-FOUNDER_SIZE..=FOUNDER_SIZE);
+        Self { tint, size, lineage, boldness: 0.5 + rng.gen_range(-FOUNDER_BOLDNESS..=FOUNDER_BOLDNESS) }
+    }
+
+    pub fn inherit(&self, rng: &mut impl Rng) -> Self {
+        let mut drift = |value: f32| (value + rng.gen_range(-MUTATION_TINT..=MUTATION_TINT)).clamp(-0.5, 0.5);
+        let tint = (drift(self.tint.0), drift(self.tint.1), drift(self.tint.2));
+        let size = (self.size * (1.0 + rng.gen_range(-MUTATION_SIZE..=MUTATION_SIZE))).clamp(0.5, 1.6);
+        let boldness = (self.boldness + rng.gen_range(-MUTATION_BOLDNESS..=MUTATION_BOLDNESS)).clamp(0.0, 1.0);
+        Self { tint, size, lineage: self.lineage, boldness }
+    }
+
+    // How far apart two genomes' traits are; lineage does not count (see speciation.rs)
+    pub fn distance(&self, other: &Genome) -> f32 {
+        let tint = Vec3::new(self.tint.0 - other.tint.0, self.tint.1 - other.tint.1, self.tint.2 - other.tint.2);
+        let size = (self.size / other.size).ln();
+        let boldness = self.boldness - other.boldness;
+        (tint.length_squared() + size * size + boldness * boldness).sqrt()
+    }
+
+    pub fn color(&self, species: &Species) -> Color {
+        let base = species.get_color().to_srgba();
+        Color::srgb(
+            (base.red + self.tint.0).clamp(0.0, 1.0),
+            (base.green + self.tint.1).clamp(0.0, 1.0),
+            (base.blue + self.tint.2).clamp(0.0, 1.0),
+        )
+    }
+}
+
+// Lineage of a founder spawned on the given tile
+pub fn founder_lineage(species_id: usize, x: usize, y: usize) -> u32 {
+    let region = (x / LINEAGE_REGION) * 1024 + y / LINEAGE_REGION;
+    (region * 16 + species_id) as u32
+}
+
+// Spread lineage hues around the color wheel by the golden angle so neighbours differ
+fn lineage_color(lineage: u32) -> Color {
+    Color::hsl((lineage as f32 * 137.508) % 360.0, 0.7, 0.55)
+}
+
+// === COLOR MODES ===
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CreatureColorMode {
+    #[default]
+    Individual, // Species color plus each creature's heritable tint
+    Lineage, // One flat color per ancestry cluster
+}
+
+fn toggle_color_mode(controls: Controls, mut mode: ResMut<CreatureColorMode>) {
+    if controls.just_pressed(Action::ToggleLineageColors) {
+        *mode = match *mode {
+            CreatureColorMode::Individual => CreatureColorMode::Lineage,
+            CreatureColorMode::Lineage => CreatureColorMode::Individual,
+        };
+        info!("🧬 Creature colors by {}", if *mode == CreatureColorMode::Lineage { "lineage" } else { "individual" });
+    }
+}
+
+// Recolors everything when the mode changes, otherwise only newly spawned creatures
+fn apply_creature_colors(
+    mode: Res<CreatureColorMode>,
+    registry: Res<SpeciesRegistry>,
+    mut creatures: Query<(Ref<Genome>, &Creature, &mut Sprite)>,
+) {
+    for (genome, creature, mut sprite) in creatures.iter_mut() {
+        if !mode.is_changed() && !genome.is_added() {
+            continue;
+        }
+        sprite.color = match *mode {
+            CreatureColorMode::Individual => genome.color(registry.get(creature.species)),
+            CreatureColorMode::Lineage => lineage_color(genome.lineage),
+        };
+    }
+}
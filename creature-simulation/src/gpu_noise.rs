@@ -0,0 +1,207 @@
+use bevy::prelude::*;
+use bevy::render::render_resource::{
+    BindGroupEntry, BindGroupLayout, BindGroupLayoutEntry, BindingType, BufferBindingType, BufferDescriptor,
+    BufferInitDescriptor, BufferUsages, CommandEncoderDescriptor, ComputePassDescriptor, ComputePipeline,
+    Maintain, MapMode, PipelineCompilationOptions, PipelineLayoutDescriptor, RawComputePipelineDescriptor,
+    ShaderModuleDescriptor, ShaderSource, ShaderStages,
+};
+use bevy::render::renderer::{RenderDevice, RenderQueue};
+use futures_lite::future;
+use noise::permutationtable::{NoiseHasher, PermutationTable};
+use std::borrow::Cow;
+use std::sync::{mpsc, Arc};
+use crate::generation_service::GenerationService;
+use crate::settings::Settings;
+use crate::world::{GenerationParams, Topology, SEA_LEVEL, WORLD_SIZE};
+
+const SHADER_SOURCE: &str = include_str!("../assets/shaders/gpu_noise.wgsl");
+const WORKGROUP_SIZE: u32 = 64; // @workgroup_size in the shader
+
+pub struct GpuNoisePlugin;
+
+impl Plugin for GpuNoisePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, init_gpu_noise);
+    }
+}
+
+// === GPU NOISE BACKEND ===
+// Evaluates the elevation, temperature and moisture noise of world tiles in a compute shader
+// (assets/shaders/gpu_noise.wgsl) instead of on the worker threads. The shader is a port of
+// WorldGenerator::sample_climate and of the noise crate's Perlin noise, fed the same permutation
+// tables, so it makes the same worlds up to f32 rounding: a tile right on a biome threshold can
+// come out differently than on the CPU, which is why the CPU path stays the reference and this
+// one is opt-in (Settings::gpu_noise): saves regenerate their world from the seed, so a world made
+// on the GPU can differ on those tiles when loaded with the CPU path or on another machine.
+// Without compute shader support, as on WebGL, or when a dispatch fails, generation falls back to
+// the CPU.
+pub struct GpuNoise {
+    device: RenderDevice,
+    queue: RenderQueue,
+    pipeline: ComputePipeline,
+    layout: BindGroupLayout,
+}
+
+// (elevation, temperature, moisture) of a tile, as WorldGenerator::sample_climate returns them
+pub type Climate = (f32, f32, f32);
+
+impl GpuNoise {
+    // None when the adapter cannot run the shader
+    pub fn new(device: &RenderDevice, queue: &RenderQueue) -> Option<Self> {
+        let limits = device.limits();
+        if limits.max_compute_workgroup_size_x < WORKGROUP_SIZE || limits.max_storage_buffers_per_shader_stage < 3 {
+            return None;
+        }
+
+        let wgpu_device = device.wgpu_device();
+        wgpu_device.push_error_scope(wgpu::ErrorFilter::Validation);
+        let shader = device.create_shader_module(ShaderModuleDescriptor {
+            label: Some("gpu_noise_shader"),
+            source: ShaderSource::Wgsl(Cow::Borrowed(SHADER_SOURCE)),
+        });
+        let storage = |read_only| BindingType::Buffer {
+            ty: BufferBindingType::Storage { read_only },
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        };
+        let entries = [
+            BindingType::Buffer { ty: BufferBindingType::Uniform, has_dynamic_offset: false, min_binding_size: None },
+            storage(true),
+            storage(true),
+            storage(false),
+        ].map(|ty| BindGroupLayoutEntry { binding: 0, visibility: ShaderStages::COMPUTE, ty, count: None });
+        let entries: Vec<_> = entries.into_iter().enumerate()
+            .map(|(binding, entry)| BindGroupLayoutEntry { binding: binding as u32, ..entry })
+            .collect();
+        let layout = device.create_bind_group_layout("gpu_noise_layout", &entries);
+        let pipeline_layout = device.create_pipeline_layout(&PipelineLayoutDescriptor {
+            label: Some("gpu_noise_pipeline_layout"),
+            bind_group_layouts: &[&layout],
+            push_constant_ranges: &[],
+        });
+        let pipeline = device.create_compute_pipeline(&RawComputePipelineDescriptor {
+            label: Some("gpu_noise_pipeline"),
+            layout: Some(&pipeline_layout),
+            module: &shader,
+            entry_point: "main",
+            compilation_options: PipelineCompilationOptions::default(),
+        });
+        if let Some(err) = future::block_on(wgpu_device.pop_error_scope()) {
+            warn!("GPU noise shader rejected, generating on the CPU: {}", err);
+            return None;
+        }
+
+        Some(Self { device: device.clone(), queue: queue.clone(), pipeline, layout })
+    }
+
+    // Climate of the given tiles, in order
+    pub fn sample_climate(&self, seed: u32, params: &GenerationParams, tiles: &[(usize, usize)]) -> Result<Vec<Climate>, String> {
+        if tiles.is_empty() {
+            return Ok(Vec::new());
+        }
+        let workgroups = (tiles.len() as u32).div_ceil(WORKGROUP_SIZE);
+        let limits = self.device.limits();
+        let output_size = (tiles.len() * 3 * 4) as u64;
+        if workgroups > limits.max_compute_workgroups_per_dimension || output_size > limits.max_storage_buffer_binding_size as u64 {
+            return Err(format!("{} tiles are too many for one GPU noise dispatch", tiles.len()));
+        }
+
+        let topology: u32 = match params.topology {
+            Topology::Flat => 0,
+            Topology::Cylinder => 1,
+            Topology::Torus => 2,
+        };
+        let mut uniform = Vec::with_capacity(32);
+        for value in [params.elevation_scale, params.temperature_scale, params.moisture_scale, SEA_LEVEL - params.sea_level, WORLD_SIZE as f32] {
+            uniform.extend(value.to_le_bytes());
+        }
+        for value in [topology, tiles.len() as u32, 0] {
+            uniform.extend(value.to_le_bytes());
+        }
+        // Same seeds as WorldGenerator::new; hashing a single index reads the table entry
+        let permutations: Vec<u8> = [seed, seed.wrapping_add(1), seed.wrapping_add(2)].into_iter()
+            .flat_map(|seed| {
+                let table = PermutationTable::new(seed);
+                (0..256).map(move |index| table.hash(&[index]) as u32)
+            })
+            .flat_map(u32::to_le_bytes)
+            .collect();
+        let positions: Vec<u8> = tiles.iter()
+            .flat_map(|&(x, y)| [x as f32, y as f32])
+            .flat_map(f32::to_le_bytes)
+            .collect();
+
+        let buffer = |label, contents: &[u8], usage| {
+            self.device.create_buffer_with_data(&BufferInitDescriptor { label: Some(label), contents, usage })
+        };
+        let uniform = buffer("gpu_noise_params", &uniform, BufferUsages::UNIFORM);
+        let permutations = buffer("gpu_noise_permutations", &permutations, BufferUsages::STORAGE);
+        let positions = buffer("gpu_noise_tiles", &positions, BufferUsages::STORAGE);
+        let output = self.device.create_buffer(&BufferDescriptor {
+            label: Some("gpu_noise_climate"),
+            size: output_size,
+            usage: BufferUsages::STORAGE | BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let readback = self.device.create_buffer(&BufferDescriptor {
+            label: Some("gpu_noise_readback"),
+            size: output_size,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let entries: Vec<_> = [&uniform, &permutations, &positions, &output].into_iter().enumerate()
+            .map(|(binding, buffer)| BindGroupEntry { binding: binding as u32, resource: buffer.as_entire_binding() })
+            .collect();
+        let bind_group = self.device.create_bind_group("gpu_noise_bind_group", &self.layout, &entries);
+
+        let mut encoder = self.device.create_command_encoder(&CommandEncoderDescriptor { label: Some("gpu_noise") });
+        {
+            let mut pass = encoder.begin_compute_pass(&ComputePassDescriptor { label: Some("gpu_noise"), timestamp_writes: None });
+            pass.set_pipeline(&self.pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(workgroups, 1, 1);
+        }
+        encoder.copy_buffer_to_buffer(&output, 0, &readback, 0, output_size);
+        let submission = self.queue.submit([encoder.finish()]);
+
+        let slice = readback.slice(..);
+        let (sender, receiver) = mpsc::channel();
+        slice.map_async(MapMode::Read, move |mapped| {
+            let _ = sender.send(mapped);
+        });
+        self.device.poll(Maintain::WaitForSubmissionIndex(submission));
+        receiver.recv()
+            .map_err(|err| err.to_string())
+            .and_then(|mapped| mapped.map_err(|err| err.to_string()))
+            .map_err(|err| format!("GPU noise readback failed: {}", err))?;
+
+        let climate = slice.get_mapped_range().chunks_exact(12)
+            .map(|values| {
+                let value = |index: usize| f32::from_le_bytes(values[index * 4..index * 4 + 4].try_into().unwrap());
+                (value(0), value(1), value(2))
+            })
+            .collect();
+        readback.unmap();
+        Ok(climate)
+    }
+}
+
+// Hands the backend to the generation service, which passes it to the generators it runs
+fn init_gpu_noise(
+    settings: Res<Settings>,
+    device: Option<Res<RenderDevice>>,
+    queue: Option<Res<RenderQueue>>,
+    mut service: ResMut<GenerationService>,
+) {
+    if !settings.gpu_noise {
+        return;
+    }
+    let (Some(device), Some(queue)) = (device, queue) else { return };
+    match GpuNoise::new(&device, &queue) {
+        Some(gpu_noise) => {
+            info!("🎛️ World noise is evaluated on the GPU");
+            service.set_gpu_noise(Arc::new(gpu_noise));
+        }
+        None => info!("GPU compute is unavailable, world noise is evaluated on the CPU"),
+    }
+}
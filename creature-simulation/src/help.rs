@@ -0,0 +1,106 @@
+use bevy::prelude::*;
+use crate::keybindings::{Action, ActionGroup, Controls, Keybindings};
+use crate::localization::Localization;
+use crate::text_input::text_input_inactive;
+
+const HEADING_COLOR: Color = Color::srgb(0.95, 0.85, 0.5);
+const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+
+pub struct HelpPlugin;
+
+impl Plugin for HelpPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, toggle_help_overlay.run_if(text_input_inactive));
+    }
+}
+
+// === HELP OVERLAY ===
+// F1 shows every keybinding, grouped like the features they belong to, plus the mouse controls.
+// The key column comes from the Keybindings table, so it always matches what the systems react
+// to; the overlay is rebuilt each time it opens so it follows language changes.
+#[derive(Component)]
+pub struct HelpOverlay;
+
+fn toggle_help_overlay(
+    mut commands: Commands,
+    controls: Controls,
+    bindings: Res<Keybindings>,
+    localization: Res<Localization>,
+    overlays: Query<Entity, With<HelpOverlay>>,
+) {
+    if !controls.just_pressed(Action::ToggleHelp) {
+        return;
+    }
+    if !overlays.is_empty() {
+        for entity in overlays.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+    spawn_help_overlay(&mut commands, &bindings, &localization);
+}
+
+fn text_style(font_size: f32, color: Color) -> TextStyle {
+    TextStyle { font_size, color, ..default() }
+}
+
+// "Heading\nkeys – description\n..." as text sections, so headings can be styled apart
+fn group_sections(group: ActionGroup, bindings: &Keybindings, localization: &Localization) -> Vec<TextSection> {
+    let mut sections = vec![TextSection::new(
+        format!("{}\n", localization.get(group.label_key())),
+        text_style(18.0, HEADING_COLOR),
+    )];
+    for action in Action::ALL.into_iter().filter(|action| action.group() == group) {
+        sections.push(TextSection::new(
+            format!("{}  –  {}\n", bindings.label(action), localization.get(action.label_key())),
+            text_style(14.0, TEXT_COLOR),
+        ));
+    }
+    sections
+}
+
+fn spawn_help_overlay(commands: &mut Commands, bindings: &Keybindings, localization: &Localization) {
+    let mouse = localization.list("help.mouse").join("\n");
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(6.0),
+                left: Val::Percent(10.0),
+                width: Val::Percent(80.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(16.0)),
+                row_gap: Val::Px(10.0),
+                ..default()
+            },
+            background_color: Color::srgba(0.05, 0.05, 0.1, 0.92).into(),
+            z_index: ZIndex::Global(50),
+            ..default()
+        },
+        HelpOverlay,
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section(localization.get("help.title"), text_style(26.0, TEXT_COLOR)));
+        parent.spawn(TextBundle::from_sections([
+            TextSection::new(format!("{}\n", localization.get("help.group.mouse")), text_style(18.0, HEADING_COLOR)),
+            TextSection::new(mouse, text_style(14.0, TEXT_COLOR)),
+        ]));
+        parent.spawn(NodeBundle {
+            style: Style {
+                flex_direction: FlexDirection::Row,
+                flex_wrap: FlexWrap::Wrap,
+                column_gap: Val::Px(32.0),
+                row_gap: Val::Px(12.0),
+                ..default()
+            },
+            ..default()
+        }).with_children(|columns| {
+            for group in ActionGroup::ALL {
+                columns.spawn(TextBundle::from_sections(group_sections(group, bindings, localization)));
+            }
+        });
+        parent.spawn(TextBundle::from_section(
+            localization.format("help.close", &[("key", bindings.label(Action::ToggleHelp))]),
+            text_style(13.0, Color::srgb(0.6, 0.6, 0.7)),
+        ));
+    });
+}
@@ -0,0 +1,169 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::ai_lod::{is_distant, AiTick};
+use crate::calendar::{local_season, Calendar, Season};
+use crate::coords::{tile_to_world, world_to_tile, TILE_SIZE};
+use crate::creature::{find_nearby_tile, move_creatures, Behavior, Creature, Needs, Surroundings, Wander, SEEK_THRESHOLD};
+use crate::optimization::LODLevel;
+use crate::predation::hunt_prey;
+use crate::save::SaveComponentExt;
+use crate::species::SpeciesRegistry;
+use crate::world::WorldMap;
+
+pub const TORPOR_METABOLISM: f32 = 0.05; // Share of the normal hunger and thirst burn left in torpor
+const FATTEN_RATE: f32 = 0.01; // Fat put on per second of autumn feeding
+const FAT_BURN: f32 = 0.002; // Hunger per second paid for from fat
+const AROUSAL_COST: f32 = 0.25; // Fat used up by being woken
+const GROGGY_SECONDS: f32 = 30.0; // A woken creature cannot move or flee for this long
+
+pub struct HibernationPlugin;
+
+impl Plugin for HibernationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .save_component::<Hibernation>("hibernation")
+            .add_systems(Update, add_hibernation)
+            .add_systems(FixedUpdate, hibernate
+                .after(hunt_prey)
+                .before(move_creatures)
+                .run_if(resource_exists::<WorldMap>));
+    }
+}
+
+// === HIBERNATION ===
+// Species with `hibernates` feed up a fat reserve in autumn. In winter, wherever it is colder
+// than they are comfortable with, they walk to a den (a burrow where they stand for burrowers,
+// otherwise the nearest wooded tile) and fall into torpor: they stop moving, burn a fraction of
+// their usual hunger and thirst (see update_needs) and pay the hunger from their fat. When the
+// fat runs out they wake hungry and forage. Torpid creatures cannot flee; a hunter closing in
+// wakes them (see hunt_prey), which costs fat and leaves them groggy and still for a while.
+// Seasons follow the creature's half of the map.
+#[derive(Component, Default, Serialize, Deserialize)]
+pub struct Hibernation {
+    pub fat: f32, // Reserve 0.0..=1.0
+    pub torpid: bool,
+    #[serde(skip)]
+    pub den: Option<Vec2>, // Chosen again after loading
+    #[serde(skip)]
+    pub groggy: f32, // Seconds until a woken creature can move again
+}
+
+impl Hibernation {
+    // Torpid, or woken and still too groggy to move
+    pub fn is_dormant(hibernation: Option<&Hibernation>) -> bool {
+        hibernation.is_some_and(|hibernation| hibernation.torpid || hibernation.groggy > 0.0)
+    }
+
+    // Woken up by a disturbance, like a hunter closing in
+    pub fn disturb(&mut self) {
+        if self.torpid {
+            self.torpid = false;
+            self.fat = (self.fat - AROUSAL_COST).max(0.0);
+            self.groggy = GROGGY_SECONDS;
+        }
+    }
+}
+
+// Newly spawned or loaded creatures without the component yet
+type NewCreature = (Added<Creature>, Without<Hibernation>);
+
+fn add_hibernation(
+    mut commands: Commands,
+    registry: Res<SpeciesRegistry>,
+    creatures: Query<(Entity, &Creature), NewCreature>,
+) {
+    for (entity, creature) in creatures.iter() {
+        if registry.get(creature.species).hibernates {
+            commands.entity(entity).insert(Hibernation::default());
+        }
+    }
+}
+
+type HibernatorQueryData = (
+    Entity,
+    &'static Transform,
+    &'static Creature,
+    &'static mut Needs,
+    &'static mut Wander,
+    &'static mut Hibernation,
+    &'static LODLevel,
+);
+
+fn hibernate(
+    time: Res<Time>,
+    ai_tick: Res<AiTick>,
+    calendar: Res<Calendar>,
+    surroundings: Surroundings,
+    registry: Res<SpeciesRegistry>,
+    mut creatures: Query<HibernatorQueryData>,
+) {
+    for (entity, transform, creature, mut needs, mut wander, mut hibernation, lod) in creatures.iter_mut() {
+        let Some(dt) = ai_tick.time_step(entity, lod, time.delta_seconds()) else { continue };
+        let position = transform.translation.truncate();
+        let Some((x, y)) = world_to_tile(position) else { continue };
+        let species = registry.get(creature.species);
+
+        match local_season(calendar.day, y) {
+            Season::Autumn => {
+                // Distant creatures are assumed to find food in their habitat, as in update_needs
+                let feeding = if is_distant(lod) {
+                    species.habitats.contains(&surroundings.tile(x, y).biome)
+                } else {
+                    surroundings.has_food(x, y, species.diet)
+                };
+                if feeding && needs.hunger < SEEK_THRESHOLD {
+                    hibernation.fat = (hibernation.fat + FATTEN_RATE * dt).min(1.0);
+                }
+            }
+            Season::Winter if species.temperature_stress(surroundings.temperature(x, y)) > 0.0 || hibernation.torpid => {
+                if hibernation.groggy > 0.0 {
+                    hibernation.groggy = (hibernation.groggy - dt).max(0.0);
+                    wander.target = None;
+                    wander.behavior = Behavior::Hibernate;
+                    continue;
+                }
+                if hibernation.torpid {
+                    let paid = needs.hunger.min(hibernation.fat).min(FAT_BURN * dt);
+                    needs.hunger -= paid;
+                    hibernation.fat -= paid;
+                    if hibernation.fat <= 0.0 && needs.hunger > SEEK_THRESHOLD {
+                        hibernation.torpid = false; // Starving; out to forage
+                    } else {
+                        wander.target = None;
+                        wander.behavior = Behavior::Hibernate;
+                    }
+                    continue;
+                }
+                if hibernation.fat <= 0.0 && needs.hunger > SEEK_THRESHOLD {
+                    continue; // Foraging until it has eaten
+                }
+                let den = *hibernation.den.get_or_insert_with(|| {
+                    let tile = if species.burrower {
+                        (x, y)
+                    } else {
+                        find_nearby_tile(&surroundings, (x, y), species.movement, |x, y| surroundings.has_roost(x, y)).unwrap_or((x, y))
+                    };
+                    tile_to_world(tile.0, tile.1)
+                });
+                if position.distance(den) < TILE_SIZE {
+                    hibernation.torpid = true;
+                    wander.target = None;
+                    wander.behavior = Behavior::Hibernate;
+                } else {
+                    wander.target = Some(den);
+                    wander.behavior = Behavior::SeekDen;
+                    wander.search_cooldown = 2.0; // Keep the food/water scan from replacing the den
+                }
+            }
+            _ => {
+                // Awake; what is left of the reserve keeps hunger down until it is used up
+                hibernation.torpid = false;
+                hibernation.groggy = 0.0;
+                hibernation.den = None;
+                let paid = needs.hunger.min(hibernation.fat).min(FAT_BURN * dt);
+                needs.hunger -= paid;
+                hibernation.fat -= paid;
+            }
+        }
+    }
+}
@@ -0,0 +1,259 @@
+use bevy::prelude::*;
+use std::collections::VecDeque;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use crate::coords::tile_to_world;
+use crate::biome::BiomeType;
+use crate::events::NotableEvent;
+use crate::profiling::budgeted;
+use crate::simulation::SimClock;
+use crate::world::{WorldMap, WORLD_SIZE};
+
+pub const HYDROLOGY_CELL_TILES: usize = 8; // Each hydrology cell covers 8x8 tiles
+pub const HYDROLOGY_SIZE: usize = WORLD_SIZE / HYDROLOGY_CELL_TILES;
+pub const HYDROLOGY_TICK_SECONDS: f32 = 6.0; // Simulated seconds between hydrology ticks
+pub const DROUGHT_DAYS: u32 = 4; // Consecutive dry days before a drought is declared
+const RAIN_PER_TICK: f32 = 1.0; // Water added per tick at normal rainfall and average moisture
+const EVAPORATION: f32 = 0.1; // Fraction of stored water lost per tick
+const FLOW_FRACTION: f32 = 0.3; // Fraction of stored water passed downhill per tick
+const DRY_DAY_RAINFALL: f32 = 0.5; // Days below this rainfall count towards a drought
+const DRY_WATER_LEVEL: f32 = 0.25; // Below this relative level, rivers and lakes run dry
+pub const FORECAST_DAYS: usize = 3;
+
+pub struct HydrologyPlugin;
+
+impl Plugin for HydrologyPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, init_hydrology.run_if(resource_added::<WorldMap>))
+            .add_systems(FixedUpdate, (update_rainfall, budgeted(hydrology_tick, 2.0)).chain().run_if(resource_exists::<Hydrology>));
+    }
+}
+
+// === HYDROLOGY ===
+// Coarse water model on a grid of HYDROLOGY_CELL_TILES² cells: rain falls, water flows to the
+// lowest neighbouring cell and evaporates, and tile moisture follows each cell's water level.
+#[derive(Resource)]
+pub struct Hydrology {
+    pub water: Vec<f32>, // Stored water per cell, indexed [cx * HYDROLOGY_SIZE + cy]
+    pub expected: Vec<f32>, // Steady-state water per cell at normal rainfall, ignoring inflow
+    pub downhill: Vec<Option<usize>>, // Lowest neighbouring cell, if lower than this one
+    pub flow_order: Vec<usize>, // Land cells sorted from highest to lowest
+    pub is_ocean: Vec<bool>,
+    pub base_moisture: Vec<f32>, // Generated moisture per tile, indexed [x * WORLD_SIZE + y]
+    pub rainfall: f32, // Today's rainfall; 1.0 = normal
+    pub regional_rain: Vec<f32>, // Share of the rainfall falling on each cell, cut by extreme weather
+    pub forecast: VecDeque<f32>, // Rainfall of the next FORECAST_DAYS days, already rolled
+    pub dry_days: u32,
+    pub last_day: Option<u32>,
+    pub rng: StdRng,
+}
+
+impl Hydrology {
+    pub fn from_world_map(world_map: &WorldMap) -> Self {
+        let cells = HYDROLOGY_SIZE * HYDROLOGY_SIZE;
+        let mut elevation = vec![0.0; cells];
+        let mut moisture = vec![0.0; cells];
+        let mut ocean_tiles = vec![0usize; cells];
+        let mut base_moisture = vec![0.0; WORLD_SIZE * WORLD_SIZE];
+
+        for (x, column) in world_map.tiles.iter().enumerate() {
+            for (y, tile) in column.iter().enumerate() {
+                let cell = Self::cell_index(x, y);
+                elevation[cell] += tile.elevation;
+                moisture[cell] += tile.moisture;
+                if tile.biome == BiomeType::Ocean {
+                    ocean_tiles[cell] += 1;
+                }
+                base_moisture[x * WORLD_SIZE + y] = tile.moisture;
+            }
+        }
+
+        let tiles_per_cell = (HYDROLOGY_CELL_TILES * HYDROLOGY_CELL_TILES) as f32;
+        let is_ocean: Vec<bool> = ocean_tiles.iter().map(|&count| count as f32 > tiles_per_cell / 2.0).collect();
+
+        let mut downhill = vec![None; cells];
+        for cx in 0..HYDROLOGY_SIZE {
+            for cy in 0..HYDROLOGY_SIZE {
+                let cell = cx * HYDROLOGY_SIZE + cy;
+                let mut lowest = elevation[cell];
+                for (dx, dy) in [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)] {
+                    let (nx, ny) = (cx as i32 + dx, cy as i32 + dy);
+                    if nx < 0 || ny < 0 || nx >= HYDROLOGY_SIZE as i32 || ny >= HYDROLOGY_SIZE as i32 {
+                        continue;
+                    }
+                    let neighbour = nx as usize * HYDROLOGY_SIZE + ny as usize;
+                    if elevation[neighbour] < lowest {
+                        lowest = elevation[neighbour];
+                        downhill[cell] = Some(neighbour);
+                    }
+                }
+            }
+        }
+
+        let mut flow_order: Vec<usize> = (0..cells).filter(|&cell| !is_ocean[cell]).collect();
+        flow_order.sort_by(|&a, &b| elevation[b].total_cmp(&elevation[a]));
+
+        let expected: Vec<f32> = (0..cells).map(|cell| {
+            let outflow = if downhill[cell].is_some() { FLOW_FRACTION } else { 0.0 };
+            Self::rain_input(1.0, moisture[cell] / tiles_per_cell) / (EVAPORATION + outflow)
+        }).collect();
+
+        Self {
+            water: expected.clone(), // Start at equilibrium so the world does not flash dry or wet
+            expected,
+            downhill,
+            flow_order,
+            is_ocean,
+            base_moisture,
+            rainfall: 1.0,
+            regional_rain: vec![1.0; cells],
+            forecast: VecDeque::new(),
+            dry_days: 0,
+            last_day: None,
+            rng: StdRng::seed_from_u64(world_map.seed as u64 ^ 0x48_5944_524f),
+        }
+    }
+
+    pub fn cell_index(x: usize, y: usize) -> usize {
+        (x / HYDROLOGY_CELL_TILES) * HYDROLOGY_SIZE + y / HYDROLOGY_CELL_TILES
+    }
+
+    // Drier regions get less of each rainfall, wetter regions more
+    fn rain_input(rainfall: f32, cell_moisture: f32) -> f32 {
+        rainfall * RAIN_PER_TICK * (0.5 + cell_moisture)
+    }
+
+    // Water level relative to normal: below 1.0 is drier than usual, above is wetter
+    pub fn relative_water(&self, x: usize, y: usize) -> f32 {
+        let cell = Self::cell_index(x, y);
+        if self.is_ocean[cell] {
+            return 1.0;
+        }
+        self.water[cell] / self.expected[cell].max(f32::EPSILON)
+    }
+
+    // 0.0 = normal or wetter, 1.0 = completely parched
+    pub fn dryness(&self, x: usize, y: usize) -> f32 {
+        (1.0 - self.relative_water(x, y)).clamp(0.0, 1.0)
+    }
+
+    // Whether rivers and lakes at this tile still hold drinkable water
+    pub fn water_available(&self, x: usize, y: usize) -> bool {
+        self.relative_water(x, y) > DRY_WATER_LEVEL
+    }
+
+    pub fn in_drought(&self) -> bool {
+        self.dry_days >= DROUGHT_DAYS
+    }
+
+    // Rainfall actually reaching this tile for a given day's rainfall, like `rain_input` per tile
+    pub fn local_rainfall(&self, rainfall: f32, x: usize, y: usize) -> f32 {
+        rainfall * (0.5 + self.base_moisture[x * WORLD_SIZE + y])
+    }
+
+    // Rain reaching this tile today, after extreme weather
+    pub fn rainfall_here(&self, x: usize, y: usize) -> f32 {
+        self.local_rainfall(self.rainfall, x, y) * self.regional_rain[Self::cell_index(x, y)]
+    }
+
+    // Dry days tend to follow dry days, so droughts come in spells
+    fn roll_rainfall(&mut self, previous: f32) -> f32 {
+        let dry = self.rng.gen_bool(if previous < DRY_DAY_RAINFALL { 0.75 } else { 0.15 });
+        if dry {
+            self.rng.gen_range(0.0..DRY_DAY_RAINFALL)
+        } else {
+            self.rng.gen_range(DRY_DAY_RAINFALL..1.8)
+        }
+    }
+}
+
+fn init_hydrology(mut commands: Commands, world_map: Res<WorldMap>) {
+    commands.insert_resource(Hydrology::from_world_map(&world_map));
+}
+
+// Moves each day's rainfall out of the forecast, which is rolled FORECAST_DAYS ahead
+fn update_rainfall(clock: Res<SimClock>, mut hydrology: ResMut<Hydrology>, mut notable: EventWriter<NotableEvent>) {
+    let day = clock.day();
+    if hydrology.last_day == Some(day) {
+        return;
+    }
+    hydrology.last_day = Some(day);
+
+    while hydrology.forecast.len() <= FORECAST_DAYS {
+        let previous = hydrology.forecast.back().copied().unwrap_or(hydrology.rainfall);
+        let rainfall = hydrology.roll_rainfall(previous);
+        hydrology.forecast.push_back(rainfall);
+    }
+    hydrology.rainfall = hydrology.forecast.pop_front().unwrap_or(1.0);
+    let dry_today = hydrology.rainfall < DRY_DAY_RAINFALL;
+
+    let was_in_drought = hydrology.in_drought();
+    hydrology.dry_days = if dry_today { hydrology.dry_days + 1 } else { 0 };
+
+    if hydrology.in_drought() && !was_in_drought {
+        info!("☀️ Day {}: drought after {} dry days", day, hydrology.dry_days);
+        // Point the event at the driest land cell
+        let driest = hydrology.flow_order.iter().copied()
+            .min_by(|&a, &b| (hydrology.water[a] / hydrology.expected[a]).total_cmp(&(hydrology.water[b] / hydrology.expected[b])));
+        if let Some(cell) = driest {
+            let (cx, cy) = (cell / HYDROLOGY_SIZE, cell % HYDROLOGY_SIZE);
+            let centre = HYDROLOGY_CELL_TILES / 2;
+            notable.send(NotableEvent {
+                position: tile_to_world(cx * HYDROLOGY_CELL_TILES + centre, cy * HYDROLOGY_CELL_TILES + centre),
+                importance: 5.0,
+                label_key: "event.drought",
+            });
+        }
+    } else if was_in_drought && !hydrology.in_drought() {
+        info!("🌧️ Day {}: the drought has broken", day);
+    }
+}
+
+fn hydrology_tick(
+    time: Res<Time>,
+    mut tick_timer: Local<f32>,
+    mut hydrology: ResMut<Hydrology>,
+    mut world_map: ResMut<WorldMap>,
+) {
+    // Hydrology is only marked changed on real ticks, which is what triggers tile recoloring
+    *tick_timer += time.delta_seconds();
+    if *tick_timer < HYDROLOGY_TICK_SECONDS {
+        return;
+    }
+    *tick_timer = 0.0;
+
+    let hydrology = &mut *hydrology;
+
+    // Rain and evaporation. A cell's normal rain input is recovered from its steady state.
+    for cell in 0..hydrology.water.len() {
+        if hydrology.is_ocean[cell] {
+            hydrology.water[cell] = 0.0;
+            continue;
+        }
+        let outflow = if hydrology.downhill[cell].is_some() { FLOW_FRACTION } else { 0.0 };
+        let normal_input = hydrology.expected[cell] * (EVAPORATION + outflow);
+        hydrology.water[cell] = hydrology.water[cell] * (1.0 - EVAPORATION) + normal_input * hydrology.rainfall * hydrology.regional_rain[cell];
+    }
+
+    // Downhill flow, highest cells first so water can travel several cells in one tick
+    for &cell in &hydrology.flow_order {
+        if let Some(target) = hydrology.downhill[cell] {
+            let outflow = hydrology.water[cell] * FLOW_FRACTION;
+            hydrology.water[cell] -= outflow;
+            hydrology.water[target] += outflow;
+        }
+    }
+
+    // Tile moisture follows the water level; wetter-than-normal cells (valleys, river
+    // confluences) raise it, drought lowers it. Not a structural change, so skip change
+    // detection rather than trigger a full world re-render.
+    let world_map = world_map.bypass_change_detection();
+    for x in 0..WORLD_SIZE {
+        for y in 0..WORLD_SIZE {
+            let level = hydrology.relative_water(x, y).min(2.0);
+            let base = hydrology.base_moisture[x * WORLD_SIZE + y];
+            world_map.tiles[x][y].moisture = (base * (0.5 + 0.5 * level)).clamp(0.0, 1.0);
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+use crate::biome::BiomeType;
+use crate::calendar::{seasonal_temperature_offset, Calendar};
+use crate::render::WorldTile;
+use crate::tides::{recolor_shoreline, Tides};
+use crate::world::{WorldMap, WORLD_SIZE};
+
+const FREEZE_TEMPERATURE: f32 = 0.12; // Water colder than this (season included) freezes
+const MELT_TEMPERATURE: f32 = 0.16; // Above this it melts again; the gap keeps the edge from flickering
+const GROWTH_PER_DAY: usize = 4; // Tiles the ice reaches further out from the shore each day
+pub const ICE_SPEED: f32 = 0.6; // Walkers slow down on ice
+const ICE_COLOR: Color = Color::srgb(0.85, 0.92, 0.97);
+
+pub struct IcePlugin;
+
+impl Plugin for IcePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, (
+                init_ice_sheet.run_if(resource_added::<WorldMap>),
+                recolor_ice.after(recolor_shoreline).run_if(resource_exists::<IceSheet>),
+            ))
+            .add_systems(FixedUpdate, update_ice
+                .run_if(resource_exists::<IceSheet>)
+                .run_if(resource_changed::<Calendar>));
+    }
+}
+
+// === ICE SHEETS ===
+// Open water freezes in the cold season, starting at the shore and growing GROWTH_PER_DAY tiles
+// outward each day while it stays cold enough, so sheets form off polar coasts first, in
+// whichever half of the map is having its winter. Walkers can cross frozen tiles (slowly), which opens seasonal migration routes;
+// everything melts back in spring. Biomes are left alone, only the tile colors change.
+#[derive(Resource)]
+pub struct IceSheet {
+    pub frozen: Vec<bool>, // Indexed [x * WORLD_SIZE + y]
+    pub frozen_tiles: usize,
+}
+
+impl Default for IceSheet {
+    fn default() -> Self {
+        Self { frozen: vec![false; WORLD_SIZE * WORLD_SIZE], frozen_tiles: 0 }
+    }
+}
+
+impl IceSheet {
+    pub fn is_frozen(&self, x: usize, y: usize) -> bool {
+        self.frozen[x * WORLD_SIZE + y]
+    }
+
+    // Land or ice next to the tile, for the ice to grow out from
+    fn touches_solid(&self, world_map: &WorldMap, x: usize, y: usize) -> bool {
+        [(0, 1), (2, 1), (1, 0), (1, 2)].into_iter().any(|(dx, dy)| {
+            let (nx, ny) = ((x + dx).wrapping_sub(1), (y + dy).wrapping_sub(1));
+            nx < WORLD_SIZE && ny < WORLD_SIZE
+                && (world_map.tiles[nx][ny].biome != BiomeType::Ocean || self.is_frozen(nx, ny))
+        })
+    }
+}
+
+fn init_ice_sheet(mut commands: Commands) {
+    commands.insert_resource(IceSheet::default());
+}
+
+// Once per day: melt what warmed up, then grow from the shore where it is cold enough
+fn update_ice(calendar: Res<Calendar>, world_map: Res<WorldMap>, mut ice: ResMut<IceSheet>) {
+    let temperature = |x: usize, y: usize| world_map.tiles[x][y].temperature
+        + seasonal_temperature_offset(calendar.day, y, &world_map.params);
+    let before = ice.frozen_tiles;
+
+    for x in 0..WORLD_SIZE {
+        for y in 0..WORLD_SIZE {
+            if ice.is_frozen(x, y) && temperature(x, y) > MELT_TEMPERATURE {
+                ice.frozen[x * WORLD_SIZE + y] = false;
+                ice.frozen_tiles -= 1;
+            }
+        }
+    }
+    for _ in 0..GROWTH_PER_DAY {
+        let freezing: Vec<usize> = (0..WORLD_SIZE)
+            .flat_map(|x| (0..WORLD_SIZE).map(move |y| (x, y)))
+            .filter(|&(x, y)| world_map.tiles[x][y].biome == BiomeType::Ocean && !ice.is_frozen(x, y)
+                && temperature(x, y) < FREEZE_TEMPERATURE && ice.touches_solid(&world_map, x, y))
+            .map(|(x, y)| x * WORLD_SIZE + y)
+            .collect();
+        if freezing.is_empty() {
+            break;
+        }
+        ice.frozen_tiles += freezing.len();
+        for index in freezing {
+            ice.frozen[index] = true;
+        }
+    }
+
+    if ice.frozen_tiles != before {
+        debug!("🧊 Day {}: {} tiles of ice", calendar.day, ice.frozen_tiles);
+    }
+}
+
+// Frozen tiles turn white; melted ones get their water or tidal flat color back
+fn recolor_ice(
+    ice: Res<IceSheet>,
+    tides: Res<Tides>,
+    world_map: Res<WorldMap>,
+    mut tiles: Query<(Ref<WorldTile>, &mut Sprite)>,
+) {
+    let recolor_all = ice.is_changed();
+    for (tile_ref, mut sprite) in tiles.iter_mut() {
+        if !recolor_all && !tile_ref.is_added() {
+            continue;
+        }
+        let tile = &world_map.tiles[tile_ref.x][tile_ref.y];
+        if tile.biome != BiomeType::Ocean {
+            continue;
+        }
+        let color = if ice.is_frozen(tile_ref.x, tile_ref.y) { ICE_COLOR } else { tides.shore_color(tile) };
+        if sprite.color != color {
+            sprite.color = color;
+        }
+    }
+}
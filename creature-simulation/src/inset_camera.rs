@@ -0,0 +1,226 @@
+use bevy::input::mouse::{MouseScrollUnit, MouseWheel};
+use bevy::prelude::*;
+use bevy::render::camera::RenderTarget;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat, TextureUsages};
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::ui::RelativeCursorPosition;
+use crate::camera::{MAX_ZOOM, MIN_ZOOM, ZOOM_STEP};
+use crate::creature::Creature;
+use crate::creature_tracking::{display_name, Bookmarked, CreatureName, SelectedCreature};
+use crate::keybindings::{Action, Controls};
+use crate::localization::Localization;
+use crate::save::SaveViewExt;
+use crate::settings::Settings;
+use crate::species::SpeciesRegistry;
+use crate::text_input::text_input_inactive;
+
+const INSET_WIDTH: f32 = 0.3; // Share of the window's width; the inset is 4:3
+const INSET_RIGHT: f32 = 10.0; // Logical pixels from the window's right edge
+const INSET_BOTTOM: f32 = 60.0; // Clear of the auto camera caption
+const INSET_BORDER: f32 = 2.0;
+const INSET_ZOOM: f32 = 0.4; // Initial orthographic scale, closer in than the main view
+const FOLLOW_SPEED: f32 = 8.0; // Exponential approach rate towards the creature (per second)
+
+pub struct InsetCameraPlugin;
+
+impl Plugin for InsetCameraPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<InsetCamera>()
+            .save_view::<(bool, f32)>("inset_camera",
+                |world| world.get_resource::<InsetCamera>().map(|inset| (inset.enabled, inset.scale)),
+                |world, (enabled, scale)| {
+                    let mut inset = world.resource_mut::<InsetCamera>();
+                    inset.enabled = enabled;
+                    inset.scale = scale.clamp(MIN_ZOOM, MAX_ZOOM);
+                })
+            .add_systems(Startup, spawn_inset_camera)
+            .add_systems(Update, (
+                toggle_inset_camera.run_if(text_input_inactive),
+                layout_inset_camera.run_if(resource_exists::<InsetTexture>),
+                zoom_inset_camera,
+                follow_tracked_creature,
+            ).chain());
+    }
+}
+
+// === INSET CAMERA ===
+// Picture in picture: a second camera that follows the selected creature, or the first pinned
+// one, while the main camera goes wherever the user takes it. It renders into a texture shown in
+// a framed UI node in the bottom right corner, so panels and the caption draw over it like over
+// any other UI. The mouse wheel zooms the inset instead of the main view while over it.
+#[derive(Resource)]
+pub struct InsetCamera {
+    pub enabled: bool,
+    pub scale: f32,
+    pub hovered: bool, // Cursor over the inset; camera.rs leaves the wheel to it
+}
+
+impl Default for InsetCamera {
+    fn default() -> Self {
+        Self { enabled: false, scale: INSET_ZOOM, hovered: false }
+    }
+}
+
+#[derive(Component)]
+struct InsetView;
+
+// What the inset camera renders into, resized with the window
+#[derive(Resource)]
+struct InsetTexture(Handle<Image>);
+
+// Border and caption drawn over the inset by the UI
+#[derive(Component)]
+struct InsetFrame;
+
+#[derive(Component)]
+struct InsetCaption;
+
+fn spawn_inset_camera(mut commands: Commands, settings: Res<Settings>, mut images: ResMut<Assets<Image>>) {
+    let mut image = Image::new_fill(
+        Extent3d { width: 1, height: 1, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        TextureFormat::Bgra8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    image.texture_descriptor.usage |= TextureUsages::RENDER_ATTACHMENT;
+    let image = images.add(image);
+
+    let camera = Camera2dBundle::default();
+    commands.spawn((
+        Camera2dBundle {
+            camera: Camera { order: -1, is_active: false, target: RenderTarget::Image(image.clone()), ..default() },
+            transform: settings.view.orient_camera(camera.transform),
+            ..camera
+        },
+        InsetView,
+    ));
+    commands.spawn((
+        ImageBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                right: Val::Px(INSET_RIGHT),
+                bottom: Val::Px(INSET_BOTTOM),
+                border: UiRect::all(Val::Px(INSET_BORDER)),
+                padding: UiRect::all(Val::Px(4.0)),
+                align_items: AlignItems::FlexStart,
+                ..default()
+            },
+            image: UiImage::new(image.clone()),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        BorderColor(Color::srgb(0.9, 0.9, 0.9)),
+        RelativeCursorPosition::default(),
+        InsetFrame,
+    )).with_children(|frame| {
+        frame.spawn((
+            TextBundle::from_section("", TextStyle {
+                font_size: 14.0,
+                color: Color::srgb(0.9, 0.9, 0.9),
+                ..default()
+            }).with_background_color(Color::srgba(0.05, 0.05, 0.1, 0.8)),
+            InsetCaption,
+        ));
+    });
+    commands.insert_resource(InsetTexture(image));
+}
+
+fn toggle_inset_camera(controls: Controls, mut inset: ResMut<InsetCamera>) {
+    if controls.just_pressed(Action::ToggleInsetCamera) {
+        inset.enabled = !inset.enabled;
+        info!("📺 Inset camera {}", if inset.enabled { "on" } else { "off" });
+    }
+}
+
+// Sizes the frame to the window, which may have been resized, and the texture to match it
+fn layout_inset_camera(
+    windows: Query<&Window>,
+    texture: Res<InsetTexture>,
+    mut images: ResMut<Assets<Image>>,
+    mut inset: ResMut<InsetCamera>,
+    mut frames: Query<(&mut Style, &mut Visibility, &RelativeCursorPosition), With<InsetFrame>>,
+) {
+    let Ok(window) = windows.get_single() else { return };
+    let size = Vec2::new(window.width() * INSET_WIDTH, window.width() * INSET_WIDTH * 0.75);
+    // The texture is in physical pixels, the UI in logical ones
+    let physical = (size * window.scale_factor()).as_uvec2().max(UVec2::ONE);
+    if images.get(&texture.0).is_some_and(|image| image.size() != physical) {
+        if let Some(image) = images.get_mut(&texture.0) {
+            image.resize(Extent3d { width: physical.x, height: physical.y, depth_or_array_layers: 1 });
+        }
+    }
+
+    for (mut style, mut visibility, cursor) in frames.iter_mut() {
+        if style.width != Val::Px(size.x) || style.height != Val::Px(size.y) {
+            style.width = Val::Px(size.x);
+            style.height = Val::Px(size.y);
+        }
+        let shown = if inset.enabled { Visibility::Visible } else { Visibility::Hidden };
+        if *visibility != shown {
+            *visibility = shown;
+        }
+        let hovered = inset.enabled && cursor.mouse_over();
+        if inset.hovered != hovered {
+            inset.hovered = hovered;
+        }
+    }
+}
+
+fn zoom_inset_camera(mut wheel: EventReader<MouseWheel>, mut inset: ResMut<InsetCamera>) {
+    let notches: f32 = wheel.read()
+        .map(|event| match event.unit {
+            MouseScrollUnit::Line => event.y,
+            MouseScrollUnit::Pixel => event.y / 40.0,
+        })
+        .sum();
+    if inset.hovered && notches != 0.0 {
+        inset.scale = (inset.scale * ZOOM_STEP.powf(-notches)).clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+}
+
+type FollowedQueryData = (Entity, &'static Transform, &'static Creature, Option<&'static CreatureName>, Has<Bookmarked>);
+
+// Without a creature to follow the inset only shows how to pick one
+fn follow_tracked_creature(
+    time: Res<Time>,
+    (settings, localization, registry): (Res<Settings>, Res<Localization>, Res<SpeciesRegistry>),
+    (inset, selected): (Res<InsetCamera>, Res<SelectedCreature>),
+    creatures: Query<FollowedQueryData, Without<InsetView>>,
+    mut cameras: Query<(&mut Camera, &mut Transform, &mut OrthographicProjection), With<InsetView>>,
+    mut captions: Query<&mut Text, With<InsetCaption>>,
+) {
+    let followed = selected.0.and_then(|entity| creatures.get(entity).ok())
+        .or_else(|| creatures.iter().filter(|(.., bookmarked)| *bookmarked).min_by_key(|(entity, ..)| *entity));
+
+    let Ok((mut camera, mut transform, mut projection)) = cameras.get_single_mut() else { return };
+    let active = inset.enabled && followed.is_some();
+    if camera.is_active != active {
+        camera.is_active = active;
+    }
+    if let Ok(mut caption) = captions.get_single_mut() {
+        let value = match followed {
+            Some((entity, _, creature, name, _)) => display_name(name, &registry.get(creature.species).name, entity),
+            None => localization.get("inset.none").to_string(),
+        };
+        if caption.sections[0].value != value {
+            caption.sections[0].value = value;
+        }
+    }
+    let Some((_, creature_transform, ..)) = followed.filter(|_| inset.enabled) else { return };
+
+    let target = creature_transform.translation.truncate();
+    let current = transform.translation.truncate();
+    // Snaps onto a newly followed creature rather than sweeping across the map
+    let next = if settings.smooth_camera && !settings.reduced_motion && current.distance(target) < 200.0 * inset.scale {
+        current.lerp(target, 1.0 - (-FOLLOW_SPEED * time.delta_seconds()).exp())
+    } else {
+        target
+    };
+    transform.translation.x = next.x;
+    transform.translation.y = next.y;
+    if projection.scale != inset.scale {
+        projection.scale = inset.scale;
+    }
+}
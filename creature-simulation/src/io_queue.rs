@@ -0,0 +1,130 @@
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use bevy::tasks::{IoTaskPool, Task};
+use futures_lite::future;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::errors::{RetryAction, SimError, SimFailure};
+
+pub struct IoQueuePlugin;
+
+impl Plugin for IoQueuePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<IoQueue>()
+            .add_systems(Last, poll_io_writes);
+    }
+}
+
+// === BACKGROUND WRITES ===
+// Save files are serialized and written on the IO task pool so saving never stalls a frame. The
+// caller builds an owned snapshot on the main thread; the task serializes that copy, writes it
+// next to the target and renames it over, so a reader only ever sees a whole file. Per path one
+// write runs and at most one waits behind it: a newer snapshot replaces the waiting one, as only
+// the latest matters. On exit the queue is drained on the main thread, so nothing queued is lost.
+#[derive(Resource, Default)]
+pub struct IoQueue {
+    paths: HashMap<PathBuf, PathWrites>,
+}
+
+#[derive(Default)]
+struct PathWrites {
+    running: Option<(Task<Result<(), SimError>>, WriteDone)>,
+    waiting: Option<(WriteJob, WriteDone)>,
+}
+
+type WriteJob = Box<dyn FnOnce() -> Result<(), SimError> + Send + Sync>;
+
+// Logged on success, or raised as a failure with this retry
+struct WriteDone {
+    message: String,
+    retry: Option<RetryAction>,
+}
+
+impl IoQueue {
+    pub fn write_ron<T: Serialize + Send + Sync + 'static>(&mut self, path: &str, value: T, message: String, retry: Option<RetryAction>) {
+        self.write_with(path, message, retry, move || {
+            ron::ser::to_string_pretty(&value, ron::ser::PrettyConfig::default()).map_err(|err| err.to_string())
+        });
+    }
+
+    // Contents already built on the main thread, such as exported graphs
+    pub fn write_text(&mut self, path: &str, contents: String, message: String, retry: Option<RetryAction>) {
+        self.write_with(path, message, retry, move || Ok(contents));
+    }
+
+    fn write_with(
+        &mut self,
+        path: &str,
+        message: String,
+        retry: Option<RetryAction>,
+        contents: impl FnOnce() -> Result<String, String> + Send + Sync + 'static,
+    ) {
+        let target = PathBuf::from(path);
+        let job: WriteJob = Box::new(move || {
+            let contents = contents().map_err(|err| SimError::io(target.display(), err))?;
+            let mut temporary = target.clone().into_os_string();
+            temporary.push(".tmp");
+            fs::write(&temporary, contents)
+                .and_then(|()| fs::rename(&temporary, &target))
+                .map_err(|err| SimError::io(target.display(), err))
+        });
+        let writes = self.paths.entry(PathBuf::from(path)).or_default();
+        if writes.waiting.is_some() {
+            debug!("Replacing a queued write of {}", path);
+        }
+        writes.waiting = Some((job, WriteDone { message, retry }));
+    }
+
+    // A write of the path is running or waiting
+    pub fn is_busy(&self, path: &str) -> bool {
+        self.paths.contains_key(Path::new(path))
+    }
+}
+
+// "Meadow Hopper" -> "meadow_hopper", for files named after something the user typed
+pub fn file_stem(name: &str) -> String {
+    name.chars().map(|c| if c.is_alphanumeric() { c.to_ascii_lowercase() } else { '_' }).collect()
+}
+
+fn report_write(result: Result<(), SimError>, done: WriteDone, failures: &mut EventWriter<SimFailure>) {
+    match result {
+        Ok(()) => info!("{}", done.message),
+        Err(error) => {
+            failures.send(SimFailure { error, retry: done.retry });
+        }
+    }
+}
+
+// Systems that queue writes on AppExit run before this, so those are drained too
+pub fn poll_io_writes(mut queue: ResMut<IoQueue>, mut failures: EventWriter<SimFailure>, mut exits: EventReader<AppExit>) {
+    if queue.paths.is_empty() {
+        return;
+    }
+    if exits.read().count() > 0 {
+        // The app closes after this frame and would drop the tasks; wait for the running writes
+        // and run the waiting ones here
+        for (_, writes) in queue.paths.drain() {
+            if let Some((task, done)) = writes.running {
+                report_write(future::block_on(task), done, &mut failures);
+            }
+            if let Some((job, done)) = writes.waiting {
+                report_write(job(), done, &mut failures);
+            }
+        }
+        return;
+    }
+    for writes in queue.paths.values_mut() {
+        if let Some((task, _)) = writes.running.as_mut() {
+            let Some(result) = future::block_on(future::poll_once(task)) else { continue };
+            let Some((_, done)) = writes.running.take() else { continue };
+            report_write(result, done, &mut failures);
+        }
+        if let Some((job, done)) = writes.waiting.take() {
+            writes.running = Some((IoTaskPool::get().spawn(async move { job() }), done));
+        }
+    }
+    queue.paths.retain(|_, writes| writes.running.is_some());
+}
@@ -0,0 +1,312 @@
+use bevy::ecs::system::SystemParam;
+use bevy::prelude::*;
+use std::collections::HashMap;
+
+pub struct KeybindingsPlugin;
+
+impl Plugin for KeybindingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Keybindings>();
+    }
+}
+
+// === KEYBINDINGS ===
+// Every keyboard shortcut goes through this table, so the help overlay (help.rs) lists exactly
+// what the systems react to. Text prompts handle their own typing and editing keys.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Action {
+    ToggleHelp,
+    OpenConsole,
+    SaveGame,
+    LoadGame,
+    ToggleWorldReport,
+    ToggleDayDigest,
+    ToggleGenerationPanel,
+    ToggleSpeciesDesigner,
+    ToggleModList,
+    TogglePauseMenu,
+    PanUp,
+    PanDown,
+    PanLeft,
+    PanRight,
+    PanFast,
+    GoToLocation,
+    RecallBookmark, // One key per slot, in slot order
+    StoreBookmark,
+    ToggleAutoCamera,
+    ToggleInsetCamera,
+    PinCreature,
+    NameCreature,
+    ToggleRelationships,
+    TameCreature,
+    ToggleStatBars,
+    ToggleHeatmap,
+    ToggleWeather,
+    ToggleLineageColors,
+    TogglePheromones,
+    CycleCensus,
+    ToggleEditor,
+    NextTool,
+    NextChoice, // Biome, stamp or species, depending on the tool
+    ToggleToolMode, // Spawn now / paint zones, brush / rectangle
+    ShrinkBrush,
+    GrowBrush,
+    Undo,
+    Redo,
+    PasteStamp,
+    SaveEdits,
+    LoadEdits,
+    SaveStamp,
+}
+
+// Sections of the help overlay
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ActionGroup {
+    General,
+    Camera,
+    Creatures,
+    Overlays,
+    Editor,
+}
+
+impl ActionGroup {
+    pub const ALL: [ActionGroup; 5] = [Self::General, Self::Camera, Self::Creatures, Self::Overlays, Self::Editor];
+
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            Self::General => "help.group.general",
+            Self::Camera => "help.group.camera",
+            Self::Creatures => "help.group.creatures",
+            Self::Overlays => "help.group.overlays",
+            Self::Editor => "help.group.editor",
+        }
+    }
+}
+
+impl Action {
+    // In the order the help overlay lists them
+    pub const ALL: [Action; 42] = [
+        Self::ToggleHelp, Self::OpenConsole, Self::SaveGame, Self::LoadGame, Self::ToggleWorldReport, Self::ToggleDayDigest,
+        Self::ToggleGenerationPanel, Self::ToggleSpeciesDesigner, Self::ToggleModList, Self::TogglePauseMenu, Self::PanUp, Self::PanDown, Self::PanLeft, Self::PanRight, Self::PanFast,
+        Self::GoToLocation, Self::RecallBookmark, Self::StoreBookmark, Self::ToggleAutoCamera, Self::ToggleInsetCamera, Self::PinCreature,
+        Self::NameCreature, Self::ToggleRelationships, Self::TameCreature, Self::ToggleStatBars, Self::ToggleHeatmap,
+        Self::ToggleWeather, Self::ToggleLineageColors, Self::TogglePheromones, Self::CycleCensus, Self::ToggleEditor, Self::NextTool,
+        Self::NextChoice, Self::ToggleToolMode, Self::ShrinkBrush, Self::GrowBrush, Self::Undo, Self::Redo,
+        Self::PasteStamp, Self::SaveEdits, Self::LoadEdits, Self::SaveStamp,
+    ];
+
+    pub fn group(&self) -> ActionGroup {
+        match self {
+            Self::ToggleHelp | Self::OpenConsole | Self::SaveGame | Self::LoadGame
+                | Self::ToggleWorldReport | Self::ToggleDayDigest | Self::ToggleGenerationPanel | Self::ToggleSpeciesDesigner
+                | Self::ToggleModList | Self::TogglePauseMenu => ActionGroup::General,
+            Self::PanUp | Self::PanDown | Self::PanLeft | Self::PanRight | Self::PanFast | Self::GoToLocation
+                | Self::RecallBookmark | Self::StoreBookmark | Self::ToggleAutoCamera | Self::ToggleInsetCamera => ActionGroup::Camera,
+            Self::PinCreature | Self::NameCreature | Self::ToggleRelationships | Self::TameCreature => ActionGroup::Creatures,
+            Self::ToggleStatBars | Self::ToggleHeatmap | Self::ToggleWeather | Self::ToggleLineageColors
+                | Self::TogglePheromones | Self::CycleCensus => ActionGroup::Overlays,
+            _ => ActionGroup::Editor,
+        }
+    }
+
+    pub fn label_key(&self) -> &'static str {
+        match self {
+            Self::ToggleHelp => "keys.toggle_help",
+            Self::OpenConsole => "keys.open_console",
+            Self::SaveGame => "keys.save_game",
+            Self::LoadGame => "keys.load_game",
+            Self::ToggleWorldReport => "keys.world_report",
+            Self::ToggleDayDigest => "keys.day_digest",
+            Self::ToggleGenerationPanel => "keys.generation_panel",
+            Self::ToggleSpeciesDesigner => "keys.species_designer",
+            Self::ToggleModList => "keys.mods",
+            Self::TogglePauseMenu => "keys.pause_menu",
+            Self::PanUp => "keys.pan_up",
+            Self::PanDown => "keys.pan_down",
+            Self::PanLeft => "keys.pan_left",
+            Self::PanRight => "keys.pan_right",
+            Self::PanFast => "keys.pan_fast",
+            Self::GoToLocation => "keys.go_to",
+            Self::RecallBookmark => "keys.recall_bookmark",
+            Self::StoreBookmark => "keys.store_bookmark",
+            Self::ToggleAutoCamera => "keys.auto_camera",
+            Self::ToggleInsetCamera => "keys.inset_camera",
+            Self::PinCreature => "keys.pin_creature",
+            Self::NameCreature => "keys.name_creature",
+            Self::ToggleRelationships => "keys.relationships",
+            Self::TameCreature => "keys.tame_creature",
+            Self::ToggleStatBars => "keys.stat_bars",
+            Self::ToggleHeatmap => "keys.heatmap",
+            Self::ToggleWeather => "keys.weather",
+            Self::ToggleLineageColors => "keys.lineage_colors",
+            Self::TogglePheromones => "keys.pheromones",
+            Self::CycleCensus => "keys.census",
+            Self::ToggleEditor => "keys.editor",
+            Self::NextTool => "keys.next_tool",
+            Self::NextChoice => "keys.next_choice",
+            Self::ToggleToolMode => "keys.tool_mode",
+            Self::ShrinkBrush => "keys.shrink_brush",
+            Self::GrowBrush => "keys.grow_brush",
+            Self::Undo => "keys.undo",
+            Self::Redo => "keys.redo",
+            Self::PasteStamp => "keys.paste_stamp",
+            Self::SaveEdits => "keys.save_edits",
+            Self::LoadEdits => "keys.load_edits",
+            Self::SaveStamp => "keys.save_stamp",
+        }
+    }
+}
+
+// A key with the modifiers it needs. Ctrl has to match exactly, so Ctrl+1 does not also recall
+// bookmark 1; Shift only has to be held when asked for, so Shift can speed up panning.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct KeyChord {
+    pub key: KeyCode,
+    pub ctrl: bool,
+    pub shift: bool,
+}
+
+impl KeyChord {
+    pub const fn key(key: KeyCode) -> Self {
+        Self { key, ctrl: false, shift: false }
+    }
+
+    pub const fn ctrl(key: KeyCode) -> Self {
+        Self { key, ctrl: true, shift: false }
+    }
+
+    pub const fn ctrl_shift(key: KeyCode) -> Self {
+        Self { key, ctrl: true, shift: true }
+    }
+
+    fn modifiers_held(&self, keyboard: &ButtonInput<KeyCode>) -> bool {
+        let ctrl = keyboard.any_pressed([KeyCode::ControlLeft, KeyCode::ControlRight]);
+        let shift = keyboard.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+        ctrl == self.ctrl && (shift || !self.shift)
+    }
+
+    pub fn label(&self) -> String {
+        format!("{}{}{}", if self.ctrl { "Ctrl+" } else { "" }, if self.shift { "Shift+" } else { "" }, key_label(self.key))
+    }
+}
+
+fn key_label(key: KeyCode) -> String {
+    match key {
+        KeyCode::ArrowUp => "↑".to_string(),
+        KeyCode::ArrowDown => "↓".to_string(),
+        KeyCode::ArrowLeft => "←".to_string(),
+        KeyCode::ArrowRight => "→".to_string(),
+        KeyCode::Backquote => "`".to_string(),
+        KeyCode::BracketLeft => "[".to_string(),
+        KeyCode::BracketRight => "]".to_string(),
+        KeyCode::ShiftLeft | KeyCode::ShiftRight => "Shift".to_string(),
+        KeyCode::ControlLeft | KeyCode::ControlRight => "Ctrl".to_string(),
+        key => {
+            let name = format!("{:?}", key);
+            name.strip_prefix("Key").or_else(|| name.strip_prefix("Digit")).unwrap_or(&name).to_string()
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct Keybindings {
+    pub chords: HashMap<Action, Vec<KeyChord>>,
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        use KeyCode::*;
+        let digits = [Digit0, Digit1, Digit2, Digit3, Digit4, Digit5, Digit6, Digit7, Digit8, Digit9];
+        let keys = |keys: &[KeyCode]| keys.iter().map(|key| KeyChord::key(*key)).collect::<Vec<_>>();
+        let chords = HashMap::from([
+            (Action::ToggleHelp, keys(&[F1])), // H is taken by the heat map
+            (Action::OpenConsole, keys(&[Backquote])),
+            (Action::SaveGame, keys(&[F5])),
+            (Action::LoadGame, keys(&[F9])),
+            (Action::ToggleWorldReport, keys(&[KeyI])),
+            (Action::ToggleDayDigest, keys(&[KeyJ])),
+            (Action::ToggleGenerationPanel, keys(&[KeyT])),
+            (Action::ToggleSpeciesDesigner, keys(&[KeyU])),
+            (Action::ToggleModList, keys(&[F2])),
+            (Action::TogglePauseMenu, keys(&[Escape])),
+            (Action::PanUp, keys(&[KeyW, ArrowUp])),
+            (Action::PanDown, keys(&[KeyS, ArrowDown])),
+            (Action::PanLeft, keys(&[KeyA, ArrowLeft])),
+            (Action::PanRight, keys(&[KeyD, ArrowRight])),
+            (Action::PanFast, keys(&[ShiftLeft, ShiftRight])),
+            (Action::GoToLocation, keys(&[KeyG])),
+            (Action::RecallBookmark, keys(&digits)),
+            (Action::StoreBookmark, digits.iter().map(|key| KeyChord::ctrl(*key)).collect()),
+            (Action::ToggleAutoCamera, keys(&[KeyC])),
+            (Action::ToggleInsetCamera, keys(&[KeyV])),
+            (Action::PinCreature, keys(&[KeyP])),
+            (Action::NameCreature, keys(&[KeyN])),
+            (Action::ToggleRelationships, keys(&[KeyR])),
+            (Action::TameCreature, keys(&[KeyK])),
+            (Action::ToggleStatBars, keys(&[KeyB])),
+            (Action::ToggleHeatmap, keys(&[KeyH])),
+            (Action::ToggleWeather, keys(&[KeyF])),
+            (Action::ToggleLineageColors, keys(&[KeyL])),
+            (Action::TogglePheromones, keys(&[KeyO])),
+            (Action::CycleCensus, keys(&[KeyX])),
+            (Action::ToggleEditor, keys(&[KeyE])),
+            (Action::NextTool, keys(&[Tab])),
+            (Action::NextChoice, keys(&[KeyQ])),
+            (Action::ToggleToolMode, keys(&[KeyM])),
+            (Action::ShrinkBrush, keys(&[BracketLeft])),
+            (Action::GrowBrush, keys(&[BracketRight])),
+            (Action::Undo, vec![KeyChord::ctrl(KeyZ)]),
+            (Action::Redo, vec![KeyChord::ctrl(KeyY), KeyChord::ctrl_shift(KeyZ)]),
+            (Action::PasteStamp, vec![KeyChord::ctrl(KeyV)]),
+            (Action::SaveEdits, keys(&[F6])),
+            (Action::LoadEdits, keys(&[F7])),
+            (Action::SaveStamp, keys(&[F8])),
+        ]);
+        Self { chords }
+    }
+}
+
+impl Keybindings {
+    pub fn chords(&self, action: Action) -> &[KeyChord] {
+        self.chords.get(&action).map_or(&[], Vec::as_slice)
+    }
+
+    // "W / ↑"; long runs such as the bookmark digits are shortened to "0 … 9"
+    pub fn label(&self, action: Action) -> String {
+        let mut labels: Vec<String> = Vec::new();
+        for chord in self.chords(action) {
+            let label = chord.label();
+            if !labels.contains(&label) {
+                labels.push(label);
+            }
+        }
+        match labels.as_slice() {
+            [first, .., last] if labels.len() > 4 => format!("{} … {}", first, last),
+            _ => labels.join(" / "),
+        }
+    }
+}
+
+// The keyboard as seen through the bindings
+#[derive(SystemParam)]
+pub struct Controls<'w> {
+    keyboard: Res<'w, ButtonInput<KeyCode>>,
+    bindings: Res<'w, Keybindings>,
+}
+
+impl Controls<'_> {
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.just_pressed_index(action).is_some()
+    }
+
+    // Which of the action's chords was pressed, e.g. the bookmark slot
+    pub fn just_pressed_index(&self, action: Action) -> Option<usize> {
+        self.bindings.chords(action).iter()
+            .position(|chord| self.keyboard.just_pressed(chord.key) && chord.modifiers_held(&self.keyboard))
+    }
+
+    pub fn pressed(&self, action: Action) -> bool {
+        self.bindings.chords(action).iter()
+            .any(|chord| self.keyboard.pressed(chord.key) && chord.modifiers_held(&self.keyboard))
+    }
+}
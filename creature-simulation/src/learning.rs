@@ -0,0 +1,96 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::creature::{Creature, SEEK_THRESHOLD};
+use crate::genetics::Genome;
+use crate::predation::hunt_prey;
+use crate::save::SaveComponentExt;
+use crate::world::WorldMap;
+
+const LEARNING_RATE: f32 = 0.1; // Share of the way to the extreme one outcome moves a weight
+const FORGETTING: f32 = 0.002; // Share of the gap to the genetic baseline closed per second
+const HUNT_THRESHOLD_SPAN: f32 = 0.4; // Hunger range over which hunt drive moves the hunting threshold
+
+pub struct LearningPlugin;
+
+impl Plugin for LearningPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .save_component::<Learned>("learned")
+            .add_event::<LearningOutcome>()
+            .add_systems(Update, add_learning)
+            .add_systems(FixedUpdate, learn_from_outcomes
+                .after(hunt_prey)
+                .run_if(resource_exists::<WorldMap>));
+    }
+}
+
+// === LEARNING ===
+// Each creature's hunt drive and caution start at its genome's boldness and shift with what
+// happens to it: every catch makes a hunter keener (it starts hunting less hungry, see
+// hunt_prey), every chase makes prey warier (it notices hunters from further away).
+// Both drift back towards the genetic baseline, so habits fade unless reinforced.
+#[derive(Component, Clone, Copy, Serialize, Deserialize)]
+pub struct Learned {
+    pub hunt_drive: f32, // 0.0..=1.0
+    pub caution: f32, // 0.0..=1.0
+}
+
+impl Learned {
+    fn baseline(genome: &Genome) -> Self {
+        Self { hunt_drive: genome.boldness, caution: 1.0 - genome.boldness }
+    }
+
+    // Hunger above which a hunter goes after prey; keen hunters start earlier
+    pub fn hunt_threshold(learned: Option<&Learned>) -> f32 {
+        learned.map_or(SEEK_THRESHOLD, |learned| SEEK_THRESHOLD + (0.5 - learned.hunt_drive) * HUNT_THRESHOLD_SPAN)
+    }
+
+    // Multiplier on the distance prey notice a chasing hunter from
+    pub fn wariness(learned: Option<&Learned>) -> f32 {
+        learned.map_or(1.0, |learned| 0.5 + learned.caution)
+    }
+}
+
+#[derive(Event, Clone, Copy)]
+pub enum LearningOutcome {
+    Caught(Entity), // A hunter made a catch
+    Chased(Entity), // Prey noticed a hunter coming for it
+}
+
+// Newly spawned or loaded creatures without the component yet
+type NewCreature = (Added<Creature>, Without<Learned>);
+
+fn add_learning(mut commands: Commands, creatures: Query<(Entity, &Genome), NewCreature>) {
+    for (entity, genome) in creatures.iter() {
+        commands.entity(entity).insert(Learned::baseline(genome));
+    }
+}
+
+fn learn_from_outcomes(
+    time: Res<Time>,
+    mut outcomes: EventReader<LearningOutcome>,
+    mut creatures: Query<(&mut Learned, &Genome)>,
+) {
+    let step = |value: &mut f32, target: f32| *value += (target - *value) * LEARNING_RATE;
+    for outcome in outcomes.read() {
+        match *outcome {
+            LearningOutcome::Caught(entity) => if let Ok((mut learned, _)) = creatures.get_mut(entity) {
+                step(&mut learned.hunt_drive, 1.0);
+            },
+            LearningOutcome::Chased(entity) => if let Ok((mut learned, _)) = creatures.get_mut(entity) {
+                step(&mut learned.caution, 1.0);
+            },
+        }
+    }
+
+    let forget = (FORGETTING * time.delta_seconds()).min(1.0);
+    for (mut learned, genome) in creatures.iter_mut() {
+        let baseline = Learned::baseline(genome);
+        // Written through only when it moves, so change detection stays meaningful
+        let hunt_drive = learned.hunt_drive + (baseline.hunt_drive - learned.hunt_drive) * forget;
+        let caution = learned.caution + (baseline.caution - learned.caution) * forget;
+        if hunt_drive != learned.hunt_drive || caution != learned.caution {
+            *learned = Learned { hunt_drive, caution };
+        }
+    }
+}
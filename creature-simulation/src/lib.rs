@@ -0,0 +1,106 @@
+// The simulation as a library: the app (app.rs, run by main.rs) and, for companion tools that read
+// worlds without running it, such as map analyzers, the world queries (world_query.rs) and the
+// world types they return. Everything else stays internal to the crate.
+mod biome;
+mod world;
+mod coords;
+mod render;
+mod environment;
+mod optimization;
+mod optimized_systems;
+mod loading;
+mod settings;
+mod localization;
+mod generation_constraints;
+mod telemetry;
+mod world_stats;
+mod simulation;
+mod rng_streams;
+mod frame_pacing;
+mod species;
+mod creature;
+mod predation;
+mod genetics;
+mod reproduction;
+mod creature_overlay;
+mod creature_lod;
+mod death_heatmap;
+mod creature_tracking;
+mod save;
+mod text_input;
+mod camera;
+mod events;
+mod auto_camera;
+mod hydrology;
+mod tides;
+mod vegetation;
+mod swarm;
+mod fisheries;
+mod experiments;
+mod population_pools;
+mod ai_lod;
+mod calendar;
+mod weather;
+mod world_registry;
+mod thumbnails;
+mod gpu_noise;
+mod names;
+mod stress_test;
+mod generation_panel;
+mod editor;
+mod stamps;
+mod spawn_zones;
+mod reserves;
+mod path_debug;
+mod behavior_inspector;
+mod schedule_dump;
+mod errors;
+mod logging;
+mod keybindings;
+mod help;
+mod pheromones;
+mod relationships;
+mod taming;
+mod climate;
+mod ice;
+mod rivers;
+mod night;
+mod calls;
+mod profiling;
+mod chunk_cache;
+mod io_queue;
+mod species_designer;
+mod scenario;
+mod mods;
+mod profile;
+mod genealogy;
+mod extreme_weather;
+mod hibernation;
+mod nesting;
+mod parental_care;
+mod learning;
+mod speciation;
+mod gait;
+mod footprints;
+mod digest;
+mod mortality;
+mod census;
+mod inset_camera;
+mod view_mode;
+mod mood_icons;
+mod ambient_fauna;
+mod world_overview;
+mod crash_report;
+mod bench;
+mod generation_service;
+mod creature_batch;
+mod world_query;
+mod app;
+#[cfg(feature = "creature-sim-py")]
+mod python;
+
+pub use app::run;
+pub use biome::{BiomeType, ResourceType};
+pub use errors::SimError;
+pub use world::{GenerationParams, Tile, Topology, WorldMap, SEA_LEVEL, WORLD_SIZE};
+pub use world_query::{ClimateSample, WorldQuery};
@@ -1,5 +1,13 @@
 use bevy::prelude::*;
 use rand::Rng;
+use crate::generation_panel::GenerationPanel;
+use crate::localization::Localization;
+use crate::optimized_systems::WorldGeneration;
+use crate::rng_streams::{RngStream, RngStreams};
+use crate::settings::{Settings, motion_enabled};
+use crate::text_input::{PromptKind, PromptSubmitted, TextPrompt};
+
+const ETA_WINDOW_SECONDS: f32 = 1.0; // Time constant of the progress rate moving average
 
 pub struct LoadingPlugin;
 
@@ -10,6 +18,10 @@ impl Plugin for LoadingPlugin {
             .add_systems(Update, (
                 update_loading_messages,
                 update_loading_bar,
+                update_loading_eta,
+                hide_cancel_button,
+                (read_seed_prompt, press_seed_buttons, update_seed_selection).chain(),
+                animate_loading_bar_color.run_if(motion_enabled),
                 cleanup_loading_screen,
             ))
             .add_systems(Startup, spawn_loading_screen);
@@ -25,18 +37,77 @@ pub struct LoadingState {
     pub is_complete: bool,
     pub world_ready: bool, // True when world generation is done
     pub first_frame_rendered: bool, // True when first frame is actually rendered
+    pub progress_rate: f32, // Moving average of progress per second
+    pub eta_seconds: Option<f32>, // Estimated time to finish generating the world
+    pub selecting_seed: Option<u32>, // Generation was cancelled; the seed on offer until one is generated
+}
+
+// Loading in order, each stage filling a fixed share of the bar
+#[derive(Clone, Copy, Debug)]
+pub enum LoadingStage {
+    Setup,
+    Generation,
+    Install, // Compressing the map and inserting its resources
+    Rendering, // First chunks around the camera
+}
+
+impl LoadingStage {
+    // Where the stage starts on the bar, and the share of the bar it fills
+    fn span(self) -> (f32, f32) {
+        match self {
+            LoadingStage::Setup => (0.0, 0.05),
+            LoadingStage::Generation => (0.05, 0.7),
+            LoadingStage::Install => (0.75, 0.05),
+            LoadingStage::Rendering => (0.8, 0.2),
+        }
+    }
+}
+
+impl LoadingState {
+    // Raises progress to `done` (0.0..=1.0) of the way through a stage, never lowering it, and
+    // updates the moving-average ETA. Every change to the bar goes through here.
+    pub fn advance(&mut self, stage: LoadingStage, done: f32, dt: f32) {
+        let (start, share) = stage.span();
+        let progress = start + share * done.clamp(0.0, 1.0);
+        let gained = (progress - self.progress).max(0.0);
+        self.progress = self.progress.max(progress);
+        if dt <= 0.0 {
+            return;
+        }
+
+        // Time-weighted so the average does not depend on the frame rate
+        let weight = 1.0 - (-dt / ETA_WINDOW_SECONDS).exp();
+        self.progress_rate += (gained / dt - self.progress_rate) * weight;
+        self.eta_seconds = (self.progress_rate > f32::EPSILON)
+            .then(|| (1.0 - self.progress) / self.progress_rate);
+    }
+
+    // Back to an empty bar for a regenerated world
+    pub fn restart(&mut self) {
+        self.progress = 0.0;
+        self.progress_rate = 0.0;
+        self.eta_seconds = None;
+        self.world_ready = false;
+        self.selecting_seed = None;
+    }
 }
 
-impl Default for LoadingState {
-    fn default() -> Self {
+impl FromWorld for LoadingState {
+    fn from_world(world: &mut World) -> Self {
+        let message = world.resource_scope(|world, mut streams: Mut<RngStreams>| {
+            world.resource::<Localization>().random("loading.messages", streams.get(RngStream::Ui))
+        });
         Self {
             progress: 0.0,
-            current_message: get_random_loading_message(),
+            current_message: message,
             message_timer: Timer::from_seconds(0.8, TimerMode::Repeating),
             bar_animation_time: 0.0,
             is_complete: false,
             world_ready: false,
             first_frame_rendered: false,
+            progress_rate: 0.0,
+            eta_seconds: None,
+            selecting_seed: None,
         }
     }
 }
@@ -53,7 +124,28 @@ pub struct LoadingText;
 #[derive(Component)]
 pub struct LoadingMessage;
 
-fn spawn_loading_screen(mut commands: Commands) {
+#[derive(Component)]
+pub struct LoadingEta;
+
+// Aborts world generation and goes back to picking a seed (see optimized_systems.rs)
+#[derive(Component)]
+pub struct CancelGenerationButton;
+
+// Shown under the bar once generation is cancelled
+#[derive(Component)]
+pub struct SeedSelection;
+
+#[derive(Component)]
+pub struct SeedLabel;
+
+#[derive(Component, Clone, Copy)]
+pub enum SeedButton {
+    Random,
+    Enter, // Opens a text prompt for the seed
+    Generate, // With the generation panel's settings
+}
+
+fn spawn_loading_screen(mut commands: Commands, localization: Res<Localization>, mut streams: ResMut<RngStreams>) {
     // Main loading screen container
     commands.spawn((
         NodeBundle {
@@ -73,7 +165,7 @@ fn spawn_loading_screen(mut commands: Commands) {
         // Title
         parent.spawn((
             TextBundle::from_section(
-                "🦎 Creature Simulation 🌍",
+                localization.get("loading.title"),
                 TextStyle {
                     font_size: 48.0,
                     color: Color::srgb(0.9, 0.9, 0.9),
@@ -88,7 +180,7 @@ fn spawn_loading_screen(mut commands: Commands) {
         // Loading message
         parent.spawn((
             TextBundle::from_section(
-                get_random_loading_message(),
+                localization.random("loading.messages", streams.get(RngStream::Ui)),
                 TextStyle {
                     font_size: 20.0,
                     color: Color::srgb(0.7, 0.8, 0.9),
@@ -129,6 +221,107 @@ fn spawn_loading_screen(mut commands: Commands) {
             ));
         });
 
+        // Time remaining, once there is a rate to estimate it from
+        parent.spawn((
+            TextBundle::from_section(
+                "",
+                TextStyle {
+                    font_size: 16.0,
+                    color: Color::srgb(0.6, 0.6, 0.7),
+                    ..default()
+                },
+            ),
+            LoadingEta,
+        ));
+
+        parent.spawn((
+            ButtonBundle {
+                style: Style {
+                    margin: UiRect::all(Val::Px(10.0)),
+                    padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                    ..default()
+                },
+                background_color: Color::srgb(0.3, 0.2, 0.25).into(),
+                ..default()
+            },
+            CancelGenerationButton,
+        )).with_children(|button| {
+            button.spawn(TextBundle::from_section(
+                localization.get("loading.cancel"),
+                TextStyle {
+                    font_size: 16.0,
+                    color: Color::srgb(0.9, 0.9, 0.9),
+                    ..default()
+                },
+            ));
+        });
+
+        parent.spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::None,
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(8.0),
+                    ..default()
+                },
+                ..default()
+            },
+            SeedSelection,
+        )).with_children(|selection| {
+            selection.spawn((
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font_size: 20.0,
+                        color: Color::srgb(0.9, 0.9, 0.9),
+                        ..default()
+                    },
+                ),
+                SeedLabel,
+            ));
+            selection.spawn(NodeBundle {
+                style: Style { column_gap: Val::Px(8.0), ..default() },
+                ..default()
+            }).with_children(|row| {
+                let buttons = [
+                    (SeedButton::Random, "loading.random_seed"),
+                    (SeedButton::Enter, "loading.enter_seed"),
+                    (SeedButton::Generate, "loading.generate"),
+                ];
+                for (button, key) in buttons {
+                    row.spawn((
+                        ButtonBundle {
+                            style: Style {
+                                padding: UiRect::axes(Val::Px(12.0), Val::Px(6.0)),
+                                ..default()
+                            },
+                            background_color: Color::srgb(0.2, 0.25, 0.35).into(),
+                            ..default()
+                        },
+                        button,
+                    )).with_children(|button| {
+                        button.spawn(TextBundle::from_section(
+                            localization.get(key),
+                            TextStyle {
+                                font_size: 16.0,
+                                color: Color::srgb(0.9, 0.9, 0.9),
+                                ..default()
+                            },
+                        ));
+                    });
+                }
+            });
+            selection.spawn(TextBundle::from_section(
+                localization.get("loading.settings_hint"),
+                TextStyle {
+                    font_size: 14.0,
+                    color: Color::srgb(0.6, 0.6, 0.7),
+                    ..default()
+                },
+            ));
+        });
+
         // Fun little creatures
         parent.spawn((
             TextBundle::from_section(
@@ -149,32 +342,19 @@ fn spawn_loading_screen(mut commands: Commands) {
 fn update_loading_messages(
     time: Res<Time>,
     mut loading_state: ResMut<LoadingState>,
+    (localization, mut streams): (Res<Localization>, ResMut<RngStreams>),
     mut message_query: Query<&mut Text, With<LoadingMessage>>,
 ) {
     loading_state.message_timer.tick(time.delta());
     loading_state.bar_animation_time += time.delta_seconds();
     
-    // Update progress for rendering phase
+    // Rendering phase messages; the bar itself only moves as chunks load
     if loading_state.world_ready && !loading_state.first_frame_rendered {
-        loading_state.progress = (loading_state.progress + time.delta_seconds() * 0.5).min(0.99);
-        
-        // Update message for rendering phase
         if loading_state.message_timer.just_finished() {
-            let rendering_messages = vec![
-                "🎨 Painting the landscape...",
-                "🖌️ Adding final details...",
-                "✨ Sprinkling magic dust...",
-                "🌟 Making everything sparkle...",
-                "🎭 Setting the stage...",
-                "📸 Adjusting the camera angle...",
-                "🌈 Calibrating colors...",
-                "🎪 Preparing the grand reveal...",
-            ];
-            let mut rng = rand::thread_rng();
-            loading_state.current_message = rendering_messages[rng.gen_range(0..rendering_messages.len())].to_string();
+            loading_state.current_message = localization.random("loading.rendering_messages", streams.get(RngStream::Ui));
         }
-    } else if loading_state.message_timer.just_finished() && !loading_state.is_complete {
-        loading_state.current_message = get_random_loading_message();
+    } else if loading_state.message_timer.just_finished() && !loading_state.is_complete && loading_state.selecting_seed.is_none() {
+        loading_state.current_message = localization.random("loading.messages", streams.get(RngStream::Ui));
     }
     
     // Update text display
@@ -186,11 +366,19 @@ fn update_loading_messages(
 fn update_loading_bar(
     time: Res<Time>,
     loading_state: Res<LoadingState>,
-    mut bar_query: Query<(&mut Style, &mut BackgroundColor), With<LoadingBar>>,
+    settings: Res<Settings>,
+    mut bar_query: Query<&mut Style, With<LoadingBar>>,
 ) {
-    for (mut style, mut color) in bar_query.iter_mut() {
-        // Smooth progress bar animation
+    for mut style in bar_query.iter_mut() {
         let target_width = loading_state.progress * 100.0;
+        
+        // Reduced motion jumps straight to the target instead of easing
+        if settings.reduced_motion {
+            style.width = Val::Percent(target_width.min(100.0));
+            continue;
+        }
+        
+        // Smooth progress bar animation
         let current_width = match style.width {
             Val::Percent(w) => w,
             _ => 0.0,
@@ -198,7 +386,103 @@ fn update_loading_bar(
         
         let new_width = current_width + (target_width - current_width) * time.delta_seconds() * 3.0;
         style.width = Val::Percent(new_width.min(100.0));
-        
+    }
+}
+
+fn update_loading_eta(
+    loading_state: Res<LoadingState>,
+    localization: Res<Localization>,
+    mut eta_query: Query<&mut Text, With<LoadingEta>>,
+) {
+    let eta = match loading_state.eta_seconds {
+        Some(seconds) if !loading_state.world_ready => {
+            localization.format("loading.eta", &[("seconds", format!("{:.0}", seconds.ceil()))])
+        }
+        _ => String::new(),
+    };
+    for mut text in eta_query.iter_mut() {
+        if text.sections[0].value != eta {
+            text.sections[0].value = eta.clone();
+        }
+    }
+}
+
+// Once the map exists, or generation was already cancelled, there is nothing left to cancel
+fn hide_cancel_button(
+    loading_state: Res<LoadingState>,
+    mut buttons: Query<&mut Visibility, With<CancelGenerationButton>>,
+) {
+    let cancellable = !loading_state.world_ready && loading_state.selecting_seed.is_none();
+    let visibility = if cancellable { Visibility::Inherited } else { Visibility::Hidden };
+    for mut button in buttons.iter_mut() {
+        button.set_if_neq(visibility);
+    }
+}
+
+fn read_seed_prompt(mut submitted: EventReader<PromptSubmitted>, mut loading_state: ResMut<LoadingState>) {
+    for event in submitted.read() {
+        if event.kind != PromptKind::WorldSeed || loading_state.selecting_seed.is_none() {
+            continue;
+        }
+        match event.text.parse::<u32>() {
+            Ok(seed) => loading_state.selecting_seed = Some(seed),
+            Err(_) => warn!("Not a seed: '{}'", event.text),
+        }
+    }
+}
+
+fn press_seed_buttons(
+    buttons: Query<(&Interaction, &SeedButton), Changed<Interaction>>,
+    mut loading_state: ResMut<LoadingState>,
+    (mut generation, mut settings, panel): (WorldGeneration, ResMut<Settings>, Res<GenerationPanel>),
+    (localization, mut prompt, mut streams): (Res<Localization>, ResMut<TextPrompt>, ResMut<RngStreams>),
+) {
+    let Some(seed) = loading_state.selecting_seed else { return };
+    let Some((_, button)) = buttons.iter().find(|(interaction, _)| **interaction == Interaction::Pressed) else { return };
+    match button {
+        SeedButton::Random => {
+            loading_state.selecting_seed = Some(streams.get(RngStream::WorldGen).gen_range(0..u32::MAX / 2));
+        }
+        SeedButton::Enter => prompt.open(PromptKind::WorldSeed, seed.to_string()),
+        SeedButton::Generate => {
+            settings.generation = panel.params;
+            info!("🌍 Generating world with seed {}", seed);
+            loading_state.restart();
+            loading_state.current_message = localization.get("loading.initializing").to_string();
+            generation.start(&settings, seed);
+        }
+    }
+}
+
+fn update_seed_selection(
+    loading_state: Res<LoadingState>,
+    localization: Res<Localization>,
+    mut selections: Query<&mut Style, With<SeedSelection>>,
+    mut labels: Query<&mut Text, With<SeedLabel>>,
+) {
+    if !loading_state.is_changed() {
+        return;
+    }
+    let display = if loading_state.selecting_seed.is_some() { Display::Flex } else { Display::None };
+    for mut style in selections.iter_mut() {
+        if style.display != display {
+            style.display = display;
+        }
+    }
+    let Some(seed) = loading_state.selecting_seed else { return };
+    let label = localization.format("loading.seed", &[("seed", seed.to_string())]);
+    for mut text in labels.iter_mut() {
+        if text.sections[0].value != label {
+            text.sections[0].value = label.clone();
+        }
+    }
+}
+
+fn animate_loading_bar_color(
+    loading_state: Res<LoadingState>,
+    mut bar_query: Query<&mut BackgroundColor, With<LoadingBar>>,
+) {
+    for mut color in bar_query.iter_mut() {
         // Color animation based on progress
         let hue = loading_state.bar_animation_time * 0.5 + loading_state.progress * 120.0;
         let saturation = 0.8;
@@ -221,57 +505,15 @@ fn cleanup_loading_screen(
     }
 }
 
-fn get_random_loading_message() -> String {
-    let messages = vec![
-        "🌱 Planting magical trees...",
-        "🏔️ Sculpting majestic mountains...",
-        "🌊 Filling oceans with mysterious creatures...",
-        "🦎 Teaching lizards how to dance...",
-        "🌵 Convincing cacti to be social...",
-        "🐸 Installing frog conversation software...",
-        "🦋 Calibrating butterfly wingbeats...",
-        "🍄 Growing mushrooms with attitude...",
-        "🌪️ Brewing perfect weather storms...",
-        "🦅 Training eagles in aerial acrobatics...",
-        "🐛 Debugging the bugs (literally)...",
-        "🌸 Painting flowers in ridiculous colors...",
-        "🦀 Teaching crabs sideways philosophy...",
-        "🐝 Installing bee-to-flower translation...",
-        "🦉 Setting owl wisdom levels to maximum...",
-        "🐙 Untangling octopus tentacles...",
-        "🌙 Adjusting moon brightness settings...",
-        "⭐ Counting stars (again, for accuracy)...",
-        "🌈 Mixing rainbow paint buckets...",
-        "🎨 Adding finishing touches to sunsets...",
-        "🦊 Teaching foxes advanced cunning...",
-        "🐺 Organizing wolf pack hierarchies...",
-        "🦉 Installing night vision goggles...",
-        "🌿 Whispering growth secrets to grass...",
-        "🪨 Polishing rocks to perfection...",
-        "💧 Quality testing every water drop...",
-        "⚡ Supercharging the simulation engine...",
-        "🚀 Activating parallel processing magic...",
-        "⚙️ Fine-tuning world generation algorithms...",
-        "🔥 Igniting multi-threaded chaos...",
-        "💫 Sprinkling optimization fairy dust...",
-        "🎯 Precision-crafting every biome...",
-        "🏃‍♂️ Racing through world creation...",
-        "🦎 Lizard management orientation complete!",
-        "🎉 Almost ready for the chaos to begin!",
-    ];
-    
-    let mut rng = rand::thread_rng();
-    messages[rng.gen_range(0..messages.len())].to_string()
-}
-
 // Helper function to update loading progress from other systems
 pub fn update_loading_progress(
     mut loading_state: ResMut<LoadingState>,
     time: Res<Time>,
+    localization: &Localization,
     progress: f32,
     custom_message: Option<String>,
 ) {
-    loading_state.progress = progress.clamp(0.0, 1.0);
+    loading_state.advance(LoadingStage::Rendering, progress, time.delta_seconds());
     loading_state.bar_animation_time += time.delta_seconds();
     
     if let Some(message) = custom_message {
@@ -280,6 +522,6 @@ pub fn update_loading_progress(
     
     if progress >= 1.0 {
         loading_state.is_complete = true;
-        loading_state.current_message = "🎉 Welcome to your new world! 🎉".to_string();
+        loading_state.current_message = localization.get("loading.welcome").to_string();
     }
 }
\ No newline at end of file
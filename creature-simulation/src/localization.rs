@@ -0,0 +1,105 @@
+use bevy::prelude::*;
+use rand::Rng;
+use serde::Deserialize;
+use std::collections::HashMap;
+use crate::settings::Settings;
+
+pub const DEFAULT_LANGUAGE: &str = "en";
+
+// Locale tables are embedded at compile time so a missing asset can never break startup
+const LOCALES: &[(&str, &str)] = &[
+    ("en", include_str!("../assets/locales/en.ron")),
+    ("es", include_str!("../assets/locales/es.ron")),
+];
+
+pub struct LocalizationPlugin;
+
+impl Plugin for LocalizationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, reload_localization_on_language_change);
+    }
+}
+
+#[derive(Deserialize, Default)]
+struct LocaleTable {
+    strings: HashMap<String, String>,
+    lists: HashMap<String, Vec<String>>,
+}
+
+impl LocaleTable {
+    fn load(language: &str) -> Option<Self> {
+        let (_, source) = LOCALES.iter().find(|(code, _)| *code == language)?;
+        match ron::from_str(source) {
+            Ok(table) => Some(table),
+            Err(err) => {
+                error!("Failed to parse locale '{}': {}", language, err);
+                None
+            }
+        }
+    }
+}
+
+// === LOCALIZATION ===
+// Key → string lookup for the active language, falling back to English and then to the key itself.
+#[derive(Resource)]
+pub struct Localization {
+    pub language: String,
+    active: LocaleTable,
+    fallback: LocaleTable,
+}
+
+impl Localization {
+    pub fn new(language: &str) -> Self {
+        let fallback = LocaleTable::load(DEFAULT_LANGUAGE).unwrap_or_default();
+        let (language, active) = match LocaleTable::load(language) {
+            Some(table) => (language.to_string(), table),
+            None => {
+                warn!("Unknown language '{}', falling back to '{}'", language, DEFAULT_LANGUAGE);
+                (DEFAULT_LANGUAGE.to_string(), LocaleTable::default())
+            }
+        };
+
+        Self { language, active, fallback }
+    }
+
+    pub fn get<'a>(&'a self, key: &'a str) -> &'a str {
+        self.active.strings.get(key)
+            .or_else(|| self.fallback.strings.get(key))
+            .map(String::as_str)
+            .unwrap_or(key)
+    }
+
+    // Substitutes `{name}` placeholders in the translated string
+    pub fn format(&self, key: &str, args: &[(&str, String)]) -> String {
+        let mut text = self.get(key).to_string();
+        for (name, value) in args {
+            text = text.replace(&format!("{{{}}}", name), value);
+        }
+        text
+    }
+
+    pub fn list(&self, key: &str) -> &[String] {
+        self.active.lists.get(key)
+            .or_else(|| self.fallback.lists.get(key))
+            .map(Vec::as_slice)
+            .unwrap_or(&[])
+    }
+
+    pub fn random(&self, key: &str, rng: &mut impl Rng) -> String {
+        let entries = self.list(key);
+        if entries.is_empty() {
+            return key.to_string();
+        }
+        entries[rng.gen_range(0..entries.len())].clone()
+    }
+}
+
+fn reload_localization_on_language_change(
+    settings: Res<Settings>,
+    mut localization: ResMut<Localization>,
+) {
+    if settings.is_changed() && settings.language != localization.language {
+        info!("Switching language to '{}'", settings.language);
+        *localization = Localization::new(&settings.language);
+    }
+}
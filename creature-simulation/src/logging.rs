@@ -0,0 +1,120 @@
+use bevy::log::tracing_subscriber::layer::Context;
+use bevy::log::tracing_subscriber::registry::LookupSpan;
+use bevy::log::tracing_subscriber::Layer;
+use bevy::log::{BoxedLayer, LogPlugin};
+use bevy::prelude::*;
+use bevy::utils::tracing::field::{Field, Visit};
+use bevy::utils::tracing::{span, Event, Subscriber};
+use serde_json::{Map, Value};
+use std::io::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+use crate::settings::Settings;
+
+pub const LOG_ENV: &str = "SIM_LOG"; // Same syntax as --log
+const CRATE_TARGET: &str = "creature_simulation";
+const DEFAULT_FILTER: &str = "wgpu=error,naga=warn"; // Bevy's own default
+
+// === LOGGING ===
+// Log levels per module from --log or SIM_LOG, e.g. "world=debug,render=warn". Bare names are
+// matched both as this crate's modules and as other crates, so "world" and "bevy_render" both
+// work. RUST_LOG still overrides everything, as it does for any Bevy app. With --log-json every
+// record is also written to stdout as one JSON object per line, for headless runs; the usual
+// text log stays on stderr.
+pub fn log_plugin(settings: &Settings) -> LogPlugin {
+    LogPlugin {
+        filter: format!("{},{}", DEFAULT_FILTER, expand_filter(&settings.log_filter)),
+        custom_layer: json_layer,
+        ..default()
+    }
+}
+
+// "world=debug" -> "world=debug,creature_simulation::world=debug"; plain levels and paths are kept
+fn expand_filter(filter: &str) -> String {
+    filter.split(',')
+        .map(str::trim)
+        .filter(|directive| !directive.is_empty())
+        .map(|directive| match directive.split_once('=') {
+            Some((target, level)) if !target.contains("::") && !target.contains('[') => {
+                format!("{}={},{}::{}={}", target, level, CRATE_TARGET, target, level)
+            }
+            _ => directive.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+// Settings are inserted before DefaultPlugins so the layer can see the flag
+fn json_layer(app: &mut App) -> Option<BoxedLayer> {
+    let enabled = app.world().get_resource::<Settings>().is_some_and(|settings| settings.log_json);
+    enabled.then(|| Box::new(JsonLayer) as BoxedLayer)
+}
+
+struct JsonLayer;
+
+// Event or span fields as JSON values; the message is a field like any other
+#[derive(Default)]
+struct JsonFields(Map<String, Value>);
+
+impl Visit for JsonFields {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.into());
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0.insert(field.name().to_string(), format!("{:?}", value).into());
+    }
+}
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> Layer<S> for JsonLayer {
+    // Span fields are kept so every record inside the span can carry them
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut fields = JsonFields::default();
+        attrs.record(&mut fields);
+        span.extensions_mut().insert(fields);
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let mut fields = JsonFields::default();
+        event.record(&mut fields);
+        let spans: Vec<Value> = ctx.event_scope(event).into_iter().flat_map(|scope| scope.from_root())
+            .map(|span| {
+                let mut entry = Map::new();
+                entry.insert("name".to_string(), span.name().into());
+                if let Some(fields) = span.extensions().get::<JsonFields>() {
+                    entry.extend(fields.0.clone());
+                }
+                Value::Object(entry)
+            })
+            .collect();
+
+        let metadata = event.metadata();
+        let mut record = Map::new();
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0.0, |time| time.as_secs_f64());
+        record.insert("timestamp".to_string(), timestamp.into());
+        record.insert("level".to_string(), metadata.level().as_str().into());
+        record.insert("target".to_string(), metadata.target().into());
+        record.insert("fields".to_string(), Value::Object(fields.0));
+        if !spans.is_empty() {
+            record.insert("spans".to_string(), spans.into());
+        }
+        // One locked write per record so lines from worker threads do not interleave
+        let _ = writeln!(std::io::stdout().lock(), "{}", Value::Object(record));
+    }
+}
@@ -0,0 +1,245 @@
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+use crate::io_queue::IoQueue;
+use crate::keybindings::{Action, Controls};
+use crate::localization::Localization;
+use crate::species::{builtin_species, Species};
+use crate::text_input::text_input_inactive;
+
+pub const MODS_DIR: &str = "mods";
+const MANIFEST_FILE: &str = "mod.ron";
+const DISABLED_PATH: &str = "mods/disabled.ron"; // Ids of the packs switched off in the mod list
+pub const SPECIES_FILE: &str = "species.ron";
+pub const RELATIONSHIPS_FILE: &str = "relationships.ron";
+// Pack content this version has no data format for yet; biomes and props are built into the generator
+const UNSUPPORTED: [&str; 3] = ["biomes.ron", "environment.ron", "scripts"];
+const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const WARNING_COLOR: Color = Color::srgb(1.0, 0.75, 0.3);
+
+pub struct ModsPlugin;
+
+impl Plugin for ModsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(ModList::discover())
+            .add_systems(Startup, report_mods)
+            .add_systems(Update, (toggle_mod_list.run_if(text_input_inactive), press_mod_toggles).chain());
+    }
+}
+
+// === MOD PACKS ===
+// Every directory in MODS_DIR is a data pack: an optional mod.ron manifest plus species.ron and
+// relationships.ron in the built-in formats. Enabled packs are applied in load order after the
+// built-in data and before designer species, so a later pack wins a species both define (by name,
+// replacing it in place). Load order is by manifest priority, then directory name, with each pack
+// after the packs its manifest lists in `after`; the same folder always loads the same way. The
+// registry is built once, so switching a pack on or off in the list applies on the next start.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct ModManifest {
+    name: String,
+    version: String,
+    priority: i32, // Lower loads first
+    after: Vec<String>, // Ids of packs to load before this one
+}
+
+pub struct ModPack {
+    pub id: String, // Directory name
+    pub name: String,
+    pub version: String,
+    pub enabled: bool,
+    pub dir: PathBuf,
+}
+
+impl ModPack {
+    // Entries of a list file in the pack; a missing file is empty and a broken one skipped
+    pub fn read_list<T: DeserializeOwned>(&self, file: &str) -> Vec<T> {
+        let path = self.dir.join(file);
+        let Ok(contents) = fs::read_to_string(&path) else { return Vec::new() };
+        ron::from_str(&contents).unwrap_or_else(|err| {
+            warn!("Skipping {}: {}", path.display(), err);
+            Vec::new()
+        })
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct ModList {
+    pub packs: Vec<ModPack>, // In load order
+    pub conflicts: Vec<String>,
+}
+
+impl ModList {
+    pub fn discover() -> Self {
+        let disabled: Vec<String> = fs::read_to_string(DISABLED_PATH).ok()
+            .and_then(|contents| ron::from_str(&contents).ok())
+            .unwrap_or_default();
+        let mut found: Vec<(ModPack, i32, Vec<String>)> = fs::read_dir(MODS_DIR).into_iter().flatten().flatten()
+            .map(|entry| entry.path())
+            .filter(|dir| dir.is_dir())
+            .filter_map(|dir| {
+                let id = dir.file_name()?.to_string_lossy().to_string();
+                let manifest: ModManifest = match fs::read_to_string(dir.join(MANIFEST_FILE)) {
+                    Ok(contents) => ron::from_str(&contents).unwrap_or_else(|err| {
+                        warn!("Invalid manifest of mod {}: {}", id, err);
+                        ModManifest::default()
+                    }),
+                    Err(_) => ModManifest::default(),
+                };
+                let name = if manifest.name.is_empty() { id.clone() } else { manifest.name };
+                let enabled = !disabled.contains(&id);
+                Some((ModPack { id, name, version: manifest.version, enabled, dir }, manifest.priority, manifest.after))
+            })
+            .collect();
+        found.sort_by(|a, b| (a.1, &a.0.id).cmp(&(b.1, &b.0.id)));
+
+        // Repeatedly take the first pack whose `after` packs have all been placed
+        let mut list = Self::default();
+        while !found.is_empty() {
+            let placed = |id: &String| list.packs.iter().any(|pack| pack.id == *id)
+                || !found.iter().any(|(pack, ..)| pack.id == *id); // Missing packs do not hold anything up
+            let next = match found.iter().position(|(_, _, after)| after.iter().all(placed)) {
+                Some(next) => next,
+                None => {
+                    let ids: Vec<_> = found.iter().map(|(pack, ..)| pack.id.as_str()).collect();
+                    list.conflicts.push(format!("load order cycle between {}; loading them by priority", ids.join(", ")));
+                    0
+                }
+            };
+            list.packs.push(found.remove(next).0);
+        }
+        list.find_conflicts();
+        list
+    }
+
+    pub fn enabled(&self) -> impl Iterator<Item = &ModPack> {
+        self.packs.iter().filter(|pack| pack.enabled)
+    }
+
+    fn find_conflicts(&mut self) {
+        let mut sources: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for species in builtin_species() {
+            sources.entry(species.name.to_lowercase()).or_default().push("built-in".to_string());
+        }
+        for pack in self.packs.iter().filter(|pack| pack.enabled) {
+            for species in pack.read_list::<Species>(SPECIES_FILE) {
+                sources.entry(species.name.to_lowercase()).or_default().push(pack.id.clone());
+            }
+            for file in UNSUPPORTED.iter().filter(|file| pack.dir.join(file).exists()) {
+                self.conflicts.push(format!("{} has {}, which this version cannot load", pack.id, file));
+            }
+        }
+        for (name, sources) in sources.into_iter().filter(|(_, sources)| sources.len() > 1) {
+            let winner = sources.last().cloned().unwrap_or_default();
+            self.conflicts.push(format!("species \"{}\" is defined by {}; {} wins", name, sources.join(", "), winner));
+        }
+    }
+}
+
+fn report_mods(mods: Res<ModList>) {
+    let enabled: Vec<_> = mods.enabled().map(|pack| pack.id.as_str()).collect();
+    if !enabled.is_empty() {
+        info!("🧩 Loaded {} mods in order: {}", enabled.len(), enabled.join(", "));
+    }
+    for conflict in &mods.conflicts {
+        warn!("🧩 Mod conflict: {}", conflict);
+    }
+}
+
+// === MOD LIST ===
+// F2 lists the packs in load order; clicking one switches it on or off for the next start.
+// Rebuilt each time it opens, like the relationship matrix.
+#[derive(Component)]
+pub struct ModListPanel;
+
+#[derive(Component)]
+pub struct ModToggle(usize); // Index into ModList::packs
+
+fn mod_label(pack: &ModPack) -> String {
+    format!("{} {} {} ({})", if pack.enabled { "✅" } else { "⬜" }, pack.name, pack.version, pack.id)
+}
+
+fn toggle_mod_list(
+    mut commands: Commands,
+    controls: Controls,
+    mods: Res<ModList>,
+    localization: Res<Localization>,
+    panels: Query<Entity, With<ModListPanel>>,
+) {
+    if !controls.just_pressed(Action::ToggleModList) {
+        return;
+    }
+    if !panels.is_empty() {
+        for entity in panels.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+
+    let text_style = |color: Color| TextStyle { font_size: 16.0, color, ..default() };
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(15.0),
+                left: Val::Percent(30.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(16.0)),
+                row_gap: Val::Px(4.0),
+                ..default()
+            },
+            background_color: Color::srgba(0.05, 0.05, 0.1, 0.92).into(),
+            z_index: ZIndex::Global(50),
+            ..default()
+        },
+        ModListPanel,
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section(localization.get("mods.title"), TextStyle { font_size: 24.0, color: TEXT_COLOR, ..default() }));
+        if mods.packs.is_empty() {
+            parent.spawn(TextBundle::from_section(
+                localization.format("mods.empty", &[("dir", MODS_DIR.to_string())]), text_style(TEXT_COLOR)));
+        }
+        for (index, pack) in mods.packs.iter().enumerate() {
+            parent.spawn((
+                ButtonBundle {
+                    style: Style { padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)), ..default() },
+                    background_color: Color::srgba(0.2, 0.2, 0.3, 0.9).into(),
+                    ..default()
+                },
+                ModToggle(index),
+            )).with_children(|button| {
+                button.spawn(TextBundle::from_section(mod_label(pack), text_style(TEXT_COLOR)));
+            });
+        }
+        for conflict in &mods.conflicts {
+            parent.spawn(TextBundle::from_section(format!("⚠️ {}", conflict), text_style(WARNING_COLOR)));
+        }
+        parent.spawn(TextBundle::from_section(localization.get("mods.next_start"), text_style(TEXT_COLOR)));
+    });
+}
+
+fn press_mod_toggles(
+    buttons: Query<(&Interaction, &ModToggle, &Children), Changed<Interaction>>,
+    mut texts: Query<&mut Text>,
+    mut mods: ResMut<ModList>,
+    mut io: ResMut<IoQueue>,
+) {
+    for (interaction, toggle, children) in buttons.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(pack) = mods.packs.get_mut(toggle.0) else { continue };
+        pack.enabled = !pack.enabled;
+        for child in children.iter() {
+            if let Ok(mut text) = texts.get_mut(*child) {
+                text.sections[0].value = mod_label(pack);
+            }
+        }
+        let disabled: Vec<String> = mods.packs.iter().filter(|pack| !pack.enabled).map(|pack| pack.id.clone()).collect();
+        io.write_ron(DISABLED_PATH, disabled, format!("🧩 Saved mod selection to {}", DISABLED_PATH), None);
+    }
+}
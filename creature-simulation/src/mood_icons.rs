@@ -0,0 +1,149 @@
+use bevy::prelude::*;
+use std::f32::consts::{PI, TAU};
+use crate::camera::MainCamera;
+use crate::creature::{Behavior, Creature, Needs, Wander};
+use crate::creature_lod::{CreatureDetail, CreatureRenderLod};
+use crate::creature_overlay::StatOverlay;
+use crate::optimization::LODLevel;
+use crate::reproduction::ready_to_breed;
+use crate::settings::Settings;
+use crate::species::SpeciesRegistry;
+
+const ICON_ZOOM: f32 = 1.2; // Orthographic scale beyond which icons are too small to read
+const ICON_SIZE: f32 = 3.0; // World units across
+const ICON_GAP: f32 = 2.0; // Above the creature's sprite
+const STAT_BARS_HEIGHT: f32 = 4.5; // Room left for the stat bars when they are shown
+const STARVING: f32 = 0.85; // Hunger or thirst from which a creature shows it is starving
+const CURVE_POINTS: usize = 16;
+
+// Icons are gizmo line strips, batched into one draw call like the stat bars
+#[derive(Default, Reflect, GizmoConfigGroup)]
+pub struct MoodIconGizmos;
+
+pub struct MoodIconsPlugin;
+
+impl Plugin for MoodIconsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(MoodGlyphs::new())
+            .insert_gizmo_config(MoodIconGizmos, GizmoConfig {
+                line_width: 2.0,
+                ..default()
+            })
+            .add_systems(Update, draw_mood_icons.run_if(close_zoom));
+    }
+}
+
+// === MOOD ICONS ===
+// A small icon above each creature close to the camera saying what state it is in: a Z while
+// roosting or in torpor, a ! while fleeing or defending its young, a heart while ready to breed and
+// free to, and an empty bowl when starving or dehydrated. Read off the behavior each creature last
+// decided on and its needs; most creatures show none.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MoodIcon {
+    Sleeping,
+    Alarmed,
+    InLove,
+    Starving,
+}
+
+impl MoodIcon {
+    const ALL: [MoodIcon; 4] = [MoodIcon::Sleeping, MoodIcon::Alarmed, MoodIcon::InLove, MoodIcon::Starving];
+
+    // Alarm over everything else, then hunger, which can kill
+    pub fn of(behavior: Behavior, creature: &Creature, needs: &Needs) -> Option<Self> {
+        match behavior {
+            Behavior::Flee | Behavior::Defend => Some(MoodIcon::Alarmed),
+            _ if needs.hunger >= STARVING || needs.thirst >= STARVING => Some(MoodIcon::Starving),
+            Behavior::Roost | Behavior::Hibernate => Some(MoodIcon::Sleeping),
+            Behavior::Wander | Behavior::AnswerCall | Behavior::Arrive if ready_to_breed(creature, needs) => Some(MoodIcon::InLove),
+            _ => None,
+        }
+    }
+
+    fn color(&self) -> Color {
+        match self {
+            MoodIcon::Sleeping => Color::srgb(0.7, 0.8, 1.0),
+            MoodIcon::Alarmed => Color::srgb(1.0, 0.85, 0.1),
+            MoodIcon::InLove => Color::srgb(1.0, 0.4, 0.6),
+            MoodIcon::Starving => Color::srgb(0.95, 0.55, 0.15),
+        }
+    }
+
+    // Strokes in a unit box centred on the icon, y up the screen
+    fn strokes(&self) -> Vec<Vec<Vec2>> {
+        match self {
+            MoodIcon::Sleeping => vec![vec![
+                Vec2::new(-0.4, 0.5), Vec2::new(0.4, 0.5), Vec2::new(-0.4, -0.5), Vec2::new(0.4, -0.5),
+            ]],
+            MoodIcon::Alarmed => vec![
+                vec![Vec2::new(0.0, 0.5), Vec2::new(0.0, -0.15)],
+                vec![Vec2::new(0.0, -0.38), Vec2::new(0.0, -0.5)],
+            ],
+            // The classic heart curve, scaled into the box
+            MoodIcon::InLove => vec![(0..=CURVE_POINTS)
+                .map(|i| {
+                    let t = TAU * i as f32 / CURVE_POINTS as f32;
+                    let x = 16.0 * t.sin().powi(3);
+                    let y = 13.0 * t.cos() - 5.0 * (2.0 * t).cos() - 2.0 * (3.0 * t).cos() - (4.0 * t).cos();
+                    Vec2::new(x, y + 2.0) / 34.0
+                })
+                .collect()],
+            // A half circle closed by its rim
+            MoodIcon::Starving => vec![(0..=CURVE_POINTS)
+                .map(|i| {
+                    let angle = PI + PI * i as f32 / CURVE_POINTS as f32;
+                    Vec2::new(angle.cos(), angle.sin()) * 0.5 + Vec2::Y * 0.2
+                })
+                .chain([Vec2::new(-0.5, 0.2)])
+                .collect()],
+        }
+    }
+}
+
+// Strokes of every icon, worked out once
+#[derive(Resource)]
+struct MoodGlyphs(Vec<Vec<Vec<Vec2>>>);
+
+impl MoodGlyphs {
+    fn new() -> Self {
+        Self(MoodIcon::ALL.iter().map(MoodIcon::strokes).collect())
+    }
+
+    fn get(&self, icon: MoodIcon) -> &[Vec<Vec2>] {
+        &self.0[icon as usize]
+    }
+}
+
+fn close_zoom(lod: Res<CreatureRenderLod>, projections: Query<&OrthographicProjection, With<MainCamera>>) -> bool {
+    lod.detail == CreatureDetail::Sprite && projections.get_single().is_ok_and(|projection| projection.scale < ICON_ZOOM)
+}
+
+type MoodQueryData = (&'static Transform, &'static Creature, &'static Needs, &'static Wander, &'static LODLevel, &'static Visibility);
+
+// Level on screen in either view, above the stat bars when those are shown
+fn draw_mood_icons(
+    mut gizmos: Gizmos<MoodIconGizmos>,
+    settings: Res<Settings>,
+    registry: Res<SpeciesRegistry>,
+    (glyphs, overlay): (Res<MoodGlyphs>, Res<StatOverlay>),
+    creatures: Query<MoodQueryData>,
+) {
+    let (across, up) = (settings.view.screen_to_world(Vec2::X), settings.view.screen_to_world(Vec2::Y));
+    let raise = ICON_GAP + ICON_SIZE / 2.0 + if overlay.enabled { STAT_BARS_HEIGHT } else { 0.0 };
+    for (transform, creature, needs, wander, lod, visibility) in creatures.iter() {
+        if lod.0 != 0 || *visibility == Visibility::Hidden {
+            continue;
+        }
+        let Some(icon) = MoodIcon::of(wander.behavior, creature, needs) else { continue };
+
+        let size = registry.get(creature.species).size;
+        let centre = transform.translation.truncate() + up * (size / 2.0 + raise);
+        for stroke in glyphs.get(icon) {
+            gizmos.linestrip_2d(
+                stroke.iter().map(|point| centre + (across * point.x + up * point.y) * ICON_SIZE),
+                icon.color(),
+            );
+        }
+    }
+}
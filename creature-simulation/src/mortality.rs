@@ -0,0 +1,174 @@
+use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::time::Duration;
+use crate::biome::BiomeType;
+use crate::coords::world_to_tile;
+use crate::creature::{CreatureDied, DeathCause};
+use crate::localization::Localization;
+use crate::simulation::SimClock;
+use crate::species::SpeciesRegistry;
+use crate::telemetry::Telemetry;
+use crate::world::WorldMap;
+use crate::world_stats::WorldReportPanel;
+
+const CHART_REFRESH_SECONDS: f32 = 2.0;
+const BAR_WIDTH: usize = 40; // Characters in the longest bar of a chart
+
+pub struct MortalityPlugin;
+
+impl Plugin for MortalityPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Mortality>()
+            .add_systems(Update, (
+                reset_mortality.run_if(resource_added::<WorldMap>),
+                record_mortality,
+                update_mortality_charts.run_if(on_timer(Duration::from_secs_f32(CHART_REFRESH_SECONDS))),
+            ).chain().run_if(resource_exists::<WorldMap>));
+    }
+}
+
+// === MORTALITY ===
+// Every death is counted by its cause (see DeathCause), per species and per biome it happened
+// in. The world report panel charts the totals for the world so far as one bar per species and
+// biome, split by cause, and each day's counts go to the telemetry log.
+type CauseCounts = [u32; DeathCause::ALL.len()];
+
+#[derive(Default)]
+pub struct MortalityTable {
+    pub by_species: HashMap<usize, CauseCounts>,
+    pub by_biome: HashMap<BiomeType, CauseCounts>,
+}
+
+impl MortalityTable {
+    fn add(&mut self, species: usize, biome: Option<BiomeType>, cause: DeathCause) {
+        self.by_species.entry(species).or_default()[cause as usize] += 1;
+        if let Some(biome) = biome {
+            self.by_biome.entry(biome).or_default()[cause as usize] += 1;
+        }
+    }
+
+    pub fn total(&self) -> u32 {
+        self.by_species.values().flatten().sum()
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Mortality {
+    pub total: MortalityTable,
+    today: MortalityTable,
+    day: Option<u32>,
+}
+
+#[derive(Serialize)]
+struct MortalityRecord {
+    day: u32,
+    by_species: BTreeMap<String, BTreeMap<DeathCause, u32>>,
+    by_biome: BTreeMap<String, BTreeMap<DeathCause, u32>>,
+}
+
+// Causes that killed anything, for the telemetry log
+fn nonzero(counts: &CauseCounts) -> BTreeMap<DeathCause, u32> {
+    DeathCause::ALL.into_iter()
+        .zip(counts.iter().copied())
+        .filter(|(_, count)| *count > 0)
+        .collect()
+}
+
+fn reset_mortality(mut mortality: ResMut<Mortality>) {
+    *mortality = Mortality::default();
+}
+
+fn record_mortality(
+    clock: Res<SimClock>,
+    world_map: Res<WorldMap>,
+    registry: Res<SpeciesRegistry>,
+    mut telemetry: ResMut<Telemetry>,
+    mut deaths: EventReader<CreatureDied>,
+    mut mortality: ResMut<Mortality>,
+) {
+    let day = clock.day();
+    if let Some(previous) = mortality.day.replace(day).filter(|previous| *previous != day) {
+        let today = std::mem::take(&mut mortality.today);
+        telemetry.record("mortality", &MortalityRecord {
+            day: previous,
+            by_species: today.by_species.iter()
+                .map(|(species, counts)| (registry.get(*species).name.clone(), nonzero(counts)))
+                .collect(),
+            by_biome: today.by_biome.iter()
+                .map(|(biome, counts)| (format!("{:?}", biome), nonzero(counts)))
+                .collect(),
+        });
+    }
+
+    for death in deaths.read() {
+        let biome = world_to_tile(death.position).map(|(x, y)| world_map.tiles[x][y].biome);
+        mortality.total.add(death.species, biome, death.cause);
+        mortality.today.add(death.species, biome, death.cause);
+    }
+}
+
+// One row per group: its name and death count, then a bar of one colored run per cause, scaled
+// so the group with the most deaths fills BAR_WIDTH
+fn chart_rows(rows: Vec<(String, CauseCounts)>, style: &TextStyle) -> Vec<TextSection> {
+    let most = rows.iter().map(|(_, counts)| counts.iter().sum::<u32>()).max().unwrap_or(0).max(1);
+    let mut sections = Vec::new();
+    for (name, counts) in rows {
+        sections.push(TextSection::new(format!("  {} ({}) ", name, counts.iter().sum::<u32>()), style.clone()));
+        for (cause, count) in DeathCause::ALL.into_iter().zip(counts).filter(|(_, count)| *count > 0) {
+            // Rare causes keep at least one character, so they stay visible
+            let width = ((count as usize * BAR_WIDTH) as f32 / most as f32).round().max(1.0) as usize;
+            sections.push(TextSection::new("#".repeat(width), TextStyle { color: cause.color(), ..style.clone() }));
+        }
+        sections.push(TextSection::new("\n", style.clone()));
+    }
+    sections
+}
+
+// Fills the sections between the reserve comparison and the hint of the world report panel
+fn update_mortality_charts(
+    mortality: Res<Mortality>,
+    registry: Res<SpeciesRegistry>,
+    localization: Res<Localization>,
+    mut panels: Query<&mut Text, With<WorldReportPanel>>,
+) {
+    for mut text in panels.iter_mut() {
+        let Some(style) = text.sections.first().map(|section| section.style.clone()) else { continue };
+        let end = text.sections.len() - 1;
+        if end < 2 {
+            continue;
+        }
+        let total = mortality.total.total();
+        if total == 0 {
+            text.sections.splice(2..end, []);
+            continue;
+        }
+
+        let mut sections = vec![TextSection::new(
+            localization.format("mortality.title", &[("count", total.to_string())]) + "\n  ",
+            style.clone(),
+        )];
+        for cause in DeathCause::ALL {
+            sections.push(TextSection::new("# ", TextStyle { color: cause.color(), ..style.clone() }));
+            sections.push(TextSection::new(format!("{}  ", localization.get(cause.name_key())), style.clone()));
+        }
+
+        let mut species: Vec<(usize, CauseCounts)> = mortality.total.by_species.iter().map(|(id, counts)| (*id, *counts)).collect();
+        species.sort_by_key(|(id, _)| *id);
+        sections.push(TextSection::new(format!("\n{}\n", localization.get("mortality.by_species")), style.clone()));
+        sections.extend(chart_rows(
+            species.into_iter().map(|(id, counts)| (registry.get(id).name.clone(), counts)).collect(),
+            &style,
+        ));
+
+        let biomes = BiomeType::ALL.into_iter()
+            .filter_map(|biome| mortality.total.by_biome.get(&biome).map(|counts| (localization.get(&biome.name_key()).to_string(), *counts)))
+            .collect();
+        sections.push(TextSection::new(localization.get("mortality.by_biome").to_string() + "\n", style.clone()));
+        sections.extend(chart_rows(biomes, &style));
+        sections.push(TextSection::new("\n", style.clone()));
+        text.sections.splice(2..end, sections);
+    }
+}
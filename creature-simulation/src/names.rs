@@ -0,0 +1,97 @@
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use crate::biome::BiomeType;
+
+// === NAME GENERATOR ===
+// Syllable-based names: onset + vowel + optional coda, drawn from a per-biome sound palette so a
+// tundra creature sounds different from a rainforest one. The same seed always gives the same name.
+struct Phonemes {
+    onsets: &'static [&'static str],
+    vowels: &'static [&'static str],
+    codas: &'static [&'static str], // "" entries make open syllables more likely
+}
+
+const COLD: Phonemes = Phonemes {
+    onsets: &["k", "sk", "v", "r", "h", "t", "br", "gr"],
+    vowels: &["a", "e", "i", "o", "u", "ei"],
+    codas: &["k", "rn", "ld", "st", "n", "", ""],
+};
+
+const ARID: Phonemes = Phonemes {
+    onsets: &["z", "kh", "sh", "r", "m", "t", "q", "d"],
+    vowels: &["a", "aa", "i", "u", "ai"],
+    codas: &["r", "n", "sh", "z", "", ""],
+};
+
+const LUSH: Phonemes = Phonemes {
+    onsets: &["l", "m", "n", "w", "y", "th", "f", "v"],
+    vowels: &["a", "e", "i", "o", "ei", "ia"],
+    codas: &["l", "n", "th", "", "", ""],
+};
+
+const COASTAL: Phonemes = Phonemes {
+    onsets: &["s", "m", "n", "r", "l", "p", "c", "mar"],
+    vowels: &["a", "o", "e", "i", "ea"],
+    codas: &["s", "n", "r", "", ""],
+};
+
+const TEMPERATE: Phonemes = Phonemes {
+    onsets: &["b", "d", "g", "t", "r", "h", "br", "dr"],
+    vowels: &["a", "e", "i", "o", "u"],
+    codas: &["n", "d", "r", "m", "", ""],
+};
+
+fn phonemes(biome: BiomeType) -> &'static Phonemes {
+    match biome {
+        BiomeType::Tundra | BiomeType::Alpine => &COLD,
+        BiomeType::Desert | BiomeType::Savanna | BiomeType::Badlands | BiomeType::Volcanic => &ARID,
+        BiomeType::Forest | BiomeType::TropicalRainforest | BiomeType::Wetlands => &LUSH,
+        BiomeType::Ocean | BiomeType::Coastal => &COASTAL,
+        BiomeType::Grasslands | BiomeType::Mountain | BiomeType::Caves => &TEMPERATE,
+    }
+}
+
+fn word(rng: &mut StdRng, palette: &Phonemes, syllables: usize) -> String {
+    let mut word = String::new();
+    for _ in 0..syllables {
+        word.push_str(palette.onsets.choose(rng).unwrap_or(&""));
+        word.push_str(palette.vowels.choose(rng).unwrap_or(&""));
+        word.push_str(palette.codas.choose(rng).unwrap_or(&""));
+    }
+    word
+}
+
+fn capitalized(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+// A given name, e.g. "Thelia" or "Skorn"
+pub fn creature_name(seed: u64, biome: BiomeType) -> String {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let syllables = rng.gen_range(1..=3);
+    capitalized(&word(&mut rng, phonemes(biome), syllables))
+}
+
+// A two-part scientific-looking name, e.g. "Zaarun qirash"
+pub fn species_name(seed: u64, biome: BiomeType) -> String {
+    let mut rng = StdRng::seed_from_u64(seed ^ 0x5350_4543);
+    let palette = phonemes(biome);
+    let genus = word(&mut rng, palette, 2);
+    let syllables = rng.gen_range(2..=3);
+    let epithet = word(&mut rng, palette, syllables);
+    format!("{} {}", capitalized(&genus), epithet)
+}
+
+// A place name, e.g. "Brodan Reach"
+pub fn region_name(seed: u64, biome: BiomeType) -> String {
+    const FEATURES: [&str; 6] = ["Reach", "Vale", "Hollow", "Expanse", "Shore", "Heights"];
+    let mut rng = StdRng::seed_from_u64(seed ^ 0x5245_4749);
+    let syllables = rng.gen_range(2..=3);
+    let name = capitalized(&word(&mut rng, phonemes(biome), syllables));
+    format!("{} {}", name, FEATURES.choose(&mut rng).unwrap_or(&"Reach"))
+}
@@ -0,0 +1,171 @@
+use bevy::prelude::*;
+use rand::Rng;
+use crate::coords::world_to_tile;
+use crate::creature::{move_creatures, spawn_creature, Behavior, Creature, Needs, Surroundings, Wander, CREATURE_Z, SEEK_THRESHOLD};
+use crate::genealogy::Genealogy;
+use crate::genetics::Genome;
+use crate::parental_care::Juvenile;
+use crate::calendar::Calendar;
+use crate::predation::hunt_prey;
+use crate::relationships::RelationshipTable;
+use crate::species::SpeciesRegistry;
+use crate::world::WorldMap;
+use crate::rng_streams::{RngStream, RngStreams};
+
+const CLUTCH_SIZE: (usize, usize) = (2, 4); // Eggs per clutch, inclusive
+const INCUBATION_SECONDS: f32 = 240.0; // Two days
+const NEST_SPREAD: f32 = 1.5; // World units between eggs of a clutch
+const EGG_SIZE: f32 = 1.2;
+const EGG_TOLERANCE: f32 = 0.1; // Temperature outside the parents' comfort zone eggs take without harm
+const EXPOSURE_DAMAGE: f32 = 0.05; // Per second and unit of temperature beyond the tolerance
+const FLOOD_DAMAGE: f32 = 0.1; // Per second under water
+const NEST_SCENT_RADIUS: f32 = 40.0; // World units a hungry hunter smells eggs from
+const RAID_DISTANCE: f32 = 3.0;
+const EGG_MEAL: f32 = 0.2; // Hunger removed by one egg
+
+pub struct NestingPlugin;
+
+impl Plugin for NestingPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, clear_nests.run_if(resource_added::<WorldMap>))
+            .add_systems(FixedUpdate, (incubate_eggs, raid_nests.after(hunt_prey).before(move_creatures))
+                .run_if(resource_exists::<WorldMap>));
+    }
+}
+
+// === NESTS ===
+// Oviparous species lay a clutch of eggs where they stand instead of giving birth (see
+// reproduction.rs). Each egg is an entity that hatches into a newborn after INCUBATION_SECONDS,
+// unless it gets too hot or cold for its species, is flooded by the tide or is eaten: hungry
+// hunters of the species go for eggs they smell nearby. Eggs are not kept in save files.
+#[derive(Component)]
+pub struct Egg {
+    pub species: usize,
+    pub genome: Genome, // Of the chick, already mutated
+    pub parent: (Entity, Genome), // For the genealogy; the parent may be gone by hatching time
+    pub incubation: f32, // Seconds left
+    pub damage: f32, // Lost at 1.0
+}
+
+// Lays a clutch around a position; returns the number of eggs
+pub fn lay_clutch(
+    commands: &mut Commands,
+    registry: &SpeciesRegistry,
+    species_id: usize,
+    position: Vec2,
+    parent: (Entity, Genome),
+    rng: &mut impl Rng,
+) -> usize {
+    let species = registry.get(species_id);
+    let count = rng.gen_range(CLUTCH_SIZE.0..=CLUTCH_SIZE.1);
+    for index in 0..count {
+        let offset = Vec2::from_angle(index as f32 / count as f32 * std::f32::consts::TAU) * NEST_SPREAD;
+        commands.spawn((
+            SpriteBundle {
+                sprite: Sprite {
+                    color: species.get_color().mix(&Color::WHITE, 0.6),
+                    custom_size: Some(Vec2::new(EGG_SIZE, EGG_SIZE * 1.3)),
+                    ..default()
+                },
+                transform: Transform::from_translation((position + offset).extend(CREATURE_Z - 0.5)),
+                ..default()
+            },
+            Egg {
+                species: species_id,
+                genome: parent.1.inherit(rng),
+                parent,
+                incubation: INCUBATION_SECONDS,
+                damage: 0.0,
+            },
+        ));
+    }
+    count
+}
+
+// Eggs belong to the world they were laid in
+fn clear_nests(mut commands: Commands, eggs: Query<Entity, With<Egg>>) {
+    for entity in eggs.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn incubate_eggs(
+    mut commands: Commands,
+    time: Res<Time>,
+    calendar: Res<Calendar>,
+    surroundings: Surroundings,
+    registry: Res<SpeciesRegistry>,
+    (mut genealogy, mut streams): (ResMut<Genealogy>, ResMut<RngStreams>),
+    mut eggs: Query<(Entity, &Transform, &mut Egg)>,
+) {
+    let dt = time.delta_seconds();
+    let rng = streams.get(RngStream::Creatures);
+    for (entity, transform, mut egg) in eggs.iter_mut() {
+        let position = transform.translation.truncate();
+        let species = registry.get(egg.species);
+        if let Some((x, y)) = world_to_tile(position) {
+            let exposure = (species.temperature_stress(surroundings.temperature(x, y)) - EGG_TOLERANCE).max(0.0);
+            egg.damage += exposure * EXPOSURE_DAMAGE * dt;
+            if surroundings.is_underwater(x, y) {
+                egg.damage += FLOOD_DAMAGE * dt;
+            }
+        }
+        if egg.damage >= 1.0 {
+            debug!("A {} egg failed at {:?}", species.name, position);
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        egg.incubation -= dt;
+        if egg.incubation > 0.0 {
+            continue;
+        }
+        let heading = rng.gen_range(0.0..std::f32::consts::TAU);
+        let chick = spawn_creature(&mut commands, &registry, egg.species, position, heading, egg.genome);
+        let (parent, parent_genome) = egg.parent;
+        commands.entity(chick).insert(Juvenile::of(parent));
+        genealogy.record_birth((parent, egg.species, parent_genome), (chick, egg.genome), calendar.day);
+        commands.entity(entity).despawn();
+    }
+}
+
+// Hungry hunters head for the nearest egg they smell, unless already chasing prey, and eat it
+fn raid_nests(
+    mut commands: Commands,
+    relationships: Res<RelationshipTable>,
+    eggs: Query<(Entity, &Transform, &Egg)>,
+    mut hunters: Query<(&Transform, &Creature, &mut Needs, &mut Wander)>,
+) {
+    if eggs.is_empty() {
+        return;
+    }
+    let mut eaten: Vec<Entity> = Vec::new();
+    for (transform, creature, mut needs, mut wander) in hunters.iter_mut() {
+        if needs.hunger <= SEEK_THRESHOLD || wander.behavior == Behavior::Hunt || !relationships.is_hunter(creature.species) {
+            continue;
+        }
+        let position = transform.translation.truncate();
+        let nearest = eggs.iter()
+            .filter(|(entity, _, egg)| relationships.preys_on(creature.species, egg.species) && !eaten.contains(entity))
+            .map(|(entity, egg_transform, _)| (entity, egg_transform.translation.truncate()))
+            .map(|(entity, egg_position)| (entity, egg_position, egg_position.distance(position)))
+            .filter(|(_, _, distance)| *distance < NEST_SCENT_RADIUS)
+            .min_by(|a, b| a.2.total_cmp(&b.2));
+        let Some((egg, egg_position, distance)) = nearest else { continue };
+
+        if distance < RAID_DISTANCE {
+            eaten.push(egg);
+            needs.hunger = (needs.hunger - EGG_MEAL).max(0.0);
+            wander.target = None;
+            wander.behavior = Behavior::Arrive;
+        } else {
+            wander.target = Some(egg_position);
+            wander.behavior = Behavior::RaidNest;
+            wander.search_cooldown = 2.0;
+        }
+    }
+    for entity in eaten {
+        commands.entity(entity).despawn();
+    }
+}
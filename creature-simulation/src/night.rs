@@ -0,0 +1,60 @@
+use bevy::prelude::*;
+use crate::calendar::{moonlight, Calendar};
+use crate::simulation::SimClock;
+
+const NIGHT_SHADE: f32 = 0.5; // Darkness of a moonless midnight
+const MOONLIGHT_SHARE: f32 = 0.5; // Share of the darkness a full moon lifts
+const TWILIGHT: f32 = 0.1; // Share of the day spent fading in or out, centred on dusk and dawn
+
+pub struct NightPlugin;
+
+impl Plugin for NightPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Startup, spawn_night_shade)
+            .add_systems(Update, update_night_shade);
+    }
+}
+
+// === NIGHT ===
+// A dark veil over the map between dusk and dawn (SimClock::is_night), thinner the fuller the
+// moon. Drawn under every panel so the UI stays readable.
+#[derive(Component)]
+pub struct NightShade;
+
+// 0.0 in daylight, 1.0 at night, ramping through twilight
+fn darkness(time_of_day: f32) -> f32 {
+    let from_noon = (time_of_day - 0.5).abs(); // Night starts 0.3 of a day from noon
+    ((from_noon - 0.3 + TWILIGHT / 2.0) / TWILIGHT).clamp(0.0, 1.0)
+}
+
+fn spawn_night_shade(mut commands: Commands) {
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                ..default()
+            },
+            background_color: Color::NONE.into(),
+            z_index: ZIndex::Global(-1),
+            ..default()
+        },
+        NightShade,
+    ));
+}
+
+fn update_night_shade(
+    clock: Res<SimClock>,
+    calendar: Res<Calendar>,
+    mut shades: Query<&mut BackgroundColor, With<NightShade>>,
+) {
+    let alpha = NIGHT_SHADE * darkness(clock.time_of_day()) * (1.0 - MOONLIGHT_SHARE * moonlight(calendar.day));
+    let color = Color::srgba(0.02, 0.03, 0.12, alpha);
+    for mut background in shades.iter_mut() {
+        if background.0 != color {
+            background.0 = color;
+        }
+    }
+}
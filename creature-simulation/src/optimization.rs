@@ -1,81 +1,345 @@
 use bevy::prelude::*;
-use std::collections::HashMap;
-use bevy::tasks::{AsyncComputeTaskPool, Task};
-use futures_lite::future;
-use std::sync::{Arc, Mutex};
-use crate::world::{WorldMap, WorldGenerator, WORLD_SIZE};
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::sync::{Arc, OnceLock};
+use crate::coords::{WorldPos, CHUNKS_PER_SIDE, CHUNK_SIZE, TILE_SIZE, WORLD_EXTENT};
+use crate::world::{Topology, WorldMap, WorldGenerator, WORLD_SIZE};
 use crate::environment::EnvironmentType;
+use crate::generation_service::GenerationId;
 
 // === CHUNK SYSTEM ===
-pub const CHUNK_SIZE: usize = 32;
 pub const RENDER_DISTANCE: f32 = 200.0; // Reduced for testing
 
 #[derive(Resource, Default)]
 pub struct ChunkManager {
     pub loaded_chunks: HashMap<(i32, i32), ChunkData>,
     pub active_chunks: Vec<(i32, i32)>,
+    pub dirty_chunks: HashSet<(i32, i32)>, // Re-rendered next frame because their tiles were edited
+    pub scratch: ChunkScratch,
 }
 
+// Buffers reused from frame to frame and chunk to chunk, cleared rather than reallocated, so
+// chunk loading does not allocate once they have grown to fit
 #[derive(Default)]
-pub struct ChunkData {
+pub struct ChunkScratch {
+    pub visible: Vec<(i32, i32)>, // Swapped with active_chunks each frame
     pub entities: Vec<Entity>,
+    pub instanced: [Vec<(Vec3, f32)>; EnvironmentType::ALL.len()], // Positions and rotations by element type
+}
+
+pub struct ChunkData {
+    pub root: Entity, // ChunkRoot parent of every tile and environment entity in the chunk
     pub is_loaded: bool,
 }
 
-// === LOD SYSTEM ===
+// Parent of a rendered chunk's entities; despawning it recursively unloads the whole chunk.
+// Sits at the origin so children keep world-space transforms - or a world width away, for copies
+// shown past a wrapped edge.
 #[derive(Component)]
+pub struct ChunkRoot {
+    pub source: (i32, i32), // Chunk of the map it shows; differs from the chunk it sits in past a wrapped edge
+}
+
+// === LOD SYSTEM ===
+#[derive(Component, PartialEq)]
 pub struct LODLevel(pub u8); // 0 = highest detail, 3 = lowest
 
 // === SPATIAL HASHING ===
-#[derive(Resource)]
-pub struct SpatialHash {
-    pub cell_size: f32,
-    pub grid: HashMap<(i32, i32), Vec<Entity>>,
+// Creature positions bucketed by cell, for finding neighbours without scanning every creature.
+// Kept in two copies: systems read the front one through SpatialView, a cheap shared handle, while
+// each tick the changes (creatures that moved or went away) are written to the back one and the
+// two are swapped. The back copy then lags one tick behind, so those changes are replayed onto it
+// before the next tick's. Entries are packed densely, a removed one taking the place of the last.
+//
+// The uniform cells suit searches about a cell across. Wider searches, and the k nearest entities
+// to a point, go through a quadtree over the same entries instead, built the first time such a
+// query comes in after a change; each query says which of the two it walks (SpatialSearch).
+const SPATIAL_CELL_SIZE: f32 = 64.0; // About the largest interaction radius
+const QUAD_LEAF_SIZE: usize = 16; // Entities a quadtree node holds before it is split
+const QUAD_MAX_DEPTH: u32 = 12; // Deeper than this, crowded nodes stay leaves
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SpatialSearch {
+    Grid, // Radii up to a cell or two
+    Quadtree, // Wide areas
+}
+
+pub enum SpatialChange {
+    Moved(Entity, Vec2),
+    Removed(Entity),
 }
 
-impl Default for SpatialHash {
+#[derive(Clone)]
+pub struct SpatialIndex {
+    cell_size: f32,
+    entries: Vec<(Entity, Vec2, (i32, i32))>, // Position and cell of each entity
+    slots: HashMap<Entity, usize>, // Index into entries
+    cells: HashMap<(i32, i32), Vec<Entity>>, // Only cells with someone in them
+    tree: OnceLock<Quadtree>, // Dropped on every change
+}
+
+impl Default for SpatialIndex {
     fn default() -> Self {
         Self {
-            cell_size: 64.0, // Adjust based on typical interaction radius
-            grid: HashMap::new(),
+            cell_size: SPATIAL_CELL_SIZE,
+            entries: Vec::new(),
+            slots: HashMap::new(),
+            cells: HashMap::new(),
+            tree: OnceLock::new(),
         }
     }
 }
 
-impl SpatialHash {
-    pub fn insert(&mut self, entity: Entity, position: Vec3) {
-        let cell = self.world_to_cell(position);
-        self.grid.entry(cell).or_default().push(entity);
+impl SpatialIndex {
+    // Entities within the radius, with the positions they were indexed at
+    pub fn within(&self, center: Vec2, radius: f32, search: SpatialSearch) -> Vec<(Entity, Vec2)> {
+        let mut found = Vec::new();
+        match search {
+            SpatialSearch::Grid => {
+                let (min, max) = (self.cell(center - Vec2::splat(radius)), self.cell(center + Vec2::splat(radius)));
+                for cell in (min.0..=max.0).flat_map(|x| (min.1..=max.1).map(move |y| (x, y))) {
+                    let Some(entities) = self.cells.get(&cell) else { continue };
+                    found.extend(entities.iter()
+                        .map(|entity| (*entity, self.entries[self.slots[entity]].1))
+                        .filter(|(_, position)| position.distance(center) <= radius));
+                }
+            }
+            SpatialSearch::Quadtree => self.tree().within(center, radius, &mut found),
+        }
+        found
     }
-    
-    pub fn remove(&mut self, entity: Entity, position: Vec3) {
-        let cell = self.world_to_cell(position);
-        if let Some(entities) = self.grid.get_mut(&cell) {
-            entities.retain(|&e| e != entity);
+
+    // Up to k entities, closest first, however far away
+    pub fn nearest(&self, center: Vec2, k: usize) -> Vec<(Entity, Vec2)> {
+        self.tree().nearest(center, k)
+    }
+
+    fn tree(&self) -> &Quadtree {
+        self.tree.get_or_init(|| Quadtree::build(&self.entries))
+    }
+
+    fn apply(&mut self, change: &SpatialChange) {
+        self.tree.take();
+        match *change {
+            SpatialChange::Moved(entity, position) => {
+                let cell = self.cell(position);
+                let Some(&slot) = self.slots.get(&entity) else {
+                    self.slots.insert(entity, self.entries.len());
+                    self.entries.push((entity, position, cell));
+                    self.cells.entry(cell).or_default().push(entity);
+                    return;
+                };
+                let previous = std::mem::replace(&mut self.entries[slot], (entity, position, cell)).2;
+                if previous != cell {
+                    self.leave_cell(entity, previous);
+                    self.cells.entry(cell).or_default().push(entity);
+                }
+            }
+            SpatialChange::Removed(entity) => {
+                let Some(slot) = self.slots.remove(&entity) else { return };
+                let (_, _, cell) = self.entries.swap_remove(slot);
+                if let Some(&(moved, _, _)) = self.entries.get(slot) {
+                    self.slots.insert(moved, slot);
+                }
+                self.leave_cell(entity, cell);
+            }
         }
     }
-    
-    pub fn get_nearby(&self, position: Vec3, radius: f32) -> Vec<Entity> {
-        let mut nearby = Vec::new();
-        let min_cell = self.world_to_cell(position - Vec3::splat(radius));
-        let max_cell = self.world_to_cell(position + Vec3::splat(radius));
-        
-        for x in min_cell.0..=max_cell.0 {
-            for y in min_cell.1..=max_cell.1 {
-                if let Some(entities) = self.grid.get(&(x, y)) {
-                    nearby.extend(entities);
+
+    fn leave_cell(&mut self, entity: Entity, cell: (i32, i32)) {
+        let Some(entities) = self.cells.get_mut(&cell) else { return };
+        if let Some(index) = entities.iter().position(|&e| e == entity) {
+            entities.swap_remove(index);
+        }
+        if entities.is_empty() {
+            self.cells.remove(&cell);
+        }
+    }
+
+    fn cell(&self, position: Vec2) -> (i32, i32) {
+        ((position.x / self.cell_size).floor() as i32, (position.y / self.cell_size).floor() as i32)
+    }
+}
+
+// Nodes split into four equal quadrants; every node's entities sit together in `items`
+#[derive(Clone)]
+struct Quadtree {
+    nodes: Vec<QuadNode>,
+    items: Vec<(Entity, Vec2)>,
+}
+
+#[derive(Clone)]
+struct QuadNode {
+    bounds: Rect,
+    items: (usize, usize), // Range in Quadtree::items
+    children: Option<usize>, // First of four consecutive nodes
+}
+
+impl Quadtree {
+    fn build(entries: &[(Entity, Vec2, (i32, i32))]) -> Self {
+        let items: Vec<(Entity, Vec2)> = entries.iter().map(|(entity, position, _)| (*entity, *position)).collect();
+        let bounds = items.iter().fold(None, |bounds: Option<Rect>, (_, position)| {
+            Some(bounds.map_or(Rect::from_corners(*position, *position), |bounds| bounds.union_point(*position)))
+        });
+        let mut tree = Self { nodes: Vec::new(), items };
+        if let Some(bounds) = bounds {
+            tree.nodes.push(QuadNode { bounds, items: (0, tree.items.len()), children: None });
+            tree.split(0, 0);
+        }
+        tree
+    }
+
+    fn split(&mut self, node: usize, depth: u32) {
+        let QuadNode { bounds, items: (start, end), .. } = self.nodes[node];
+        if end - start <= QUAD_LEAF_SIZE || depth >= QUAD_MAX_DEPTH {
+            return;
+        }
+        // West then east, each south then north
+        let center = bounds.center();
+        let items = &mut self.items[start..end];
+        let east = partition(items, |position| position.x < center.x);
+        let (west_items, east_items) = items.split_at_mut(east);
+        let west_north = partition(west_items, |position| position.y < center.y);
+        let east_north = east + partition(east_items, |position| position.y < center.y);
+        let ranges = [(0, west_north), (west_north, east), (east, east_north), (east_north, end - start)];
+        let quadrants = [
+            Rect::from_corners(bounds.min, center),
+            Rect::new(bounds.min.x, center.y, center.x, bounds.max.y),
+            Rect::new(center.x, bounds.min.y, bounds.max.x, center.y),
+            Rect::from_corners(center, bounds.max),
+        ];
+
+        let first = self.nodes.len();
+        self.nodes[node].children = Some(first);
+        for (bounds, (from, to)) in quadrants.into_iter().zip(ranges) {
+            self.nodes.push(QuadNode { bounds, items: (start + from, start + to), children: None });
+        }
+        for child in first..first + 4 {
+            self.split(child, depth + 1);
+        }
+    }
+
+    fn within(&self, center: Vec2, radius: f32, found: &mut Vec<(Entity, Vec2)>) {
+        let mut stack = if self.nodes.is_empty() { Vec::new() } else { vec![0] };
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            if distance_to_rect(node.bounds, center) > radius {
+                continue;
+            }
+            match node.children {
+                Some(first) => stack.extend(first..first + 4),
+                None => found.extend(self.items[node.items.0..node.items.1].iter()
+                    .filter(|(_, position)| position.distance(center) <= radius)),
+            }
+        }
+    }
+
+    // Nodes are visited nearest first, until none can hold anything closer than the k found
+    fn nearest(&self, center: Vec2, k: usize) -> Vec<(Entity, Vec2)> {
+        let mut best: Vec<(f32, Entity, Vec2)> = Vec::with_capacity(k + 1);
+        if self.nodes.is_empty() || k == 0 {
+            return Vec::new();
+        }
+        let mut queue = BinaryHeap::from([Reverse(Ranked(distance_to_rect(self.nodes[0].bounds, center), 0))]);
+        while let Some(Reverse(Ranked(distance, node))) = queue.pop() {
+            if best.len() == k && distance > best[k - 1].0 {
+                break;
+            }
+            let node = &self.nodes[node];
+            match node.children {
+                Some(first) => queue.extend((first..first + 4)
+                    .map(|child| Reverse(Ranked(distance_to_rect(self.nodes[child].bounds, center), child)))),
+                None => {
+                    for (entity, position) in &self.items[node.items.0..node.items.1] {
+                        let distance = position.distance(center);
+                        let index = best.partition_point(|(closer, _, _)| *closer <= distance);
+                        if index < k {
+                            best.insert(index, (distance, *entity, *position));
+                            best.truncate(k);
+                        }
+                    }
                 }
             }
         }
-        nearby
+        best.into_iter().map(|(_, entity, position)| (entity, position)).collect()
     }
-    
-    pub fn clear(&mut self) {
-        self.grid.clear();
+}
+
+// Node of the quadtree ordered by its distance from the query point
+struct Ranked(f32, usize);
+
+impl PartialEq for Ranked {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
     }
-    
-    fn world_to_cell(&self, position: Vec3) -> (i32, i32) {
-        ((position.x / self.cell_size) as i32, (position.y / self.cell_size) as i32)
+}
+
+impl Eq for Ranked {}
+
+impl PartialOrd for Ranked {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Ranked {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.0.total_cmp(&other.0).then(self.1.cmp(&other.1))
+    }
+}
+
+// Moves the items below the split to the front and returns how many there are
+fn partition(items: &mut [(Entity, Vec2)], below: impl Fn(Vec2) -> bool) -> usize {
+    let mut split = 0;
+    for index in 0..items.len() {
+        if below(items[index].1) {
+            items.swap(index, split);
+            split += 1;
+        }
+    }
+    split
+}
+
+fn distance_to_rect(rect: Rect, point: Vec2) -> f32 {
+    point.distance(point.clamp(rect.min, rect.max))
+}
+
+#[derive(Resource, Default)]
+pub struct SpatialHash {
+    front: Arc<SpatialIndex>,
+    back: Arc<SpatialIndex>,
+    lagging: Vec<SpatialChange>, // Applied to the front copy but not yet to the back one
+}
+
+impl SpatialHash {
+    // Never waits on readers: a back copy still held through an old view is cloned instead
+    pub fn update(&mut self, changes: impl IntoIterator<Item = SpatialChange>) {
+        let back = Arc::make_mut(&mut self.back);
+        for change in self.lagging.drain(..) {
+            back.apply(&change);
+        }
+        for change in changes {
+            back.apply(&change);
+            self.lagging.push(change);
+        }
+        std::mem::swap(&mut self.front, &mut self.back);
+    }
+
+    pub fn view(&self) -> SpatialView {
+        SpatialView(Arc::clone(&self.front))
+    }
+}
+
+// Read-only handle on the latest positions; systems reading it do not conflict with the one
+// writing SpatialHash, and it can be cloned into tasks
+#[derive(Resource, Clone, Default)]
+pub struct SpatialView(Arc<SpatialIndex>);
+
+impl std::ops::Deref for SpatialView {
+    type Target = SpatialIndex;
+
+    fn deref(&self) -> &SpatialIndex {
+        &self.0
     }
 }
 
@@ -147,11 +411,17 @@ impl CompressedWorldData {
         }
     }
 
+    // Elevation at a tile, blended from the four nearest samples
     pub fn get_elevation(&self, x: usize, y: usize) -> f32 {
-        let sample_x = (x / self.sample_resolution).min(self.elevation_samples.len() - 1);
-        let sample_y = (y / self.sample_resolution).min(self.elevation_samples.len() - 1);
-        // Simple lookup - could be improved with interpolation
-        self.elevation_samples.get(sample_x * sample_y).copied().unwrap_or(0.0)
+        let side = WORLD_SIZE.div_ceil(self.sample_resolution);
+        let (fx, fy) = (x as f32 / self.sample_resolution as f32, y as f32 / self.sample_resolution as f32);
+        let (x0, y0) = (fx as usize, fy as usize);
+        let (x1, y1) = ((x0 + 1).min(side - 1), (y0 + 1).min(side - 1));
+        let sample = |sx: usize, sy: usize| self.elevation_samples.get(sx * side + sy).copied().unwrap_or(0.0);
+        let (tx, ty) = (fx - x0 as f32, fy - y0 as f32);
+        let low = sample(x0, y0) * (1.0 - tx) + sample(x1, y0) * tx;
+        let high = sample(x0, y1) * (1.0 - tx) + sample(x1, y1) * tx;
+        low * (1.0 - ty) + high * ty
     }
 }
 
@@ -183,44 +453,41 @@ pub struct InstancedSprites {
 }
 
 // === ASYNC WORLD GENERATION ===
-#[derive(Component)]
-pub struct WorldGenerationTask {
-    pub task: Task<WorldMap>,
-    pub progress_tracker: Arc<Mutex<(f32, String)>>,
-}
+// The world the loading screen is waiting for, queued with the generation service: its job and
+// base seed, kept for retrying a failed generation
+#[derive(Resource, Default)]
+pub struct PendingWorld(pub Option<(GenerationId, u32)>);
 
 // === UTILITY FUNCTIONS ===
-pub fn calculate_visible_chunks(camera_pos: Vec3) -> Vec<(i32, i32)> {
-    let tile_size = 4.0; // From render.rs
-    let chunk_x = (camera_pos.x / (CHUNK_SIZE as f32 * tile_size)) as i32;
-    let chunk_y = (camera_pos.y / (CHUNK_SIZE as f32 * tile_size)) as i32;
-    let render_chunks = (RENDER_DISTANCE / (CHUNK_SIZE as f32 * tile_size)) as i32 + 1;
+// Chunks within RENDER_DISTANCE times reach of the camera, written over `visible_chunks`. Past a
+// wrapped edge the coordinates keep counting (-1, -2, ... or CHUNKS_PER_SIDE and up); those show
+// the chunks from the other side of the map, see wrapped_chunk.
+pub fn calculate_visible_chunks(camera_pos: Vec3, reach: f32, topology: Topology, visible_chunks: &mut Vec<(i32, i32)>) {
+    let size = WORLD_SIZE as i32;
+    let to_chunk = |tile: i32, wraps: bool| {
+        if wraps {
+            tile.div_euclid(size) * CHUNKS_PER_SIDE + tile.rem_euclid(size) / CHUNK_SIZE as i32
+        } else {
+            tile.div_euclid(CHUNK_SIZE as i32)
+        }
+    };
+    let grid = WorldPos(camera_pos.truncate()).grid();
+    let chunk_x = to_chunk(grid.x, topology.wraps_x());
+    let chunk_y = to_chunk(grid.y, topology.wraps_y());
+    let render_chunks = (RENDER_DISTANCE * reach / (CHUNK_SIZE as f32 * TILE_SIZE)) as i32 + 1;
     
-    let mut visible_chunks = Vec::new();
+    visible_chunks.clear();
     for x in (chunk_x - render_chunks)..=(chunk_x + render_chunks) {
         for y in (chunk_y - render_chunks)..=(chunk_y + render_chunks) {
             visible_chunks.push((x, y));
         }
     }
-    visible_chunks
 }
 
-pub fn world_to_chunk_coord(world_x: usize, world_y: usize) -> (i32, i32) {
-    ((world_x / CHUNK_SIZE) as i32, (world_y / CHUNK_SIZE) as i32)
+// The map chunk a visible chunk shows, and how far it is moved from where that chunk lies: a
+// world width for every lap around a wrapped axis
+pub fn wrapped_chunk(coord: (i32, i32), topology: Topology) -> ((i32, i32), Vec2) {
+    let wrap = |chunk: i32, wraps: bool| if wraps { (chunk.rem_euclid(CHUNKS_PER_SIDE), chunk.div_euclid(CHUNKS_PER_SIDE)) } else { (chunk, 0) };
+    let ((x, laps_x), (y, laps_y)) = (wrap(coord.0, topology.wraps_x()), wrap(coord.1, topology.wraps_y()));
+    ((x, y), Vec2::new(laps_x as f32, laps_y as f32) * WORLD_EXTENT)
 }
-
-pub fn chunk_to_world_bounds(chunk_x: i32, chunk_y: i32) -> (usize, usize, usize, usize) {
-    // Calculate bounds in i32 first to handle negative coordinates properly
-    let start_x_i32 = chunk_x * CHUNK_SIZE as i32;
-    let start_y_i32 = chunk_y * CHUNK_SIZE as i32;
-    let end_x_i32 = (chunk_x + 1) * CHUNK_SIZE as i32;
-    let end_y_i32 = (chunk_y + 1) * CHUNK_SIZE as i32;
-    
-    // Clamp to valid world bounds and convert to usize
-    let start_x = start_x_i32.max(0).min(WORLD_SIZE as i32) as usize;
-    let start_y = start_y_i32.max(0).min(WORLD_SIZE as i32) as usize;
-    let end_x = end_x_i32.max(0).min(WORLD_SIZE as i32) as usize;
-    let end_y = end_y_i32.max(0).min(WORLD_SIZE as i32) as usize;
-    
-    (start_x, start_y, end_x, end_y)
-}
\ No newline at end of file
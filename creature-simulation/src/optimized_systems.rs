@@ -1,15 +1,24 @@
+use bevy::ecs::system::SystemParam;
 use bevy::prelude::*;
-use bevy::tasks::{AsyncComputeTaskPool, Task};
-use futures_lite::future;
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex};
 use std::time::{Instant, Duration};
-use crate::world::{WorldMap, WorldGenerator, WORLD_SIZE};
+use crate::camera::MainCamera;
+use crate::coords::{chunk_tile_bounds, tile_to_world, TILE_SIZE};
+use crate::world::{WorldMap, WORLD_SIZE};
 use crate::biome::BiomeType;
-use crate::environment::{EnvironmentSprite, SwayAnimation, EnvironmentType, get_environment_elements};
-use crate::render::{WorldTile, TILE_SIZE};
+use crate::environment::{environment_elements_at_density, EnvironmentSprite, EnvironmentType, SwayAnimation, VegetationDensity};
+use crate::render::WorldTile;
 use crate::optimization::*;
-use crate::loading::LoadingState;
+use crate::loading::{CancelGenerationButton, LoadingStage, LoadingState};
+use crate::localization::Localization;
+use crate::settings::{Settings, motion_enabled};
+use crate::creature::Creature;
+use crate::errors::{RetryAction, SimError, SimFailure};
+use crate::generation_service::{GenerationJob, GenerationOutput, GenerationPriority, GenerationService};
+
+pub const DEFAULT_WORLD_SEED: u32 = 12345;
+const CLIFF_LINE_WIDTH: f32 = 0.8;
+const CLIFF_COLOR: Color = Color::srgb(0.2, 0.16, 0.12);
+const CLIFF_Z: f32 = 0.5; // Over the tiles, under the props
 
 pub struct OptimizationPlugin;
 
@@ -17,205 +26,225 @@ impl Plugin for OptimizationPlugin {
     fn build(&self, app: &mut App) {
         app
             .init_resource::<ChunkManager>()
+            .init_resource::<PendingWorld>()
             .init_resource::<SpatialHash>()
+            .init_resource::<SpatialView>()
             .init_resource::<SharedAnimationState>()
             .add_systems(Update, (
-                update_shared_animation_state,
+                update_shared_animation_state.run_if(motion_enabled),
                 update_lod_system,
-                optimized_sway_system,
+                optimized_sway_system.run_if(motion_enabled),
                 chunk_management_system,
-                check_world_generation_system,
-                update_spatial_hash_system,
-            ));
+                (cancel_world_generation, check_world_generation_system).chain(),
+            ))
+            // After the tick's movement, so the next tick's systems see where everyone ended up
+            .add_systems(FixedPostUpdate, (update_spatial_hash_system, publish_spatial_view).chain());
     }
 }
 
 // === ASYNC WORLD GENERATION ===
-pub fn start_world_generation(mut commands: Commands) {
-    let start_time = Instant::now();
-    info!("⏱️ TIMING: Starting world generation at {:?}", start_time);
-    
-    let task_pool = AsyncComputeTaskPool::get();
-    
-    // Create progress tracker
-    let progress_tracker = Arc::new(Mutex::new((0.0, "🌍 Initializing world...".to_string())));
-    let progress_tracker_clone = Arc::clone(&progress_tracker);
-    
-    let task = task_pool.spawn(async move {
-        let gen_start = Instant::now();
-        info!("⏱️ TIMING: World generation task started in background thread at {:?}", gen_start);
-        
-        let generator = WorldGenerator::new(Some(12345));
-        let noise_setup_time = gen_start.elapsed();
-        info!("⏱️ TIMING: Noise setup took: {:?}", noise_setup_time);
-        
-        let map_gen_start = Instant::now();
-        info!("⏱️ TIMING: Starting world map generation at {:?}", map_gen_start);
-        
-        // Create progress callback with timing
-        let progress_callback: Box<dyn Fn(f32, &str) + Send + Sync> = Box::new(move |progress: f32, message: &str| {
-            if let Ok(mut tracker) = progress_tracker_clone.lock() {
-                tracker.0 = progress * 0.7; // Scale to 0-70% of total progress
-                tracker.1 = message.to_string();
-                info!("⏱️ TIMING: Progress {:.1}% - {} (elapsed: {:?})", 
-                      progress * 100.0, message, map_gen_start.elapsed());
-            }
-        });
-        
-        let world_map = generator.generate_world_with_progress(Some(progress_callback));
-        let map_gen_time = map_gen_start.elapsed();
-        info!("⏱️ TIMING: World map generation completed! Took: {:?}", map_gen_time);
-        world_map
-    });
-    
-    commands.spawn(WorldGenerationTask {
-        task,
-        progress_tracker,
-    });
-    
-    let spawn_time = start_time.elapsed();
-    info!("⏱️ TIMING: World generation task spawned in: {:?}", spawn_time);
+// What systems start a new world through
+#[derive(SystemParam)]
+pub struct WorldGeneration<'w> {
+    service: ResMut<'w, GenerationService>,
+    pending: ResMut<'w, PendingWorld>,
+}
+
+impl WorldGeneration<'_> {
+    // Replaces any world still being generated
+    pub fn start(&mut self, settings: &Settings, base_seed: u32) {
+        if let Some((previous, _)) = self.pending.0.take() {
+            self.service.discard(previous);
+        }
+        let id = self.service.request(GenerationJob::world(settings, base_seed), GenerationPriority::Urgent);
+        self.pending.0 = Some((id, base_seed));
+        debug!(seed = base_seed, "World generation requested");
+    }
+}
+
+pub fn start_world_generation(mut generation: WorldGeneration, settings: Res<Settings>) {
+    generation.start(&settings, DEFAULT_WORLD_SEED);
+}
+
+// The loading screen's cancel button cancels the job; the generator stops at the next chunk
+fn cancel_world_generation(
+    buttons: Query<&Interaction, (Changed<Interaction>, With<CancelGenerationButton>)>,
+    mut service: ResMut<GenerationService>,
+    pending: Res<PendingWorld>,
+) {
+    if !buttons.iter().any(|interaction| *interaction == Interaction::Pressed) {
+        return;
+    }
+    if let Some((id, _)) = pending.0 {
+        info!("🛑 World generation cancelled");
+        service.cancel(id);
+    }
 }
 
 fn check_world_generation_system(
     mut commands: Commands,
-    mut tasks: Query<(Entity, &mut WorldGenerationTask)>,
+    mut generation: WorldGeneration,
     mut loading_state: ResMut<LoadingState>,
+    localization: Res<Localization>,
     time: Res<Time>,
+    mut failures: EventWriter<SimFailure>,
 ) {
-    // Update loading progress from the progress tracker
-    for (entity, mut task_wrapper) in tasks.iter_mut() {
-        // Get progress from the shared tracker
-        if let Ok(tracker) = task_wrapper.progress_tracker.lock() {
-            let (progress, message) = tracker.clone();
-            loading_state.progress = (loading_state.progress * 0.9 + progress * 0.1).max(progress); // Smooth progress
-            loading_state.current_message = message;
+    let Some((id, base_seed)) = generation.pending.0 else { return };
+    if let Some((progress, message_key)) = generation.service.progress(id) {
+        loading_state.advance(LoadingStage::Generation, progress, time.delta_seconds());
+        loading_state.current_message = localization.get(&message_key).to_string();
+    }
+
+    let Some(result) = generation.service.take(id) else { return };
+    generation.pending.0 = None;
+    let (world_map, report) = match result {
+        Ok(GenerationOutput::World(generated)) => *generated,
+        Ok(_) => return,
+        // Cancelled: back to picking a seed, starting from the cancelled one
+        Err(SimError::Cancelled) => {
+            info!("🌍 Generation of seed {} cancelled", base_seed);
+            loading_state.restart();
+            loading_state.selecting_seed = Some(base_seed);
+            loading_state.current_message = localization.get("loading.cancelled").to_string();
+            return;
         }
-        
-        if let Some(world_map) = future::block_on(future::poll_once(&mut task_wrapper.task)) {
-            let compression_start = Instant::now();
-            info!("⏱️ TIMING: World generation task completed! Starting compression at {:?}", compression_start);
-            
-            // Update loading to 75%
-            loading_state.progress = 0.75;
-            loading_state.current_message = "🗜️ Compressing world data...".to_string();
-            
-            // Convert to compressed format
-            let compressed_data = CompressedWorldData::from_world_map(&world_map);
-            let compression_time = compression_start.elapsed();
-            info!("⏱️ TIMING: World compression took: {:?}", compression_time);
-            
-            let resource_insert_start = Instant::now();
-            info!("⏱️ TIMING: Starting resource insertion at {:?}", resource_insert_start);
-            
-            // Update loading to 80%
-            loading_state.progress = 0.8;
-            loading_state.current_message = "🎨 Preparing the canvas...".to_string();
-            
-            commands.insert_resource(compressed_data);
-            commands.insert_resource(world_map);
-            commands.entity(entity).despawn();
-            
-            let resource_insert_time = resource_insert_start.elapsed();
-            info!("⏱️ TIMING: Resource insertion took: {:?}", resource_insert_time);
-            
-            // Mark world as ready and start rendering phase
-            loading_state.progress = 0.72;
-            loading_state.world_ready = true;
-            loading_state.current_message = "📐 Calculating camera position...".to_string();
-            
-            info!("⏱️ TIMING: World map resource inserted! Ready to render.");
+        Err(err) => {
+            failures.send(SimFailure::with_retry(err, RetryAction::GenerateWorld { seed: base_seed }));
+            return;
         }
-    }
+    };
+
+    let _install = info_span!("world_install", seed = world_map.seed).entered();
+    let compression_start = Instant::now();
+    
+    loading_state.advance(LoadingStage::Install, 0.0, 0.0);
+    loading_state.current_message = localization.get("loading.compressing").to_string();
+    
+    // Convert to compressed format
+    let compressed_data = CompressedWorldData::from_world_map(&world_map);
+    debug!(elapsed = ?compression_start.elapsed(), "World compressed");
+    
+    let resource_insert_start = Instant::now();
+    
+    loading_state.advance(LoadingStage::Install, 0.5, 0.0);
+    loading_state.current_message = localization.get("loading.preparing_canvas").to_string();
+    
+    commands.insert_resource(compressed_data);
+    // Removed first so systems keyed on `resource_added` set up again for a regenerated world
+    commands.remove_resource::<WorldMap>();
+    commands.insert_resource(world_map);
+    commands.insert_resource(report);
+    
+    debug!(elapsed = ?resource_insert_start.elapsed(), "World resources inserted");
+    
+    // Mark world as ready and start rendering phase
+    loading_state.advance(LoadingStage::Install, 1.0, 0.0);
+    loading_state.world_ready = true;
+    loading_state.current_message = localization.get("loading.camera_position").to_string();
+    info!("World ready to render");
 }
 
 // === OPTIMIZED CHUNK RENDERING ===
 pub fn optimized_render_world_tiles(
     mut commands: Commands,
-    camera_query: Query<&Transform, With<Camera>>,
+    camera_query: Query<(&Transform, &OrthographicProjection), With<MainCamera>>,
     world_map: Option<Res<WorldMap>>,
     mut chunk_manager: ResMut<ChunkManager>,
-    existing_tiles: Query<Entity, With<WorldTile>>,
-    existing_environment: Query<Entity, With<EnvironmentSprite>>,
+    existing_chunks: Query<Entity, With<ChunkRoot>>,
     mut loading_state: ResMut<LoadingState>,
-    time: Res<Time>,
+    localization: Res<Localization>,
+    density: Res<VegetationDensity>,
 ) {
     let Some(world_map) = world_map else { 
         // Update loading message while waiting for world
         if loading_state.world_ready {
-            loading_state.current_message = "⏳ Waiting for world data...".to_string();
-            loading_state.progress = 0.74;
+            loading_state.current_message = localization.get("loading.waiting_world").to_string();
+            loading_state.advance(LoadingStage::Rendering, 0.0, 0.0);
         }
         return; 
     };
     
-    let Ok(camera_transform) = camera_query.get_single() else { 
+    let Ok((camera_transform, projection)) = camera_query.get_single() else { 
         if loading_state.world_ready {
-            loading_state.current_message = "📷 Setting up camera...".to_string();
-            loading_state.progress = 0.76;
+            loading_state.current_message = localization.get("loading.camera").to_string();
+            loading_state.advance(LoadingStage::Rendering, 0.0, 0.0);
         }
         return;
     };
 
+    // A new vegetation density rebuilds every loaded chunk with it
+    if density.is_changed() && !density.is_added() && !world_map.is_changed() {
+        for chunk_data in chunk_manager.loaded_chunks.values() {
+            commands.entity(chunk_data.root).despawn_recursive();
+        }
+        chunk_manager.loaded_chunks.clear();
+    }
+
     if world_map.is_changed() {
-        loading_state.current_message = "🧹 Clearing previous world...".to_string();
-        loading_state.progress = 0.78;
+        loading_state.current_message = localization.get("loading.clearing").to_string();
+        loading_state.advance(LoadingStage::Rendering, 0.0, 0.0);
         
         debug!("World map changed! Clearing existing entities...");
-        // Clear all existing entities
-        for entity in existing_tiles.iter() {
-            commands.entity(entity).despawn();
-        }
-        for entity in existing_environment.iter() {
-            commands.entity(entity).despawn();
+        // Clear all existing chunks along with their tiles and environment
+        for root in existing_chunks.iter() {
+            commands.entity(root).despawn_recursive();
         }
         chunk_manager.loaded_chunks.clear();
-        debug!("Cleared {} tiles and {} environment entities", existing_tiles.iter().count(), existing_environment.iter().count());
+        chunk_manager.dirty_chunks.clear();
+        debug!("Cleared {} chunks", existing_chunks.iter().len());
     }
 
+    // Edited chunks (and their copies past wrapped edges) are unloaded here and picked up again by
+    // the loading pass below
+    let topology = world_map.params.topology;
+    let dirty_chunks = std::mem::take(&mut chunk_manager.dirty_chunks);
+    chunk_manager.loaded_chunks.retain(|coord, chunk_data| {
+        let dirty = dirty_chunks.contains(&wrapped_chunk(*coord, topology).0);
+        if dirty {
+            commands.entity(chunk_data.root).despawn_recursive();
+        }
+        !dirty
+    });
+
     // Update loading for chunk calculation phase
     if loading_state.world_ready && !loading_state.first_frame_rendered {
-        loading_state.current_message = "🗺️ Calculating visible areas...".to_string();
-        loading_state.progress = 0.8;
+        loading_state.current_message = localization.get("loading.visible_areas").to_string();
+        loading_state.advance(LoadingStage::Rendering, 0.0, 0.0);
     }
 
     // Calculate visible chunks
     debug!("Calculating visible chunks from camera position: {:?}", camera_transform.translation);
-    let visible_chunks = calculate_visible_chunks(camera_transform.translation);
-    debug!("Found {} visible chunks", visible_chunks.len());
+    // The isometric view stretches the camera, showing more of the map up the screen, and zooming
+    // out widens the projection
+    let reach = camera_transform.scale.max_element() * projection.scale;
+    // Borrowed field by field, so the scratch buffers can be used while chunks are loaded
+    let manager = &mut *chunk_manager;
+    calculate_visible_chunks(camera_transform.translation, reach, topology, &mut manager.scratch.visible);
+    debug!("Found {} visible chunks", manager.scratch.visible.len());
     
     // Unload chunks that are no longer visible
-    let mut chunks_to_unload = Vec::new();
-    for &chunk_coord in chunk_manager.active_chunks.iter() {
-        if !visible_chunks.contains(&chunk_coord) {
-            chunks_to_unload.push(chunk_coord);
-        }
-    }
-    
-    for chunk_coord in chunks_to_unload {
-        if let Some(chunk_data) = chunk_manager.loaded_chunks.get(&chunk_coord) {
-            for entity in &chunk_data.entities {
-                commands.entity(*entity).despawn();
+    for chunk_coord in manager.active_chunks.iter() {
+        if !manager.scratch.visible.contains(chunk_coord) {
+            if let Some(chunk_data) = manager.loaded_chunks.remove(chunk_coord) {
+                commands.entity(chunk_data.root).despawn_recursive();
             }
         }
-        chunk_manager.loaded_chunks.remove(&chunk_coord);
     }
 
-    // Update active chunks
-    chunk_manager.active_chunks = visible_chunks.clone();
+    // Update active chunks; last frame's list becomes the next scratch buffer
+    std::mem::swap(&mut manager.active_chunks, &mut manager.scratch.visible);
 
     // Load new chunks with progress tracking
     debug!("Loading new chunks...");
     let mut chunks_loaded = 0;
-    let total_chunks_to_load = visible_chunks.len() - chunk_manager.loaded_chunks.len();
+    let total_chunks_to_load = manager.active_chunks.len() - manager.loaded_chunks.len();
     
-    for (i, chunk_coord) in visible_chunks.iter().enumerate() {
-        if !chunk_manager.loaded_chunks.contains_key(chunk_coord) {
+    for chunk_coord in manager.active_chunks.iter() {
+        if !manager.loaded_chunks.contains_key(chunk_coord) {
             debug!("Loading chunk {:?}", chunk_coord);
-            let entities = render_chunk(&mut commands, &world_map, *chunk_coord);
-            debug!("Chunk {:?} loaded with {} entities", chunk_coord, entities.len());
-            chunk_manager.loaded_chunks.insert(*chunk_coord, ChunkData {
-                entities,
+            let root = render_chunk(&mut commands, &world_map, *chunk_coord, density.current, &mut manager.scratch);
+            manager.loaded_chunks.insert(*chunk_coord, ChunkData {
+                root,
                 is_loaded: true,
             });
             chunks_loaded += 1;
@@ -223,22 +252,15 @@ pub fn optimized_render_world_tiles(
             // Update loading progress for rendering phase
             if loading_state.world_ready && !loading_state.first_frame_rendered {
                 let render_progress = chunks_loaded as f32 / total_chunks_to_load.max(1) as f32;
-                loading_state.progress = 0.82 + (render_progress * 0.18); // 82-100%
+                loading_state.advance(LoadingStage::Rendering, 0.1 + render_progress * 0.9, 0.0);
                 
-                let render_messages = [
-                    "🎨 Painting the landscape...",
-                    "🖌️ Adding environmental details...", 
-                    "🌿 Placing vegetation...",
-                    "🏔️ Positioning mountain ranges...",
-                    "🌊 Filling water bodies...",
-                    "✨ Final touches and polish...",
-                ];
+                let render_messages = localization.list("loading.render_stages");
                 let message_index = ((render_progress * render_messages.len() as f32) as usize)
-                    .min(render_messages.len() - 1);
-                loading_state.current_message = render_messages[message_index].to_string();
+                    .min(render_messages.len().saturating_sub(1));
+                let render_message = render_messages.get(message_index).map(String::as_str).unwrap_or_default();
+                loading_state.current_message = render_message.to_string();
                 
-                info!("⏱️ TIMING: Rendering progress: {:.1}% - {} (chunk {}/{})", 
-                      render_progress * 100.0, render_messages[message_index], chunks_loaded, total_chunks_to_load);
+                debug!(progress = render_progress, stage = render_message, chunk = chunks_loaded, of = total_chunks_to_load, "Initial render progress");
             }
         }
     }
@@ -246,13 +268,12 @@ pub fn optimized_render_world_tiles(
     
     // Mark first frame as rendered if we have any chunks loaded
     if chunks_loaded > 0 && loading_state.world_ready && !loading_state.first_frame_rendered {
-        let render_complete_time = Instant::now();
-        info!("⏱️ TIMING: First frame rendered! Loading complete at {:?}", render_complete_time);
+        info!("First frame rendered, loading complete");
         
         loading_state.first_frame_rendered = true;
-        loading_state.progress = 1.0;
+        loading_state.advance(LoadingStage::Rendering, 1.0, 0.0);
         loading_state.is_complete = true;
-        loading_state.current_message = "🎉 Welcome to your new world! 🎉".to_string();
+        loading_state.current_message = localization.get("loading.welcome").to_string();
     }
 }
 
@@ -260,21 +281,32 @@ fn render_chunk(
     commands: &mut Commands,
     world_map: &WorldMap,
     chunk_coord: (i32, i32),
-) -> Vec<Entity> {
+    density: f32,
+    scratch: &mut ChunkScratch,
+) -> Entity {
+    let _span = trace_span!("render_chunk", x = chunk_coord.0, y = chunk_coord.1).entered();
     let chunk_render_start = Instant::now();
-    debug!("⏱️ TIMING: Rendering chunk {:?} at {:?}", chunk_coord, chunk_render_start);
-    let mut entities = Vec::new();
-    let (start_x, start_y, end_x, end_y) = chunk_to_world_bounds(chunk_coord.0, chunk_coord.1);
+    let (source, shift) = wrapped_chunk(chunk_coord, world_map.params.topology);
+    let root = commands.spawn((
+        SpatialBundle::from_transform(Transform::from_translation(shift.extend(0.0))),
+        ChunkRoot { source },
+    )).id();
+    let entities = &mut scratch.entities;
+    entities.clear();
+    let (start_x, start_y, end_x, end_y) = chunk_tile_bounds(source.0, source.1);
     debug!("Chunk bounds: ({}, {}) to ({}, {})", start_x, start_y, end_x, end_y);
     
     // Skip invalid chunks
     if start_x >= end_x || start_y >= end_y || start_x >= WORLD_SIZE || start_y >= WORLD_SIZE {
         debug!("Skipping invalid chunk {:?}", chunk_coord);
-        return entities;
+        return root;
     }
 
-    // Group similar elements for instancing
-    let mut instanced_elements: HashMap<EnvironmentType, Vec<(Vec3, f32)>> = HashMap::new();
+    // Group similar elements for instancing, one list per element type
+    let instanced_elements = &mut scratch.instanced;
+    for group in instanced_elements.iter_mut() {
+        group.clear();
+    }
     
     let mut tiles_processed = 0;
 
@@ -293,11 +325,7 @@ fn render_chunk(
                         custom_size: Some(Vec2::new(TILE_SIZE, TILE_SIZE)),
                         ..default()
                     },
-                    transform: Transform::from_translation(Vec3::new(
-                        (x as f32 - WORLD_SIZE as f32 / 2.0) * TILE_SIZE,
-                        (y as f32 - WORLD_SIZE as f32 / 2.0) * TILE_SIZE,
-                        0.0,
-                    )),
+                    transform: Transform::from_translation(tile_to_world(x, y).extend(0.0)),
                     ..default()
                 },
                 WorldTile { x, y },
@@ -305,24 +333,45 @@ fn render_chunk(
             )).id();
             entities.push(tile_entity);
 
+            // Cliff lines along the right and top edges; the neighbouring tiles own the other two
+            for (nx, ny) in [(x + 1, y), (x, y + 1)] {
+                if nx < WORLD_SIZE && ny < WORLD_SIZE && world_map.is_cliff((x, y), (nx, ny)) {
+                    let (offset, size) = if nx > x {
+                        (Vec2::new(TILE_SIZE / 2.0, 0.0), Vec2::new(CLIFF_LINE_WIDTH, TILE_SIZE))
+                    } else {
+                        (Vec2::new(0.0, TILE_SIZE / 2.0), Vec2::new(TILE_SIZE, CLIFF_LINE_WIDTH))
+                    };
+                    let center = tile_to_world(x, y) + offset;
+                    entities.push(commands.spawn(SpriteBundle {
+                        sprite: Sprite { color: CLIFF_COLOR, custom_size: Some(size), ..default() },
+                        transform: Transform::from_translation(center.extend(CLIFF_Z)),
+                        ..default()
+                    }).id());
+                }
+            }
+
             // Collect environment elements for instancing
-            let environment_elements = get_environment_elements(&tile.biome, x, y);
-            for element_type in environment_elements {
-                let base_x = (x as f32 - WORLD_SIZE as f32 / 2.0) * TILE_SIZE;
-                let base_y = (y as f32 - WORLD_SIZE as f32 / 2.0) * TILE_SIZE;
-                let position = Vec3::new(base_x, base_y, 1.0);
+            // Edited props are read in place; generated ones are built inline
+            let generated;
+            let environment_elements: &[EnvironmentType] = match world_map.props.get(&(x, y)) {
+                Some(props) => props,
+                None => {
+                    generated = environment_elements_at_density(&tile.biome, x, y, density);
+                    &generated
+                }
+            };
+            for &element_type in environment_elements {
+                let position = tile_to_world(x, y).extend(1.0);
                 
-                instanced_elements.entry(element_type)
-                    .or_default()
-                    .push((position, 0.0)); // rotation
+                instanced_elements[element_type as usize].push((position, 0.0)); // rotation
             }
         }
     }
 
     // Create instanced sprites
-    for (element_type, positions_rotations) in instanced_elements {
+    for (element_type, positions_rotations) in EnvironmentType::ALL.into_iter().zip(instanced_elements.iter()) {
         if positions_rotations.len() > 5 { // Only instance if we have enough
-            let (positions, rotations): (Vec<_>, Vec<_>) = positions_rotations.into_iter().unzip();
+            let (positions, rotations): (Vec<_>, Vec<_>) = positions_rotations.iter().copied().unzip();
             let scales = vec![element_type.get_size(); positions.len()];
             
             let instanced_entity = commands.spawn((
@@ -337,16 +386,16 @@ fn render_chunk(
             entities.push(instanced_entity);
         } else {
             // Spawn individual sprites for small groups
-            for (position, _rotation) in positions_rotations {
+            for &(position, _rotation) in positions_rotations {
                 let env_entity = spawn_individual_environment_element(commands, element_type, position);
                 entities.push(env_entity);
             }
         }
     }
 
-    let chunk_render_time = chunk_render_start.elapsed();
-    debug!("⏱️ TIMING: Chunk {:?} rendered in {:?} with {} entities", chunk_coord, chunk_render_time, entities.len());
-    entities
+    debug!(elapsed = ?chunk_render_start.elapsed(), entities = entities.len(), "Chunk {:?} rendered", chunk_coord);
+    commands.entity(root).push_children(entities);
+    root
 }
 
 fn spawn_individual_environment_element(
@@ -391,19 +440,19 @@ fn spawn_individual_environment_element(
 
 // === LOD SYSTEM ===
 fn update_lod_system(
-    camera_query: Query<&Transform, With<Camera>>,
-    mut lod_query: Query<(&Transform, &mut LODLevel), (Without<Camera>, With<EnvironmentSprite>)>,
+    camera_query: Query<&Transform, With<MainCamera>>,
+    mut lod_query: Query<(&Transform, &mut LODLevel), (Without<Camera>, Or<(With<EnvironmentSprite>, With<Creature>)>)>,
 ) {
     let Ok(camera_transform) = camera_query.get_single() else { return };
     
     for (transform, mut lod) in lod_query.iter_mut() {
         let distance = camera_transform.translation.distance(transform.translation);
-        lod.0 = match distance {
+        lod.set_if_neq(LODLevel(match distance {
             d if d < 100.0 => 0,
             d if d < 300.0 => 1,
             d if d < 600.0 => 2,
             _ => 3,
-        };
+        }));
     }
 }
 
@@ -417,13 +466,16 @@ fn update_shared_animation_state(
     wind_state.wind_strength = 0.8 + 0.2 * (wind_state.wind_time * 0.1).sin();
 }
 
+// Sprites outside every camera's view (as of last frame's frustum culling, which Bevy already
+// skips drawing) are not animated either, so their transforms stay unchanged and cost nothing to
+// propagate. One frame late, which only delays the sway of sprites scrolling into view.
 fn optimized_sway_system(
     wind_state: Res<SharedAnimationState>,
-    mut query: Query<(&mut Transform, &SwayAnimation, &LODLevel)>,
+    mut query: Query<(&mut Transform, &SwayAnimation, &LODLevel, &ViewVisibility)>,
 ) {
-    for (mut transform, sway, lod) in query.iter_mut() {
-        // Skip animation for distant objects
-        if lod.0 >= 2 { continue; }
+    for (mut transform, sway, lod, view_visibility) in query.iter_mut() {
+        // Skip animation for distant and off-screen objects
+        if lod.0 >= 2 || !view_visibility.get() { continue; }
         
         let time_offset = wind_state.wind_time + sway.phase_offset;
         let effective_wind = wind_state.wind_strength * (if lod.0 == 0 { 1.0 } else { 0.5 });
@@ -433,19 +485,24 @@ fn optimized_sway_system(
 }
 
 // === SPATIAL HASH SYSTEM ===
+// Only creatures that moved, were spawned or went away this tick
 fn update_spatial_hash_system(
     mut spatial_hash: ResMut<SpatialHash>,
-    environment_query: Query<(Entity, &Transform), (With<EnvironmentSprite>, Changed<Transform>)>,
+    creatures: Query<(Entity, &Transform), (With<Creature>, Changed<Transform>)>,
+    mut removed: RemovedComponents<Creature>,
 ) {
-    // Clear and rebuild spatial hash for changed entities
-    for (entity, transform) in environment_query.iter() {
-        spatial_hash.insert(entity, transform.translation);
-    }
+    let removed = removed.read().map(SpatialChange::Removed);
+    let moved = creatures.iter().map(|(entity, transform)| SpatialChange::Moved(entity, transform.translation.truncate()));
+    spatial_hash.update(removed.chain(moved).collect::<Vec<_>>());
+}
+
+fn publish_spatial_view(spatial_hash: Res<SpatialHash>, mut view: ResMut<SpatialView>) {
+    *view = spatial_hash.view();
 }
 
 // === CHUNK MANAGEMENT ===
 fn chunk_management_system(
-    camera_query: Query<&Transform, With<Camera>>,
+    camera_query: Query<&Transform, With<MainCamera>>,
     chunk_manager: Res<ChunkManager>,
 ) {
     let Ok(_camera_transform) = camera_query.get_single() else { return };
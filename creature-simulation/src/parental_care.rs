@@ -0,0 +1,97 @@
+use bevy::prelude::*;
+use crate::creature::{move_creatures, Behavior, Creature, Needs, Wander, SEEK_THRESHOLD};
+use crate::hibernation::Hibernation;
+use crate::predation::hunt_prey;
+use crate::reproduction::MATURITY_AGE;
+use crate::world::WorldMap;
+
+const FOLLOW_DISTANCE: f32 = 12.0; // World units a juvenile strays before heading back to its parent
+const FEED_DISTANCE: f32 = 6.0;
+const FEED_RATE: f32 = 0.02; // Hunger passed from juvenile to parent per second
+const DEFEND_RADIUS: f32 = 60.0; // A parent this close comes to help its fleeing young
+const GUARD_RADIUS: f32 = 8.0; // Young this close to their parent cannot be caught
+const ORPHAN_HUNGER: f32 = 0.002; // Extra hunger per second of a juvenile without a parent
+
+pub struct ParentalCarePlugin;
+
+impl Plugin for ParentalCarePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedUpdate, care_for_young
+            .after(hunt_prey)
+            .before(move_creatures)
+            .run_if(resource_exists::<WorldMap>));
+    }
+}
+
+// === PARENTAL CARE ===
+// Young born or hatched (see reproduction.rs and nesting.rs) stay with their parent until they
+// reach breeding age: they follow it around, it passes them food while it is fed itself, and it
+// rushes in when they flee from a hunter; young it is guarding cannot be caught (see hunt_prey).
+// Young whose parent died go hungrier until they grow up. Not kept in save files, as entity ids
+// do not survive loading; loaded young are on their own without counting as orphans.
+#[derive(Component)]
+pub struct Juvenile {
+    pub parent: Option<Entity>, // None once orphaned
+    pub guarded: bool, // Its parent is close enough to drive hunters off
+}
+
+impl Juvenile {
+    pub fn of(parent: Entity) -> Self {
+        Self { parent: Some(parent), guarded: false }
+    }
+
+    pub fn is_guarded(juvenile: Option<&Juvenile>) -> bool {
+        juvenile.is_some_and(|juvenile| juvenile.guarded)
+    }
+}
+
+fn care_for_young(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut young: Query<(Entity, &mut Juvenile)>,
+    mut creatures: Query<(&Transform, &Creature, &mut Needs, &mut Wander, Option<&Hibernation>)>,
+) {
+    let dt = time.delta_seconds();
+    for (entity, mut juvenile) in young.iter_mut() {
+        let Some(parent) = juvenile.parent.filter(|parent| creatures.contains(*parent)) else {
+            juvenile.parent = None;
+            juvenile.guarded = false;
+            if let Ok((_, creature, mut needs, _, _)) = creatures.get_mut(entity) {
+                needs.hunger += ORPHAN_HUNGER * dt;
+                if creature.age >= MATURITY_AGE {
+                    commands.entity(entity).remove::<Juvenile>();
+                }
+            }
+            continue;
+        };
+        let Ok([child, parent]) = creatures.get_many_mut([entity, parent]) else { continue };
+        let (child_transform, child_creature, mut child_needs, mut child_wander, child_hibernation) = child;
+        let (parent_transform, _, mut parent_needs, mut parent_wander, parent_hibernation) = parent;
+        if child_creature.age >= MATURITY_AGE {
+            commands.entity(entity).remove::<Juvenile>();
+            continue;
+        }
+
+        let child_position = child_transform.translation.truncate();
+        let parent_position = parent_transform.translation.truncate();
+        let distance = child_position.distance(parent_position);
+        let parent_awake = !Hibernation::is_dormant(parent_hibernation);
+        juvenile.guarded = parent_awake && distance < GUARD_RADIUS;
+
+        if parent_awake && distance < FEED_DISTANCE && parent_needs.hunger < SEEK_THRESHOLD {
+            let meal = child_needs.hunger.min(FEED_RATE * dt);
+            child_needs.hunger -= meal;
+            parent_needs.hunger += meal;
+        }
+        if child_wander.behavior == Behavior::Flee && parent_awake && distance < DEFEND_RADIUS {
+            parent_wander.target = Some(child_position);
+            parent_wander.behavior = Behavior::Defend;
+            parent_wander.search_cooldown = 2.0;
+        } else if child_wander.behavior != Behavior::Flee && !Hibernation::is_dormant(child_hibernation)
+            && child_needs.thirst < SEEK_THRESHOLD && distance > FOLLOW_DISTANCE {
+            child_wander.target = Some(parent_position);
+            child_wander.behavior = Behavior::FollowParent;
+            child_wander.search_cooldown = 2.0;
+        }
+    }
+}
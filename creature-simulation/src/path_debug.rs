@@ -0,0 +1,154 @@
+use bevy::prelude::*;
+use crate::coords::{tile_to_world, world_offset, world_to_tile, wrap_position, TILE_SIZE};
+use crate::creature::{Creature, Surroundings, Wander};
+use crate::creature_tracking::SelectedCreature;
+use crate::species::{MovementMode, SpeciesRegistry};
+use crate::world::{WorldMap, WORLD_SIZE};
+
+const BLOCKED_RADIUS: i32 = 12; // Tiles around the selected creature checked for blocking
+const FLOW_RADIUS: i32 = 24; // Tiles around the goal covered by flow arrows
+const FLOW_SPACING: usize = 4; // One arrow every this many tiles
+const ROUTE_COLOR: Color = Color::srgb(0.3, 0.9, 0.9);
+const BLOCKED_COLOR: Color = Color::srgb(0.95, 0.25, 0.25);
+const SLOW_COLOR: Color = Color::srgb(0.95, 0.75, 0.3);
+
+pub struct PathDebugPlugin;
+
+impl Plugin for PathDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<PathDebug>()
+            .add_systems(Update, (
+                draw_creature_route,
+                draw_flow_field,
+            ).run_if(|debug: Res<PathDebug>| debug.enabled).run_if(resource_exists::<WorldMap>));
+    }
+}
+
+// === PATHFINDING DEBUG OVERLAY ===
+// Toggled with the "paths" console command. Creatures steer straight at their target and turn
+// around when the next step is blocked (move_creatures), so the overlay shows that straight route
+// for the selected creature, the tiles nearby it cannot enter (red) or only crosses slowly, like
+// ice, mud or deep snow (amber), and - for a goal set with "paths <x> <y>" - the direction creatures around the goal
+// would head, red where they would get stuck.
+#[derive(Resource, Default)]
+pub struct PathDebug {
+    pub enabled: bool,
+    pub goal: Option<(usize, usize)>,
+}
+
+impl PathDebug {
+    // A goal always shows the overlay; without one the command toggles it
+    pub fn command(&mut self, goal: Option<(usize, usize)>) {
+        match goal {
+            Some(goal) => {
+                self.enabled = true;
+                self.goal = Some(goal);
+            }
+            None => {
+                self.enabled = !self.enabled;
+                self.goal = None;
+            }
+        }
+        info!("🧭 Path overlay {}{}", if self.enabled { "shown" } else { "hidden" },
+            self.goal.map(|(x, y)| format!(" with goal ({}, {})", x, y)).unwrap_or_default());
+    }
+}
+
+// First point on the straight line between two positions that the creature could not step onto
+// or climb up to. `to` is taken the short way round, as returned by `unwrapped`.
+fn first_blocked(surroundings: &Surroundings, from: Vec2, to: Vec2, movement: MovementMode) -> Option<Vec2> {
+    let steps = (from.distance(to) / (TILE_SIZE * 0.5)).ceil().max(1.0) as usize;
+    let mut previous = world_to_tile(from);
+    (1..=steps)
+        .map(|step| from.lerp(to, step as f32 / steps as f32))
+        .find(|point| {
+            let tile = world_to_tile(wrap_position(*point, surroundings.topology()));
+            let blocked = !tile.is_some_and(|(x, y)| surroundings.can_enter(x, y, movement)
+                && previous.is_none_or(|from| !surroundings.is_cliff(from, (x, y), movement)));
+            previous = tile;
+            blocked
+        })
+}
+
+// Where a target lies seen from a position, past the map edge when that is the shorter way
+fn unwrapped(surroundings: &Surroundings, from: Vec2, to: Vec2) -> Vec2 {
+    from + world_offset(from, to, surroundings.topology())
+}
+
+fn draw_creature_route(
+    mut gizmos: Gizmos,
+    selected: Res<SelectedCreature>,
+    registry: Res<SpeciesRegistry>,
+    surroundings: Surroundings,
+    creatures: Query<(&Transform, &Creature, &Wander)>,
+) {
+    let Some((transform, creature, wander)) = selected.0.and_then(|entity| creatures.get(entity).ok()) else { return };
+    let movement = registry.get(creature.species).movement;
+    let position = transform.translation.truncate();
+
+    gizmos.arrow_2d(position, position + Vec2::from_angle(wander.heading) * TILE_SIZE * 3.0, ROUTE_COLOR);
+    if let Some(target) = wander.target.map(|target| unwrapped(&surroundings, position, target)) {
+        match first_blocked(&surroundings, position, target, movement) {
+            Some(blocked) => {
+                gizmos.line_2d(position, blocked, ROUTE_COLOR);
+                gizmos.line_2d(blocked, target, BLOCKED_COLOR);
+            }
+            None => gizmos.line_2d(position, target, ROUTE_COLOR),
+        }
+        gizmos.circle_2d(target, TILE_SIZE, ROUTE_COLOR);
+    }
+
+    let Some((cx, cy)) = world_to_tile(position) else { return };
+    for dx in -BLOCKED_RADIUS..=BLOCKED_RADIUS {
+        for dy in -BLOCKED_RADIUS..=BLOCKED_RADIUS {
+            let (x, y) = (cx as i32 + dx, cy as i32 + dy);
+            if x < 0 || y < 0 || x >= WORLD_SIZE as i32 || y >= WORLD_SIZE as i32 {
+                continue;
+            }
+            let (x, y) = (x as usize, y as usize);
+            let color = if !surroundings.can_enter(x, y, movement) {
+                BLOCKED_COLOR
+            } else if surroundings.speed_factor(x, y, movement) < 1.0 {
+                SLOW_COLOR
+            } else {
+                continue;
+            };
+            gizmos.rect_2d(tile_to_world(x, y), 0.0, Vec2::splat(TILE_SIZE * 0.8), color);
+        }
+    }
+}
+
+// Arrows for walkers, or for the selected creature's kind of movement when one is selected
+fn draw_flow_field(
+    mut gizmos: Gizmos,
+    debug: Res<PathDebug>,
+    selected: Res<SelectedCreature>,
+    registry: Res<SpeciesRegistry>,
+    surroundings: Surroundings,
+    creatures: Query<&Creature>,
+) {
+    let Some((gx, gy)) = debug.goal else { return };
+    let movement = selected.0.and_then(|entity| creatures.get(entity).ok())
+        .map_or(MovementMode::Walk, |creature| registry.get(creature.species).movement);
+    let goal = tile_to_world(gx, gy);
+    gizmos.circle_2d(goal, TILE_SIZE * 2.0, ROUTE_COLOR);
+
+    let radius = FLOW_RADIUS as usize;
+    let xs = gx.saturating_sub(radius)..=(gx + radius).min(WORLD_SIZE - 1);
+    for x in xs.step_by(FLOW_SPACING) {
+        for y in (gy.saturating_sub(radius)..=(gy + radius).min(WORLD_SIZE - 1)).step_by(FLOW_SPACING) {
+            let start = tile_to_world(x, y);
+            if (x, y) == (gx, gy) || !surroundings.can_enter(x, y, movement) {
+                continue;
+            }
+            let goal = unwrapped(&surroundings, start, goal);
+            let color = match first_blocked(&surroundings, start, goal, movement) {
+                Some(_) => BLOCKED_COLOR,
+                None => ROUTE_COLOR,
+            };
+            let direction = (goal - start).normalize_or_zero();
+            gizmos.arrow_2d(start, start + direction * TILE_SIZE * (FLOW_SPACING as f32 - 1.0), color);
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use crate::coords::{world_to_tile, MAP_CENTER, TILE_SIZE, WORLD_EXTENT};
+use crate::creature::{move_creatures, Creature, Needs, Surroundings, SEEK_THRESHOLD};
+use crate::keybindings::{Action, Controls};
+use crate::profiling::budgeted;
+use crate::save::SaveViewExt;
+use crate::species::SpeciesRegistry;
+use crate::text_input::text_input_inactive;
+use crate::world::{WorldMap, WORLD_SIZE};
+
+pub const PHEROMONE_CELL_TILES: usize = 4; // Each grid cell covers 4x4 tiles
+pub const PHEROMONE_SIZE: usize = WORLD_SIZE / PHEROMONE_CELL_TILES;
+const FOOD_DEPOSIT: f32 = 2.0; // Per second while standing on food
+const TRAIL_DEPOSIT: f32 = 0.5; // Per second while fed, marking where fed ants walk
+const EVAPORATION_SECONDS: f32 = 60.0; // Time constant of the exponential decay
+const DIFFUSION_RATE: f32 = 0.5; // Share of the gap to the neighbour average closed per second
+const SENSE_DISTANCE: f32 = PHEROMONE_CELL_TILES as f32 * TILE_SIZE * 1.5;
+const SENSE_ANGLE: f32 = 0.6; // Radians between the middle and the side sensors
+const FOLLOW_THRESHOLD: f32 = 0.05; // Weakest scent that still steers
+const FOLLOW_HUNGER: f32 = 0.3; // Ants hungrier than this follow trails
+const OVERLAY_SATURATION: f32 = 4.0; // Scent drawn fully opaque
+const OVERLAY_Z: f32 = 4.5; // Just under the death heat map
+
+pub struct PheromonesPlugin;
+
+impl Plugin for PheromonesPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<PheromoneGrid>()
+            .save_visibility::<PheromoneOverlay>("pheromones")
+            .add_systems(Startup, spawn_pheromone_overlay)
+            .add_systems(FixedUpdate, (lay_pheromones, budgeted(spread_pheromones, 2.0))
+                .chain()
+                .after(move_creatures)
+                .run_if(resource_exists::<WorldMap>)
+                .run_if(pheromone_species_exist))
+            .add_systems(Update, (
+                reset_pheromones.run_if(resource_added::<WorldMap>),
+                toggle_pheromone_overlay.run_if(text_input_inactive),
+                update_pheromone_image,
+            ).chain());
+    }
+}
+
+// === PHEROMONE GRID ===
+// Scent laid by species with `pheromones` set: strongly where they find food and lightly wherever
+// they walk while fed. Each tick the grid spreads into neighbouring cells and evaporates. Hungry
+// ants without a target steer towards the strongest of three sensors ahead of them (see
+// move_creatures), so trails to food build up and fade again once nobody reinforces them.
+#[derive(Resource)]
+pub struct PheromoneGrid {
+    pub cells: Vec<f32>, // Indexed [cx * PHEROMONE_SIZE + cy], matching tiles[x][y]
+    scratch: Vec<f32>, // Next state while spreading
+}
+
+impl Default for PheromoneGrid {
+    fn default() -> Self {
+        Self {
+            cells: vec![0.0; PHEROMONE_SIZE * PHEROMONE_SIZE],
+            scratch: vec![0.0; PHEROMONE_SIZE * PHEROMONE_SIZE],
+        }
+    }
+}
+
+impl PheromoneGrid {
+    fn index(position: Vec2) -> Option<usize> {
+        world_to_tile(position).map(|(x, y)| (x / PHEROMONE_CELL_TILES) * PHEROMONE_SIZE + y / PHEROMONE_CELL_TILES)
+    }
+
+    pub fn deposit(&mut self, position: Vec2, amount: f32) {
+        if let Some(index) = Self::index(position) {
+            self.cells[index] += amount;
+        }
+    }
+
+    pub fn sample(&self, position: Vec2) -> f32 {
+        Self::index(position).map_or(0.0, |index| self.cells[index])
+    }
+
+    // Heading towards the strongest of the left, middle and right sensors, if any smells enough
+    pub fn steer(&self, position: Vec2, heading: f32) -> Option<f32> {
+        [heading - SENSE_ANGLE, heading, heading + SENSE_ANGLE].into_iter()
+            .map(|angle| (angle, self.sample(position + Vec2::from_angle(angle) * SENSE_DISTANCE)))
+            .filter(|(_, scent)| *scent >= FOLLOW_THRESHOLD)
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(angle, _)| angle)
+    }
+
+    pub fn follows(hunger: f32) -> bool {
+        hunger > FOLLOW_HUNGER
+    }
+}
+
+pub fn pheromone_species_exist(registry: Res<SpeciesRegistry>) -> bool {
+    registry.species.iter().any(|species| species.pheromones)
+}
+
+fn reset_pheromones(mut grid: ResMut<PheromoneGrid>) {
+    *grid = PheromoneGrid::default();
+}
+
+fn lay_pheromones(
+    time: Res<Time>,
+    registry: Res<SpeciesRegistry>,
+    surroundings: Surroundings,
+    creatures: Query<(&Transform, &Creature, &Needs)>,
+    mut grid: ResMut<PheromoneGrid>,
+) {
+    let dt = time.delta_seconds();
+    for (transform, creature, needs) in creatures.iter() {
+        let species = registry.get(creature.species);
+        if !species.pheromones {
+            continue;
+        }
+        let position = transform.translation.truncate();
+        let Some((x, y)) = world_to_tile(position) else { continue };
+        if surroundings.has_food(x, y, species.diet) {
+            grid.deposit(position, FOOD_DEPOSIT * dt);
+        } else if needs.hunger < SEEK_THRESHOLD {
+            grid.deposit(position, TRAIL_DEPOSIT * dt);
+        }
+    }
+}
+
+// Blends every cell towards the average of its four neighbours, then evaporates
+fn spread_pheromones(time: Res<Time>, mut grid: ResMut<PheromoneGrid>) {
+    let dt = time.delta_seconds();
+    let evaporation = (-dt / EVAPORATION_SECONDS).exp();
+    let diffusion = (DIFFUSION_RATE * dt).min(1.0);
+    let PheromoneGrid { cells, scratch } = &mut *grid;
+    let cell = |cx: usize, cy: usize| cells[cx * PHEROMONE_SIZE + cy];
+
+    for cx in 0..PHEROMONE_SIZE {
+        for cy in 0..PHEROMONE_SIZE {
+            let here = cell(cx, cy);
+            // Edges reflect, so scent does not leak off the map
+            let neighbours = cell(cx.saturating_sub(1), cy)
+                + cell((cx + 1).min(PHEROMONE_SIZE - 1), cy)
+                + cell(cx, cy.saturating_sub(1))
+                + cell(cx, (cy + 1).min(PHEROMONE_SIZE - 1));
+            let next = (here + (neighbours / 4.0 - here) * diffusion) * evaporation;
+            scratch[cx * PHEROMONE_SIZE + cy] = if next < 1e-4 { 0.0 } else { next };
+        }
+    }
+    std::mem::swap(cells, scratch);
+}
+
+#[derive(Component)]
+pub struct PheromoneOverlay {
+    pub image: Handle<Image>,
+}
+
+fn spawn_pheromone_overlay(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let image = Image::new_fill(
+        Extent3d { width: PHEROMONE_SIZE as u32, height: PHEROMONE_SIZE as u32, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        &[0, 0, 0, 0],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    let image = images.add(image);
+
+    // Same placement as the death heat map
+    commands.spawn((
+        SpriteBundle {
+            texture: image.clone(),
+            sprite: Sprite {
+                custom_size: Some(Vec2::splat(WORLD_EXTENT)),
+                ..default()
+            },
+            transform: Transform::from_translation(MAP_CENTER.extend(OVERLAY_Z)),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        PheromoneOverlay { image },
+    ));
+}
+
+fn toggle_pheromone_overlay(controls: Controls, mut overlay: Query<&mut Visibility, With<PheromoneOverlay>>) {
+    if !controls.just_pressed(Action::TogglePheromones) {
+        return;
+    }
+    for mut visibility in overlay.iter_mut() {
+        *visibility = match *visibility {
+            Visibility::Hidden => Visibility::Visible,
+            _ => Visibility::Hidden,
+        };
+        info!("🐜 Pheromone trails {}", if *visibility == Visibility::Hidden { "hidden" } else { "shown" });
+    }
+}
+
+// Re-uploads the texture while the overlay is visible
+fn update_pheromone_image(
+    grid: Res<PheromoneGrid>,
+    overlay: Query<(&PheromoneOverlay, &Visibility)>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    let Ok((overlay, visibility)) = overlay.get_single() else { return };
+    if *visibility == Visibility::Hidden {
+        return;
+    }
+    let Some(image) = images.get_mut(&overlay.image) else { return };
+
+    for cx in 0..PHEROMONE_SIZE {
+        for cy in 0..PHEROMONE_SIZE {
+            let scent = (grid.cells[cx * PHEROMONE_SIZE + cy] / OVERLAY_SATURATION).min(1.0);
+            // Image rows run top-down while world y runs bottom-up
+            let pixel = ((PHEROMONE_SIZE - 1 - cy) * PHEROMONE_SIZE + cx) * 4;
+            image.data[pixel..pixel + 4].copy_from_slice(&[
+                200,
+                120,
+                255,
+                if scent > 0.01 { (40.0 + 160.0 * scent) as u8 } else { 0 },
+            ]);
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashMap;
+use std::time::Duration;
+use crate::camera::MainCamera;
+use crate::coords::{chunk_tile_bounds, tile_to_chunk, tile_to_world, world_to_tile, CHUNK_SIZE, TILE_SIZE};
+use crate::creature::{spawn_creature, Creature};
+use crate::creature_tracking::{Bookmarked, CreatureName, SelectedCreature};
+use crate::genetics::Genome;
+use crate::optimization::RENDER_DISTANCE;
+use crate::settings::Settings;
+use crate::spawn_zones::SpawnZones;
+use crate::species::SpeciesRegistry;
+use crate::tides::Tides;
+use crate::world::WorldMap;
+use crate::rng_streams::{RngStream, RngStreams};
+
+const CULL_INTERVAL_SECONDS: f32 = 1.0;
+pub const POOL_TICK_SECONDS: f32 = 30.0; // Simulated seconds between statistical pool updates
+const POOL_GROWTH_RATE: f32 = 0.02; // Logistic growth rate per pool tick
+const POOL_DENSITY: f32 = 0.05; // Pooled creatures of one species a habitat tile can support
+const OBSERVE_INTERVAL_SECONDS: f32 = 0.5;
+// Chunks whose center is this close to the camera count as observed: their creatures are never
+// culled, and pooled creatures there turn back into entities
+const OBSERVED_DISTANCE: f32 = RENDER_DISTANCE + CHUNK_SIZE as f32 * TILE_SIZE;
+
+pub struct PopulationPoolsPlugin;
+
+impl Plugin for PopulationPoolsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<PopulationPools>()
+            .add_systems(Update, (
+                cull_creatures.run_if(on_timer(Duration::from_secs_f32(CULL_INTERVAL_SECONDS))),
+                rematerialize_observed.run_if(on_timer(Duration::from_secs_f32(OBSERVE_INTERVAL_SECONDS))),
+            ).chain().run_if(resource_exists::<WorldMap>))
+            .add_systems(FixedUpdate, simulate_pools.run_if(resource_exists::<WorldMap>));
+    }
+}
+
+// What survives of a creature while it is merged into its chunk's pool
+#[derive(Clone, Copy)]
+pub struct PooledCreature {
+    pub species: usize,
+    pub age: f32,
+    pub genome: Genome,
+}
+
+// === POPULATION POOLS ===
+// Creatures over the global or per-chunk cap, and all creatures beyond the statistical distance,
+// are merged into per-chunk pools instead of despawned outright. Pools grow and shrink
+// statistically, and their creatures come back as entities when the camera returns.
+#[derive(Resource, Default)]
+pub struct PopulationPools {
+    pub chunks: HashMap<(i32, i32), Vec<PooledCreature>>,
+}
+
+impl PopulationPools {
+    pub fn total(&self) -> usize {
+        self.chunks.values().map(Vec::len).sum()
+    }
+}
+
+pub fn chunk_center(chunk: (i32, i32)) -> Vec2 {
+    let (start_x, start_y, end_x, end_y) = chunk_tile_bounds(chunk.0, chunk.1);
+    tile_to_world((start_x + end_x) / 2, (start_y + end_y) / 2)
+}
+
+// Higher is more important: close to the camera and of a rare species
+fn importance(distance: f32, species_share: f32) -> f32 {
+    (1.0 - species_share + 0.5) / (1.0 + distance / RENDER_DISTANCE)
+}
+
+type CullQueryData = (
+    Entity,
+    &'static Transform,
+    &'static Creature,
+    &'static Genome,
+    Has<CreatureName>,
+    Has<Bookmarked>,
+);
+
+// Named, bookmarked, selected and observed creatures are never culled
+fn cull_creatures(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    selected: Res<SelectedCreature>,
+    cameras: Query<&Transform, With<MainCamera>>,
+    mut pools: ResMut<PopulationPools>,
+    creatures: Query<CullQueryData>,
+) {
+    let Ok(camera_transform) = cameras.get_single() else { return };
+    let camera = camera_transform.translation.truncate();
+
+    let total = creatures.iter().len();
+    let mut species_counts: HashMap<usize, usize> = HashMap::new();
+    let mut chunk_counts: HashMap<(i32, i32), usize> = HashMap::new();
+    let mut candidates = Vec::new();
+    for (entity, transform, creature, genome, named, bookmarked) in creatures.iter() {
+        let Some((x, y)) = world_to_tile(transform.translation.truncate()) else { continue };
+        let chunk = tile_to_chunk(x, y);
+        *species_counts.entry(creature.species).or_default() += 1;
+        *chunk_counts.entry(chunk).or_default() += 1;
+
+        let distance = chunk_center(chunk).distance(camera);
+        if named || bookmarked || selected.0 == Some(entity) || distance < OBSERVED_DISTANCE {
+            continue;
+        }
+        candidates.push((entity, chunk, distance, creature, genome));
+    }
+
+    let distant = candidates.iter().any(|(_, _, distance, _, _)| *distance > settings.statistical_distance);
+    let mut excess = total.saturating_sub(settings.creature_cap);
+    let mut chunk_excess: HashMap<(i32, i32), usize> = chunk_counts.iter()
+        .filter(|(_, count)| **count > settings.chunk_creature_cap)
+        .map(|(chunk, count)| (*chunk, count - settings.chunk_creature_cap))
+        .collect();
+    if excess == 0 && chunk_excess.is_empty() && !distant {
+        return;
+    }
+
+    let score = |(_, _, distance, creature, _): &(Entity, (i32, i32), f32, &Creature, &Genome)| {
+        importance(*distance, species_counts[&creature.species] as f32 / total as f32)
+    };
+    candidates.sort_by(|a, b| score(a).total_cmp(&score(b)));
+
+    let mut culled = 0;
+    for (entity, chunk, distance, creature, genome) in candidates {
+        // Everything beyond the statistical distance is pooled regardless of the caps
+        let over_chunk_cap = chunk_excess.get(&chunk).is_some_and(|excess| *excess > 0);
+        if excess == 0 && !over_chunk_cap && distance <= settings.statistical_distance {
+            continue;
+        }
+        excess = excess.saturating_sub(1);
+        if let Some(chunk_excess) = chunk_excess.get_mut(&chunk) {
+            *chunk_excess = chunk_excess.saturating_sub(1);
+        }
+        pools.chunks.entry(chunk).or_default().push(PooledCreature { species: creature.species, age: creature.age, genome: *genome });
+        commands.entity(entity).despawn();
+        culled += 1;
+    }
+    debug!("Merged {} creatures into population pools ({} pooled)", culled, pools.total());
+}
+
+// Only as many as the global and per-chunk caps leave room for; the rest stay pooled until there is
+fn rematerialize_observed(
+    mut commands: Commands,
+    (world_map, tides): (Res<WorldMap>, Res<Tides>),
+    (settings, registry): (Res<Settings>, Res<SpeciesRegistry>),
+    cameras: Query<&Transform, With<MainCamera>>,
+    creatures: Query<&Transform, With<Creature>>,
+    mut pools: ResMut<PopulationPools>,
+    mut streams: ResMut<RngStreams>,
+) {
+    let Ok(camera_transform) = cameras.get_single() else { return };
+    let camera = camera_transform.translation.truncate();
+    let observed: Vec<(i32, i32)> = pools.chunks.keys()
+        .filter(|chunk| chunk_center(**chunk).distance(camera) < OBSERVED_DISTANCE)
+        .copied()
+        .collect();
+    if observed.is_empty() {
+        return;
+    }
+
+    let mut chunk_counts: HashMap<(i32, i32), usize> = HashMap::new();
+    for transform in creatures.iter() {
+        let Some((x, y)) = world_to_tile(transform.translation.truncate()) else { continue };
+        *chunk_counts.entry(tile_to_chunk(x, y)).or_default() += 1;
+    }
+    let mut room = settings.creature_cap.saturating_sub(creatures.iter().len());
+
+    let rng = streams.get(RngStream::Creatures);
+    for chunk in observed {
+        let (start_x, start_y, end_x, end_y) = chunk_tile_bounds(chunk.0, chunk.1);
+        let land: Vec<(usize, usize)> = (start_x..end_x)
+            .flat_map(|x| (start_y..end_y).map(move |y| (x, y)))
+            .filter(|(x, y)| !tides.is_underwater(&world_map.tiles[*x][*y]))
+            .collect();
+        // Stays pooled until the tide leaves somewhere to stand
+        if land.is_empty() {
+            continue;
+        }
+        let Some(pool) = pools.chunks.get_mut(&chunk) else { continue };
+        let chunk_room = settings.chunk_creature_cap.saturating_sub(chunk_counts.get(&chunk).copied().unwrap_or(0));
+        let count = pool.len().min(chunk_room).min(room);
+        room -= count;
+        for creature in pool.drain(pool.len() - count..) {
+            let (x, y) = land[rng.gen_range(0..land.len())];
+            let heading = rng.gen_range(0.0..std::f32::consts::TAU);
+            let entity = spawn_creature(&mut commands, &registry, creature.species, tile_to_world(x, y), heading, creature.genome);
+            commands.entity(entity).insert(Creature { species: creature.species, age: creature.age });
+        }
+        if pool.is_empty() {
+            pools.chunks.remove(&chunk);
+        }
+    }
+}
+
+// Logistic growth towards what the chunk's habitat tiles (and any painted spawn zones) can support, per species
+fn simulate_pools(
+    time: Res<Time>,
+    mut tick_timer: Local<f32>,
+    world_map: Res<WorldMap>,
+    registry: Res<SpeciesRegistry>,
+    zones: Res<SpawnZones>,
+    mut pools: ResMut<PopulationPools>,
+    mut streams: ResMut<RngStreams>,
+) {
+    *tick_timer += time.delta_seconds();
+    if *tick_timer < POOL_TICK_SECONDS {
+        return;
+    }
+    *tick_timer = 0.0;
+
+    let rng = streams.get(RngStream::Creatures);
+    for (chunk, pool) in pools.chunks.iter_mut() {
+        let (start_x, start_y, end_x, end_y) = chunk_tile_bounds(chunk.0, chunk.1);
+        for creature in pool.iter_mut() {
+            creature.age += POOL_TICK_SECONDS;
+        }
+
+        for (species_id, species) in registry.species.iter().enumerate() {
+            let members: Vec<usize> = (0..pool.len()).filter(|i| pool[*i].species == species_id).collect();
+            if members.is_empty() {
+                continue;
+            }
+            let habitat_tiles = (start_x..end_x)
+                .flat_map(|x| (start_y..end_y).map(move |y| (x, y)))
+                .filter(|(x, y)| species.habitats.contains(&world_map.tiles[*x][*y].biome))
+                .count();
+            let capacity = habitat_tiles as f32 * POOL_DENSITY + zones.target(*chunk, species_id);
+            let count = members.len() as f32;
+            let change = if capacity <= 0.0 {
+                -count
+            } else {
+                POOL_GROWTH_RATE * count * (1.0 - count / capacity)
+            };
+            // Round the fractional part stochastically so small pools still change over time
+            let change = change.trunc() as i32 + rng.gen_bool(change.fract().abs() as f64) as i32 * change.signum() as i32;
+
+            if change > 0 {
+                for _ in 0..change {
+                    let parent = pool[members[rng.gen_range(0..members.len())]];
+                    pool.push(PooledCreature { species: species_id, age: 0.0, genome: parent.genome.inherit(rng) });
+                }
+            } else {
+                let mut dying: Vec<usize> = members;
+                dying.shuffle(rng);
+                dying.truncate((-change) as usize);
+                dying.sort_unstable_by(|a, b| b.cmp(a));
+                for index in dying {
+                    pool.swap_remove(index);
+                }
+            }
+        }
+    }
+    pools.chunks.retain(|_, pool| !pool.is_empty());
+}
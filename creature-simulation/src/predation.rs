@@ -0,0 +1,110 @@
+use bevy::prelude::*;
+use std::collections::HashMap;
+use crate::calendar::{moonlight, Calendar};
+use crate::creature::{Behavior, Creature, DeathCause, Killed, Needs, Wander};
+use crate::hibernation::Hibernation;
+use crate::learning::{Learned, LearningOutcome};
+use crate::optimization::{SpatialSearch, SpatialView};
+use crate::parental_care::Juvenile;
+use crate::relationships::RelationshipTable;
+use crate::simulation::SimClock;
+use crate::species::SpeciesRegistry;
+use crate::taming::Tamed;
+
+const HUNT_RADIUS: f32 = 80.0; // World units a hunter can spot prey from
+const NEW_MOON_SIGHT: f32 = 0.4; // Share of HUNT_RADIUS nocturnal hunters see on moonless nights
+const CATCH_DISTANCE: f32 = 3.0;
+const MEAL: f32 = 0.6; // Hunger removed by one catch
+const ALERT_RADIUS: f32 = 30.0; // Prey of average caution notice a hunter chasing them this close
+pub const FLEE_DISTANCE: f32 = 40.0; // How far ahead of the hunter fleeing prey aim
+
+// Hungry hunters chase the nearest creature of a species they prey on (see relationships.ron),
+// and prey that notice the chase run the other way unless tamed. Runs before move_creatures,
+// which then steers both towards their targets like any other. Most hunters are roosting at
+// night; nocturnal ones hunt only then, and see furthest under a full moon. Hibernating prey
+// cannot run; the chase wakes it, too late to get away. Young guarded by a parent are left alone.
+// How hungry a hunter gets before hunting and how soon prey notice it depend on what each has
+// learned (see learning.rs).
+pub fn hunt_prey(
+    mut commands: Commands,
+    (clock, calendar, spatial): (Res<SimClock>, Res<Calendar>, Res<SpatialView>),
+    registry: Res<SpeciesRegistry>,
+    relationships: Res<RelationshipTable>,
+    mut creatures: Query<(Entity, &Transform, &Creature, &mut Needs, &mut Wander), Without<Killed>>,
+    (tamed, mut hibernating, young): (Query<&Tamed>, Query<&mut Hibernation>, Query<&Juvenile>),
+    (learned, mut outcomes): (Query<&Learned>, EventWriter<LearningOutcome>),
+) {
+    let night = clock.is_night();
+    let night_radius = HUNT_RADIUS * (NEW_MOON_SIGHT + (1.0 - NEW_MOON_SIGHT) * moonlight(calendar.day));
+
+    let prey: HashMap<Entity, (Vec2, usize)> = creatures.iter()
+        .map(|(entity, transform, creature, _, _)| (entity, (transform.translation.truncate(), creature.species)))
+        .collect();
+    let mut caught: Vec<Entity> = Vec::new();
+    let mut chased: Vec<(Entity, Vec2)> = Vec::new(); // Prey and the position of its hunter
+
+    for (entity, transform, creature, mut needs, mut wander) in creatures.iter_mut() {
+        // A hunter being led follows its marker instead
+        let nocturnal = registry.get(creature.species).nocturnal;
+        if needs.hunger <= Learned::hunt_threshold(learned.get(entity).ok()) || !relationships.is_hunter(creature.species) || nocturnal != night
+            || Tamed::lead(tamed.get(entity).ok()).is_some() || Hibernation::is_dormant(hibernating.get(entity).ok()) {
+            continue;
+        }
+        let hunt_radius = if nocturnal { night_radius } else { HUNT_RADIUS };
+        let position = transform.translation.truncate();
+        // Candidates from the spatial hash, as of the last tick; where they are now from the query
+        let nearest = spatial.within(position, hunt_radius, SpatialSearch::Grid).into_iter()
+            .filter_map(|(entity, _)| prey.get(&entity).map(|(prey_position, species)| (entity, *prey_position, *species)))
+            .filter(|(entity, _, species)| relationships.preys_on(creature.species, *species) && !caught.contains(entity)
+                && !Juvenile::is_guarded(young.get(*entity).ok()))
+            .map(|(entity, prey_position, _)| (entity, prey_position, prey_position.distance(position)))
+            .filter(|(_, _, distance)| *distance < hunt_radius)
+            .min_by(|a, b| a.2.total_cmp(&b.2));
+        let Some((target, target_position, distance)) = nearest else { continue };
+
+        if distance < CATCH_DISTANCE {
+            caught.push(target);
+            outcomes.send(LearningOutcome::Caught(entity));
+            needs.hunger = (needs.hunger - MEAL).max(0.0);
+            wander.target = None;
+            wander.behavior = Behavior::Arrive;
+        } else {
+            wander.target = Some(target_position);
+            wander.behavior = Behavior::Hunt;
+            wander.search_cooldown = 2.0; // Keep the food/water scan from replacing the chase
+            if distance < ALERT_RADIUS * Learned::wariness(learned.get(target).ok()) {
+                chased.push((target, position));
+            }
+        }
+    }
+
+    for (entity, hunter_position) in chased {
+        if caught.contains(&entity) || tamed.contains(entity) {
+            continue;
+        }
+        if let Ok(mut hibernation) = hibernating.get_mut(entity) {
+            let dormant = Hibernation::is_dormant(Some(&hibernation));
+            hibernation.disturb();
+            if dormant {
+                continue;
+            }
+        }
+        if let Ok((_, transform, _, _, mut wander)) = creatures.get_mut(entity) {
+            let position = transform.translation.truncate();
+            let away = (position - hunter_position).normalize_or_zero();
+            if wander.behavior != Behavior::Flee {
+                outcomes.send(LearningOutcome::Chased(entity));
+            }
+            wander.target = Some(position + away * FLEE_DISTANCE);
+            wander.behavior = Behavior::Flee;
+            wander.search_cooldown = 2.0;
+        }
+    }
+
+    for entity in caught {
+        if let Ok((_, _, _, mut needs, _)) = creatures.get_mut(entity) {
+            needs.health = 0.0;
+        }
+        commands.entity(entity).insert(Killed(DeathCause::Predation));
+    }
+}
@@ -0,0 +1,150 @@
+use bevy::prelude::*;
+use directories::ProjectDirs;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use crate::camera::CameraBookmarks;
+use crate::creature::Creature;
+use crate::io_queue::{poll_io_writes, IoQueue};
+use crate::simulation::SimClock;
+use crate::species::SpeciesRegistry;
+use crate::text_input::{PromptKind, PromptSubmitted};
+use crate::world::WorldMap;
+
+const PROFILE_FILE: &str = "profile.ron";
+const MAX_NAMED_CREATURES: usize = 200; // The oldest names are dropped first
+
+pub struct ProfilePlugin;
+
+impl Plugin for ProfilePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(Profile::load())
+            .add_systems(Startup, restore_bookmarks)
+            .add_systems(Update, record_profile)
+            .add_systems(Last, save_profile.before(poll_io_writes));
+    }
+}
+
+// === USER DIRECTORIES ===
+// Machine settings (settings.rs) live in the platform's config directory and the user profile in
+// its data directory: ~/.config and ~/.local/share on Linux, Application Support on macOS and
+// AppData on Windows. Without a home directory both fall back to the working directory.
+fn project_dirs() -> Option<ProjectDirs> {
+    ProjectDirs::from("", "", "creature-simulation")
+}
+
+pub fn config_path(file: &str) -> PathBuf {
+    project_dirs().map_or_else(|| PathBuf::from(file), |dirs| dirs.config_dir().join(file))
+}
+
+pub fn data_path(file: &str) -> PathBuf {
+    project_dirs().map_or_else(|| PathBuf::from(file), |dirs| dirs.data_dir().join(file))
+}
+
+// Moves a file older versions kept in the working directory to its new place, unless one is there
+pub fn migrate_flat_file(file: &str, target: &Path) {
+    let flat = Path::new(file);
+    if flat == target || !flat.exists() || target.exists() {
+        return;
+    }
+    let moved = target.parent().map_or(Ok(()), fs::create_dir_all)
+        .and_then(|()| fs::copy(flat, target))
+        .and_then(|_| fs::remove_file(flat));
+    match moved {
+        Ok(()) => info!("Moved {} to {}", file, target.display()),
+        Err(err) => warn!("Could not move {} to {}: {}", file, target.display(), err),
+    }
+}
+
+// === USER PROFILE ===
+// What belongs to the player rather than the machine, kept across worlds and saves. Saved when it
+// changes and on exit; play time alone does not trigger a save.
+#[derive(Resource, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Profile {
+    pub bookmarks: [Option<(f32, f32)>; 10], // Camera bookmarks (camera.rs)
+    pub named_creatures: Vec<NamedCreature>, // Every name given, oldest first
+    pub stats: ProfileStats,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct NamedCreature {
+    pub name: String,
+    pub species: String,
+    pub day: u32, // Simulated day it was named on
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ProfileStats {
+    pub play_seconds: f64,
+    pub worlds_generated: u32,
+    pub creatures_named: u32,
+}
+
+impl Profile {
+    fn load() -> Self {
+        let path = data_path(PROFILE_FILE);
+        match fs::read_to_string(&path) {
+            Ok(contents) => ron::from_str(&contents).unwrap_or_else(|err| {
+                warn!("Failed to parse {}: {} - starting a new profile", path.display(), err);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        }
+    }
+}
+
+fn restore_bookmarks(profile: Res<Profile>, mut bookmarks: ResMut<CameraBookmarks>) {
+    for (slot, saved) in bookmarks.slots.iter_mut().zip(profile.bookmarks) {
+        *slot = saved.map(|(x, y)| Vec2::new(x, y));
+    }
+}
+
+fn record_profile(
+    time: Res<Time<Real>>,
+    mut profile: ResMut<Profile>,
+    bookmarks: Res<CameraBookmarks>,
+    mut submitted: EventReader<PromptSubmitted>,
+    creatures: Query<&Creature>,
+    (registry, clock): (Res<SpeciesRegistry>, Res<SimClock>),
+    world_map: Option<Res<WorldMap>>,
+) {
+    profile.bypass_change_detection().stats.play_seconds += time.delta_seconds_f64();
+
+    if world_map.is_some_and(|world_map| world_map.is_added()) {
+        profile.stats.worlds_generated += 1;
+    }
+    let marks = bookmarks.slots.map(|slot| slot.map(|position| (position.x, position.y)));
+    if profile.bookmarks != marks {
+        profile.bookmarks = marks;
+    }
+    for event in submitted.read() {
+        let PromptKind::CreatureName(entity) = event.kind else { continue };
+        let Ok(creature) = creatures.get(entity) else { continue };
+        if event.text.is_empty() {
+            continue;
+        }
+        profile.stats.creatures_named += 1;
+        profile.named_creatures.push(NamedCreature {
+            name: event.text.clone(),
+            species: registry.get(creature.species).name.clone(),
+            day: clock.day(),
+        });
+        let overflow = profile.named_creatures.len().saturating_sub(MAX_NAMED_CREATURES);
+        profile.named_creatures.drain(..overflow);
+    }
+}
+
+// Queued like other writes; on exit the queue writes it before the app closes
+fn save_profile(profile: Res<Profile>, mut io: ResMut<IoQueue>) {
+    if !profile.is_changed() || profile.is_added() {
+        return;
+    }
+    let path = data_path(PROFILE_FILE);
+    if let Some(Err(err)) = path.parent().map(fs::create_dir_all) {
+        return warn!("Could not save the profile to {}: {}", path.display(), err);
+    }
+    io.write_ron(&path.to_string_lossy(), profile.clone(), format!("👤 Saved profile to {}", path.display()), None);
+}
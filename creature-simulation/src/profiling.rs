@@ -0,0 +1,177 @@
+use bevy::ecs::system::{Adapt, AdapterSystem};
+use bevy::prelude::*;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Instant;
+use crate::localization::Localization;
+use crate::telemetry::Telemetry;
+
+const ALARM_FRAMES: u32 = 30; // Consecutive frames over budget before a system is reported
+
+// Run times of budgeted systems since the last check, pushed from whichever thread ran them
+static SAMPLES: Mutex<Vec<(Cow<'static, str>, f64, f64)>> = Mutex::new(Vec::new()); // (system, budget, ms)
+
+pub struct ProfilingPlugin;
+
+impl Plugin for ProfilingPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<SystemBudgets>()
+            .add_systems(Startup, spawn_budget_hud)
+            .add_systems(Last, (check_budgets, update_budget_hud).chain());
+    }
+}
+
+// === SYSTEM BUDGETS ===
+// Heavy systems are added as budgeted(system, ms) instead of plain system; ordering against the
+// plain function still works. Each frame the time a system took (summed, as FixedUpdate systems
+// can run several times a frame) is compared to its budget. Over budget for ALARM_FRAMES frames
+// in a row raises an alarm: a warning on screen for as long as it stays over, and one structured
+// log line and telemetry record ("budget_alarm") when it starts.
+pub fn budgeted<M, S: IntoSystem<(), (), M>>(system: S, budget_ms: f64) -> AdapterSystem<Budget, S::System> {
+    let system = IntoSystem::into_system(system);
+    let name = system.name();
+    AdapterSystem::new(Budget { name: name.clone(), budget_ms }, system, name)
+}
+
+pub struct Budget {
+    name: Cow<'static, str>,
+    budget_ms: f64,
+}
+
+impl<S: System<In = (), Out = ()>> Adapt<S> for Budget {
+    type In = ();
+    type Out = ();
+
+    fn adapt(&mut self, input: (), run_system: impl FnOnce(())) {
+        let start = Instant::now();
+        run_system(input);
+        let ms = start.elapsed().as_secs_f64() * 1000.0;
+        if let Ok(mut samples) = SAMPLES.lock() {
+            samples.push((self.name.clone(), self.budget_ms, ms));
+        }
+    }
+}
+
+#[derive(Default)]
+struct BudgetStreak {
+    frames_over: u32,
+    last_ms: f64,
+    budget_ms: f64,
+}
+
+#[derive(Resource, Default)]
+pub struct SystemBudgets {
+    streaks: HashMap<Cow<'static, str>, BudgetStreak>,
+}
+
+impl SystemBudgets {
+    // Systems currently alarmed, worst first: (short name, ms last frame, budget)
+    pub fn alarms(&self) -> Vec<(&str, f64, f64)> {
+        let mut alarms: Vec<_> = self.streaks.iter()
+            .filter(|(_, streak)| streak.frames_over >= ALARM_FRAMES)
+            .map(|(name, streak)| (short_name(name), streak.last_ms, streak.budget_ms))
+            .collect();
+        alarms.sort_by(|a, b| (b.1 / b.2).total_cmp(&(a.1 / a.2)));
+        alarms
+    }
+
+    // Every budgeted system, slowest first: (short name, ms last frame, budget, frames over)
+    pub fn timings(&self) -> Vec<(&str, f64, f64, u32)> {
+        let mut timings: Vec<_> = self.streaks.iter()
+            .map(|(name, streak)| (short_name(name), streak.last_ms, streak.budget_ms, streak.frames_over))
+            .collect();
+        timings.sort_by(|a, b| b.1.total_cmp(&a.1));
+        timings
+    }
+}
+
+// "creature_simulation::creature::move_creatures" -> "move_creatures"
+fn short_name(name: &str) -> &str {
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+#[derive(Serialize)]
+struct BudgetAlarm<'a> {
+    system: &'a str,
+    budget_ms: f64,
+    frame_ms: f64,
+    frames: u32,
+}
+
+#[derive(Component)]
+struct BudgetHud;
+
+fn check_budgets(mut budgets: ResMut<SystemBudgets>, mut telemetry: ResMut<Telemetry>) {
+    let Ok(mut samples) = SAMPLES.lock() else { return };
+    let mut frame: HashMap<Cow<'static, str>, (f64, f64)> = HashMap::new();
+    for (name, budget_ms, ms) in samples.drain(..) {
+        let total = frame.entry(name).or_insert((budget_ms, 0.0));
+        total.1 += ms;
+    }
+    drop(samples);
+
+    // Systems that did not run this frame keep their streak
+    for (name, (budget_ms, ms)) in frame {
+        let streak = budgets.streaks.entry(name.clone()).or_default();
+        streak.last_ms = ms;
+        streak.budget_ms = budget_ms;
+        if ms <= budget_ms {
+            streak.frames_over = 0;
+            continue;
+        }
+        streak.frames_over += 1;
+        if streak.frames_over == ALARM_FRAMES {
+            let system = short_name(&name);
+            warn!(system, budget_ms, frame_ms = ms, frames = ALARM_FRAMES, "⏱️ {} over its {:.1} ms budget for {} frames ({:.1} ms)",
+                system, budget_ms, ALARM_FRAMES, ms);
+            telemetry.record("budget_alarm", &BudgetAlarm { system, budget_ms, frame_ms: ms, frames: ALARM_FRAMES });
+        }
+    }
+}
+
+fn spawn_budget_hud(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 16.0,
+                color: Color::srgb(1.0, 0.75, 0.3),
+                ..default()
+            },
+        ).with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(120.0),
+            left: Val::Px(10.0),
+            padding: UiRect::all(Val::Px(6.0)),
+            ..default()
+        }).with_background_color(Color::srgba(0.1, 0.05, 0.0, 0.8)),
+        Visibility::Hidden,
+        BudgetHud,
+    ));
+}
+
+fn update_budget_hud(
+    budgets: Res<SystemBudgets>,
+    localization: Res<Localization>,
+    mut huds: Query<(&mut Text, &mut Visibility), With<BudgetHud>>,
+) {
+    let alarms = budgets.alarms();
+    for (mut text, mut visibility) in huds.iter_mut() {
+        if alarms.is_empty() {
+            visibility.set_if_neq(Visibility::Hidden);
+            continue;
+        }
+        text.sections[0].value = alarms.iter()
+            .map(|(system, ms, budget)| localization.format("profiling.over_budget", &[
+                ("system", system.to_string()),
+                ("ms", format!("{:.1}", ms)),
+                ("budget", format!("{:.1}", budget)),
+            ]))
+            .collect::<Vec<_>>()
+            .join("\n");
+        visibility.set_if_neq(Visibility::Visible);
+    }
+}
@@ -0,0 +1,229 @@
+// The #[pymethods] expansion converts PyErr into itself (pyo3 0.22 on newer clippy)
+#![allow(clippy::useless_conversion)]
+
+use pyo3::exceptions::{PyIOError, PyIndexError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use serde::Serialize;
+use std::fs;
+use crate::biome::BiomeType;
+use crate::errors::SimError;
+use crate::world::{GenerationParams, Topology, WorldMap, WORLD_SIZE};
+use crate::world_query::WorldQuery;
+
+// === PYTHON BINDINGS ===
+// The world generator for notebooks, built with the creature-sim-py feature (see pyproject.toml):
+// the same code the game runs, so a seed and settings give the same map in both. Exposes
+// GenerationSettings (GenerationParams), WorldGenerator and the generated WorldMap, with its
+// layers as nested lists and an export to JSON. Generation runs without holding the GIL.
+#[pymodule]
+#[pyo3(name = "creature_sim")]
+fn creature_sim(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyGenerationSettings>()?;
+    module.add_class::<PyWorldGenerator>()?;
+    module.add_class::<PyWorldMap>()?;
+    module.add("WORLD_SIZE", WORLD_SIZE)?;
+    module.add("BIOMES", biome_names())?;
+    Ok(())
+}
+
+fn to_py_err(err: SimError) -> PyErr {
+    PyValueError::new_err(err.to_string())
+}
+
+fn biome_name(biome: BiomeType) -> String {
+    format!("{:?}", biome)
+}
+
+// Indexed by biome id
+fn biome_names() -> Vec<String> {
+    (0..BiomeType::ALL.len() as u8).map(|id| biome_name(BiomeType::from_id(id))).collect()
+}
+
+fn topology_name(topology: Topology) -> String {
+    format!("{:?}", topology).to_lowercase()
+}
+
+#[pyclass(name = "GenerationSettings")]
+#[derive(Clone, Default)]
+struct PyGenerationSettings(GenerationParams);
+
+#[pymethods]
+impl PyGenerationSettings {
+    #[new]
+    fn new() -> Self {
+        Self::default()
+    }
+
+    #[getter]
+    fn sea_level(&self) -> f32 { self.0.sea_level }
+    #[setter]
+    fn set_sea_level(&mut self, value: f32) { self.0.sea_level = value; }
+    #[getter]
+    fn elevation_scale(&self) -> f32 { self.0.elevation_scale }
+    #[setter]
+    fn set_elevation_scale(&mut self, value: f32) { self.0.elevation_scale = value; }
+    #[getter]
+    fn temperature_scale(&self) -> f32 { self.0.temperature_scale }
+    #[setter]
+    fn set_temperature_scale(&mut self, value: f32) { self.0.temperature_scale = value; }
+    #[getter]
+    fn moisture_scale(&self) -> f32 { self.0.moisture_scale }
+    #[setter]
+    fn set_moisture_scale(&mut self, value: f32) { self.0.moisture_scale = value; }
+    #[getter]
+    fn mountain_elevation(&self) -> f32 { self.0.mountain_elevation }
+    #[setter]
+    fn set_mountain_elevation(&mut self, value: f32) { self.0.mountain_elevation = value; }
+    #[getter]
+    fn hot_temperature(&self) -> f32 { self.0.hot_temperature }
+    #[setter]
+    fn set_hot_temperature(&mut self, value: f32) { self.0.hot_temperature = value; }
+    #[getter]
+    fn cold_temperature(&self) -> f32 { self.0.cold_temperature }
+    #[setter]
+    fn set_cold_temperature(&mut self, value: f32) { self.0.cold_temperature = value; }
+    #[getter]
+    fn axial_tilt(&self) -> f32 { self.0.axial_tilt }
+    #[setter]
+    fn set_axial_tilt(&mut self, value: f32) { self.0.axial_tilt = value; }
+
+    // "flat", "cylinder" or "torus"
+    #[getter]
+    fn topology(&self) -> String {
+        topology_name(self.0.topology)
+    }
+
+    #[setter]
+    fn set_topology(&mut self, value: &str) -> PyResult<()> {
+        self.0.topology = Topology::ALL.into_iter()
+            .find(|topology| topology_name(*topology) == value.to_lowercase())
+            .ok_or_else(|| PyValueError::new_err(format!("unknown topology {:?}, expected flat, cylinder or torus", value)))?;
+        Ok(())
+    }
+
+    fn validate(&self) -> PyResult<()> {
+        self.0.validate().map_err(to_py_err)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("{:?}", self.0)
+    }
+}
+
+#[pyclass(name = "WorldGenerator")]
+struct PyWorldGenerator {
+    seed: Option<u32>,
+    settings: GenerationParams,
+}
+
+#[pymethods]
+impl PyWorldGenerator {
+    // No seed picks a random one, as the game does
+    #[new]
+    #[pyo3(signature = (seed=None, settings=None))]
+    fn new(seed: Option<u32>, settings: Option<PyGenerationSettings>) -> Self {
+        Self { seed, settings: settings.unwrap_or_default().0 }
+    }
+
+    fn generate(&self, py: Python<'_>) -> PyResult<PyWorldMap> {
+        let (seed, settings) = (self.seed, self.settings);
+        let map = py.allow_threads(|| {
+            settings.validate()?;
+            crate::world::WorldGenerator::new(seed).with_params(settings).generate_world()
+        }).map_err(to_py_err)?;
+        Ok(PyWorldMap(map))
+    }
+}
+
+#[pyclass(name = "WorldMap")]
+struct PyWorldMap(WorldMap);
+
+// What export_json writes: the map's layers, indexed [x][y] like the map itself
+#[derive(Serialize)]
+struct MapExport<'a> {
+    seed: u32,
+    size: usize,
+    settings: &'a GenerationParams,
+    biomes: Vec<String>, // Names for the ids in `biome`
+    biome: Vec<Vec<u8>>,
+    elevation: Vec<Vec<f32>>,
+    temperature: Vec<Vec<f32>>,
+    moisture: Vec<Vec<f32>>,
+}
+
+impl PyWorldMap {
+    fn layer<T>(&self, value: impl Fn(&crate::world::Tile) -> T) -> Vec<Vec<T>> {
+        self.0.tiles.iter().map(|column| column.iter().map(&value).collect()).collect()
+    }
+}
+
+#[pymethods]
+impl PyWorldMap {
+    #[getter]
+    fn seed(&self) -> u32 {
+        self.0.seed
+    }
+
+    #[getter]
+    fn size(&self) -> usize {
+        WORLD_SIZE
+    }
+
+    #[getter]
+    fn settings(&self) -> PyGenerationSettings {
+        PyGenerationSettings(self.0.params)
+    }
+
+    // Biome name, elevation, temperature, moisture and resource names of one tile
+    fn tile<'py>(&self, py: Python<'py>, x: usize, y: usize) -> PyResult<Bound<'py, PyDict>> {
+        let tile = WorldQuery::new(&self.0).tile(x, y)
+            .ok_or_else(|| PyIndexError::new_err(format!("tile ({}, {}) is off the map", x, y)))?;
+        let dict = PyDict::new_bound(py);
+        dict.set_item("biome", biome_name(tile.biome))?;
+        dict.set_item("elevation", tile.elevation)?;
+        dict.set_item("temperature", tile.temperature)?;
+        dict.set_item("moisture", tile.moisture)?;
+        dict.set_item("resources", tile.resources.iter().map(|resource| format!("{:?}", resource)).collect::<Vec<_>>())?;
+        Ok(dict)
+    }
+
+    // Biome ids, indexes into BIOMES
+    fn biome_grid(&self) -> Vec<Vec<u8>> {
+        self.layer(|tile| tile.biome.to_id())
+    }
+
+    // "elevation", "temperature" or "moisture"
+    fn layer_grid(&self, name: &str) -> PyResult<Vec<Vec<f32>>> {
+        match name {
+            "elevation" => Ok(self.layer(|tile| tile.elevation)),
+            "temperature" => Ok(self.layer(|tile| tile.temperature)),
+            "moisture" => Ok(self.layer(|tile| tile.moisture)),
+            _ => Err(PyValueError::new_err(format!("unknown layer {:?}, expected elevation, temperature or moisture", name))),
+        }
+    }
+
+    // At a point in world units, as the game places creatures; None off the map
+    fn biome_at(&self, x: f32, y: f32) -> Option<String> {
+        WorldQuery::new(&self.0).biome_at(bevy::math::Vec2::new(x, y)).map(biome_name)
+    }
+
+    fn export_json(&self, path: &str) -> PyResult<()> {
+        let export = MapExport {
+            seed: self.0.seed,
+            size: WORLD_SIZE,
+            settings: &self.0.params,
+            biomes: biome_names(),
+            biome: self.biome_grid(),
+            elevation: self.layer(|tile| tile.elevation),
+            temperature: self.layer(|tile| tile.temperature),
+            moisture: self.layer(|tile| tile.moisture),
+        };
+        let json = serde_json::to_string(&export).map_err(|err| PyValueError::new_err(err.to_string()))?;
+        fs::write(path, json).map_err(|err| PyIOError::new_err(format!("could not write {}: {}", path, err)))
+    }
+
+    fn __repr__(&self) -> String {
+        format!("WorldMap(seed={}, size={})", self.0.seed, WORLD_SIZE)
+    }
+}
@@ -0,0 +1,257 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::errors::SimError;
+use crate::keybindings::{Action, Controls, Keybindings};
+use crate::localization::Localization;
+use crate::mods::{ModList, RELATIONSHIPS_FILE};
+use crate::species::SpeciesRegistry;
+use crate::text_input::text_input_inactive;
+
+// Built-in relationship table, embedded like the species list
+const RELATIONSHIPS_PATH: &str = "assets/relationships.ron"; // For error messages
+const RELATIONSHIPS_SOURCE: &str = include_str!("../assets/relationships.ron");
+const HEADING_COLOR: Color = Color::srgb(0.95, 0.85, 0.5);
+const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const CELL_SIZE: f32 = 26.0;
+
+pub struct RelationshipsPlugin;
+
+impl Plugin for RelationshipsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<RelationshipTable>()
+            .add_systems(Update, toggle_relationship_matrix.run_if(text_input_inactive));
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Relationship {
+    #[default]
+    Neutral,
+    Predation, // `a` hunts `b` (see predation.rs)
+    Competition, // Same food; shown in the matrix only
+    Mutualism, // Tolerated even by species that would otherwise hunt them
+}
+
+// One line of relationships.ron
+#[derive(Debug, Clone, Deserialize)]
+struct RelationshipEntry {
+    a: String,
+    b: String,
+    kind: Relationship,
+}
+
+// === RELATIONSHIP TABLE ===
+// How every pair of species treats each other, by species index. Unlisted pairs are neutral, and
+// a pair has a single relationship, so listing a cleaner as a mutualist of a hunter keeps it off
+// the menu.
+#[derive(Resource)]
+pub struct RelationshipTable {
+    size: usize,
+    kinds: Vec<Relationship>, // [a * size + b], the same both ways
+    hunts: Vec<bool>, // [hunter * size + prey]
+}
+
+impl FromWorld for RelationshipTable {
+    fn from_world(world: &mut World) -> Self {
+        let registry = world.resource::<SpeciesRegistry>();
+        // Everyone neutral; the error screen reports why (see errors.rs)
+        Self::load(registry).unwrap_or_else(|_| Self::neutral(registry.species.len()))
+    }
+}
+
+impl RelationshipTable {
+    fn neutral(size: usize) -> Self {
+        Self { size, kinds: vec![Relationship::Neutral; size * size], hunts: vec![false; size * size] }
+    }
+
+    pub fn load(registry: &SpeciesRegistry) -> Result<Self, SimError> {
+        let entries: Vec<RelationshipEntry> = ron::from_str(RELATIONSHIPS_SOURCE)
+            .map_err(|err| SimError::parse(RELATIONSHIPS_PATH, err))?;
+        let index = |name: &str| registry.species.iter().position(|species| species.name == name)
+            .ok_or_else(|| SimError::parse(RELATIONSHIPS_PATH, format!("unknown species \"{}\"", name)));
+
+        let mut table = Self::neutral(registry.species.len());
+        for entry in entries {
+            let (a, b) = (index(&entry.a)?, index(&entry.b)?);
+            table.set(a, b, entry.kind);
+        }
+        // Mod entries come after, so they replace built-in pairs; unknown species only skip the entry
+        for pack in ModList::discover().enabled() {
+            for entry in pack.read_list::<RelationshipEntry>(RELATIONSHIPS_FILE) {
+                match (registry.find(&entry.a), registry.find(&entry.b)) {
+                    (Some(a), Some(b)) => table.set(a, b, entry.kind),
+                    _ => warn!("Skipping relationship {} / {} of mod {}: unknown species", entry.a, entry.b, pack.id),
+                }
+            }
+        }
+        Ok(table)
+    }
+
+    fn set(&mut self, a: usize, b: usize, kind: Relationship) {
+        self.kinds[a * self.size + b] = kind;
+        self.kinds[b * self.size + a] = kind;
+        self.hunts[a * self.size + b] = kind == Relationship::Predation;
+        self.hunts[b * self.size + a] = false; // A later entry replaces the pair
+    }
+
+    // Makes room for a species added while running (see species_designer.rs), neutral to all others
+    pub fn add_species(&mut self) {
+        let mut grown = Self::neutral(self.size + 1);
+        for a in 0..self.size {
+            for b in 0..self.size {
+                grown.kinds[a * grown.size + b] = self.kinds[a * self.size + b];
+                grown.hunts[a * grown.size + b] = self.hunts[a * self.size + b];
+            }
+        }
+        *self = grown;
+    }
+
+    // Makes room for a species split off from another (see speciation.rs), which inherits its
+    // ancestor's relationships with everyone, itself included
+    pub fn add_descendant(&mut self, ancestor: usize) {
+        self.add_species();
+        let new = self.size - 1;
+        let source = |species: usize| if species == new { ancestor } else { species };
+        for other in 0..self.size {
+            self.kinds[new * self.size + other] = self.kinds[ancestor * self.size + source(other)];
+            self.kinds[other * self.size + new] = self.kinds[source(other) * self.size + ancestor];
+            self.hunts[new * self.size + other] = self.hunts[ancestor * self.size + source(other)];
+            self.hunts[other * self.size + new] = self.hunts[source(other) * self.size + ancestor];
+        }
+    }
+
+    pub fn between(&self, a: usize, b: usize) -> Relationship {
+        self.kinds[a * self.size + b]
+    }
+
+    pub fn preys_on(&self, hunter: usize, prey: usize) -> bool {
+        self.hunts[hunter * self.size + prey]
+    }
+
+    pub fn is_hunter(&self, species: usize) -> bool {
+        (0..self.size).any(|prey| self.preys_on(species, prey))
+    }
+}
+
+// === RELATIONSHIP MATRIX ===
+// R shows every pair as a grid: read a row as "this species ... the column species". Rebuilt each
+// time it opens, like the help overlay.
+#[derive(Component)]
+pub struct RelationshipMatrix;
+
+fn toggle_relationship_matrix(
+    mut commands: Commands,
+    controls: Controls,
+    (bindings, localization): (Res<Keybindings>, Res<Localization>),
+    (registry, table): (Res<SpeciesRegistry>, Res<RelationshipTable>),
+    matrices: Query<Entity, With<RelationshipMatrix>>,
+) {
+    if !controls.just_pressed(Action::ToggleRelationships) {
+        return;
+    }
+    if !matrices.is_empty() {
+        for entity in matrices.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    }
+    spawn_relationship_matrix(&mut commands, &bindings, &localization, &registry, &table);
+}
+
+fn text_style(font_size: f32, color: Color) -> TextStyle {
+    TextStyle { font_size, color, ..default() }
+}
+
+// Symbol and color of a cell, seen from the row species
+fn cell(table: &RelationshipTable, row: usize, column: usize) -> (&'static str, Color) {
+    match table.between(row, column) {
+        Relationship::Predation if table.preys_on(row, column) => ("→", Color::srgb(0.9, 0.35, 0.3)),
+        Relationship::Predation => ("←", Color::srgb(0.95, 0.6, 0.3)),
+        Relationship::Competition => ("=", Color::srgb(0.9, 0.85, 0.35)),
+        Relationship::Mutualism => ("+", Color::srgb(0.45, 0.85, 0.45)),
+        Relationship::Neutral => ("·", Color::srgb(0.45, 0.45, 0.5)),
+    }
+}
+
+fn spawn_relationship_matrix(
+    commands: &mut Commands,
+    bindings: &Keybindings,
+    localization: &Localization,
+    registry: &SpeciesRegistry,
+    table: &RelationshipTable,
+) {
+    let cell_style = || Style {
+        width: Val::Px(CELL_SIZE),
+        height: Val::Px(CELL_SIZE),
+        justify_content: JustifyContent::Center,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    let row_style = || Style {
+        flex_direction: FlexDirection::Row,
+        align_items: AlignItems::Center,
+        ..default()
+    };
+    let name_style = || Style {
+        width: Val::Px(180.0),
+        ..default()
+    };
+
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Percent(12.0),
+                left: Val::Percent(25.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(16.0)),
+                row_gap: Val::Px(2.0),
+                ..default()
+            },
+            background_color: Color::srgba(0.05, 0.05, 0.1, 0.92).into(),
+            z_index: ZIndex::Global(50),
+            ..default()
+        },
+        RelationshipMatrix,
+    )).with_children(|parent| {
+        parent.spawn(TextBundle::from_section(localization.get("relations.title"), text_style(24.0, TEXT_COLOR))
+            .with_style(Style { margin: UiRect::bottom(Val::Px(8.0)), ..default() }));
+
+        // Columns are numbered; the numbers match the row labels
+        parent.spawn(NodeBundle { style: row_style(), ..default() }).with_children(|header| {
+            header.spawn(NodeBundle { style: name_style(), ..default() });
+            for column in 0..registry.species.len() {
+                header.spawn(NodeBundle { style: cell_style(), ..default() }).with_children(|label| {
+                    label.spawn(TextBundle::from_section((column + 1).to_string(), text_style(14.0, HEADING_COLOR)));
+                });
+            }
+        });
+
+        for (row, species) in registry.species.iter().enumerate() {
+            parent.spawn(NodeBundle { style: row_style(), ..default() }).with_children(|line| {
+                line.spawn(TextBundle::from_section(
+                    format!("{}  {}", row + 1, species.name),
+                    text_style(14.0, species.get_color()),
+                ).with_style(name_style()));
+                for column in 0..registry.species.len() {
+                    let (symbol, color) = cell(table, row, column);
+                    line.spawn(NodeBundle {
+                        style: cell_style(),
+                        background_color: color.with_alpha(0.15).into(),
+                        ..default()
+                    }).with_children(|label| {
+                        label.spawn(TextBundle::from_section(symbol, text_style(16.0, color)));
+                    });
+                }
+            });
+        }
+
+        parent.spawn(TextBundle::from_section(localization.get("relations.legend"), text_style(13.0, TEXT_COLOR))
+            .with_style(Style { margin: UiRect::top(Val::Px(8.0)), ..default() }));
+        parent.spawn(TextBundle::from_section(
+            localization.format("help.close", &[("key", bindings.label(Action::ToggleRelationships))]),
+            text_style(13.0, Color::srgb(0.6, 0.6, 0.7)),
+        ));
+    });
+}
@@ -1,13 +1,42 @@
 use bevy::prelude::*;
 use rand::Rng;
+use rand::rngs::StdRng;
+use crate::coords::{tile_to_world, TILE_SIZE};
 use crate::world::{WorldMap, WORLD_SIZE};
+use crate::loading::{LoadingStage, LoadingState};
+use crate::localization::Localization;
+use crate::rng_streams::{RngStream, RngStreams};
+use crate::settings::Settings;
 use crate::environment::{EnvironmentSprite, SwayAnimation, EnvironmentType, get_environment_elements};
 
 pub struct RenderPlugin;
 
 impl Plugin for RenderPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (render_world_tiles, handle_camera_movement));
+        app
+            .init_resource::<TileRenderer>()
+            .add_systems(Update, render_world_tiles.run_if(resource_equals(TileRenderer::Legacy)));
+    }
+}
+
+// === TILE RENDERER SELECTION ===
+// Exactly one renderer draws the world. The chunked renderer (optimized_systems.rs) is the default;
+// the legacy renderer spawns a sprite for every one of the WORLD_SIZE² tiles and is only reachable
+// with the --legacy-render debug flag. Each renderer's systems run only while it is selected here.
+#[derive(Resource, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TileRenderer {
+    Chunked,
+    Legacy,
+}
+
+impl FromWorld for TileRenderer {
+    fn from_world(world: &mut World) -> Self {
+        if world.resource::<Settings>().legacy_render {
+            warn!("Legacy full-world rendering enabled: {} tile sprites, no editor updates", WORLD_SIZE * WORLD_SIZE);
+            TileRenderer::Legacy
+        } else {
+            TileRenderer::Chunked
+        }
     }
 }
 
@@ -17,14 +46,14 @@ pub struct WorldTile {
     pub y: usize,
 }
 
-pub const TILE_SIZE: f32 = 4.0;
-const CAMERA_SPEED: f32 = 300.0;
-
-fn render_world_tiles(
+pub fn render_world_tiles(
     mut commands: Commands,
     world_map: Option<Res<WorldMap>>,
     existing_tiles: Query<Entity, With<WorldTile>>,
     existing_environment: Query<Entity, With<EnvironmentSprite>>,
+    mut loading_state: ResMut<LoadingState>,
+    localization: Res<Localization>,
+    mut streams: ResMut<RngStreams>,
 ) {
     if let Some(world_map) = world_map {
         if world_map.is_changed() {
@@ -50,11 +79,7 @@ fn render_world_tiles(
                                 custom_size: Some(Vec2::new(TILE_SIZE, TILE_SIZE)),
                                 ..default()
                             },
-                            transform: Transform::from_translation(Vec3::new(
-                                (x as f32 - WORLD_SIZE as f32 / 2.0) * TILE_SIZE,
-                                (y as f32 - WORLD_SIZE as f32 / 2.0) * TILE_SIZE,
-                                0.0,
-                            )),
+                            transform: Transform::from_translation(tile_to_world(x, y).extend(0.0)),
                             ..default()
                         },
                         WorldTile { x, y },
@@ -63,10 +88,18 @@ fn render_world_tiles(
                     // Spawn environment elements
                     let environment_elements = get_environment_elements(&tile.biome, x, y);
                     for element_type in environment_elements {
-                        spawn_environment_element(&mut commands, element_type, x, y);
+                        spawn_environment_element(&mut commands, element_type, x, y, streams.get(RngStream::Environment));
                     }
                 }
             }
+
+            // The chunked renderer normally ends the loading screen once its first chunks are up
+            if loading_state.world_ready && !loading_state.first_frame_rendered {
+                loading_state.first_frame_rendered = true;
+                loading_state.advance(LoadingStage::Rendering, 1.0, 0.0);
+                loading_state.is_complete = true;
+                loading_state.current_message = localization.get("loading.welcome").to_string();
+            }
         }
     }
 }
@@ -76,18 +109,13 @@ fn spawn_environment_element(
     element_type: EnvironmentType,
     tile_x: usize,
     tile_y: usize,
+    rng: &mut StdRng,
 ) {
-    let mut rng = rand::thread_rng();
-    
-    // Calculate base position
-    let base_x = (tile_x as f32 - WORLD_SIZE as f32 / 2.0) * TILE_SIZE;
-    let base_y = (tile_y as f32 - WORLD_SIZE as f32 / 2.0) * TILE_SIZE;
-    
-    // Add small random offset within the tile
+    // Small random offset within the tile
     let offset_x = rng.gen_range(-TILE_SIZE * 0.3..TILE_SIZE * 0.3);
     let offset_y = rng.gen_range(-TILE_SIZE * 0.3..TILE_SIZE * 0.3);
     
-    let position = Vec3::new(base_x + offset_x, base_y + offset_y, 1.0);
+    let position = (tile_to_world(tile_x, tile_y) + Vec2::new(offset_x, offset_y)).extend(1.0);
     let size = element_type.get_size();
     let color = element_type.get_color();
 
@@ -121,31 +149,3 @@ fn spawn_environment_element(
         });
     }
 }
-
-fn handle_camera_movement(
-    mut camera_query: Query<&mut Transform, With<Camera>>,
-    keyboard_input: Res<ButtonInput<KeyCode>>,
-    time: Res<Time>,
-) {
-    if let Ok(mut camera_transform) = camera_query.get_single_mut() {
-        let mut direction = Vec3::ZERO;
-
-        if keyboard_input.pressed(KeyCode::ArrowUp) || keyboard_input.pressed(KeyCode::KeyW) {
-            direction.y += 1.0;
-        }
-        if keyboard_input.pressed(KeyCode::ArrowDown) || keyboard_input.pressed(KeyCode::KeyS) {
-            direction.y -= 1.0;
-        }
-        if keyboard_input.pressed(KeyCode::ArrowLeft) || keyboard_input.pressed(KeyCode::KeyA) {
-            direction.x -= 1.0;
-        }
-        if keyboard_input.pressed(KeyCode::ArrowRight) || keyboard_input.pressed(KeyCode::KeyD) {
-            direction.x += 1.0;
-        }
-
-        if direction.length() > 0.0 {
-            direction = direction.normalize();
-            camera_transform.translation += direction * CAMERA_SPEED * time.delta_seconds();
-        }
-    }
-}
\ No newline at end of file
@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+use rand::Rng;
+use std::collections::HashMap;
+use crate::calendar::{Calendar, MoonPhase, LUNAR_CYCLE_DAYS};
+use crate::creature::{spawn_creature, Creature, Needs};
+use crate::genealogy::Genealogy;
+use crate::genetics::Genome;
+use crate::nesting::{lay_clutch, Egg};
+use crate::parental_care::Juvenile;
+use crate::population_pools::PopulationPools;
+use crate::species::SpeciesRegistry;
+use crate::world::WorldMap;
+use crate::rng_streams::{RngStream, RngStreams};
+
+pub const MATURITY_AGE: f32 = 240.0; // Simulated seconds before a creature can breed
+const BIRTH_CHANCE: f32 = 1.0 / 300.0; // Per second, while healthy and well fed
+const BIRTH_COST: f32 = 0.35; // Hunger added to the parent
+const POPULATION_LIMIT: usize = 3; // Multiple of the species' initial population
+
+pub struct ReproductionPlugin;
+
+impl Plugin for ReproductionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedUpdate, give_birth.run_if(resource_exists::<WorldMap>));
+    }
+}
+
+// Grown up, healthy and well fed
+pub fn ready_to_breed(creature: &Creature, needs: &Needs) -> bool {
+    creature.age >= MATURITY_AGE && needs.health >= 0.9 && needs.hunger <= 0.3 && needs.thirst <= 0.3
+}
+
+// Asexual births next to the parent; offspring inherit a mutated copy of its genome. Lunar
+// breeders save it all for the new moon, at LUNAR_CYCLE_DAYS times the chance, so they keep the
+// same birth rate over a whole cycle. Oviparous species lay a clutch of eggs instead (see nesting.rs),
+// which count towards the population limit until they hatch. So do creatures merged into population
+// pools, or a species would breed back up to the limit as fast as it is pooled.
+fn give_birth(
+    mut commands: Commands,
+    time: Res<Time>,
+    calendar: Res<Calendar>,
+    (registry, pools): (Res<SpeciesRegistry>, Res<PopulationPools>),
+    (mut genealogy, mut streams): (ResMut<Genealogy>, ResMut<RngStreams>),
+    mut creatures: Query<(Entity, &Transform, &Creature, &mut Needs, &Genome)>,
+    eggs: Query<&Egg>,
+) {
+    let dt = time.delta_seconds();
+    let rng = streams.get(RngStream::Creatures);
+
+    let mut population: HashMap<usize, usize> = HashMap::new();
+    for (_, _, creature, _, _) in creatures.iter() {
+        *population.entry(creature.species).or_default() += 1;
+    }
+    for egg in eggs.iter() {
+        *population.entry(egg.species).or_default() += 1;
+    }
+    for pooled in pools.chunks.values().flatten() {
+        *population.entry(pooled.species).or_default() += 1;
+    }
+
+    for (parent, transform, creature, mut needs, genome) in creatures.iter_mut() {
+        let species = registry.get(creature.species);
+        let count = population.entry(creature.species).or_default();
+        let birth_chance = match (species.lunar_breeder, calendar.moon) {
+            (false, _) => BIRTH_CHANCE,
+            (true, MoonPhase::New) => BIRTH_CHANCE * LUNAR_CYCLE_DAYS as f32,
+            (true, _) => 0.0,
+        };
+        if !ready_to_breed(creature, &needs)
+            || *count >= species.initial_population * POPULATION_LIMIT
+            || !rng.gen_bool((birth_chance * dt).min(1.0) as f64)
+        {
+            continue;
+        }
+
+        needs.hunger += BIRTH_COST;
+        let position = transform.translation.truncate();
+        if species.oviparous {
+            *count += lay_clutch(&mut commands, &registry, creature.species, position, (parent, *genome), rng);
+            continue;
+        }
+        *count += 1;
+        let heading = rng.gen_range(0.0..std::f32::consts::TAU);
+        let child_genome = genome.inherit(rng);
+        let child = spawn_creature(&mut commands, &registry, creature.species, position, heading, child_genome);
+        commands.entity(child).insert(Juvenile::of(parent));
+        genealogy.record_birth((parent, creature.species, *genome), (child, child_genome), calendar.day);
+    }
+}
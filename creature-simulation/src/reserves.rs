@@ -0,0 +1,232 @@
+use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use std::collections::HashSet;
+use std::time::Duration;
+use crate::camera::MainCameraQuery;
+use crate::coords::{tile_to_world, world_to_tile, TILE_SIZE};
+use crate::keybindings::{Action, Controls};
+use crate::creature::Creature;
+use crate::editor::{cursor_tile, editor_active, Editor, EditorTool};
+use crate::localization::Localization;
+use crate::species::SpeciesRegistry;
+use crate::text_input::text_input_inactive;
+use crate::vegetation::Vegetation;
+use crate::world::{WorldMap, WORLD_SIZE};
+use crate::world_stats::WorldReportPanel;
+
+const STATS_REFRESH_SECONDS: f32 = 2.0;
+const RESERVE_COLOR: Color = Color::srgb(0.3, 0.9, 0.4);
+
+pub struct ReservesPlugin;
+
+impl Plugin for ReservesPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Reserves>()
+            .init_resource::<ReserveBrush>()
+            .add_systems(Update, (
+                reset_reserves.run_if(resource_added::<WorldMap>),
+                reserve_hotkeys.run_if(text_input_inactive).run_if(editor_active),
+                designate_reserves.run_if(editor_active),
+                draw_reserves.run_if(editor_active),
+                update_reserve_stats.run_if(on_timer(Duration::from_secs_f32(STATS_REFRESH_SECONDS))),
+            ).chain().run_if(resource_exists::<WorldMap>));
+    }
+}
+
+// === NATURE RESERVES ===
+// Tiles set aside with the editor's reserve tool, either painted with the brush or dragged out as
+// rectangles. Locust swarms do not feed on them, and the console experiments, the spawner brush,
+// spawn zones and the editor's terrain tools leave them alone. The world report panel compares
+// the populations inside them with the wild.
+#[derive(Resource, Default)]
+pub struct Reserves {
+    pub tiles: HashSet<(usize, usize)>,
+}
+
+impl Reserves {
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        self.tiles.contains(&(x, y))
+    }
+
+    pub fn contains_point(&self, point: Vec2) -> bool {
+        world_to_tile(point).is_some_and(|(x, y)| self.contains(x, y))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ReserveShape {
+    #[default]
+    Brush,
+    Rectangle,
+}
+
+#[derive(Resource, Default)]
+pub struct ReserveBrush {
+    pub shape: ReserveShape,
+    drag_start: Option<((usize, usize), bool)>, // Rectangle corner and whether it erases
+}
+
+fn reset_reserves(mut reserves: ResMut<Reserves>) {
+    reserves.tiles.clear();
+}
+
+// M switches between painting and dragging rectangles
+fn reserve_hotkeys(controls: Controls, editor: Res<Editor>, mut brush: ResMut<ReserveBrush>) {
+    if editor.tool == EditorTool::Reserve && controls.just_pressed(Action::ToggleToolMode) {
+        brush.shape = match brush.shape {
+            ReserveShape::Brush => ReserveShape::Rectangle,
+            ReserveShape::Rectangle => ReserveShape::Brush,
+        };
+    }
+}
+
+fn rectangle(start: (usize, usize), end: (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+    let ((min_x, min_y), (max_x, max_y)) = ((start.0.min(end.0), start.1.min(end.1)), (start.0.max(end.0), start.1.max(end.1)));
+    (min_x..=max_x).flat_map(move |x| (min_y..=max_y).map(move |y| (x, y)))
+}
+
+// Left mouse designates, right mouse removes
+fn designate_reserves(
+    mouse: Res<ButtonInput<MouseButton>>,
+    (windows, cameras): (Query<&Window>, MainCameraQuery),
+    interactions: Query<&Interaction>,
+    editor: Res<Editor>,
+    mut brush: ResMut<ReserveBrush>,
+    mut reserves: ResMut<Reserves>,
+) {
+    if editor.tool != EditorTool::Reserve {
+        return;
+    }
+    let Some(cursor) = cursor_tile(&windows, &cameras) else { return };
+    let on_ui = interactions.iter().any(|interaction| *interaction != Interaction::None);
+
+    match brush.shape {
+        ReserveShape::Rectangle => {
+            if !on_ui && mouse.any_just_pressed([MouseButton::Left, MouseButton::Right]) {
+                brush.drag_start = Some((cursor, mouse.just_pressed(MouseButton::Right)));
+            }
+            if !mouse.any_just_released([MouseButton::Left, MouseButton::Right]) {
+                return;
+            }
+            let Some((start, erase)) = brush.drag_start.take() else { return };
+            for tile in rectangle(start, cursor) {
+                if erase {
+                    reserves.tiles.remove(&tile);
+                } else {
+                    reserves.tiles.insert(tile);
+                }
+            }
+        }
+        ReserveShape::Brush => {
+            let erase = mouse.pressed(MouseButton::Right);
+            if on_ui || !(erase || mouse.pressed(MouseButton::Left)) {
+                return;
+            }
+            let radius = editor.radius as i32;
+            for dx in -radius..=radius {
+                for dy in -radius..=radius {
+                    let (x, y) = (cursor.0 as i32 + dx, cursor.1 as i32 + dy);
+                    let distance = ((dx * dx + dy * dy) as f32).sqrt();
+                    if x < 0 || y < 0 || x >= WORLD_SIZE as i32 || y >= WORLD_SIZE as i32 || distance > radius as f32 + 0.5 {
+                        continue;
+                    }
+                    if erase {
+                        reserves.tiles.remove(&(x as usize, y as usize));
+                    } else {
+                        reserves.tiles.insert((x as usize, y as usize));
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Reserve borders (tile edges facing unprotected tiles) and the rectangle being dragged
+fn draw_reserves(
+    mut gizmos: Gizmos,
+    editor: Res<Editor>,
+    brush: Res<ReserveBrush>,
+    reserves: Res<Reserves>,
+    windows: Query<&Window>,
+    cameras: MainCameraQuery,
+) {
+    let half = TILE_SIZE / 2.0;
+    for &(x, y) in &reserves.tiles {
+        let center = tile_to_world(x, y);
+        let edges = [
+            (x.checked_sub(1).map(|x| (x, y)), Vec2::new(-half, -half), Vec2::new(-half, half)),
+            (Some((x + 1, y)), Vec2::new(half, -half), Vec2::new(half, half)),
+            (y.checked_sub(1).map(|y| (x, y)), Vec2::new(-half, -half), Vec2::new(half, -half)),
+            (Some((x, y + 1)), Vec2::new(-half, half), Vec2::new(half, half)),
+        ];
+        for (neighbour, from, to) in edges {
+            if !neighbour.is_some_and(|tile| reserves.tiles.contains(&tile)) {
+                gizmos.line_2d(center + from, center + to, RESERVE_COLOR);
+            }
+        }
+    }
+
+    let Some((start, erase)) = brush.drag_start.filter(|_| editor.tool == EditorTool::Reserve) else { return };
+    let Some(cursor) = cursor_tile(&windows, &cameras) else { return };
+    let corner_min = tile_to_world(start.0.min(cursor.0), start.1.min(cursor.1)) - Vec2::splat(half);
+    let corner_max = tile_to_world(start.0.max(cursor.0), start.1.max(cursor.1)) + Vec2::splat(half);
+    let color = if erase { Color::srgb(0.9, 0.3, 0.3) } else { RESERVE_COLOR };
+    gizmos.rect_2d((corner_min + corner_max) / 2.0, 0.0, corner_max - corner_min, color);
+}
+
+// Fills the live section of the world report panel while any reserve exists
+fn update_reserve_stats(
+    reserves: Res<Reserves>,
+    registry: Res<SpeciesRegistry>,
+    localization: Res<Localization>,
+    vegetation: Option<Res<Vegetation>>,
+    creatures: Query<(&Transform, &Creature)>,
+    mut panels: Query<&mut Text, With<WorldReportPanel>>,
+) {
+    let mut lines = Vec::new();
+    if !reserves.tiles.is_empty() {
+        let reserve_tiles = reserves.tiles.len();
+        let wild_tiles = (WORLD_SIZE * WORLD_SIZE - reserve_tiles).max(1);
+        let mut counts = vec![(0usize, 0usize); registry.species.len()];
+        for (transform, creature) in creatures.iter() {
+            let Some(count) = counts.get_mut(creature.species) else { continue };
+            if reserves.contains_point(transform.translation.truncate()) {
+                count.0 += 1;
+            } else {
+                count.1 += 1;
+            }
+        }
+
+        lines.push(localization.format("reserves.title", &[
+            ("tiles", reserve_tiles.to_string()),
+            ("percent", format!("{:.1}", reserve_tiles as f32 * 100.0 / (WORLD_SIZE * WORLD_SIZE) as f32)),
+        ]));
+        // Densities per thousand tiles, since reserves are far smaller than the wild
+        let density = |count: usize, tiles: usize| format!("{:.1}", count as f32 * 1000.0 / tiles as f32);
+        for (species, (reserve, wild)) in registry.species.iter().zip(counts) {
+            lines.push(localization.format("reserves.population", &[
+                ("species", species.name.clone()),
+                ("reserve", density(reserve, reserve_tiles)),
+                ("wild", density(wild, wild_tiles)),
+            ]));
+        }
+        if let Some(vegetation) = vegetation {
+            let reserve_cover: f32 = reserves.tiles.iter().map(|(x, y)| vegetation.cover(*x, *y)).sum();
+            let total_cover: f32 = (0..WORLD_SIZE).flat_map(|x| (0..WORLD_SIZE).map(move |y| (x, y)))
+                .map(|(x, y)| vegetation.cover(x, y))
+                .sum();
+            lines.push(localization.format("reserves.plant_cover", &[
+                ("reserve", format!("{:.0}", reserve_cover * 100.0 / reserve_tiles as f32)),
+                ("wild", format!("{:.0}", (total_cover - reserve_cover).max(0.0) * 100.0 / wild_tiles as f32)),
+            ]));
+        }
+    }
+
+    let value = if lines.is_empty() { String::new() } else { lines.join("\n") + "\n\n" };
+    for mut text in panels.iter_mut() {
+        if let Some(section) = text.sections.get_mut(1) {
+            section.value = value.clone();
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use std::collections::HashMap;
+use crate::coords::{chunk_tile_bounds, tile_to_world, TILE_SIZE};
+use crate::biome::{BiomeType, ResourceType};
+use crate::optimization::ChunkRoot;
+use crate::world::{WorldMap, WORLD_SIZE};
+use crate::world_stats::Drainage;
+
+const CROSSING_SPACING: u32 = 24; // One crossing per this many river tiles, on average
+pub const FORD_SPEED: f32 = 0.5; // Walkers wade slowly through fords
+pub const LOG_SPEED: f32 = 0.8;
+const RIVER_COLOR: Color = Color::srgb(0.25, 0.45, 0.8);
+const FORD_COLOR: Color = Color::srgb(0.5, 0.68, 0.82);
+const LOG_COLOR: Color = Color::srgb(0.42, 0.28, 0.15);
+const RIVER_Z: f32 = 0.4; // Over the tiles, under cliff lines and props
+
+pub struct RiversPlugin;
+
+impl Plugin for RiversPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, (
+            init_rivers.run_if(resource_added::<WorldMap>),
+            draw_rivers.run_if(resource_exists::<Rivers>),
+        ).chain());
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Crossing {
+    Ford, // Shallow water to wade through
+    Log, // A fallen tree across the channel
+}
+
+// === RIVERS ===
+// Channels of the drainage network (see world_stats.rs) that walkers cannot enter. Diagonal steps
+// of a channel are widened by one tile so nothing slips between them. Every CROSSING_SPACING tiles
+// or so the river can be crossed: over a fallen log where trees grow on the bank, through a ford
+// elsewhere. Rivers never enclose land, so they make long detours rather than separate regions.
+#[derive(Resource)]
+pub struct Rivers {
+    channel: Vec<bool>, // Indexed [x * WORLD_SIZE + y]
+    crossings: HashMap<(usize, usize), Crossing>,
+    pub river_tiles: usize,
+}
+
+impl Rivers {
+    // Heavy (sorts every tile by elevation) - once per world
+    pub fn from_world_map(world_map: &WorldMap) -> Self {
+        let drainage = Drainage::compute(world_map);
+        let is_land = |x: usize, y: usize| world_map.tiles[x][y].biome != BiomeType::Ocean;
+        let mut channel = vec![false; WORLD_SIZE * WORLD_SIZE];
+        let mut sources = Vec::new(); // Channel tiles proper, where crossings are placed
+
+        for index in 0..WORLD_SIZE * WORLD_SIZE {
+            if !drainage.is_river(index) {
+                continue;
+            }
+            channel[index] = true;
+            sources.push(index);
+            let Some(receiver) = drainage.receivers[index] else { continue };
+            let (x, y) = (index / WORLD_SIZE, index % WORLD_SIZE);
+            let (rx, ry) = (receiver / WORLD_SIZE, receiver % WORLD_SIZE);
+            if x != rx && y != ry {
+                if let Some((cx, cy)) = [(rx, y), (x, ry)].into_iter().find(|&(cx, cy)| is_land(cx, cy)) {
+                    channel[cx * WORLD_SIZE + cy] = true;
+                }
+            }
+        }
+
+        // Each crossing opens the channel around a river tile
+        let mut rng = StdRng::seed_from_u64(world_map.seed as u64 ^ 0x52_4956_4552);
+        let mut crossings = HashMap::new();
+        for index in sources {
+            if !rng.gen_ratio(1, CROSSING_SPACING) {
+                continue;
+            }
+            let (x, y) = (index / WORLD_SIZE, index % WORLD_SIZE);
+            let around = || (x.saturating_sub(1)..(x + 2).min(WORLD_SIZE))
+                .flat_map(move |nx| (y.saturating_sub(1)..(y + 2).min(WORLD_SIZE)).map(move |ny| (nx, ny)));
+            let wooded = around().any(|(nx, ny)| world_map.tiles[nx][ny].resources.contains(&ResourceType::Wood));
+            let kind = if wooded { Crossing::Log } else { Crossing::Ford };
+            for (nx, ny) in around().filter(|&(nx, ny)| channel[nx * WORLD_SIZE + ny]) {
+                crossings.insert((nx, ny), kind);
+            }
+        }
+
+        let river_tiles = channel.iter().filter(|river| **river).count();
+        Self { channel, crossings, river_tiles }
+    }
+
+    pub fn is_river(&self, x: usize, y: usize) -> bool {
+        self.channel[x * WORLD_SIZE + y]
+    }
+
+    pub fn crossing(&self, x: usize, y: usize) -> Option<Crossing> {
+        self.crossings.get(&(x, y)).copied()
+    }
+
+    // River that walkers cannot get across here
+    pub fn blocks(&self, x: usize, y: usize) -> bool {
+        self.is_river(x, y) && self.crossing(x, y).is_none()
+    }
+}
+
+fn init_rivers(mut commands: Commands, world_map: Res<WorldMap>) {
+    let rivers = Rivers::from_world_map(&world_map);
+    info!("🏞️ {} river tiles, {} of them crossings", rivers.river_tiles, rivers.crossings.len());
+    commands.insert_resource(rivers);
+}
+
+// Water and crossings are drawn over each chunk as it loads, so tile tints stay untouched
+fn draw_rivers(mut commands: Commands, rivers: Res<Rivers>, chunks: Query<(Entity, Ref<ChunkRoot>)>) {
+    let draw_all = rivers.is_added();
+    for (root, chunk) in chunks.iter() {
+        if !draw_all && !chunk.is_added() {
+            continue;
+        }
+        let (start_x, start_y, end_x, end_y) = chunk_tile_bounds(chunk.source.0, chunk.source.1);
+        commands.entity(root).with_children(|parent| {
+            for x in start_x..end_x.min(WORLD_SIZE) {
+                for y in start_y..end_y.min(WORLD_SIZE) {
+                    if !rivers.is_river(x, y) {
+                        continue;
+                    }
+                    let center = tile_to_world(x, y);
+                    let crossing = rivers.crossing(x, y);
+                    let color = if crossing == Some(Crossing::Ford) { FORD_COLOR } else { RIVER_COLOR };
+                    parent.spawn(SpriteBundle {
+                        sprite: Sprite { color, custom_size: Some(Vec2::splat(TILE_SIZE)), ..default() },
+                        transform: Transform::from_translation(center.extend(RIVER_Z)),
+                        ..default()
+                    });
+                    if crossing == Some(Crossing::Log) {
+                        parent.spawn(SpriteBundle {
+                            sprite: Sprite { color: LOG_COLOR, custom_size: Some(Vec2::new(TILE_SIZE, TILE_SIZE * 0.35)), ..default() },
+                            transform: Transform::from_translation(center.extend(RIVER_Z + 0.01)),
+                            ..default()
+                        });
+                    }
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use crate::world::WorldMap;
+
+pub struct RngStreamsPlugin;
+
+impl Plugin for RngStreamsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<RngStreams>()
+            // Before anything draws from the streams for the new world, fixed updates included
+            .add_systems(PreUpdate, reseed_rng_streams.run_if(resource_added::<WorldMap>));
+    }
+}
+
+// === RNG STREAMS ===
+// One random number generator per subsystem, each seeded from the world seed, so a world plays
+// out the same way every time it is generated with the same seed and the same inputs, and one
+// subsystem drawing more numbers does not shift what another draws. The ui stream alone is seeded
+// from entropy: loading messages and the like may vary freely. Work done off the main thread, like
+// world generation, seeds its own generator with seed_for instead.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RngStream {
+    WorldGen, // World seeds and generation passes
+    Environment, // Props and their placement
+    Creatures, // Movement, calls, births, pools and spawning
+    Ui,
+}
+
+impl RngStream {
+    pub const ALL: [RngStream; 4] = [RngStream::WorldGen, RngStream::Environment, RngStream::Creatures, RngStream::Ui];
+
+    fn salt(&self) -> u64 {
+        match self {
+            RngStream::WorldGen => 0x5747_454e,
+            RngStream::Environment => 0x454e_5649,
+            RngStream::Creatures => 0x4352_4541,
+            RngStream::Ui => 0x5549,
+        }
+    }
+
+    // Seed of the stream for a world
+    pub fn seed_for(&self, world_seed: u64) -> u64 {
+        world_seed ^ self.salt()
+    }
+}
+
+#[derive(Resource)]
+pub struct RngStreams {
+    streams: [StdRng; RngStream::ALL.len()],
+}
+
+impl RngStreams {
+    pub fn seeded(world_seed: u64) -> Self {
+        Self {
+            streams: RngStream::ALL.map(|stream| match stream {
+                RngStream::Ui => StdRng::from_entropy(),
+                _ => StdRng::seed_from_u64(stream.seed_for(world_seed)),
+            }),
+        }
+    }
+
+    pub fn get(&mut self, stream: RngStream) -> &mut StdRng {
+        &mut self.streams[stream as usize]
+    }
+}
+
+// Until the first world is up, seeded as for the default world seed of 0
+impl Default for RngStreams {
+    fn default() -> Self {
+        Self::seeded(0)
+    }
+}
+
+// The ui stream keeps going; only the simulation streams restart with the world
+fn reseed_rng_streams(world_map: Res<WorldMap>, mut streams: ResMut<RngStreams>) {
+    let ui = std::mem::replace(streams.get(RngStream::Ui), StdRng::seed_from_u64(0));
+    *streams = RngStreams::seeded(world_map.seed as u64);
+    *streams.get(RngStream::Ui) = ui;
+    debug!(seed = world_map.seed, "RNG streams reseeded");
+}
@@ -0,0 +1,487 @@
+use bevy::ecs::system::{EntityCommands, SystemState};
+use bevy::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use crate::coords::world_to_tile;
+use crate::keybindings::{Action, Controls};
+use crate::nesting::Egg;
+use crate::creature::{spawn_creature, Creature, Needs};
+use crate::calendar::{ScheduledEvent, Scheduler};
+use crate::creature_tracking::{Bookmarked, CreatureName, SelectedCreature};
+use crate::experiments::Experiments;
+use crate::errors::{RetryAction, SimError, SimFailure};
+use crate::genetics::{founder_lineage, Genome};
+use crate::io_queue::IoQueue;
+use crate::population_pools::{PooledCreature, PopulationPools};
+use crate::settings::Settings;
+use crate::simulation::SimClock;
+use crate::relationships::RelationshipTable;
+use crate::speciation::register_species;
+use crate::species::{Species, SpeciesRegistry};
+use crate::world::{GenerationParams, WorldMap};
+use crate::rng_streams::{RngStream, RngStreams};
+
+pub const SAVE_PATH: &str = "savegame.ron";
+
+pub struct SavePlugin;
+
+impl Plugin for SavePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<SavedComponents>()
+            .init_resource::<SavedView>()
+            .add_event::<PersistenceRequest>()
+            .add_systems(Update, (save_hotkeys, autosave, save_game, load_game).chain());
+    }
+}
+
+// F5 and F9, or the error screen's retry button
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PersistenceRequest {
+    Save,
+    Load,
+}
+
+// === SAVE FILE ===
+// Creatures, pooled or not, and the view of them; the world itself is regenerated from its seed
+// and generation parameters.
+#[derive(Serialize, Deserialize)]
+pub struct SaveGame {
+    pub seed: u32,
+    #[serde(default)]
+    pub params: GenerationParams, // Older saves were made with the defaults
+    pub creatures: Vec<SavedCreature>,
+    #[serde(default)]
+    pub elapsed: Option<f64>, // SimClock time; older saves keep the current clock
+    #[serde(default)]
+    pub scheduled: Vec<ScheduledEvent>,
+    #[serde(default)]
+    pub experiments: Experiments, // Running experiments, whose snapshots are among the scheduled events
+    #[serde(default)]
+    pub species: Vec<Species>, // Split off while running (see speciation.rs), oldest first
+    #[serde(default)]
+    pub view: BTreeMap<String, String>, // RON of each registered view state, by save key
+    #[serde(default)]
+    pub pools: Vec<SavedPool>, // Creatures merged into population pools (see population_pools.rs)
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SavedCreature {
+    pub species: String, // By name, so saves survive reordering of species.ron
+    pub position: (f32, f32),
+    pub age: f32,
+    pub health: f32,
+    pub hunger: f32,
+    pub thirst: f32,
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default)]
+    pub bookmarked: bool,
+    #[serde(default)]
+    pub selected: bool,
+    #[serde(default)]
+    pub genome: Option<Genome>, // Missing in older saves; those get a fresh founder genome
+    #[serde(default)]
+    pub components: BTreeMap<String, String>, // RON of each registered component, by save key
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SavedPool {
+    pub chunk: (i32, i32),
+    pub creatures: Vec<SavedPooledCreature>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct SavedPooledCreature {
+    pub species: String, // By name, as for SavedCreature
+    pub age: f32,
+    pub genome: Genome,
+}
+
+// === SAVED COMPONENTS ===
+// Any component can opt into save files with one line in its plugin:
+//     app.save_component::<Tamed>("tamed");
+// It is then written for every creature that has it, under that key, and inserted again on load.
+// Keys rather than type names, so saves survive renaming the type. A save holding a key this
+// version has not registered is refused rather than loaded with the component silently dropped.
+#[derive(Resource, Default)]
+pub struct SavedComponents {
+    entries: Vec<SavedComponent>,
+}
+
+struct SavedComponent {
+    key: &'static str,
+    save: fn(&World, Entity) -> Option<Result<String, ron::Error>>,
+    load: fn(&mut EntityCommands, &str) -> Result<(), ron::error::SpannedError>,
+}
+
+impl SavedComponents {
+    fn get(&self, key: &str) -> Option<&SavedComponent> {
+        self.entries.iter().find(|entry| entry.key == key)
+    }
+
+    // Registered components the entity has, as RON
+    fn save_entity(&self, world: &World, entity: Entity) -> Result<BTreeMap<String, String>, ron::Error> {
+        let mut components = BTreeMap::new();
+        for entry in &self.entries {
+            if let Some(saved) = (entry.save)(world, entity) {
+                components.insert(entry.key.to_string(), saved?);
+            }
+        }
+        Ok(components)
+    }
+}
+
+pub trait SaveComponentExt {
+    fn save_component<T: Component + Serialize + DeserializeOwned>(&mut self, key: &'static str) -> &mut Self;
+}
+
+impl SaveComponentExt for App {
+    fn save_component<T: Component + Serialize + DeserializeOwned>(&mut self, key: &'static str) -> &mut Self {
+        let mut saved = self.world_mut().get_resource_or_insert_with(SavedComponents::default);
+        assert!(saved.get(key).is_none(), "save key '{}' registered twice", key);
+        saved.entries.push(SavedComponent {
+            key,
+            save: |world, entity| world.get::<T>(entity).map(ron::to_string),
+            load: |commands, contents| {
+                commands.insert(ron::from_str::<T>(contents)?);
+                Ok(())
+            },
+        });
+        self
+    }
+}
+
+// === SAVED VIEW ===
+// The camera, overlays and panels opt into save files the same way, so loading a save puts the
+// user back where they left off:
+//     app.save_view_resource::<StatOverlay>("stat_bars");
+//     app.save_visibility::<HeatmapOverlay>("heatmap");
+// or save_view with a getter and setter for state spread over several places. Unlike components,
+// unknown keys are skipped: a view that does not come back is no reason to refuse the creatures.
+#[derive(Resource, Default)]
+pub struct SavedView {
+    entries: Vec<SavedViewEntry>,
+}
+
+type SaveView = Box<dyn Fn(&mut World) -> Option<Result<String, ron::Error>> + Send + Sync>;
+type LoadView = Box<dyn Fn(&mut World, &str) -> Result<(), ron::error::SpannedError> + Send + Sync>;
+
+struct SavedViewEntry {
+    key: &'static str,
+    save: SaveView,
+    load: LoadView,
+}
+
+impl SavedView {
+    fn get(&self, key: &str) -> Option<&SavedViewEntry> {
+        self.entries.iter().find(|entry| entry.key == key)
+    }
+}
+
+pub trait SaveViewExt {
+    fn save_view<T: Serialize + DeserializeOwned + 'static>(
+        &mut self,
+        key: &'static str,
+        get: fn(&mut World) -> Option<T>,
+        set: fn(&mut World, T),
+    ) -> &mut Self;
+
+    fn save_view_resource<R: Resource + Clone + Serialize + DeserializeOwned>(&mut self, key: &'static str) -> &mut Self {
+        self.save_view::<R>(key, |world| world.get_resource::<R>().cloned(), |world, resource| world.insert_resource(resource))
+    }
+
+    // Whether the overlay or panel marked with C is shown
+    fn save_visibility<C: Component>(&mut self, key: &'static str) -> &mut Self {
+        self.save_view::<bool>(key, |world| {
+            world.query_filtered::<&Visibility, With<C>>().iter(world).next().map(|visibility| *visibility != Visibility::Hidden)
+        }, |world, shown| {
+            for mut visibility in world.query_filtered::<&mut Visibility, With<C>>().iter_mut(world) {
+                *visibility = if shown { Visibility::Visible } else { Visibility::Hidden };
+            }
+        })
+    }
+}
+
+impl SaveViewExt for App {
+    fn save_view<T: Serialize + DeserializeOwned + 'static>(
+        &mut self,
+        key: &'static str,
+        get: fn(&mut World) -> Option<T>,
+        set: fn(&mut World, T),
+    ) -> &mut Self {
+        let mut saved = self.world_mut().get_resource_or_insert_with(SavedView::default);
+        assert!(saved.get(key).is_none(), "view key '{}' registered twice", key);
+        saved.entries.push(SavedViewEntry {
+            key,
+            save: Box::new(move |world| get(world).map(|value| ron::to_string(&value))),
+            load: Box::new(move |world, contents| {
+                set(world, ron::from_str::<T>(contents)?);
+                Ok(())
+            }),
+        });
+        self
+    }
+}
+
+impl SaveGame {
+    // Creatures are placed by position, so the save only fits the world it was made in
+    pub fn check_compatible(&self, world_map: &WorldMap, saved_components: &SavedComponents) -> Result<(), SimError> {
+        let mut unknown: Vec<&str> = self.creatures.iter()
+            .flat_map(|creature| creature.components.keys())
+            .filter(|key| saved_components.get(key).is_none())
+            .map(String::as_str)
+            .collect();
+        unknown.sort_unstable();
+        unknown.dedup();
+        let reason = if self.seed != world_map.seed {
+            format!("it is for seed {} but the current world is seed {}", self.seed, world_map.seed)
+        } else if self.params != world_map.params {
+            "it was made with different generation parameters".to_string()
+        } else if !unknown.is_empty() {
+            format!("it has components this version cannot load: {}", unknown.join(", "))
+        } else {
+            return Ok(());
+        };
+        Err(SimError::Incompatible { path: SAVE_PATH.to_string(), reason })
+    }
+}
+
+pub fn read_save() -> Result<SaveGame, SimError> {
+    let contents = fs::read_to_string(SAVE_PATH).map_err(|err| SimError::io(SAVE_PATH, err))?;
+    ron::from_str(&contents).map_err(|err| SimError::parse(SAVE_PATH, err))
+}
+
+type SaveQueryData = (
+    Entity,
+    &'static Transform,
+    &'static Creature,
+    &'static Needs,
+    &'static Genome,
+    Option<&'static CreatureName>,
+    Has<Bookmarked>,
+);
+
+fn save_hotkeys(controls: Controls, mut requests: EventWriter<PersistenceRequest>) {
+    if controls.just_pressed(Action::SaveGame) {
+        requests.send(PersistenceRequest::Save);
+    }
+    if controls.just_pressed(Action::LoadGame) {
+        requests.send(PersistenceRequest::Load);
+    }
+}
+
+// Every Settings::autosave_minutes of real time while a world is loaded
+fn autosave(
+    time: Res<Time<Real>>,
+    settings: Res<Settings>,
+    world_map: Option<Res<WorldMap>>,
+    mut since_save: Local<f32>,
+    mut requests: EventWriter<PersistenceRequest>,
+) {
+    if settings.autosave_minutes <= 0.0 || world_map.is_none() {
+        return;
+    }
+    *since_save += time.delta_seconds();
+    if *since_save >= settings.autosave_minutes * 60.0 {
+        *since_save = 0.0;
+        requests.send(PersistenceRequest::Save);
+    }
+}
+
+type SaveParams = (
+    EventReader<'static, 'static, PersistenceRequest>,
+    Option<Res<'static, WorldMap>>,
+    Res<'static, SpeciesRegistry>,
+    Res<'static, SavedComponents>,
+    (Res<'static, SimClock>, Res<'static, Scheduler>, Res<'static, Experiments>, Res<'static, PopulationPools>),
+    Res<'static, SelectedCreature>,
+    Query<'static, 'static, SaveQueryData>,
+);
+
+// Exclusive, so registered components can be read off each creature whatever their type
+fn save_game(world: &mut World, state: &mut SystemState<SaveParams>) {
+    let (mut requests, world_map, registry, saved_components, (clock, scheduler, experiments, pools), selected, creatures) = state.get(world);
+    // Counted rather than any(), so every request of this frame is consumed
+    if requests.read().filter(|request| **request == PersistenceRequest::Save).count() == 0 {
+        return;
+    }
+    let Some(world_map) = world_map else { return };
+
+    let saved_creatures: Result<Vec<SavedCreature>, ron::Error> = creatures.iter()
+        .map(|(entity, transform, creature, needs, genome, name, bookmarked)| Ok(SavedCreature {
+            species: registry.get(creature.species).name.clone(),
+            position: (transform.translation.x, transform.translation.y),
+            age: creature.age,
+            health: needs.health,
+            hunger: needs.hunger,
+            thirst: needs.thirst,
+            name: name.map(|name| name.0.clone()),
+            bookmarked,
+            selected: selected.0 == Some(entity),
+            genome: Some(*genome),
+            components: saved_components.save_entity(world, entity)?,
+        }))
+        .collect();
+    let save = saved_creatures.map(|creatures| SaveGame {
+        seed: world_map.seed,
+        params: world_map.params,
+        creatures,
+        elapsed: Some(clock.elapsed),
+        scheduled: scheduler.events.clone(),
+        experiments: experiments.clone(),
+        species: registry.species.iter().filter(|species| species.ancestor.is_some()).cloned().collect(),
+        view: BTreeMap::new(),
+        pools: pools.chunks.iter().map(|(chunk, pool)| SavedPool {
+            chunk: *chunk,
+            creatures: pool.iter().map(|creature| SavedPooledCreature {
+                species: registry.get(creature.species).name.clone(),
+                age: creature.age,
+                genome: creature.genome,
+            }).collect(),
+        }).collect(),
+    });
+    let save = save.and_then(|mut save| {
+        world.resource_scope(|world, view: Mut<SavedView>| {
+            for entry in &view.entries {
+                if let Some(saved) = (entry.save)(world) {
+                    save.view.insert(entry.key.to_string(), saved?);
+                }
+            }
+            Ok(save)
+        })
+    });
+
+    // The snapshot is written in the background (see io_queue.rs)
+    match save {
+        Ok(save) => {
+            let message = format!("💾 Saved {} creatures to {}", save.creatures.len(), SAVE_PATH);
+            world.resource_mut::<IoQueue>().write_ron(SAVE_PATH, save, message, Some(RetryAction::Save));
+        }
+        Err(err) => {
+            world.send_event(SimFailure::with_retry(SimError::io(SAVE_PATH, err), RetryAction::Save));
+        }
+    }
+}
+
+// Replaced by a loaded save; eggs are not saved, so they go too
+type Replaced = Or<(With<Creature>, With<Egg>)>;
+
+fn load_game(
+    mut commands: Commands,
+    (mut requests, mut failures): (EventReader<PersistenceRequest>, EventWriter<SimFailure>),
+    world_map: Option<Res<WorldMap>>,
+    (mut registry, mut relationships, saved_components): (ResMut<SpeciesRegistry>, ResMut<RelationshipTable>, Res<SavedComponents>),
+    (mut clock, mut scheduler, mut experiments, mut pools): (ResMut<SimClock>, ResMut<Scheduler>, ResMut<Experiments>, ResMut<PopulationPools>),
+    (mut selected, io, mut deferred, mut streams): (ResMut<SelectedCreature>, Res<IoQueue>, Local<bool>, ResMut<RngStreams>),
+    existing: Query<Entity, Replaced>,
+) {
+    // Counted rather than any(), so every request of this frame is consumed
+    let requested = requests.read().filter(|request| **request == PersistenceRequest::Load).count() > 0;
+    if !requested && !*deferred {
+        return;
+    }
+    // Load what was just saved, not the file before it
+    *deferred = io.is_busy(SAVE_PATH);
+    if *deferred {
+        return;
+    }
+    let Some(world_map) = world_map else { return };
+    // Nothing saved yet is not a failure
+    if !Path::new(SAVE_PATH).exists() {
+        return warn!("No save to load from {}", SAVE_PATH);
+    }
+
+    let save = match read_save() {
+        Ok(save) => save,
+        Err(err) => {
+            failures.send(SimFailure::with_retry(err, RetryAction::Load));
+            return;
+        }
+    };
+    // Retrying would not change the world the save is for
+    if let Err(err) = save.check_compatible(&world_map, &saved_components) {
+        failures.send(SimFailure::new(err));
+        return;
+    }
+
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+    if let Some(elapsed) = save.elapsed {
+        clock.elapsed = elapsed;
+    }
+    scheduler.events = save.scheduled;
+    *experiments = save.experiments;
+    for species in save.species {
+        if registry.find(&species.name).is_none() {
+            register_species(species, &mut registry, &mut relationships);
+        }
+    }
+
+    let rng = streams.get(RngStream::Creatures);
+    let mut loaded = 0;
+    for saved in &save.creatures {
+        let Some(species_id) = registry.species.iter().position(|s| s.name == saved.species) else {
+            warn!("Skipping saved creature of unknown species '{}'", saved.species);
+            continue;
+        };
+        let position = Vec2::new(saved.position.0, saved.position.1);
+        let genome = saved.genome.unwrap_or_else(|| {
+            let (x, y) = world_to_tile(position).unwrap_or_default();
+            Genome::founder(rng, founder_lineage(species_id, x, y))
+        });
+        let entity = spawn_creature(&mut commands, &registry, species_id, position, 0.0, genome);
+        let mut entity_commands = commands.entity(entity);
+        entity_commands.insert((
+            Creature { species: species_id, age: saved.age },
+            Needs { health: saved.health, hunger: saved.hunger, thirst: saved.thirst },
+        ));
+        if let Some(name) = &saved.name {
+            entity_commands.insert(CreatureName(name.clone()));
+        }
+        if saved.bookmarked {
+            entity_commands.insert(Bookmarked);
+        }
+        if saved.selected {
+            selected.0 = Some(entity);
+        }
+        for (key, contents) in &saved.components {
+            let Some(entry) = saved_components.get(key) else { continue }; // Checked above
+            if let Err(err) = (entry.load)(&mut entity_commands, contents) {
+                warn!("Dropping saved '{}' component of a {}: {}", key, saved.species, err);
+            }
+        }
+        loaded += 1;
+    }
+    info!("📂 Loaded {} creatures from {}", loaded, SAVE_PATH);
+
+    pools.chunks.clear();
+    for saved in save.pools {
+        let pool: Vec<PooledCreature> = saved.creatures.iter()
+            .filter_map(|creature| {
+                let species = registry.species.iter().position(|s| s.name == creature.species)?;
+                Some(PooledCreature { species, age: creature.age, genome: creature.genome })
+            })
+            .collect();
+        if !pool.is_empty() {
+            pools.chunks.insert(saved.chunk, pool);
+        }
+    }
+
+    // Once the creatures are in, so views can refer to them
+    let saved_view = save.view;
+    commands.add(move |world: &mut World| {
+        world.resource_scope(|world, view: Mut<SavedView>| {
+            for (key, contents) in &saved_view {
+                let Some(entry) = view.get(key) else { continue };
+                if let Err(err) = (entry.load)(world, contents) {
+                    warn!("Dropping saved '{}' view: {}", key, err);
+                }
+            }
+        });
+    });
+}
@@ -0,0 +1,179 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use crate::calendar::{ScheduledEvent, Scheduler};
+use crate::editor::{edit_command, reset_editor, Editor, TileState};
+use crate::errors::{SimError, SimFailure};
+use crate::generation_panel::WorldInhabitant;
+use crate::io_queue::{file_stem, IoQueue};
+use crate::optimization::ChunkManager;
+use crate::optimized_systems::WorldGeneration;
+use crate::population_pools::PopulationPools;
+use crate::relationships::RelationshipTable;
+use crate::settings::Settings;
+use crate::simulation::SimClock;
+use crate::species::{Species, SpeciesRegistry};
+use crate::species_designer::install_species;
+use crate::world::{GenerationParams, WorldMap};
+
+pub const SCENARIOS_DIR: &str = "scenarios";
+const SCENARIO_EXTENSION: &str = "scenario.ron";
+const SCENARIO_VERSION: u32 = 1; // Bump when a field changes meaning; new fields get a default
+
+pub struct ScenarioPlugin;
+
+impl Plugin for ScenarioPlugin {
+    fn build(&self, app: &mut App) {
+        // --scenario imports as soon as the first world is up
+        let requested = app.world().resource::<Settings>().scenario.clone().map(ScenarioRequest::Import);
+        app
+            .insert_resource(Scenarios { requested, pending: None })
+            .add_systems(Update, (
+                handle_scenario_requests.run_if(resource_exists::<WorldMap>),
+                apply_pending_scenario.run_if(resource_added::<WorldMap>).after(reset_editor),
+            ));
+    }
+}
+
+// === SCENARIO BUNDLES ===
+// A scenario is everything needed to set up the same starting point elsewhere, in one shareable
+// file: the seed and generation parameters, the editor's changes to the generated world, the
+// mod and designer species it may use and the scheduled calendar events. Importing installs missing species
+// (saved like designer species), regenerates the world from the seed and, once it is up, applies
+// the edits and schedules the events counting from the day of the import.
+#[derive(Serialize, Deserialize)]
+pub struct Scenario {
+    pub version: u32,
+    pub seed: u32,
+    pub params: GenerationParams,
+    #[serde(default)]
+    pub edits: Vec<((usize, usize), TileState)>,
+    #[serde(default)]
+    pub species: Vec<Species>, // Mod and designer species; built-in ones are not bundled
+    #[serde(default)]
+    pub scheduled: Vec<ScheduledEvent>, // Days counted from the start of the scenario
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ScenarioRequest {
+    Export(String), // Scenario name
+    Import(String), // Scenario name, or a path to a bundle
+}
+
+#[derive(Resource)]
+pub struct Scenarios {
+    pub requested: Option<ScenarioRequest>,
+    pending: Option<Scenario>, // Imported, waiting for its world to be generated
+}
+
+// "island" -> "scenarios/island.scenario.ron"; paths are used as given
+fn scenario_path(name: &str) -> String {
+    if name.contains(['/', '\\']) || name.ends_with(".ron") {
+        return name.to_string();
+    }
+    format!("{}/{}.{}", SCENARIOS_DIR, file_stem(name), SCENARIO_EXTENSION)
+}
+
+fn read_scenario(path: &str) -> Result<Scenario, SimError> {
+    let contents = fs::read_to_string(path).map_err(|err| SimError::io(path, err))?;
+    let scenario: Scenario = ron::from_str(&contents).map_err(|err| SimError::parse(path, err))?;
+    if scenario.version > SCENARIO_VERSION {
+        return Err(SimError::Incompatible {
+            path: path.to_string(),
+            reason: format!("it was made by a newer version (format {})", scenario.version),
+        });
+    }
+    Ok(scenario)
+}
+
+fn handle_scenario_requests(
+    mut commands: Commands,
+    mut scenarios: ResMut<Scenarios>,
+    (world_map, editor, clock, scheduler): (Res<WorldMap>, Res<Editor>, Res<SimClock>, Res<Scheduler>),
+    (mut registry, mut relationships): (ResMut<SpeciesRegistry>, ResMut<RelationshipTable>),
+    (mut settings, mut pools, mut generation): (ResMut<Settings>, ResMut<PopulationPools>, WorldGeneration),
+    inhabitants: Query<Entity, WorldInhabitant>,
+    (mut io, mut failures): (ResMut<IoQueue>, EventWriter<SimFailure>),
+) {
+    let Some(request) = scenarios.requested.take() else { return };
+    match request {
+        ScenarioRequest::Export(name) => {
+            if let Err(err) = fs::create_dir_all(SCENARIOS_DIR) {
+                failures.send(SimFailure::new(SimError::io(SCENARIOS_DIR, err)));
+                return;
+            }
+            let today = clock.day();
+            let scenario = Scenario {
+                version: SCENARIO_VERSION,
+                seed: world_map.seed,
+                params: world_map.params,
+                edits: editor.edited_tiles(),
+                species: registry.custom().to_vec(),
+                scheduled: scheduler.events.iter()
+                    .map(|event| ScheduledEvent { day: event.day.saturating_sub(today), ..event.clone() })
+                    .collect(),
+            };
+            let path = scenario_path(&name);
+            let message = format!("📦 Exported scenario with {} edited tiles, {} species and {} events to {}",
+                scenario.edits.len(), scenario.species.len(), scenario.scheduled.len(), path);
+            io.write_ron(&path, scenario, message, None);
+        }
+        ScenarioRequest::Import(name) => {
+            let path = scenario_path(&name);
+            let scenario = match read_scenario(&path) {
+                Ok(scenario) => scenario,
+                Err(err) => {
+                    failures.send(SimFailure::new(err));
+                    return;
+                }
+            };
+            for species in &scenario.species {
+                if registry.find(&species.name).is_some() {
+                    continue; // Installed earlier, or a designer species of the same name
+                }
+                if let Err(err) = install_species(species.clone(), &mut registry, &mut relationships, &mut io) {
+                    failures.send(SimFailure::new(err));
+                    return;
+                }
+            }
+
+            // Same as regenerating from the generation panel
+            settings.generation = scenario.params;
+            for entity in inhabitants.iter() {
+                commands.entity(entity).despawn();
+            }
+            pools.chunks.clear();
+            info!("📦 Importing scenario {} (seed {})", path, scenario.seed);
+            generation.start(&settings, scenario.seed);
+            scenarios.pending = Some(scenario);
+        }
+    }
+}
+
+fn apply_pending_scenario(
+    mut scenarios: ResMut<Scenarios>,
+    mut world_map: ResMut<WorldMap>,
+    (mut editor, mut chunk_manager): (ResMut<Editor>, ResMut<ChunkManager>),
+    (clock, mut scheduler): (Res<SimClock>, ResMut<Scheduler>),
+    mut failures: EventWriter<SimFailure>,
+) {
+    let Some(scenario) = scenarios.pending.take() else { return };
+    // Generation constraints can move on to the next seed
+    if scenario.seed != world_map.seed || scenario.params != world_map.params {
+        failures.send(SimFailure::new(SimError::Incompatible {
+            path: SCENARIOS_DIR.to_string(),
+            reason: format!("the scenario is for seed {} but seed {} was generated", scenario.seed, world_map.seed),
+        }));
+        return;
+    }
+    let world_map = world_map.bypass_change_detection();
+    let command = edit_command(world_map, scenario.edits);
+    let edited = command.tiles.len();
+    editor.apply(world_map, &mut chunk_manager, command);
+
+    let today = clock.day();
+    scheduler.events = scenario.scheduled.into_iter()
+        .map(|event| ScheduledEvent { day: event.day + today, ..event })
+        .collect();
+    info!("📦 Scenario ready: {} edited tiles, {} scheduled events", edited, scheduler.events.len());
+}
@@ -0,0 +1,155 @@
+use bevy::ecs::schedule::{Dag, NodeId, ScheduleGraph};
+use bevy::prelude::*;
+use serde::Serialize;
+use std::fmt::Write;
+use std::fs;
+
+pub const SCHEDULE_DOT_PATH: &str = "schedule_graph.dot";
+pub const SCHEDULE_JSON_PATH: &str = "schedule_graph.json";
+
+pub struct ScheduleDumpPlugin;
+
+impl Plugin for ScheduleDumpPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ScheduleDump>()
+            // Last, so Update and the fixed-step schedules are back in Schedules after running
+            .add_systems(Last, dump_schedules.run_if(|dump: Res<ScheduleDump>| dump.requested));
+    }
+}
+
+// === SCHEDULE DUMP ===
+// Requested with the "systems" console command: writes every built schedule's systems, their
+// run conditions and sets, the ordering edges between them and the pairs of systems that conflict
+// without an explicit order, as a Graphviz graph and as JSON. Main and Last are running while the
+// dump is taken and are left out.
+#[derive(Resource, Default)]
+pub struct ScheduleDump {
+    pub requested: bool,
+}
+
+#[derive(Serialize)]
+pub struct SystemNode {
+    pub id: String,
+    pub name: String,
+    pub conditions: Vec<String>,
+}
+
+#[derive(Serialize)]
+pub struct Ambiguity {
+    pub first: String,
+    pub second: String,
+    pub components: Vec<String>, // Empty when the conflict is on the whole World
+}
+
+#[derive(Serialize)]
+pub struct ScheduleSummary {
+    pub label: String,
+    pub systems: Vec<SystemNode>,
+    pub sets: Vec<SystemNode>,
+    pub order: Vec<String>, // Node ids in dependency order
+    pub before: Vec<(String, String)>, // (runs first, runs after)
+    pub parents: Vec<(String, String)>, // (set, member)
+    pub ambiguities: Vec<Ambiguity>,
+}
+
+fn node_id(id: NodeId) -> String {
+    match id {
+        NodeId::System(index) => format!("system{}", index),
+        NodeId::Set(index) => format!("set{}", index),
+    }
+}
+
+fn summarize(label: String, graph: &ScheduleGraph, world: &World) -> ScheduleSummary {
+    let system_name = |id: NodeId| graph.get_system_at(id).map_or_else(|| node_id(id), |system| system.name().to_string());
+    let systems = graph.systems()
+        .map(|(id, system, conditions)| SystemNode {
+            id: node_id(id),
+            name: system.name().to_string(),
+            conditions: conditions.iter().map(|condition| condition.name().to_string()).collect(),
+        })
+        .collect();
+    let sets = graph.system_sets()
+        .map(|(id, set, conditions)| SystemNode {
+            id: node_id(id),
+            name: format!("{:?}", set),
+            conditions: conditions.iter().map(|condition| condition.name().to_string()).collect(),
+        })
+        .collect();
+    let edges = |graph: &Dag| graph.graph().all_edges()
+        .map(|(from, to, _)| (node_id(from), node_id(to)))
+        .collect::<Vec<_>>();
+    let ambiguities = graph.conflicting_systems().iter()
+        .map(|(first, second, components)| Ambiguity {
+            first: system_name(*first),
+            second: system_name(*second),
+            components: components.iter()
+                .map(|component| world.components().get_name(*component).unwrap_or("?").to_string())
+                .collect(),
+        })
+        .collect();
+
+    ScheduleSummary {
+        label,
+        systems,
+        sets,
+        order: graph.dependency().cached_topsort().iter().map(|id| node_id(*id)).collect(),
+        before: edges(graph.dependency()),
+        parents: edges(graph.hierarchy()),
+        ambiguities,
+    }
+}
+
+// One cluster per schedule: solid edges for ordering, dotted for set membership, red for ambiguities
+fn to_dot(schedules: &[ScheduleSummary]) -> String {
+    let mut dot = String::from("digraph schedules {\n    rankdir=LR;\n    node [shape=box, fontsize=10];\n");
+    for (index, schedule) in schedules.iter().enumerate() {
+        let prefix = |id: &str| format!("s{}_{}", index, id);
+        let _ = writeln!(dot, "    subgraph cluster_{} {{\n        label=\"{}\";", index, schedule.label);
+        for system in &schedule.systems {
+            let conditions = system.conditions.iter().map(|condition| format!("\\n[if {}]", condition)).collect::<String>();
+            let _ = writeln!(dot, "        {} [label=\"{}{}\"];", prefix(&system.id), system.name, conditions);
+        }
+        for set in &schedule.sets {
+            let _ = writeln!(dot, "        {} [label=\"{}\", shape=ellipse];", prefix(&set.id), set.name.replace('"', "'"));
+        }
+        for (from, to) in &schedule.before {
+            let _ = writeln!(dot, "        {} -> {};", prefix(from), prefix(to));
+        }
+        for (set, member) in &schedule.parents {
+            let _ = writeln!(dot, "        {} -> {} [style=dotted];", prefix(set), prefix(member));
+        }
+        let ids = |name: &str| schedule.systems.iter().find(|system| system.name == name).map(|system| prefix(&system.id));
+        for ambiguity in &schedule.ambiguities {
+            if let (Some(first), Some(second)) = (ids(&ambiguity.first), ids(&ambiguity.second)) {
+                let _ = writeln!(dot, "        {} -> {} [dir=none, color=red, constraint=false];", first, second);
+            }
+        }
+        dot.push_str("    }\n");
+    }
+    dot.push_str("}\n");
+    dot
+}
+
+fn dump_schedules(world: &mut World) {
+    world.resource_mut::<ScheduleDump>().requested = false;
+    let world: &World = world;
+    let schedules = world.resource::<Schedules>();
+    let mut summaries: Vec<ScheduleSummary> = schedules.iter()
+        .map(|(label, schedule)| summarize(format!("{:?}", label), schedule.graph(), world))
+        .collect();
+    summaries.sort_by(|a, b| a.label.cmp(&b.label));
+
+    let ambiguities: usize = summaries.iter().map(|schedule| schedule.ambiguities.len()).sum();
+    let result = serde_json::to_string_pretty(&summaries)
+        .map_err(|err| err.to_string())
+        .and_then(|json| fs::write(SCHEDULE_JSON_PATH, json).map_err(|err| err.to_string()))
+        .and_then(|_| fs::write(SCHEDULE_DOT_PATH, to_dot(&summaries)).map_err(|err| err.to_string()));
+    match result {
+        Ok(()) => info!(
+            "🗺️ Wrote {} schedules ({} ambiguous system pairs) to {} and {}",
+            summaries.len(), ambiguities, SCHEDULE_DOT_PATH, SCHEDULE_JSON_PATH
+        ),
+        Err(err) => error!("Failed to write the schedule dump: {}", err),
+    }
+}
@@ -0,0 +1,233 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use crate::bench::BenchRun;
+use crate::generation_constraints::{GenerationConstraints, parse_biome_value};
+use crate::simulation::BackgroundMode;
+use crate::frame_pacing::VsyncMode;
+use crate::logging::LOG_ENV;
+use crate::profile::{config_path, migrate_flat_file};
+use crate::view_mode::ViewMode;
+use crate::world::GenerationParams;
+
+const SETTINGS_FILE: &str = "settings.ron"; // In the config directory (see profile.rs)
+
+// === MACHINE SETTINGS ===
+// Graphics, performance and input options for this machine, loaded once at startup from
+// settings.ron in the platform config directory (if present), then overridden by CLI flags. A
+// settings.ron in the working directory, where older versions read it, is moved there first.
+// What belongs to the player rather than the machine is kept in the profile (profile.rs).
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub language: String, // Locale code, e.g. "en" or "es"
+    pub reduced_motion: bool, // Disables sway, bar hue cycling and camera easing
+    pub generation_constraints: GenerationConstraints,
+    pub generation: GenerationParams, // Sea level, noise scales and biome thresholds for new worlds
+    pub background_mode: BackgroundMode, // Simulation behaviour while the window is unfocused
+    pub background_fps: f32, // Frame cap while unfocused or minimized
+    pub fps_cap: f32, // 0 = uncapped
+    pub vsync: VsyncMode,
+    pub gpu_noise: bool, // Evaluate world noise in a compute shader when the GPU supports it (see gpu_noise.rs)
+    pub smooth_camera: bool, // Animate camera jumps (bookmarks, go-to, tracked creatures)
+    pub edge_scrolling: bool, // Pan when the cursor touches the window edge
+    pub view: ViewMode, // Top-down or isometric presentation, fixed for the session (see view_mode.rs)
+    pub call_sounds: bool, // Play creature calls heard near the camera (see calls.rs)
+    pub auto_camera: bool, // Start in follow-the-action mode (demo/screensaver)
+    pub auto_camera_dwell: f32, // Seconds the auto camera lingers on each hot spot
+    pub creature_cap: usize, // Creature entities allowed at once; the rest are pooled
+    pub chunk_creature_cap: usize, // Creature entities allowed per chunk
+    pub batch_creatures_from: usize, // Creature count from which they are drawn in per-species batches (see creature_batch.rs); 0 = never
+    pub statistical_distance: f32, // World units beyond which creatures are only simulated as pools
+    pub speciation_distance: f32, // Trait distance at which lineages stop mating and split off (see speciation.rs); 0 = off
+    pub hybrid_zones: bool, // Let creatures somewhat past speciation_distance still answer each other's mating calls
+    pub autosave_minutes: f32, // Save to savegame.ron this often; 0 = only on F5
+    pub vegetation_density: f32, // Multiplier on environment prop spawn chances, 0.25-2
+    pub auto_vegetation_density: bool, // Lower the density while frames are slower than target_frame_ms
+    pub target_frame_ms: f32,
+    pub ai_budget_ms: f32, // Creature AI time allowed per frame before nearby creatures take turns (see ai_lod.rs); 0 = no limit
+    pub ai_max_interval: u32, // Fixed ticks a nearby creature may go without an update, whatever the budget
+    pub chunk_cache_mb: u32, // Disk space for generated chunks of recent worlds (see chunk_cache.rs); 0 = off
+    pub legacy_render: bool, // Debug: one sprite per tile for the whole world instead of chunks
+    pub log_filter: String, // Per-module log levels, e.g. "world=debug,render=warn" (see logging.rs)
+    pub log_json: bool, // Also write logs to stdout as JSON lines
+    #[serde(skip)]
+    pub scenario: Option<String>, // Scenario bundle to import once the first world is up (see scenario.rs)
+    #[serde(skip)]
+    pub bench: Option<BenchRun>, // Benchmark or soak run instead of an interactive session (see bench.rs)
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            language: "en".to_string(),
+            reduced_motion: false,
+            generation_constraints: GenerationConstraints::default(),
+            generation: GenerationParams::default(),
+            background_mode: BackgroundMode::default(),
+            background_fps: 10.0,
+            fps_cap: 0.0,
+            vsync: VsyncMode::default(),
+            gpu_noise: false,
+            smooth_camera: true,
+            edge_scrolling: false,
+            view: ViewMode::default(),
+            call_sounds: false,
+            auto_camera: false,
+            auto_camera_dwell: 8.0,
+            creature_cap: 8000,
+            chunk_creature_cap: 150,
+            batch_creatures_from: 10000,
+            statistical_distance: 1500.0,
+            speciation_distance: 0.25,
+            hybrid_zones: true,
+            autosave_minutes: 0.0,
+            vegetation_density: 1.0,
+            auto_vegetation_density: false,
+            target_frame_ms: 25.0,
+            ai_budget_ms: 6.0,
+            ai_max_interval: 4,
+            chunk_cache_mb: 256,
+            legacy_render: false,
+            log_filter: String::new(),
+            log_json: false,
+            scenario: None,
+            bench: None,
+        }
+    }
+}
+
+impl Settings {
+    pub fn load() -> Self {
+        let path = config_path(SETTINGS_FILE);
+        migrate_flat_file(SETTINGS_FILE, &path);
+        let mut settings = match fs::read_to_string(&path) {
+            Ok(contents) => ron::from_str(&contents).unwrap_or_else(|err| {
+                warn!("Failed to parse {}: {} - using defaults", path.display(), err);
+                Self::default()
+            }),
+            Err(_) => Self::default(),
+        };
+
+        if let Ok(filter) = std::env::var(LOG_ENV) {
+            settings.log_filter = filter;
+        }
+        settings.apply_cli_args(std::env::args().skip(1));
+        settings
+    }
+
+    fn apply_cli_args(&mut self, args: impl Iterator<Item = String>) {
+        let mut args = args;
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--lang" => {
+                    if let Some(value) = args.next() {
+                        self.language = value;
+                    }
+                }
+                "--reduced-motion" => self.reduced_motion = true,
+                "--min-coverage" => {
+                    match args.next().as_deref().and_then(parse_biome_value) {
+                        Some(constraint) => self.generation_constraints.min_biome_coverage.push(constraint),
+                        None => warn!("--min-coverage expects Biome:percent, e.g. Ocean:20"),
+                    }
+                }
+                "--min-region" => {
+                    match args.next().as_deref().and_then(parse_biome_value) {
+                        Some(constraint) => self.generation_constraints.min_biome_region.push(constraint),
+                        None => warn!("--min-region expects Biome:tiles, e.g. Forest:5000"),
+                    }
+                }
+                "--background" => {
+                    match args.next().as_deref().and_then(BackgroundMode::from_name) {
+                        Some(mode) => self.background_mode = mode,
+                        None => warn!("--background expects one of: run, pause, throttle"),
+                    }
+                }
+                "--background-fps" => {
+                    if let Some(value) = args.next().and_then(|v| v.parse().ok()) {
+                        self.background_fps = value;
+                    }
+                }
+                "--fps-cap" => {
+                    if let Some(value) = args.next().and_then(|v| v.parse().ok()) {
+                        self.fps_cap = value;
+                    }
+                }
+                "--vsync" => {
+                    match args.next().as_deref().and_then(VsyncMode::from_name) {
+                        Some(mode) => self.vsync = mode,
+                        None => warn!("--vsync expects one of: on, off, adaptive"),
+                    }
+                }
+                "--gpu-noise" => self.gpu_noise = true,
+                "--instant-camera" => self.smooth_camera = false,
+                "--edge-scroll" => self.edge_scrolling = true,
+                "--isometric" => self.view = ViewMode::Isometric,
+                "--view" => {
+                    match args.next().as_deref().and_then(ViewMode::parse) {
+                        Some(view) => self.view = view,
+                        None => warn!("--view expects one of: top-down, isometric"),
+                    }
+                }
+                "--legacy-render" => self.legacy_render = true,
+                "--log" => {
+                    if let Some(value) = args.next() {
+                        self.log_filter = value;
+                    }
+                }
+                "--log-json" => self.log_json = true,
+                "--scenario" => self.scenario = args.next(),
+                "--bench-worldgen" => {
+                    match args.next().and_then(|v| v.parse().ok()) {
+                        Some(runs) => self.bench = Some(BenchRun::Worldgen { runs }),
+                        None => warn!("--bench-worldgen expects a number of worlds"),
+                    }
+                }
+                "--soak-hours" => {
+                    match args.next().and_then(|v| v.parse().ok()).filter(|hours: &f64| *hours > 0.0) {
+                        Some(hours) => self.bench = Some(BenchRun::Soak { hours }),
+                        None => warn!("--soak-hours expects a positive number of simulated hours"),
+                    }
+                }
+                "--auto-camera" => self.auto_camera = true,
+                "--auto-camera-dwell" => {
+                    if let Some(value) = args.next().and_then(|v| v.parse().ok()) {
+                        self.auto_camera_dwell = value;
+                    }
+                }
+                "--creature-cap" => {
+                    if let Some(value) = args.next().and_then(|v| v.parse().ok()) {
+                        self.creature_cap = value;
+                    }
+                }
+                "--ai-budget-ms" => {
+                    if let Some(value) = args.next().and_then(|v| v.parse().ok()) {
+                        self.ai_budget_ms = value;
+                    }
+                }
+                "--chunk-creature-cap" => {
+                    if let Some(value) = args.next().and_then(|v| v.parse().ok()) {
+                        self.chunk_creature_cap = value;
+                    }
+                }
+                "--statistical-distance" => {
+                    if let Some(value) = args.next().and_then(|v| v.parse().ok()) {
+                        self.statistical_distance = value;
+                    }
+                }
+                "--constraint-retries" => {
+                    if let Some(value) = args.next().and_then(|v| v.parse().ok()) {
+                        self.generation_constraints.max_retries = value;
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+// Run condition for purely cosmetic animation systems
+pub fn motion_enabled(settings: Res<Settings>) -> bool {
+    !settings.reduced_motion
+}
@@ -0,0 +1,113 @@
+use bevy::prelude::*;
+use bevy::window::WindowFocused;
+use bevy::winit::{UpdateMode, WinitSettings};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use crate::settings::Settings;
+use crate::world::WorldMap;
+
+pub const SIMULATION_HZ: f64 = 20.0; // Fixed-update ticks per second
+pub const BACKGROUND_SIMULATION_HZ: f64 = 1.0; // Tick rate while throttled in the background
+pub const SECONDS_PER_DAY: f64 = 120.0; // Simulated seconds in one in-game day
+pub const DAYS_PER_YEAR: u32 = 12;
+
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        let background_fps = app.world().resource::<Settings>().background_fps.max(1.0);
+
+        app
+            .insert_resource(Time::<Fixed>::from_hz(SIMULATION_HZ))
+            .init_resource::<SimClock>()
+            // Unfocused (including minimized) windows only redraw at the background frame cap
+            .insert_resource(WinitSettings {
+                focused_mode: UpdateMode::Continuous,
+                unfocused_mode: UpdateMode::reactive_low_power(Duration::from_secs_f32(1.0 / background_fps)),
+            })
+            .add_systems(Update, apply_background_mode)
+            .add_systems(FixedFirst, advance_sim_clock.run_if(resource_exists::<WorldMap>));
+    }
+}
+
+// In-game time, advanced only by fixed-update ticks so it pauses and throttles with the simulation
+#[derive(Resource, Default)]
+pub struct SimClock {
+    pub elapsed: f64, // Simulated seconds since the world was created
+}
+
+impl SimClock {
+    pub fn day(&self) -> u32 {
+        (self.elapsed / SECONDS_PER_DAY) as u32
+    }
+
+    // 0.0 at the start of the day, approaching 1.0 at its end
+    pub fn time_of_day(&self) -> f32 {
+        (self.elapsed / SECONDS_PER_DAY).fract() as f32
+    }
+
+    // Darkness spans the turn of the day
+    pub fn is_night(&self) -> bool {
+        let time_of_day = self.time_of_day();
+        !(0.2..0.8).contains(&time_of_day)
+    }
+}
+
+fn advance_sim_clock(time: Res<Time>, mut clock: ResMut<SimClock>) {
+    clock.elapsed += time.delta_seconds_f64();
+}
+
+// What the fixed-update simulation does while the window is unfocused
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BackgroundMode {
+    #[default]
+    Run, // Keep simulating at full speed
+    Pause, // Pause virtual time entirely
+    Throttle, // Keep simulating at BACKGROUND_SIMULATION_HZ
+}
+
+impl BackgroundMode {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "run" => Some(Self::Run),
+            "pause" => Some(Self::Pause),
+            "throttle" => Some(Self::Throttle),
+            _ => None,
+        }
+    }
+}
+
+fn apply_background_mode(
+    mut focus_events: EventReader<WindowFocused>,
+    settings: Res<Settings>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut fixed_time: ResMut<Time<Fixed>>,
+    mut paused_by_focus: Local<bool>,
+) {
+    let Some(event) = focus_events.read().last() else { return };
+
+    if event.focused {
+        // Only undo a pause we caused ourselves
+        if *paused_by_focus {
+            virtual_time.unpause();
+            *paused_by_focus = false;
+        }
+        fixed_time.set_timestep_hz(SIMULATION_HZ);
+        return;
+    }
+
+    match settings.background_mode {
+        BackgroundMode::Run => {}
+        BackgroundMode::Pause => {
+            if !virtual_time.is_paused() {
+                info!("⏸️ Window unfocused - pausing simulation");
+                virtual_time.pause();
+                *paused_by_focus = true;
+            }
+        }
+        BackgroundMode::Throttle => {
+            info!("🐢 Window unfocused - throttling simulation to {} Hz", BACKGROUND_SIMULATION_HZ);
+            fixed_time.set_timestep_hz(BACKGROUND_SIMULATION_HZ);
+        }
+    }
+}
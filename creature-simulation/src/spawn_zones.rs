@@ -0,0 +1,236 @@
+use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::collections::HashMap;
+use std::time::Duration;
+use crate::camera::MainCameraQuery;
+use crate::coords::{tile_to_chunk, tile_to_world, world_to_tile};
+use crate::keybindings::{Action, Controls};
+use crate::creature::{spawn_creature, Creature};
+use crate::editor::{cursor_tile, editor_active, Editor, EditorTool};
+use crate::genetics::{founder_lineage, Genome};
+use crate::population_pools::{chunk_center, PopulationPools};
+use crate::reserves::Reserves;
+use crate::species::SpeciesRegistry;
+use crate::text_input::text_input_inactive;
+use crate::world::{WorldMap, WORLD_SIZE};
+use crate::rng_streams::{RngStream, RngStreams};
+
+const BRUSH_SPAWNS_PER_SECOND: f32 = 20.0; // Immediate mode, while the mouse is held
+const ZONE_PAINT_RATE: f32 = 0.05; // Density added per second at the brush centre
+const MAX_ZONE_DENSITY: f32 = 0.1; // Creatures per tile a zone asks for
+const ZONE_TICK_SECONDS: f32 = 2.0;
+const ZONE_SPAWNS_PER_TICK: usize = 5; // Per chunk and species, so zones fill up gradually
+
+pub struct SpawnZonesPlugin;
+
+impl Plugin for SpawnZonesPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<SpawnBrush>()
+            .init_resource::<SpawnZones>()
+            .add_systems(Update, (
+                reset_spawn_zones.run_if(resource_added::<WorldMap>),
+                spawn_brush_hotkeys.run_if(text_input_inactive).run_if(editor_active),
+                paint_spawns.run_if(editor_active),
+                draw_spawn_zones.run_if(editor_active),
+                maintain_spawn_zones.run_if(on_timer(Duration::from_secs_f32(ZONE_TICK_SECONDS))),
+            ).chain().run_if(resource_exists::<WorldMap>));
+    }
+}
+
+// === SPAWNER BRUSH ===
+// The editor's spawn tool either drops creatures of the selected species straight onto habitat
+// tiles under the brush, or paints a spawn zone: a per-tile density that the zone system keeps
+// topped up and that raises the carrying capacity of pooled populations in those chunks.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SpawnMode {
+    #[default]
+    Immediate,
+    Zone,
+}
+
+#[derive(Resource, Default)]
+pub struct SpawnBrush {
+    pub species: usize,
+    pub mode: SpawnMode,
+    pending: f32, // Fractional spawns carried over between frames
+}
+
+// Painted (species, density) per tile of one chunk
+pub type ZoneTiles = HashMap<(usize, usize), (usize, f32)>;
+
+// Painted densities, grouped by chunk so per-chunk targets are cheap to sum
+#[derive(Resource, Default)]
+pub struct SpawnZones {
+    pub chunks: HashMap<(i32, i32), ZoneTiles>,
+}
+
+impl SpawnZones {
+    // Creatures of a species the zones in a chunk ask for
+    pub fn target(&self, chunk: (i32, i32), species: usize) -> f32 {
+        self.chunks.get(&chunk).map_or(0.0, |tiles| {
+            tiles.values().filter(|(zone_species, _)| *zone_species == species).map(|(_, density)| density).sum()
+        })
+    }
+}
+
+fn reset_spawn_zones(mut zones: ResMut<SpawnZones>) {
+    zones.chunks.clear();
+}
+
+// Q cycles species and M switches between spawning now and painting zones
+fn spawn_brush_hotkeys(
+    controls: Controls,
+    editor: Res<Editor>,
+    registry: Res<SpeciesRegistry>,
+    mut brush: ResMut<SpawnBrush>,
+) {
+    if editor.tool != EditorTool::SpawnCreatures {
+        return;
+    }
+    if controls.just_pressed(Action::NextChoice) && !registry.species.is_empty() {
+        brush.species = (brush.species + 1) % registry.species.len();
+    }
+    if controls.just_pressed(Action::ToggleToolMode) {
+        brush.mode = match brush.mode {
+            SpawnMode::Immediate => SpawnMode::Zone,
+            SpawnMode::Zone => SpawnMode::Immediate,
+        };
+    }
+}
+
+fn brush_tiles(center: (usize, usize), radius: usize) -> impl Iterator<Item = ((usize, usize), f32)> {
+    let radius = radius as i32;
+    (-radius..=radius).flat_map(move |dx| (-radius..=radius).map(move |dy| (dx, dy)))
+        .filter_map(move |(dx, dy)| {
+            let (x, y) = (center.0 as i32 + dx, center.1 as i32 + dy);
+            let distance = ((dx * dx + dy * dy) as f32).sqrt();
+            let inside = x >= 0 && y >= 0 && x < WORLD_SIZE as i32 && y < WORLD_SIZE as i32 && distance <= radius as f32 + 0.5;
+            inside.then(|| ((x as usize, y as usize), 1.0 - distance / (radius as f32 + 1.0)))
+        })
+}
+
+// Left mouse spawns or paints, right mouse erases zones under the brush
+fn paint_spawns(
+    mut commands: Commands,
+    time: Res<Time>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    (windows, cameras): (Query<&Window>, MainCameraQuery),
+    interactions: Query<&Interaction>,
+    (editor, world_map, registry, reserves): (Res<Editor>, Res<WorldMap>, Res<SpeciesRegistry>, Res<Reserves>),
+    (mut brush, mut zones, mut streams): (ResMut<SpawnBrush>, ResMut<SpawnZones>, ResMut<RngStreams>),
+) {
+    if editor.tool != EditorTool::SpawnCreatures || interactions.iter().any(|interaction| *interaction != Interaction::None) {
+        return;
+    }
+    let Some(center) = cursor_tile(&windows, &cameras) else { return };
+    let Some(species) = registry.species.get(brush.species) else { return };
+    let dt = time.delta_seconds();
+
+    if mouse.pressed(MouseButton::Right) {
+        for (tile, _) in brush_tiles(center, editor.radius) {
+            if let Some(tiles) = zones.chunks.get_mut(&tile_to_chunk(tile.0, tile.1)) {
+                tiles.remove(&tile);
+            }
+        }
+        zones.chunks.retain(|_, tiles| !tiles.is_empty());
+        return;
+    }
+    if !mouse.pressed(MouseButton::Left) {
+        return;
+    }
+
+    let habitat: Vec<((usize, usize), f32)> = brush_tiles(center, editor.radius)
+        .filter(|((x, y), _)| species.habitats.contains(&world_map.tiles[*x][*y].biome) && !reserves.contains(*x, *y))
+        .collect();
+    match brush.mode {
+        SpawnMode::Immediate => {
+            brush.pending += BRUSH_SPAWNS_PER_SECOND * dt;
+            let rng = streams.get(RngStream::Creatures);
+            while brush.pending >= 1.0 {
+                brush.pending -= 1.0;
+                let Some(((x, y), _)) = habitat.choose(rng) else { continue };
+                let genome = Genome::founder(rng, founder_lineage(brush.species, *x, *y));
+                let heading = rng.gen_range(0.0..std::f32::consts::TAU);
+                spawn_creature(&mut commands, &registry, brush.species, tile_to_world(*x, *y), heading, genome);
+            }
+        }
+        SpawnMode::Zone => {
+            for ((x, y), strength) in habitat {
+                let tiles = zones.chunks.entry(tile_to_chunk(x, y)).or_default();
+                let zone = tiles.entry((x, y)).or_insert((brush.species, 0.0));
+                // Painting another species over a zone replaces it
+                if zone.0 != brush.species {
+                    *zone = (brush.species, 0.0);
+                }
+                zone.1 = (zone.1 + ZONE_PAINT_RATE * strength * dt).min(MAX_ZONE_DENSITY);
+            }
+        }
+    }
+}
+
+// Zones below their target get a few new founders per tick; pooled creatures count towards it
+fn maintain_spawn_zones(
+    mut commands: Commands,
+    zones: Res<SpawnZones>,
+    registry: Res<SpeciesRegistry>,
+    (pools, reserves): (Res<PopulationPools>, Res<Reserves>),
+    creatures: Query<(&Transform, &Creature)>,
+    mut streams: ResMut<RngStreams>,
+) {
+    if zones.chunks.is_empty() {
+        return;
+    }
+    let mut counts: HashMap<((i32, i32), usize), usize> = HashMap::new();
+    for (transform, creature) in creatures.iter() {
+        if let Some((x, y)) = world_to_tile(transform.translation.truncate()) {
+            *counts.entry((tile_to_chunk(x, y), creature.species)).or_default() += 1;
+        }
+    }
+    for (chunk, pooled) in &pools.chunks {
+        for creature in pooled {
+            *counts.entry((*chunk, creature.species)).or_default() += 1;
+        }
+    }
+
+    let rng = streams.get(RngStream::Creatures);
+    for (chunk, tiles) in &zones.chunks {
+        for species in 0..registry.species.len() {
+            let target = zones.target(*chunk, species).round() as usize;
+            let missing = target.saturating_sub(counts.get(&(*chunk, species)).copied().unwrap_or(0));
+            let zone_tiles: Vec<(usize, usize)> = tiles.iter()
+                .filter(|(tile, (zone_species, _))| *zone_species == species && !reserves.contains(tile.0, tile.1))
+                .map(|(tile, _)| *tile)
+                .collect();
+            for _ in 0..missing.min(ZONE_SPAWNS_PER_TICK) {
+                let Some((x, y)) = zone_tiles.choose(rng) else { break };
+                let genome = Genome::founder(rng, founder_lineage(species, *x, *y));
+                let heading = rng.gen_range(0.0..std::f32::consts::TAU);
+                spawn_creature(&mut commands, &registry, species, tile_to_world(*x, *y), heading, genome);
+            }
+        }
+    }
+}
+
+// One marker per zone chunk in the colour of its species, sized by the creatures it asks for
+fn draw_spawn_zones(
+    mut gizmos: Gizmos,
+    editor: Res<Editor>,
+    zones: Res<SpawnZones>,
+    registry: Res<SpeciesRegistry>,
+) {
+    if editor.tool != EditorTool::SpawnCreatures {
+        return;
+    }
+    for (chunk, tiles) in &zones.chunks {
+        for (species_id, species) in registry.species.iter().enumerate() {
+            let target = zones.target(*chunk, species_id);
+            if target <= 0.0 || !tiles.values().any(|(zone_species, _)| *zone_species == species_id) {
+                continue;
+            }
+            gizmos.circle_2d(chunk_center(*chunk), 4.0 + target.sqrt() * 4.0, species.get_color());
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use bevy::prelude::*;
+use serde::Serialize;
+use std::collections::HashMap;
+use crate::coords::world_to_tile;
+use crate::creature::Creature;
+use crate::events::NotableEvent;
+use crate::genetics::Genome;
+use crate::names::species_name;
+use crate::relationships::RelationshipTable;
+use crate::settings::Settings;
+use crate::simulation::SimClock;
+use crate::species::{Species, SpeciesRegistry};
+use crate::telemetry::Telemetry;
+use crate::world::WorldMap;
+
+const MIN_LINEAGE: usize = 20; // Members a lineage, and the rest of its species, need for a split
+const NAME_ATTEMPTS: u64 = 16; // Generated names tried before giving up on a unique one
+
+pub struct SpeciationPlugin;
+
+impl Plugin for SpeciationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(FixedUpdate, split_species
+            .run_if(speciation_enabled)
+            .run_if(resource_exists::<WorldMap>));
+    }
+}
+
+// === SPECIATION ===
+// Once a day, each species is checked for a lineage whose average traits have drifted more than
+// Settings::speciation_distance from the rest of its species. Such a lineage splits off as a new
+// species: a copy of its ancestor under a generated name, with the same relationships, registered
+// like a designed one and kept in save files. Creatures only answer mating calls of creatures
+// whose traits are within that distance of their own (see calls.rs); with hybrid zones, sister
+// species that still come that close pair up where their ranges meet.
+#[derive(Serialize)]
+struct SpeciationRecord<'a> {
+    day: u32,
+    species: &'a str,
+    ancestor: &'a str,
+    lineage: u32,
+    members: usize,
+    distance: f32,
+}
+
+fn speciation_enabled(settings: Res<Settings>) -> bool {
+    settings.speciation_distance > 0.0
+}
+
+// Whether two creatures count as one species for mating
+pub fn can_interbreed(
+    (species_a, genome_a): (usize, &Genome),
+    (species_b, genome_b): (usize, &Genome),
+    registry: &SpeciesRegistry,
+    settings: &Settings,
+) -> bool {
+    let close = settings.speciation_distance <= 0.0 || genome_a.distance(genome_b) <= settings.speciation_distance;
+    if species_a == species_b {
+        return close;
+    }
+    let (a, b) = (registry.get(species_a), registry.get(species_b));
+    let sisters = a.ancestor.as_deref() == Some(b.name.as_str())
+        || b.ancestor.as_deref() == Some(a.name.as_str())
+        || (a.ancestor.is_some() && a.ancestor == b.ancestor);
+    settings.hybrid_zones && sisters && close
+}
+
+// Adds a species that split off from another, or was loaded from a save, with its ancestor's
+// relationships; returns its index
+pub fn register_species(species: Species, registry: &mut SpeciesRegistry, relationships: &mut RelationshipTable) -> usize {
+    match species.ancestor.as_deref().and_then(|ancestor| registry.find(ancestor)) {
+        Some(ancestor) => relationships.add_descendant(ancestor),
+        None => relationships.add_species(),
+    }
+    registry.add(species)
+}
+
+// Mean traits of a group of genomes; size is averaged on a log scale
+fn centroid(genomes: &[&Genome]) -> Genome {
+    let count = genomes.len().max(1) as f32;
+    let sum = |trait_of: fn(&Genome) -> f32| genomes.iter().map(|genome| trait_of(genome)).sum::<f32>() / count;
+    Genome {
+        tint: (sum(|genome| genome.tint.0), sum(|genome| genome.tint.1), sum(|genome| genome.tint.2)),
+        size: sum(|genome| genome.size.ln()).exp(),
+        lineage: 0,
+        boldness: sum(|genome| genome.boldness),
+    }
+}
+
+fn split_species(
+    clock: Res<SimClock>,
+    settings: Res<Settings>,
+    world_map: Res<WorldMap>,
+    (mut registry, mut relationships): (ResMut<SpeciesRegistry>, ResMut<RelationshipTable>),
+    (mut telemetry, mut notable): (ResMut<Telemetry>, EventWriter<NotableEvent>),
+    mut last_day: Local<Option<u32>>,
+    mut creatures: Query<(&Transform, &mut Creature, &Genome)>,
+) {
+    let day = clock.day();
+    if *last_day == Some(day) {
+        return;
+    }
+    *last_day = Some(day);
+
+    let mut by_species: HashMap<usize, HashMap<u32, Vec<&Genome>>> = HashMap::new();
+    for (_, creature, genome) in creatures.iter() {
+        by_species.entry(creature.species).or_default().entry(genome.lineage).or_default().push(genome);
+    }
+
+    // At most one split per species and day, of its most divergent lineage
+    let mut splits: Vec<(usize, u32, usize, f32)> = Vec::new();
+    for (species, lineages) in &by_species {
+        let total: usize = lineages.values().map(Vec::len).sum();
+        let divergent = lineages.iter()
+            .filter(|(_, members)| members.len() >= MIN_LINEAGE && total - members.len() >= MIN_LINEAGE)
+            .map(|(lineage, members)| {
+                let rest: Vec<&Genome> = lineages.iter()
+                    .filter(|(other, _)| *other != lineage)
+                    .flat_map(|(_, genomes)| genomes.iter().copied())
+                    .collect();
+                (*lineage, members.len(), centroid(members).distance(&centroid(&rest)))
+            })
+            .filter(|(_, _, distance)| *distance > settings.speciation_distance)
+            .max_by(|a, b| a.2.total_cmp(&b.2));
+        if let Some((lineage, members, distance)) = divergent {
+            splits.push((*species, lineage, members, distance));
+        }
+    }
+
+    for (ancestor_id, lineage, members, distance) in splits {
+        let positions: Vec<Vec2> = creatures.iter()
+            .filter(|(_, creature, genome)| creature.species == ancestor_id && genome.lineage == lineage)
+            .map(|(transform, _, _)| transform.translation.truncate())
+            .collect();
+        let centre = positions.iter().sum::<Vec2>() / positions.len().max(1) as f32;
+        let ancestor = registry.get(ancestor_id).clone();
+        let biome = world_to_tile(centre).map(|(x, y)| world_map.tiles[x][y].biome).or(ancestor.habitats.first().copied());
+        let Some(biome) = biome else { continue };
+        let seed = (world_map.seed as u64) ^ ((lineage as u64) << 16) ^ ((day as u64) << 40);
+        let Some(name) = (0..NAME_ATTEMPTS).map(|attempt| species_name(seed + attempt, biome))
+            .find(|name| registry.find(name).is_none()) else { continue };
+
+        let species = Species { name, ancestor: Some(ancestor.name.clone()), ..ancestor.clone() };
+        let name = species.name.clone();
+        let species_id = register_species(species, &mut registry, &mut relationships);
+        for (_, mut creature, genome) in creatures.iter_mut() {
+            if creature.species == ancestor_id && genome.lineage == lineage {
+                creature.species = species_id;
+            }
+        }
+
+        info!("🧬 Day {}: {} {} of lineage {} split off as {}", day, members, ancestor.name, lineage, name);
+        telemetry.record("speciation", &SpeciationRecord {
+            day,
+            species: &name,
+            ancestor: &ancestor.name,
+            lineage,
+            members,
+            distance,
+        });
+        notable.send(NotableEvent { position: centre, importance: 5.0, label_key: "event.speciation" });
+    }
+}
@@ -0,0 +1,159 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use crate::biome::{BiomeType, ResourceType};
+use crate::errors::SimError;
+use crate::mods::{ModList, SPECIES_FILE};
+
+// Built-in species definitions, embedded like the locale tables
+const SPECIES_PATH: &str = "assets/species.ron"; // For error messages
+const SPECIES_SOURCE: &str = include_str!("../assets/species.ron");
+pub const DESIGNED_SPECIES_DIR: &str = "species"; // One file per species made in the designer
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Diet {
+    Herbivore,
+    Omnivore,
+    Nectarivore, // Insects feeding on flowers; they pollinate the flowers they visit
+    Carnivore, // Fishes; what it hunts comes from relationships.ron (see predation.rs)
+}
+
+impl Diet {
+    pub const ALL: [Diet; 4] = [Diet::Herbivore, Diet::Omnivore, Diet::Nectarivore, Diet::Carnivore];
+
+    pub fn name_key(&self) -> String {
+        format!("diet.{:?}", self)
+    }
+
+    pub fn edible_resources(&self) -> &'static [ResourceType] {
+        match self {
+            Diet::Herbivore => &[ResourceType::Berries, ResourceType::Herbs],
+            Diet::Omnivore => &[ResourceType::Berries, ResourceType::Herbs, ResourceType::Fish, ResourceType::Mushrooms, ResourceType::Shellfish],
+            Diet::Nectarivore => &[], // Feeds on blooming flowers, see vegetation.rs
+            Diet::Carnivore => &[ResourceType::Fish],
+        }
+    }
+}
+
+// How a species gets around; decides which tiles it may enter
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MovementMode {
+    #[default]
+    Walk, // Stays on dry land
+    Fly, // Ignores terrain entirely, including open water
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Species {
+    pub name: String,
+    pub color: (f32, f32, f32),
+    pub size: f32, // Sprite size in world units
+    pub speed: f32, // World units per second
+    pub diet: Diet,
+    pub habitats: Vec<BiomeType>, // Biomes the species spawns in and prefers to stay in
+    pub drought_tolerance: f32, // 0.0 = normal thirst, 1.0 = never thirsty
+    pub comfort_temperature: (f32, f32), // Tile temperature range with no extra energy cost
+    pub initial_population: usize,
+    #[serde(default)]
+    pub movement: MovementMode,
+    #[serde(default)]
+    pub burrower: bool, // Digs burrows, leaving bare dirt patches
+    #[serde(default)]
+    pub pheromones: bool, // Lays and follows scent trails like ants (see pheromones.rs)
+    #[serde(default)]
+    pub nocturnal: bool, // Hunts and stays awake at night, seeing further the fuller the moon
+    #[serde(default)]
+    pub lunar_breeder: bool, // Only gives birth around the new moon
+    #[serde(default)]
+    pub hibernates: bool, // Fattens up in autumn and sleeps through cold winters in a den
+    #[serde(default)]
+    pub oviparous: bool, // Lays clutches of eggs instead of giving birth (see nesting.rs)
+    #[serde(default)]
+    pub ancestor: Option<String>, // Species this one split off from while running (see speciation.rs)
+}
+
+impl Species {
+    pub fn get_color(&self) -> Color {
+        Color::srgb(self.color.0, self.color.1, self.color.2)
+    }
+
+    // How far the temperature lies outside the comfort zone, 0.0 inside it
+    pub fn temperature_stress(&self, temperature: f32) -> f32 {
+        let (low, high) = self.comfort_temperature;
+        (low - temperature).max(temperature - high).max(0.0)
+    }
+}
+
+// The species compiled into the game, or none if they do not parse
+pub fn builtin_species() -> Vec<Species> {
+    ron::from_str(SPECIES_SOURCE).unwrap_or_default()
+}
+
+// === SPECIES REGISTRY ===
+// Creatures refer to their species by index into this list: the built-in species first, then
+// those of enabled mods (mods.rs) in load order, then those saved from the species designer
+// (species_designer.rs) in file name order.
+#[derive(Resource)]
+pub struct SpeciesRegistry {
+    pub species: Vec<Species>,
+    builtin: usize, // Species from species.ron; designed ones follow
+}
+
+impl Default for SpeciesRegistry {
+    fn default() -> Self {
+        // An empty registry; the error screen reports why (see errors.rs)
+        Self::load().unwrap_or_else(|_| Self { species: Vec::new(), builtin: 0 })
+    }
+}
+
+impl SpeciesRegistry {
+    pub fn load() -> Result<Self, SimError> {
+        let species: Vec<Species> = ron::from_str(SPECIES_SOURCE).map_err(|err| SimError::parse(SPECIES_PATH, err))?;
+        let mut registry = Self { builtin: species.len(), species };
+        // Conflicts are reported by mods.rs
+        for pack in ModList::discover().enabled() {
+            for species in pack.read_list::<Species>(SPECIES_FILE) {
+                match registry.find(&species.name) {
+                    Some(id) => registry.species[id] = species,
+                    None => { registry.add(species); }
+                }
+            }
+        }
+        let mut designed: Vec<PathBuf> = fs::read_dir(DESIGNED_SPECIES_DIR).into_iter().flatten().flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.extension().is_some_and(|extension| extension == "ron"))
+            .collect();
+        designed.sort();
+        for path in designed {
+            match fs::read_to_string(&path).map_err(|err| err.to_string())
+                .and_then(|contents| ron::from_str::<Species>(&contents).map_err(|err| err.to_string())) {
+                Ok(species) if registry.find(&species.name).is_some() => {
+                    warn!("Skipping species {}: \"{}\" already exists", path.display(), species.name);
+                }
+                Ok(species) => { registry.add(species); }
+                Err(err) => warn!("Skipping species {}: {}", path.display(), err),
+            }
+        }
+        Ok(registry)
+    }
+
+    pub fn get(&self, id: usize) -> &Species {
+        &self.species[id]
+    }
+
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.species.iter().position(|species| species.name.eq_ignore_ascii_case(name))
+    }
+
+    // Species from mods and the designer
+    pub fn custom(&self) -> &[Species] {
+        &self.species[self.builtin..]
+    }
+
+    // Appends a species, returning its index
+    pub fn add(&mut self, species: Species) -> usize {
+        self.species.push(species);
+        self.species.len() - 1
+    }
+}
@@ -0,0 +1,380 @@
+use bevy::prelude::*;
+use bevy::ui::RelativeCursorPosition;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use std::fs;
+use crate::camera::MainCamera;
+use crate::biome::BiomeType;
+use crate::coords::{tile_to_world, world_to_tile};
+use crate::creature::spawn_creature;
+use crate::errors::{SimError, SimFailure};
+use crate::genetics::{founder_lineage, Genome};
+use crate::io_queue::{file_stem, IoQueue};
+use crate::keybindings::{Action, Controls};
+use crate::localization::Localization;
+use crate::relationships::RelationshipTable;
+use crate::reserves::Reserves;
+use crate::species::{Diet, MovementMode, Species, SpeciesRegistry, DESIGNED_SPECIES_DIR};
+use crate::text_input::{text_input_inactive, PromptKind, PromptSubmitted, TextPrompt};
+use crate::world::{WorldMap, WORLD_SIZE};
+use crate::rng_streams::{RngStream, RngStreams};
+
+const TRACK_WIDTH: f32 = 140.0;
+const FOUNDERS: usize = 20; // Creatures dropped around the camera when a species is added
+const FOUNDING_RADIUS: usize = 12; // Tiles around the camera searched for habitat
+
+pub struct SpeciesDesignerPlugin;
+
+impl Plugin for SpeciesDesignerPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<SpeciesDesigner>()
+            .add_systems(Startup, spawn_species_designer)
+            .add_systems(Update, (
+                toggle_species_designer.run_if(text_input_inactive),
+                drag_designer_sliders,
+                press_designer_buttons,
+                update_species_designer.run_if(resource_changed::<SpeciesDesigner>),
+                add_designed_species.run_if(resource_exists::<WorldMap>),
+            ).chain());
+    }
+}
+
+// === SPECIES DESIGNER ===
+// Composes a new species from parts (toggled with U): body, speed, color and climate on sliders,
+// diet and habitats on buttons. "Add to world" asks for a name, puts the species in the registry,
+// drops a founding group on habitat around the camera and saves the definition to
+// DESIGNED_SPECIES_DIR in the species.ron format, so it is in the registry on every later start.
+#[derive(Resource)]
+pub struct SpeciesDesigner {
+    pub draft: Species,
+}
+
+impl Default for SpeciesDesigner {
+    fn default() -> Self {
+        Self {
+            draft: Species {
+                name: String::new(), // Asked for on adding
+                color: (0.6, 0.5, 0.8),
+                size: 3.0,
+                speed: 12.0,
+                diet: Diet::Herbivore,
+                habitats: vec![BiomeType::Grasslands],
+                drought_tolerance: 0.2,
+                comfort_temperature: (0.3, 0.75),
+                initial_population: 300,
+                movement: MovementMode::Walk,
+                burrower: false,
+                pheromones: false,
+                nocturnal: false,
+                lunar_breeder: false,
+                hibernates: false,
+                oviparous: false,
+                ancestor: None,
+            },
+        }
+    }
+}
+
+struct SliderSpec {
+    label_key: &'static str,
+    min: f32,
+    max: f32,
+    value: fn(&mut Species) -> &mut f32,
+}
+
+const SLIDERS: [SliderSpec; 8] = [
+    SliderSpec { label_key: "designer.size", min: 1.5, max: 6.0, value: |species| &mut species.size },
+    SliderSpec { label_key: "designer.speed", min: 4.0, max: 25.0, value: |species| &mut species.speed },
+    SliderSpec { label_key: "designer.red", min: 0.0, max: 1.0, value: |species| &mut species.color.0 },
+    SliderSpec { label_key: "designer.green", min: 0.0, max: 1.0, value: |species| &mut species.color.1 },
+    SliderSpec { label_key: "designer.blue", min: 0.0, max: 1.0, value: |species| &mut species.color.2 },
+    SliderSpec { label_key: "designer.drought_tolerance", min: 0.0, max: 1.0, value: |species| &mut species.drought_tolerance },
+    SliderSpec { label_key: "designer.comfort_low", min: 0.0, max: 1.0, value: |species| &mut species.comfort_temperature.0 },
+    SliderSpec { label_key: "designer.comfort_high", min: 0.0, max: 1.0, value: |species| &mut species.comfort_temperature.1 },
+];
+
+#[derive(Component)]
+pub struct SpeciesDesignerRoot;
+
+#[derive(Component)]
+pub struct DesignerTrack(usize); // Index into SLIDERS
+
+#[derive(Component)]
+pub struct DesignerFill(usize);
+
+#[derive(Component)]
+pub struct DesignerValue(usize);
+
+// Filled with the draft's color, scaled with its size
+#[derive(Component)]
+pub struct DesignerSwatch;
+
+#[derive(Component, Clone, Copy)]
+pub enum DesignerButton {
+    Diet, // Cycles through Diet::ALL
+    Habitat(BiomeType), // Toggles the biome
+    Add,
+}
+
+#[derive(Component)]
+pub struct DietLabel;
+
+fn spawn_species_designer(mut commands: Commands, localization: Res<Localization>) {
+    let text_style = TextStyle { font_size: 14.0, color: Color::srgb(0.9, 0.9, 0.9), ..default() };
+    let button_style = Style { padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)), ..default() };
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                top: Val::Px(10.0),
+                right: Val::Px(10.0),
+                width: Val::Px(360.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(8.0)),
+                row_gap: Val::Px(4.0),
+                ..default()
+            },
+            background_color: Color::srgba(0.05, 0.05, 0.1, 0.8).into(),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        SpeciesDesignerRoot,
+    )).with_children(|parent| {
+        parent.spawn(NodeBundle {
+            style: Style { align_items: AlignItems::Center, column_gap: Val::Px(8.0), ..default() },
+            ..default()
+        }).with_children(|row| {
+            row.spawn(TextBundle::from_section(localization.get("designer.title"), text_style.clone()));
+            row.spawn((NodeBundle::default(), DesignerSwatch));
+        });
+
+        for (index, slider) in SLIDERS.iter().enumerate() {
+            parent.spawn(NodeBundle {
+                style: Style { align_items: AlignItems::Center, column_gap: Val::Px(6.0), ..default() },
+                ..default()
+            }).with_children(|row| {
+                row.spawn(TextBundle::from_section(localization.get(slider.label_key), text_style.clone())
+                    .with_style(Style { width: Val::Px(130.0), ..default() }));
+                row.spawn((
+                    ButtonBundle {
+                        style: Style { width: Val::Px(TRACK_WIDTH), height: Val::Px(10.0), ..default() },
+                        background_color: Color::srgb(0.2, 0.2, 0.3).into(),
+                        ..default()
+                    },
+                    RelativeCursorPosition::default(),
+                    DesignerTrack(index),
+                )).with_children(|track| {
+                    track.spawn((
+                        NodeBundle {
+                            style: Style { height: Val::Percent(100.0), ..default() },
+                            background_color: Color::srgb(0.4, 0.6, 0.9).into(),
+                            ..default()
+                        },
+                        DesignerFill(index),
+                    ));
+                });
+                row.spawn((TextBundle::from_section("", text_style.clone()), DesignerValue(index)));
+            });
+        }
+
+        parent.spawn((
+            ButtonBundle { style: button_style.clone(), background_color: Color::srgba(0.2, 0.2, 0.3, 0.9).into(), ..default() },
+            DesignerButton::Diet,
+        )).with_children(|button| {
+            button.spawn((TextBundle::from_section("", text_style.clone()), DietLabel));
+        });
+
+        parent.spawn(TextBundle::from_section(localization.get("designer.habitats"), text_style.clone()));
+        parent.spawn(NodeBundle {
+            style: Style { flex_wrap: FlexWrap::Wrap, column_gap: Val::Px(4.0), row_gap: Val::Px(4.0), ..default() },
+            ..default()
+        }).with_children(|grid| {
+            for biome in BiomeType::ALL {
+                grid.spawn((
+                    ButtonBundle { style: button_style.clone(), ..default() },
+                    DesignerButton::Habitat(biome),
+                )).with_children(|button| {
+                    button.spawn(TextBundle::from_section(localization.get(&biome.name_key()), text_style.clone()));
+                });
+            }
+        });
+
+        parent.spawn((
+            ButtonBundle { style: button_style.clone(), background_color: Color::srgba(0.2, 0.35, 0.2, 0.9).into(), ..default() },
+            DesignerButton::Add,
+        )).with_children(|button| {
+            button.spawn(TextBundle::from_section(localization.get("designer.add"), text_style.clone()));
+        });
+    });
+}
+
+fn toggle_species_designer(
+    controls: Controls,
+    mut panels: Query<&mut Visibility, With<SpeciesDesignerRoot>>,
+) {
+    if !controls.just_pressed(Action::ToggleSpeciesDesigner) {
+        return;
+    }
+    for mut visibility in panels.iter_mut() {
+        *visibility = match *visibility {
+            Visibility::Hidden => Visibility::Visible,
+            _ => Visibility::Hidden,
+        };
+    }
+}
+
+// A held track follows the cursor, even when dragged past either end
+fn drag_designer_sliders(
+    tracks: Query<(&Interaction, &RelativeCursorPosition, &DesignerTrack)>,
+    mut designer: ResMut<SpeciesDesigner>,
+) {
+    for (interaction, cursor, track) in tracks.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let Some(position) = cursor.normalized else { continue };
+        let slider = &SLIDERS[track.0];
+        let value = slider.min + position.x.clamp(0.0, 1.0) * (slider.max - slider.min);
+        let current = (slider.value)(&mut designer.bypass_change_detection().draft);
+        if *current != value {
+            *current = value;
+            designer.set_changed();
+        }
+    }
+}
+
+fn press_designer_buttons(
+    buttons: Query<(&Interaction, &DesignerButton), Changed<Interaction>>,
+    mut designer: ResMut<SpeciesDesigner>,
+    mut prompt: ResMut<TextPrompt>,
+) {
+    for (interaction, button) in buttons.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+        let draft = &mut designer.draft;
+        match *button {
+            DesignerButton::Diet => {
+                let next = Diet::ALL.iter().position(|diet| *diet == draft.diet).map_or(0, |i| i + 1);
+                draft.diet = Diet::ALL[next % Diet::ALL.len()];
+            }
+            DesignerButton::Habitat(biome) => match draft.habitats.iter().position(|habitat| *habitat == biome) {
+                Some(index) => { draft.habitats.remove(index); }
+                None => draft.habitats.push(biome),
+            },
+            DesignerButton::Add => prompt.open(PromptKind::SpeciesName, String::new()),
+        }
+    }
+}
+
+fn update_species_designer(
+    designer: Res<SpeciesDesigner>,
+    localization: Res<Localization>,
+    mut fills: Query<(&mut Style, &DesignerFill), Without<DesignerSwatch>>,
+    mut texts: Query<(&mut Text, Option<&DesignerValue>, Has<DietLabel>)>,
+    mut buttons: Query<(&DesignerButton, &mut BackgroundColor), Without<DesignerSwatch>>,
+    mut swatches: Query<(&mut Style, &mut BackgroundColor), With<DesignerSwatch>>,
+) {
+    let mut draft = designer.draft.clone();
+    for (mut style, fill) in fills.iter_mut() {
+        let slider = &SLIDERS[fill.0];
+        let fraction = (*(slider.value)(&mut draft) - slider.min) / (slider.max - slider.min);
+        style.width = Val::Percent(fraction * 100.0);
+    }
+    for (mut text, value, diet_label) in texts.iter_mut() {
+        if let Some(value) = value {
+            text.sections[0].value = format!("{:.2}", (SLIDERS[value.0].value)(&mut draft));
+        } else if diet_label {
+            text.sections[0].value = localization.format("designer.diet", &[("diet", localization.get(&draft.diet.name_key()).to_string())]);
+        }
+    }
+    for (button, mut background) in buttons.iter_mut() {
+        if let DesignerButton::Habitat(biome) = button {
+            let alpha = if draft.habitats.contains(biome) { 0.9 } else { 0.3 };
+            *background = Color::srgba(0.3, 0.5, 0.3, alpha).into();
+        }
+    }
+    for (mut style, mut background) in swatches.iter_mut() {
+        let side = Val::Px(draft.size * 4.0);
+        (style.width, style.height) = (side, side);
+        *background = draft.get_color().into();
+    }
+}
+
+// Adds a species to the registry and saves it to DESIGNED_SPECIES_DIR, returning its index
+pub fn install_species(
+    species: Species,
+    registry: &mut SpeciesRegistry,
+    relationships: &mut RelationshipTable,
+    io: &mut IoQueue,
+) -> Result<usize, SimError> {
+    fs::create_dir_all(DESIGNED_SPECIES_DIR).map_err(|err| SimError::io(DESIGNED_SPECIES_DIR, err))?;
+    let path = format!("{}/{}.ron", DESIGNED_SPECIES_DIR, file_stem(&species.name));
+    io.write_ron(&path, species.clone(), format!("🧬 Saved species {} to {}", species.name, path), None);
+    relationships.add_species();
+    Ok(registry.add(species))
+}
+
+fn add_designed_species(
+    mut commands: Commands,
+    mut submitted: EventReader<PromptSubmitted>,
+    designer: Res<SpeciesDesigner>,
+    (mut registry, mut relationships): (ResMut<SpeciesRegistry>, ResMut<RelationshipTable>),
+    (world_map, reserves): (Res<WorldMap>, Res<Reserves>),
+    cameras: Query<&Transform, With<MainCamera>>,
+    (mut io, mut failures, mut streams): (ResMut<IoQueue>, EventWriter<SimFailure>, ResMut<RngStreams>),
+) {
+    for event in submitted.read() {
+        if event.kind != PromptKind::SpeciesName {
+            continue;
+        }
+        let mut species = designer.draft.clone();
+        species.name = event.text.clone();
+        if species.name.is_empty() {
+            continue;
+        }
+        if registry.find(&species.name).is_some() {
+            warn!("A species named \"{}\" already exists", species.name);
+            continue;
+        }
+        if species.habitats.is_empty() {
+            warn!("Species \"{}\" needs at least one habitat", species.name);
+            continue;
+        }
+        let (low, high) = species.comfort_temperature;
+        species.comfort_temperature = (low.min(high), low.max(high));
+
+        let name = species.name.clone();
+        let habitats = species.habitats.clone();
+        let species_id = match install_species(species, &mut registry, &mut relationships, &mut io) {
+            Ok(species_id) => species_id,
+            Err(err) => {
+                failures.send(SimFailure::new(err));
+                continue;
+            }
+        };
+
+        // Founders on habitat around the camera, outside reserves
+        let center = cameras.get_single().ok().and_then(|camera| world_to_tile(camera.translation.truncate()));
+        let habitat: Vec<(usize, usize)> = center.into_iter()
+            .flat_map(|(cx, cy)| {
+                let xs = cx.saturating_sub(FOUNDING_RADIUS)..(cx + FOUNDING_RADIUS + 1).min(WORLD_SIZE);
+                xs.flat_map(move |x| (cy.saturating_sub(FOUNDING_RADIUS)..(cy + FOUNDING_RADIUS + 1).min(WORLD_SIZE)).map(move |y| (x, y)))
+            })
+            .filter(|(x, y)| habitats.contains(&world_map.tiles[*x][*y].biome) && !reserves.contains(*x, *y))
+            .collect();
+        if habitat.is_empty() {
+            warn!("🧬 Added {} but found no habitat near the camera for its founders", name);
+            continue;
+        }
+        let rng = streams.get(RngStream::Creatures);
+        for _ in 0..FOUNDERS {
+            let Some((x, y)) = habitat.choose(rng) else { break };
+            let genome = Genome::founder(rng, founder_lineage(species_id, *x, *y));
+            let heading = rng.gen_range(0.0..std::f32::consts::TAU);
+            spawn_creature(&mut commands, &registry, species_id, tile_to_world(*x, *y), heading, genome);
+        }
+        info!("🧬 Added {} with {} founders", name, FOUNDERS);
+    }
+}
@@ -0,0 +1,239 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+use crate::camera::MainCameraQuery;
+use crate::coords::{tile_to_world, TILE_SIZE};
+use crate::keybindings::{Action, Controls};
+use crate::editor::{cursor_tile, editor_active, tile_state, EditCommand, Editor, EditorTool, TileState};
+use crate::errors::{SimError, SimFailure};
+use crate::optimization::ChunkManager;
+use crate::reserves::Reserves;
+use crate::text_input::text_input_inactive;
+use crate::world::{WorldMap, WORLD_SIZE};
+
+pub const STAMPS_DIR: &str = "stamps";
+const MAX_COPY_TILES: usize = 64 * 64; // Largest region that can be copied at once
+
+// Prefabs that ship with the game; stamps saved by the user live in STAMPS_DIR
+const BUILTIN_STAMPS: [&str; 3] = [
+    include_str!("../assets/stamps/oasis.ron"),
+    include_str!("../assets/stamps/volcano_cone.ron"),
+    include_str!("../assets/stamps/forest_grove.ron"),
+];
+
+pub struct StampsPlugin;
+
+impl Plugin for StampsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(StampLibrary::load())
+            .add_systems(Update, (
+                (stamp_hotkeys, save_selected_stamp).run_if(text_input_inactive),
+                copy_region,
+                place_stamp,
+                draw_stamp_outline,
+            ).chain().run_if(editor_active));
+    }
+}
+
+// === STAMPS ===
+// A stamp is a block of tile states relative to its centre. Copying a region with the editor
+// puts it on the clipboard stamp; placing a stamp is one undoable editor command.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct Stamp {
+    pub name: String,
+    pub tiles: Vec<((i32, i32), TileState)>,
+}
+
+impl Stamp {
+    // (min, max) offsets covered by the stamp
+    fn bounds(&self) -> ((i32, i32), (i32, i32)) {
+        self.tiles.iter().fold(((0, 0), (0, 0)), |((min_x, min_y), (max_x, max_y)), ((dx, dy), _)| {
+            ((min_x.min(*dx), min_y.min(*dy)), (max_x.max(*dx), max_y.max(*dy)))
+        })
+    }
+}
+
+#[derive(Resource)]
+pub struct StampLibrary {
+    pub stamps: Vec<Stamp>,
+    pub selected: usize,
+    clipboard: Option<Stamp>, // Shown first in the library once something has been copied
+    selection_start: Option<(usize, usize)>, // Corner of the region being copied
+}
+
+impl StampLibrary {
+    fn load() -> Self {
+        let mut stamps: Vec<Stamp> = BUILTIN_STAMPS.iter()
+            .filter_map(|source| ron::from_str(source).map_err(|err| error!("Invalid built-in stamp: {}", err)).ok())
+            .collect();
+        let saved = fs::read_dir(STAMPS_DIR).into_iter().flatten().flatten()
+            .filter(|entry| entry.path().extension().is_some_and(|extension| extension == "ron"));
+        for entry in saved {
+            match fs::read_to_string(entry.path()).map_err(|err| err.to_string())
+                .and_then(|contents| ron::from_str(&contents).map_err(|err| err.to_string())) {
+                Ok(stamp) => stamps.push(stamp),
+                Err(err) => warn!("Skipping stamp {}: {}", entry.path().display(), err),
+            }
+        }
+        Self { stamps, selected: 0, clipboard: None, selection_start: None }
+    }
+
+    // The clipboard followed by the library
+    fn all(&self) -> impl Iterator<Item = &Stamp> {
+        self.clipboard.iter().chain(self.stamps.iter())
+    }
+
+    pub fn selected(&self) -> Option<&Stamp> {
+        self.all().nth(self.selected)
+    }
+}
+
+// Q cycles stamps in the stamp tool; Ctrl+V jumps to the stamp tool with the clipboard selected
+fn stamp_hotkeys(controls: Controls, mut editor: ResMut<Editor>, mut library: ResMut<StampLibrary>) {
+    let count = library.all().count();
+    if controls.just_pressed(Action::NextChoice) && editor.tool == EditorTool::Stamp && count > 0 {
+        library.selected = (library.selected + 1) % count;
+    }
+    if controls.just_pressed(Action::PasteStamp) && library.clipboard.is_some() {
+        editor.tool = EditorTool::Stamp;
+        library.selected = 0;
+    }
+}
+
+// F8 writes the selected stamp to STAMPS_DIR so it is in the library next time
+fn save_selected_stamp(
+    controls: Controls,
+    mut library: ResMut<StampLibrary>,
+    mut failures: EventWriter<SimFailure>,
+) {
+    if !controls.just_pressed(Action::SaveStamp) {
+        return;
+    }
+    let Some(mut stamp) = library.selected().cloned() else { return };
+    let index = library.stamps.len();
+    if library.clipboard.is_some() && library.selected == 0 {
+        stamp.name = format!("Stamp {}", index + 1);
+    }
+    let path = PathBuf::from(STAMPS_DIR).join(format!("stamp_{}.ron", index + 1));
+    let result = ron::ser::to_string_pretty(&stamp, ron::ser::PrettyConfig::default())
+        .map_err(|err| SimError::io(path.display(), err))
+        .and_then(|contents| {
+            fs::create_dir_all(STAMPS_DIR).and_then(|_| fs::write(&path, contents)).map_err(|err| SimError::io(path.display(), err))
+        });
+    match result {
+        Ok(()) => {
+            info!("✏️ Saved stamp \"{}\" to {}", stamp.name, path.display());
+            library.stamps.push(stamp);
+        }
+        Err(err) => {
+            failures.send(SimFailure::new(err));
+        }
+    }
+}
+
+fn region(start: (usize, usize), end: (usize, usize)) -> ((usize, usize), (usize, usize)) {
+    ((start.0.min(end.0), start.1.min(end.1)), (start.0.max(end.0), start.1.max(end.1)))
+}
+
+// Drag a rectangle with the copy tool; on release it becomes the clipboard stamp
+fn copy_region(
+    mouse: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window>,
+    cameras: MainCameraQuery,
+    interactions: Query<&Interaction>,
+    editor: Res<Editor>,
+    world_map: Option<Res<WorldMap>>,
+    mut library: ResMut<StampLibrary>,
+) {
+    let Some(world_map) = world_map else { return };
+    if editor.tool != EditorTool::CopyRegion {
+        return;
+    }
+    if mouse.just_pressed(MouseButton::Left) && interactions.iter().all(|interaction| *interaction == Interaction::None) {
+        library.selection_start = cursor_tile(&windows, &cameras);
+    }
+    if !mouse.just_released(MouseButton::Left) {
+        return;
+    }
+    let Some(start) = library.selection_start.take() else { return };
+    let Some(end) = cursor_tile(&windows, &cameras) else { return };
+    let ((min_x, min_y), (max_x, max_y)) = region(start, end);
+    if (max_x - min_x + 1) * (max_y - min_y + 1) > MAX_COPY_TILES {
+        warn!("✏️ Region too large to copy (at most {} tiles)", MAX_COPY_TILES);
+        return;
+    }
+
+    let (center_x, center_y) = ((min_x + max_x) / 2, (min_y + max_y) / 2);
+    let tiles = (min_x..=max_x)
+        .flat_map(|x| (min_y..=max_y).map(move |y| (x, y)))
+        .map(|(x, y)| ((x as i32 - center_x as i32, y as i32 - center_y as i32), tile_state(&world_map, x, y)))
+        .collect::<Vec<_>>();
+    info!("✏️ Copied {} tiles", tiles.len());
+    library.clipboard = Some(Stamp { name: "Clipboard".to_string(), tiles });
+    library.selected = 0;
+}
+
+fn place_stamp(
+    mouse: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window>,
+    cameras: MainCameraQuery,
+    interactions: Query<&Interaction>,
+    mut editor: ResMut<Editor>,
+    world_map: Option<ResMut<WorldMap>>,
+    (library, mut chunk_manager, reserves): (Res<StampLibrary>, ResMut<ChunkManager>, Res<Reserves>),
+) {
+    let Some(mut world_map) = world_map else { return };
+    if editor.tool != EditorTool::Stamp || !mouse.just_pressed(MouseButton::Left) {
+        return;
+    }
+    if interactions.iter().any(|interaction| *interaction != Interaction::None) {
+        return;
+    }
+    let Some(stamp) = library.selected() else { return };
+    let Some((cx, cy)) = cursor_tile(&windows, &cameras) else { return };
+    let world_map = world_map.bypass_change_detection();
+
+    let mut command = EditCommand::default();
+    for ((dx, dy), state) in &stamp.tiles {
+        let (x, y) = (cx as i32 + dx, cy as i32 + dy);
+        if x < 0 || y < 0 || x >= WORLD_SIZE as i32 || y >= WORLD_SIZE as i32 {
+            continue;
+        }
+        let (x, y) = (x as usize, y as usize);
+        if reserves.contains(x, y) {
+            continue;
+        }
+        command.tiles.insert((x, y), (tile_state(world_map, x, y), state.clone()));
+    }
+    info!("✏️ Placed stamp \"{}\" at ({}, {})", stamp.name, cx, cy);
+    editor.apply(world_map, &mut chunk_manager, command);
+}
+
+// Selection rectangle while copying, stamp footprint under the cursor while stamping
+fn draw_stamp_outline(
+    mut gizmos: Gizmos,
+    editor: Res<Editor>,
+    library: Res<StampLibrary>,
+    windows: Query<&Window>,
+    cameras: MainCameraQuery,
+) {
+    let Some(cursor) = cursor_tile(&windows, &cameras) else { return };
+    let ((min_x, min_y), (max_x, max_y)) = match (editor.tool, library.selection_start, library.selected()) {
+        (EditorTool::CopyRegion, Some(start), _) => {
+            let ((min_x, min_y), (max_x, max_y)) = region(start, cursor);
+            ((min_x as i32, min_y as i32), (max_x as i32, max_y as i32))
+        }
+        (EditorTool::Stamp, _, Some(stamp)) => {
+            let ((min_dx, min_dy), (max_dx, max_dy)) = stamp.bounds();
+            let (cx, cy) = (cursor.0 as i32, cursor.1 as i32);
+            ((cx + min_dx, cy + min_dy), (cx + max_dx, cy + max_dy))
+        }
+        _ => return,
+    };
+    let clamp = |value: i32| value.clamp(0, WORLD_SIZE as i32 - 1) as usize;
+    let corner_min = tile_to_world(clamp(min_x), clamp(min_y)) - Vec2::splat(TILE_SIZE / 2.0);
+    let corner_max = tile_to_world(clamp(max_x), clamp(max_y)) + Vec2::splat(TILE_SIZE / 2.0);
+    gizmos.rect_2d((corner_min + corner_max) / 2.0, 0.0, corner_max - corner_min, Color::srgb(1.0, 1.0, 1.0));
+}
@@ -0,0 +1,217 @@
+use bevy::ecs::entity::Entities;
+use bevy::prelude::*;
+use rand::seq::SliceRandom;
+use rand::Rng;
+use serde::Serialize;
+use std::fs;
+use crate::coords::{chunk_tile_bounds, tile_to_world, CHUNK_SIZE};
+use crate::biome::BiomeType;
+use crate::camera::CameraPan;
+use crate::creature::{spawn_creature, Creature};
+use crate::genetics::{founder_lineage, Genome};
+use crate::population_pools::chunk_center;
+use crate::settings::Settings;
+use crate::species::SpeciesRegistry;
+use crate::tides::Tides;
+use crate::world::{WorldMap, WORLD_SIZE};
+use crate::rng_streams::{RngStream, RngStreams};
+
+pub const STRESS_REPORT_PATH: &str = "stress_report.json";
+pub const DEFAULT_STRESS_SECONDS: f32 = 20.0;
+const STRESS_CREATURES: usize = 10_000; // Total creatures alive during the run
+const SPAWN_RADIUS_CHUNKS: i32 = 2; // Creatures are packed into the chunks around the densest one
+const WARMUP_SECONDS: f32 = 3.0; // Not recorded: camera pan, chunk loading, first AI ticks
+const FRAME_BUDGET_MS: f64 = 33.3; // 95th percentile frame time above this fails the run
+const ENTITY_BUDGET: usize = 250_000; // Peak entity count above this fails the run
+
+pub struct StressTestPlugin;
+
+impl Plugin for StressTestPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<StressTest>()
+            .add_systems(Update, (
+                start_stress_test.run_if(resource_exists::<WorldMap>),
+                record_stress_test,
+            ).chain());
+    }
+}
+
+// === STRESS TEST ===
+// Development guard against performance regressions, started from the console with "stress [seconds]".
+// Packs STRESS_CREATURES creatures into the densest rainforest on the map (the most environment
+// sprites per chunk), lifts the creature caps so none are pooled, records frame times and writes
+// percentiles with a pass/fail verdict to STRESS_REPORT_PATH.
+#[derive(Resource, Default)]
+pub struct StressTest {
+    pub requested: Option<f32>, // Duration of a run the console asked for
+    run: Option<StressRun>,
+}
+
+struct StressRun {
+    duration: f32,
+    elapsed: f32,
+    frame_times_ms: Vec<f64>,
+    peak_entities: usize,
+    center: Vec2,
+    spawned: Vec<Entity>,
+    saved_caps: (usize, usize, f32),
+}
+
+#[derive(Serialize)]
+pub struct StressReport {
+    pub seed: u32,
+    pub center: (f32, f32),
+    pub creatures: usize,
+    pub frames: usize,
+    pub p50_ms: f64,
+    pub p95_ms: f64,
+    pub p99_ms: f64,
+    pub max_ms: f64,
+    pub peak_entities: usize,
+    pub frame_budget_ms: f64,
+    pub entity_budget: usize,
+    pub passed: bool,
+}
+
+// The chunk with the most rainforest tiles, falling back to any forest
+fn densest_chunk(world_map: &WorldMap) -> (i32, i32) {
+    let chunks_per_side = WORLD_SIZE.div_ceil(CHUNK_SIZE) as i32;
+    let density = |chunk: &(i32, i32)| {
+        let (start_x, start_y, end_x, end_y) = chunk_tile_bounds(chunk.0, chunk.1);
+        (start_x..end_x).flat_map(|x| (start_y..end_y).map(move |y| (x, y)))
+            .map(|(x, y)| match world_map.tiles[x][y].biome {
+                BiomeType::TropicalRainforest => 2,
+                BiomeType::Forest => 1,
+                _ => 0,
+            })
+            .sum::<usize>()
+    };
+    (0..chunks_per_side).flat_map(|x| (0..chunks_per_side).map(move |y| (x, y)))
+        .max_by_key(density)
+        .unwrap_or((chunks_per_side / 2, chunks_per_side / 2))
+}
+
+pub fn percentile(sorted: &[f64], fraction: f64) -> f64 {
+    let index = ((sorted.len() as f64 - 1.0) * fraction).round() as usize;
+    sorted.get(index).copied().unwrap_or(0.0)
+}
+
+fn start_stress_test(
+    mut commands: Commands,
+    mut stress: ResMut<StressTest>,
+    (world_map, tides, registry): (Res<WorldMap>, Res<Tides>, Res<SpeciesRegistry>),
+    mut settings: ResMut<Settings>,
+    mut pan: ResMut<CameraPan>,
+    creatures: Query<(), With<Creature>>,
+    mut streams: ResMut<RngStreams>,
+) {
+    let Some(duration) = stress.requested.take() else { return };
+    if stress.run.is_some() {
+        warn!("🔥 A stress test is already running");
+        return;
+    }
+
+    let chunk = densest_chunk(&world_map);
+    let land: Vec<(usize, usize)> = (-SPAWN_RADIUS_CHUNKS..=SPAWN_RADIUS_CHUNKS)
+        .flat_map(|dx| (-SPAWN_RADIUS_CHUNKS..=SPAWN_RADIUS_CHUNKS).map(move |dy| (chunk.0 + dx, chunk.1 + dy)))
+        .flat_map(|(cx, cy)| {
+            let (start_x, start_y, end_x, end_y) = chunk_tile_bounds(cx, cy);
+            (start_x..end_x).flat_map(move |x| (start_y..end_y).map(move |y| (x, y)))
+        })
+        .filter(|(x, y)| !tides.is_underwater(&world_map.tiles[*x][*y]))
+        .collect();
+    if land.is_empty() || registry.species.is_empty() {
+        warn!("🔥 Stress test: no dry land to spawn creatures on");
+        return;
+    }
+
+    // Nothing may be pooled while measuring, wherever the camera is
+    let saved_caps = (settings.creature_cap, settings.chunk_creature_cap, settings.statistical_distance);
+    settings.creature_cap = usize::MAX;
+    settings.chunk_creature_cap = usize::MAX;
+    settings.statistical_distance = f32::MAX;
+
+    let rng = streams.get(RngStream::Creatures);
+    let species_ids: Vec<usize> = (0..registry.species.len()).collect();
+    let spawned = (creatures.iter().len()..STRESS_CREATURES).filter_map(|_| {
+        let (x, y) = *land.choose(rng)?;
+        let species = *species_ids.choose(rng)?;
+        let genome = Genome::founder(rng, founder_lineage(species, x, y));
+        let heading = rng.gen_range(0.0..std::f32::consts::TAU);
+        Some(spawn_creature(&mut commands, &registry, species, tile_to_world(x, y), heading, genome))
+    }).collect::<Vec<_>>();
+
+    let center = chunk_center(chunk);
+    pan.go_to(center);
+    info!("🔥 Stress test: {} extra creatures around {:?} for {}s", spawned.len(), center, duration);
+    stress.run = Some(StressRun {
+        duration,
+        elapsed: 0.0,
+        frame_times_ms: Vec::new(),
+        peak_entities: 0,
+        center,
+        spawned,
+        saved_caps,
+    });
+}
+
+fn record_stress_test(
+    mut commands: Commands,
+    time: Res<Time<Real>>,
+    entities: &Entities,
+    mut stress: ResMut<StressTest>,
+    mut settings: ResMut<Settings>,
+    world_map: Option<Res<WorldMap>>,
+    creatures: Query<(), With<Creature>>,
+) {
+    let Some(run) = stress.run.as_mut() else { return };
+    run.elapsed += time.delta_seconds();
+    if run.elapsed < WARMUP_SECONDS {
+        return;
+    }
+    run.frame_times_ms.push(time.delta_seconds_f64() * 1000.0);
+    run.peak_entities = run.peak_entities.max(entities.len() as usize);
+    if run.elapsed < WARMUP_SECONDS + run.duration {
+        return;
+    }
+
+    let Some(run) = stress.run.take() else { return };
+    let mut sorted = run.frame_times_ms;
+    sorted.sort_by(f64::total_cmp);
+    let p95_ms = percentile(&sorted, 0.95);
+    let report = StressReport {
+        seed: world_map.map_or(0, |world_map| world_map.seed),
+        center: (run.center.x, run.center.y),
+        creatures: creatures.iter().len(),
+        frames: sorted.len(),
+        p50_ms: percentile(&sorted, 0.5),
+        p95_ms,
+        p99_ms: percentile(&sorted, 0.99),
+        max_ms: sorted.last().copied().unwrap_or(0.0),
+        peak_entities: run.peak_entities,
+        frame_budget_ms: FRAME_BUDGET_MS,
+        entity_budget: ENTITY_BUDGET,
+        passed: p95_ms <= FRAME_BUDGET_MS && run.peak_entities <= ENTITY_BUDGET,
+    };
+
+    if report.passed {
+        info!("🔥 Stress test passed: p95 {:.1} ms, {} entities at peak", report.p95_ms, report.peak_entities);
+    } else {
+        warn!("🔥 Stress test FAILED: p95 {:.1} ms (budget {:.1}), {} entities at peak (budget {})",
+              report.p95_ms, FRAME_BUDGET_MS, report.peak_entities, ENTITY_BUDGET);
+    }
+    match serde_json::to_string_pretty(&report).map_err(|err| err.to_string())
+        .and_then(|json| fs::write(STRESS_REPORT_PATH, json).map_err(|err| err.to_string())) {
+        Ok(()) => info!("🔥 Stress report written to {}", STRESS_REPORT_PATH),
+        Err(err) => error!("Failed to write {}: {}", STRESS_REPORT_PATH, err),
+    }
+
+    // Put the world back the way it was
+    (settings.creature_cap, settings.chunk_creature_cap, settings.statistical_distance) = run.saved_caps;
+    for entity in run.spawned {
+        if creatures.contains(entity) {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use std::f32::consts::TAU;
+use crate::camera::MainCamera;
+use crate::coords::{tile_to_world, world_to_tile, wrap_position, TILE_SIZE};
+use crate::biome::BiomeType;
+use crate::reserves::Reserves;
+use crate::settings::Settings;
+use crate::tides::Tides;
+use crate::vegetation::{FlowerState, Vegetation};
+use crate::world::{WorldMap, WORLD_SIZE};
+use crate::rng_streams::{RngStream, RngStreams};
+
+const SWARM_Z: f32 = 3.0; // Above creatures
+const SWARM_DRAW_DISTANCE: f32 = 400.0; // World units from the camera
+const MAX_SWARM_SIZE: u32 = 20_000;
+const INSECTS_PER_PARTICLE: u32 = 50; // Rendering: one particle per this many insects
+const MAX_PARTICLES: u32 = 80;
+const LOCUST_APPETITE: f32 = 0.0002; // Plant cover stripped per insect per second, per tile
+
+pub struct SwarmPlugin;
+
+impl Plugin for SwarmPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, (
+                spawn_initial_swarms.run_if(resource_added::<WorldMap>),
+                draw_swarms,
+            ))
+            .add_systems(FixedUpdate, (move_swarms, feed_swarms).chain()
+                .run_if(resource_exists::<WorldMap>.and_then(resource_exists::<Vegetation>)));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwarmKind {
+    Bees, // Pollinate flowers under the swarm and grow where flowers are plentiful
+    Locusts, // Strip plant cover under the swarm and grow while there is food
+}
+
+impl SwarmKind {
+    fn color(&self) -> Color {
+        match self {
+            SwarmKind::Bees => Color::srgb(0.95, 0.8, 0.2),
+            SwarmKind::Locusts => Color::srgb(0.5, 0.55, 0.2),
+        }
+    }
+
+    fn speed(&self) -> f32 {
+        match self {
+            SwarmKind::Bees => 12.0,
+            SwarmKind::Locusts => 20.0,
+        }
+    }
+
+    fn habitats(&self) -> &'static [BiomeType] {
+        match self {
+            SwarmKind::Bees => &[BiomeType::Grasslands, BiomeType::TropicalRainforest],
+            SwarmKind::Locusts => &[BiomeType::Savanna, BiomeType::Grasslands, BiomeType::Desert],
+        }
+    }
+
+    fn initial_swarms(&self) -> usize {
+        match self {
+            SwarmKind::Bees => 12,
+            SwarmKind::Locusts => 3,
+        }
+    }
+}
+
+// === SWARMS ===
+// A whole swarm is one entity: a count, a centre and a radius, never individual insects.
+#[derive(Component)]
+pub struct Swarm {
+    pub kind: SwarmKind,
+    pub count: u32,
+    pub heading: f32,
+}
+
+impl Swarm {
+    // Denser swarms cover more ground
+    pub fn radius(&self) -> f32 {
+        2.0 + (self.count as f32).sqrt() * 0.1
+    }
+}
+
+fn spawn_initial_swarms(mut commands: Commands, world_map: Res<WorldMap>) {
+    let mut rng = StdRng::seed_from_u64(world_map.seed as u64 ^ 0x53_5741_524d);
+
+    for kind in [SwarmKind::Bees, SwarmKind::Locusts] {
+        for _ in 0..kind.initial_swarms() {
+            let tile = (0..100).find_map(|_| {
+                let (x, y) = (rng.gen_range(0..WORLD_SIZE), rng.gen_range(0..WORLD_SIZE));
+                kind.habitats().contains(&world_map.tiles[x][y].biome).then_some((x, y))
+            });
+            let Some((x, y)) = tile else { continue };
+
+            commands.spawn((
+                SpatialBundle::from_transform(Transform::from_translation(tile_to_world(x, y).extend(SWARM_Z))),
+                Swarm { kind, count: rng.gen_range(500..3000), heading: rng.gen_range(0.0..TAU) },
+            ));
+        }
+    }
+}
+
+fn move_swarms(
+    time: Res<Time>,
+    world_map: Res<WorldMap>,
+    tides: Res<Tides>,
+    mut swarms: Query<(&mut Transform, &mut Swarm)>,
+    mut streams: ResMut<RngStreams>,
+) {
+    let dt = time.delta_seconds();
+    let rng = streams.get(RngStream::Creatures);
+
+    for (mut transform, mut swarm) in swarms.iter_mut() {
+        swarm.heading += rng.gen_range(-1.0..1.0) * dt;
+        let next = wrap_position(
+            transform.translation.truncate() + Vec2::from_angle(swarm.heading) * swarm.kind.speed() * dt,
+            world_map.params.topology,
+        );
+        match world_to_tile(next) {
+            Some((x, y)) if !tides.is_underwater(&world_map.tiles[x][y]) => {
+                transform.translation.x = next.x;
+                transform.translation.y = next.y;
+            }
+            _ => swarm.heading += TAU / 2.0, // Insect swarms do not cross open water
+        }
+    }
+}
+
+// Applies each swarm to the tiles under it and grows or shrinks it with the food it finds
+fn feed_swarms(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut vegetation: ResMut<Vegetation>,
+    reserves: Res<Reserves>,
+    mut swarms: Query<(Entity, &Transform, &mut Swarm)>,
+) {
+    let dt = time.delta_seconds();
+
+    for (entity, transform, mut swarm) in swarms.iter_mut() {
+        let Some((cx, cy)) = world_to_tile(transform.translation.truncate()) else { continue };
+        let reach = (swarm.radius() / TILE_SIZE).ceil() as usize;
+        let tiles = (cx.saturating_sub(reach)..=(cx + reach).min(WORLD_SIZE - 1))
+            .flat_map(|x| (cy.saturating_sub(reach)..=(cy + reach).min(WORLD_SIZE - 1)).map(move |y| (x, y)));
+
+        let mut food = 0.0;
+        match swarm.kind {
+            SwarmKind::Bees => {
+                for (x, y) in tiles {
+                    let flower = &mut vegetation.flowers[x * WORLD_SIZE + y];
+                    if *flower != FlowerState::None {
+                        *flower = FlowerState::Pollinated;
+                        food += 1.0;
+                    }
+                }
+            }
+            SwarmKind::Locusts => {
+                let appetite = swarm.count as f32 * LOCUST_APPETITE * dt;
+                // Plagues stop at reserve borders
+                for (x, y) in tiles.filter(|(x, y)| !reserves.contains(*x, *y)) {
+                    food += vegetation.strip(x, y, appetite);
+                }
+                food /= appetite.max(f32::EPSILON); // Tiles' worth of full meals
+            }
+        }
+
+        // Grow by a few percent per second with food, shrink without
+        let growth = if food > 0.0 { 0.02 } else { -0.05 };
+        let change = (swarm.count as f32 * growth * dt).round() as i64;
+        let change = if change == 0 { growth.signum() as i64 } else { change };
+        swarm.count = (swarm.count as i64 + change).clamp(0, MAX_SWARM_SIZE as i64) as u32;
+
+        if swarm.count == 0 {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+// Particle cloud drawn with gizmos; particles orbit the centre unless reduced motion is on
+fn draw_swarms(
+    mut gizmos: Gizmos,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    cameras: Query<&Transform, With<MainCamera>>,
+    swarms: Query<(Entity, &Transform, &Swarm)>,
+) {
+    let Ok(camera_transform) = cameras.get_single() else { return };
+    let t = if settings.reduced_motion { 0.0 } else { time.elapsed_seconds() };
+
+    for (entity, transform, swarm) in swarms.iter() {
+        let centre = transform.translation.truncate();
+        if centre.distance(camera_transform.translation.truncate()) > SWARM_DRAW_DISTANCE {
+            continue;
+        }
+        let particles = (swarm.count / INSECTS_PER_PARTICLE).clamp(4, MAX_PARTICLES);
+        let radius = swarm.radius();
+        // Stable per-swarm pseudo-random layout so particles don't flicker between frames
+        let mut rng = StdRng::seed_from_u64(entity.to_bits());
+        for _ in 0..particles {
+            let orbit = rng.gen_range(0.2..1.0) * radius;
+            let angle = rng.gen_range(0.0..TAU) + t * rng.gen_range(-2.0..2.0);
+            gizmos.circle_2d(centre + Vec2::from_angle(angle) * orbit, 0.3, swarm.kind.color());
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::camera::MainCameraQuery;
+use crate::creature::Creature;
+use crate::creature_tracking::{Bookmarked, SelectedCreature};
+use crate::editor::editor_active;
+use crate::keybindings::{Action, Controls};
+use crate::save::SaveComponentExt;
+use crate::text_input::text_input_inactive;
+
+const LEAD_COLOR: Color = Color::srgb(0.4, 0.9, 1.0);
+
+pub struct TamingPlugin;
+
+impl Plugin for TamingPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .save_component::<Tamed>("tamed")
+            .add_systems(Update, (
+                toggle_tamed.run_if(text_input_inactive),
+                place_lead_marker.run_if(not(editor_active)),
+                draw_lead_markers,
+            ).chain());
+    }
+}
+
+// === TAMING ===
+// A sandbox tool for demos and for watching one behavior at a time. K tames the selected creature:
+// it no longer flees from hunters, and right-clicking sets a marker it walks to and waits at
+// instead of looking for food or water (see move_creatures). Taming also pins the creature, so
+// its needs stay on the tracked creatures panel.
+#[derive(Component, Default, Serialize, Deserialize)]
+pub struct Tamed {
+    #[serde(skip)]
+    pub lead: Option<Vec2>, // Not saved: a loaded tamed creature waits for a new marker
+}
+
+impl Tamed {
+    // Where a tamed creature is being led, if anywhere
+    pub fn lead(tamed: Option<&Tamed>) -> Option<Vec2> {
+        tamed.and_then(|tamed| tamed.lead)
+    }
+}
+
+fn toggle_tamed(
+    mut commands: Commands,
+    controls: Controls,
+    selected: Res<SelectedCreature>,
+    creatures: Query<Has<Tamed>, With<Creature>>,
+) {
+    if !controls.just_pressed(Action::TameCreature) {
+        return;
+    }
+    let Some(entity) = selected.0 else { return };
+    match creatures.get(entity) {
+        Ok(true) => {
+            commands.entity(entity).remove::<Tamed>();
+            info!("🦮 Creature #{} released", entity.index());
+        }
+        Ok(false) => {
+            commands.entity(entity).insert((Tamed::default(), Bookmarked));
+            info!("🦮 Creature #{} tamed", entity.index());
+        }
+        Err(_) => {}
+    }
+}
+
+// Right click leads the selected tamed creature to the cursor
+fn place_lead_marker(
+    mouse: Res<ButtonInput<MouseButton>>,
+    (windows, cameras): (Query<&Window>, MainCameraQuery),
+    interactions: Query<&Interaction>,
+    selected: Res<SelectedCreature>,
+    mut tamed: Query<&mut Tamed>,
+) {
+    if !mouse.just_pressed(MouseButton::Right) || interactions.iter().any(|interaction| *interaction != Interaction::None) {
+        return;
+    }
+    let Some(mut tamed) = selected.0.and_then(|entity| tamed.get_mut(entity).ok()) else { return };
+    let Ok(window) = windows.get_single() else { return };
+    let Ok((camera, camera_transform)) = cameras.get_single() else { return };
+    tamed.lead = window.cursor_position()
+        .and_then(|cursor| camera.viewport_to_world_2d(camera_transform, cursor));
+}
+
+fn draw_lead_markers(mut gizmos: Gizmos, tamed: Query<(&Transform, &Tamed)>) {
+    for (transform, tamed) in tamed.iter() {
+        gizmos.circle_2d(transform.translation.truncate(), 4.0, LEAD_COLOR);
+        let Some(lead) = tamed.lead else { continue };
+        gizmos.line_2d(transform.translation.truncate(), lead, LEAD_COLOR.with_alpha(0.4));
+        gizmos.line_2d(lead + Vec2::new(-3.0, -3.0), lead + Vec2::new(3.0, 3.0), LEAD_COLOR);
+        gizmos.line_2d(lead + Vec2::new(-3.0, 3.0), lead + Vec2::new(3.0, -3.0), LEAD_COLOR);
+    }
+}
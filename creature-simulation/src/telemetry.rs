@@ -0,0 +1,80 @@
+use bevy::prelude::*;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const TELEMETRY_PATH: &str = "telemetry.jsonl";
+
+pub struct TelemetryPlugin;
+
+impl Plugin for TelemetryPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Telemetry>();
+    }
+}
+
+// === TELEMETRY LOG ===
+// Append-only JSON lines file: one record per line with a kind tag and a unix timestamp.
+#[derive(Resource)]
+pub struct Telemetry {
+    file: Option<File>,
+}
+
+impl Default for Telemetry {
+    fn default() -> Self {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(TELEMETRY_PATH)
+            .map_err(|err| warn!("Telemetry disabled, could not open {}: {}", TELEMETRY_PATH, err))
+            .ok();
+        Self { file }
+    }
+}
+
+impl Telemetry {
+    pub fn record<T: Serialize>(&mut self, kind: &str, data: &T) {
+        let Some(file) = self.file.as_mut() else { return };
+
+        let unix_time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs_f64())
+            .unwrap_or(0.0);
+        let line = serde_json::json!({
+            "kind": kind,
+            "unix_time": unix_time,
+            "data": data,
+        });
+
+        if let Err(err) = writeln!(file, "{}", line) {
+            warn!("Failed to write telemetry record '{}': {}", kind, err);
+        }
+    }
+}
+
+// === RING BUFFERS ===
+// Fixed-capacity in-memory history for graphs; the oldest sample is dropped when full.
+pub struct RingBuffer<T> {
+    samples: VecDeque<T>,
+    capacity: usize,
+}
+
+impl<T> RingBuffer<T> {
+    pub fn new(capacity: usize) -> Self {
+        Self { samples: VecDeque::with_capacity(capacity), capacity }
+    }
+
+    pub fn push(&mut self, sample: T) {
+        if self.samples.len() == self.capacity {
+            self.samples.pop_front();
+        }
+        self.samples.push_back(sample);
+    }
+
+    // Oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.samples.iter()
+    }
+}
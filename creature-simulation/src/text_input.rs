@@ -0,0 +1,139 @@
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::{ButtonState, InputSystem};
+use bevy::prelude::*;
+use crate::localization::Localization;
+
+const MAX_INPUT_LENGTH: usize = 48;
+
+pub struct TextInputPlugin;
+
+impl Plugin for TextInputPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<TextPrompt>()
+            .add_event::<PromptSubmitted>()
+            .add_systems(Startup, spawn_prompt_display)
+            // Before Update, so the hotkey that opens a prompt is not also typed into it
+            .add_systems(PreUpdate, edit_text_prompt.after(InputSystem))
+            .add_systems(Update, update_prompt_display.run_if(resource_changed::<TextPrompt>));
+    }
+}
+
+// What a submitted prompt is for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PromptKind {
+    CreatureName(Entity),
+    GoToLocation,
+    Console,
+    SpeciesName,
+    WorldSeed, // On the loading screen, after cancelling generation
+}
+
+impl PromptKind {
+    fn label_key(&self) -> &'static str {
+        match self {
+            PromptKind::CreatureName(_) => "prompt.creature_name",
+            PromptKind::GoToLocation => "prompt.go_to",
+            PromptKind::Console => "prompt.console",
+            PromptKind::SpeciesName => "prompt.species_name",
+            PromptKind::WorldSeed => "prompt.world_seed",
+        }
+    }
+}
+
+// === TEXT PROMPT ===
+// A single line of keyboard text entry shared by every feature that needs one.
+// While a prompt is open, letter hotkeys are suspended (see `text_input_inactive`).
+#[derive(Resource, Default)]
+pub struct TextPrompt {
+    pub kind: Option<PromptKind>,
+    pub buffer: String,
+}
+
+impl TextPrompt {
+    pub fn open(&mut self, kind: PromptKind, initial: String) {
+        self.kind = Some(kind);
+        self.buffer = initial;
+    }
+}
+
+// Sent when the user confirms a prompt with Enter; Escape cancels without an event
+#[derive(Event)]
+pub struct PromptSubmitted {
+    pub kind: PromptKind,
+    pub text: String,
+}
+
+#[derive(Component)]
+pub struct PromptDisplay;
+
+// Run condition for keyboard shortcuts that would otherwise fire while typing
+pub fn text_input_inactive(prompt: Res<TextPrompt>) -> bool {
+    prompt.kind.is_none()
+}
+
+fn edit_text_prompt(
+    mut key_events: EventReader<KeyboardInput>,
+    mut prompt: ResMut<TextPrompt>,
+    mut submitted: EventWriter<PromptSubmitted>,
+) {
+    for event in key_events.read() {
+        let Some(kind) = prompt.kind else { continue };
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Enter => {
+                submitted.send(PromptSubmitted { kind, text: prompt.buffer.trim().to_string() });
+                prompt.kind = None;
+            }
+            Key::Escape => prompt.kind = None,
+            Key::Backspace => { prompt.buffer.pop(); }
+            Key::Space if prompt.buffer.len() < MAX_INPUT_LENGTH => prompt.buffer.push(' '),
+            Key::Character(text) if prompt.buffer.len() < MAX_INPUT_LENGTH => {
+                prompt.buffer.extend(text.chars().filter(|c| !c.is_control()));
+            }
+            _ => {}
+        }
+    }
+}
+
+fn spawn_prompt_display(mut commands: Commands) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 18.0,
+                color: Color::srgb(1.0, 0.9, 0.4),
+                ..default()
+            },
+        ).with_style(Style {
+            position_type: PositionType::Absolute,
+            bottom: Val::Px(20.0),
+            left: Val::Px(20.0),
+            padding: UiRect::all(Val::Px(8.0)),
+            ..default()
+        }).with_background_color(Color::srgba(0.05, 0.05, 0.1, 0.9)),
+        // Over every screen, as the loading screen asks for a seed
+        ZIndex::Global(110),
+        Visibility::Hidden,
+        PromptDisplay,
+    ));
+}
+
+fn update_prompt_display(
+    prompt: Res<TextPrompt>,
+    localization: Res<Localization>,
+    mut displays: Query<(&mut Text, &mut Visibility), With<PromptDisplay>>,
+) {
+    for (mut text, mut visibility) in displays.iter_mut() {
+        match prompt.kind {
+            Some(kind) => {
+                text.sections[0].value = localization.format(kind.label_key(), &[("text", prompt.buffer.clone())]);
+                *visibility = Visibility::Visible;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+}
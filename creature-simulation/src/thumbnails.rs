@@ -0,0 +1,207 @@
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use crate::chunk_cache::fnv1a;
+use crate::world::{GenerationParams, WorldGenerator, WORLD_SIZE};
+use crate::generation_service::{GenerationId, GenerationJob, GenerationOutput, GenerationPriority, GenerationService};
+use crate::world_registry::WorldRegistry;
+
+pub const THUMBNAIL_SIZE: usize = 64; // Pixels per side
+pub const THUMBNAIL_DIR: &str = "thumbnails";
+const PREVIEW_SCALE: f32 = 1.5; // On-screen size relative to the thumbnail
+
+pub struct ThumbnailsPlugin;
+
+impl Plugin for ThumbnailsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Thumbnails>()
+            .add_systems(Update, (
+                collect_thumbnails,
+                update_preview_strip.run_if(resource_changed::<Thumbnails>),
+                generate_previewed_world,
+            ).chain());
+    }
+}
+
+// === THUMBNAIL SERVICE ===
+// Biome-colour previews of whole worlds, sampled straight from the noise so a seed costs
+// 64x64 tiles instead of a full generation. Every seed is its own background job of the
+// generation service, and finished thumbnails are cached in THUMBNAIL_DIR as binary PPM files,
+// named by the seed and a hash of the generation parameters they were rendered with.
+#[derive(Resource, Default)]
+pub struct Thumbnails {
+    pub requested: Vec<u32>, // Seeds in the preview strip, in request order
+    pub params: GenerationParams, // What the strip's thumbnails were rendered with
+    pub images: HashMap<u32, Handle<Image>>,
+    generating: Vec<(u32, GenerationId)>,
+}
+
+#[derive(Component)]
+pub struct PreviewStrip;
+
+#[derive(Component)]
+pub struct PreviewEntry(pub u32);
+
+// Replaces the preview strip with the given seeds rendered with `params`, dropping thumbnails of
+// the old one still being made; an empty batch closes it
+pub fn request_thumbnails(service: &mut GenerationService, thumbnails: &mut Thumbnails, seeds: &[u32], params: GenerationParams) {
+    if thumbnails.params != params {
+        thumbnails.params = params;
+        thumbnails.images.clear();
+        for (_, id) in thumbnails.generating.drain(..) {
+            service.discard(id);
+        }
+    }
+    thumbnails.generating.retain(|(seed, id)| {
+        let wanted = seeds.contains(seed);
+        if !wanted {
+            service.discard(*id);
+        }
+        wanted
+    });
+    for &seed in seeds {
+        if thumbnails.images.contains_key(&seed) || thumbnails.generating.iter().any(|(generating, _)| *generating == seed) {
+            continue;
+        }
+        let id = service.request(GenerationJob::Thumbnail { seed, params }, GenerationPriority::Background);
+        thumbnails.generating.push((seed, id));
+    }
+    thumbnails.requested = seeds.to_vec();
+}
+
+fn cache_path(seed: u32, params: &GenerationParams) -> PathBuf {
+    let params = fnv1a(ron::to_string(params).unwrap_or_default().as_bytes());
+    PathBuf::from(THUMBNAIL_DIR).join(format!("{}-{:016x}.ppm", seed, params))
+}
+
+fn ppm_header() -> String {
+    format!("P6\n{} {}\n255\n", THUMBNAIL_SIZE, THUMBNAIL_SIZE)
+}
+
+pub fn load_or_render(generator: &WorldGenerator) -> Vec<u8> {
+    let path = cache_path(generator.seed(), generator.params());
+    let cached = fs::read(&path).ok().and_then(|bytes| {
+        bytes.strip_prefix(ppm_header().as_bytes())
+            .filter(|pixels| pixels.len() == THUMBNAIL_SIZE * THUMBNAIL_SIZE * 3)
+            .map(<[u8]>::to_vec)
+    });
+    if let Some(pixels) = cached {
+        return pixels;
+    }
+
+    let pixels = biome_pixels(generator, THUMBNAIL_SIZE);
+    let mut file = ppm_header().into_bytes();
+    file.extend_from_slice(&pixels);
+    if let Err(err) = fs::create_dir_all(THUMBNAIL_DIR).and_then(|_| fs::write(&path, file)) {
+        warn!("Could not cache thumbnail {}: {}", path.display(), err);
+    }
+    pixels
+}
+
+// RGB rows of biome colours covering the whole world at `size` x `size` pixels
+pub fn biome_pixels(generator: &WorldGenerator, size: usize) -> Vec<u8> {
+    let step = WORLD_SIZE / size;
+    // Image rows run top to bottom while world y grows upwards
+    let tiles: Vec<_> = (0..size).rev()
+        .flat_map(|row| (0..size).map(move |column| (column * step + step / 2, row * step + step / 2)))
+        .collect();
+    generator.biomes_at(&tiles).into_iter()
+        .flat_map(|biome| {
+            let color = biome.get_color().to_srgba();
+            [color.red, color.green, color.blue].map(|channel| (channel * 255.0) as u8)
+        })
+        .collect()
+}
+
+fn collect_thumbnails(
+    mut service: ResMut<GenerationService>,
+    mut images: ResMut<Assets<Image>>,
+    mut thumbnails: ResMut<Thumbnails>,
+) {
+    let finished: Vec<_> = thumbnails.generating.iter()
+        .filter_map(|(seed, id)| service.take(*id).map(|result| (*seed, *id, result)))
+        .collect();
+    for (seed, id, result) in finished {
+        thumbnails.generating.retain(|(_, generating)| *generating != id);
+        let Ok(GenerationOutput::Pixels(pixels)) = result else { continue };
+        let rgba = pixels.chunks(3).flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 255]).collect();
+        let image = Image::new(
+            Extent3d { width: THUMBNAIL_SIZE as u32, height: THUMBNAIL_SIZE as u32, depth_or_array_layers: 1 },
+            TextureDimension::D2,
+            rgba,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::RENDER_WORLD,
+        );
+        thumbnails.images.insert(seed, images.add(image));
+    }
+}
+
+// === PREVIEW STRIP ===
+// Finished thumbnails along the bottom of the screen; clicking one generates that seed, with the
+// same parameters, as a scratch world for comparison.
+fn update_preview_strip(
+    mut commands: Commands,
+    thumbnails: Res<Thumbnails>,
+    strips: Query<Entity, With<PreviewStrip>>,
+) {
+    for strip in strips.iter() {
+        commands.entity(strip).despawn_recursive();
+    }
+    if thumbnails.requested.is_empty() {
+        return;
+    }
+
+    let size = Val::Px(THUMBNAIL_SIZE as f32 * PREVIEW_SCALE);
+    let text_style = TextStyle { font_size: 12.0, color: Color::srgb(0.9, 0.9, 0.9), ..default() };
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(10.0),
+                left: Val::Px(10.0),
+                padding: UiRect::all(Val::Px(6.0)),
+                column_gap: Val::Px(6.0),
+                ..default()
+            },
+            background_color: Color::srgba(0.05, 0.05, 0.1, 0.8).into(),
+            ..default()
+        },
+        PreviewStrip,
+    )).with_children(|parent| {
+        for seed in &thumbnails.requested {
+            let Some(image) = thumbnails.images.get(seed) else { continue };
+            parent.spawn((
+                ButtonBundle {
+                    style: Style { flex_direction: FlexDirection::Column, align_items: AlignItems::Center, ..default() },
+                    background_color: Color::NONE.into(),
+                    ..default()
+                },
+                PreviewEntry(*seed),
+            )).with_children(|button| {
+                button.spawn(ImageBundle {
+                    style: Style { width: size, height: size, ..default() },
+                    image: UiImage::new(image.clone()),
+                    ..default()
+                });
+                button.spawn(TextBundle::from_section(seed.to_string(), text_style.clone()));
+            });
+        }
+    });
+}
+
+fn generate_previewed_world(
+    entries: Query<(&Interaction, &PreviewEntry), Changed<Interaction>>,
+    mut registry: ResMut<WorldRegistry>,
+    mut service: ResMut<GenerationService>,
+    thumbnails: Res<Thumbnails>,
+) {
+    for (interaction, entry) in entries.iter() {
+        if *interaction == Interaction::Pressed {
+            registry.generate(&mut service, entry.0, thumbnails.params);
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use bevy::prelude::*;
+use std::f32::consts::TAU;
+use crate::biome::BiomeType;
+use crate::ice::IceSheet;
+use crate::render::WorldTile;
+use crate::simulation::SimClock;
+use crate::world::{Tile, WorldMap, SEA_LEVEL};
+
+pub const TIDES_PER_DAY: f32 = 2.0;
+pub const TIDE_AMPLITUDE: f32 = 0.01; // Elevation units; roughly one or two tiles of shoreline
+const TIDE_COLOR_STEPS: f32 = 4.0; // Shoreline is recolored when the tide crosses a step
+const FLOODED_SHORE: Color = Color::srgb(0.25, 0.45, 0.7);
+const TIDAL_FLAT: Color = Color::srgb(0.7, 0.65, 0.5);
+
+pub struct TidesPlugin;
+
+impl Plugin for TidesPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Tides>()
+            .add_systems(FixedFirst, update_tide_level)
+            .add_systems(Update, recolor_shoreline.run_if(resource_exists::<WorldMap>));
+    }
+}
+
+// === TIDES ===
+// The shoreline moves with the tide without changing tile biomes: low-lying coast floods at
+// high tide and the shallowest ocean is exposed as walkable tidal flats at low tide.
+#[derive(Resource, Default)]
+pub struct Tides {
+    pub level: f32, // -1.0 = low tide, 1.0 = high tide
+}
+
+impl Tides {
+    pub fn water_line(&self) -> f32 {
+        SEA_LEVEL + self.level * TIDE_AMPLITUDE
+    }
+
+    pub fn is_underwater(&self, tile: &Tile) -> bool {
+        tile.elevation < self.water_line()
+    }
+
+    // Tile color at the current tide
+    pub fn shore_color(&self, tile: &Tile) -> Color {
+        if (tile.elevation - SEA_LEVEL).abs() > TIDE_AMPLITUDE {
+            return tile.biome.get_color();
+        }
+        match (tile.biome, self.is_underwater(tile)) {
+            (BiomeType::Ocean, false) => TIDAL_FLAT,
+            (BiomeType::Ocean, true) => tile.biome.get_color(),
+            (_, true) => FLOODED_SHORE,
+            (_, false) => tile.biome.get_color(),
+        }
+    }
+
+    fn color_step(&self) -> i32 {
+        (self.level * TIDE_COLOR_STEPS).round() as i32
+    }
+}
+
+fn update_tide_level(clock: Res<SimClock>, mut tides: ResMut<Tides>) {
+    tides.level = (clock.time_of_day() * TIDES_PER_DAY * TAU).sin();
+}
+
+// Only tiles within the tidal range can change, so everything else is skipped cheaply; frozen
+// shallows are colored by ice.rs
+pub fn recolor_shoreline(
+    tides: Res<Tides>,
+    world_map: Res<WorldMap>,
+    ice: Option<Res<IceSheet>>,
+    mut last_step: Local<Option<i32>>,
+    mut tiles: Query<(Ref<WorldTile>, &mut Sprite)>,
+) {
+    let step = tides.color_step();
+    let recolor_all = *last_step != Some(step);
+    *last_step = Some(step);
+
+    for (tile_ref, mut sprite) in tiles.iter_mut() {
+        if !recolor_all && !tile_ref.is_added() {
+            continue;
+        }
+        let tile = &world_map.tiles[tile_ref.x][tile_ref.y];
+        if (tile.elevation - SEA_LEVEL).abs() > TIDE_AMPLITUDE
+            || ice.as_ref().is_some_and(|ice| ice.is_frozen(tile_ref.x, tile_ref.y)) {
+            continue;
+        }
+        let color = tides.shore_color(tile);
+        if sprite.color != color {
+            sprite.color = color;
+        }
+    }
+}
@@ -0,0 +1,358 @@
+use bevy::prelude::*;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use crate::camera::MainCamera;
+use crate::coords::{tile_to_world, world_to_tile, TILE_SIZE};
+use crate::biome::BiomeType;
+use crate::creature::{Creature, REFERENCE_SIZE};
+use crate::environment::{EnvironmentSprite, EnvironmentType};
+use crate::extreme_weather::ExtremeWeather;
+use crate::hydrology::Hydrology;
+use crate::profiling::budgeted;
+use crate::render::WorldTile;
+use crate::species::{Diet, MovementMode, SpeciesRegistry};
+use crate::tides::Tides;
+use crate::world::{WorldMap, WORLD_SIZE};
+
+pub const VEGETATION_TICK_SECONDS: f32 = 5.0;
+pub const SEED_SPREAD_RADIUS: i32 = 3; // Tiles a pollinated flower can seed away from itself
+const WITHER_CHANCE: f64 = 0.01; // Per tick, for flowers nobody pollinates
+const COVER_REGROWTH: f32 = 0.02; // Plant cover regained per tick
+const FLOWER_DRAW_DISTANCE: f32 = 300.0; // World units around the camera
+const TRAMPLE_RATE: f32 = 0.01; // Ground wear per second from a reference-size creature
+const WEAR_RECOVERY: f32 = 0.005; // Ground wear healed per tick
+const DIG_CHANCE: f64 = 1.0 / 120.0; // Per second, for burrowing species
+const GRAZE_RATE: f32 = 0.02; // Plant cover a grazer eats per second
+const GRAZING_DECAY: f32 = 0.8; // Fraction of grazing pressure carried into the next tick
+const OVERGRAZE_PRESSURE: f32 = 0.5; // Pressure above which grazing damages the ground
+const OVERGRAZE_WEAR: f32 = 0.05; // Wear added per tick while overgrazed
+const FORAGE_COVER: f32 = 0.2; // Below this, grazed-out ground has no plant food left
+const SPARSE_ELEMENT_COVER: f32 = 0.3; // Grass and bushes disappear below this cover
+
+// Biomes with ground plant cover that can be grazed or stripped
+const COVER_BIOMES: [BiomeType; 5] = [
+    BiomeType::Grasslands,
+    BiomeType::Savanna,
+    BiomeType::Forest,
+    BiomeType::TropicalRainforest,
+    BiomeType::Wetlands,
+];
+
+// Biomes flowers grow in, with their initial coverage
+const FLOWER_BIOMES: [(BiomeType, f64); 2] = [
+    (BiomeType::Grasslands, 0.1),
+    (BiomeType::TropicalRainforest, 0.1),
+];
+
+pub struct VegetationPlugin;
+
+impl Plugin for VegetationPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, (
+                init_vegetation.run_if(resource_added::<WorldMap>),
+                (draw_flowers, tint_vegetated_tiles, hide_grazed_elements).run_if(resource_exists::<Vegetation>),
+            ).chain())
+            .add_systems(FixedUpdate, (pollinate_flowers, graze, wear_ground, budgeted(vegetation_tick, 2.0)).chain()
+                .run_if(resource_exists::<Vegetation>));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlowerState {
+    None,
+    Blooming, // Waiting for a pollinator; slowly withers without one
+    Pollinated, // Will set seed on the next vegetation tick
+}
+
+// === VEGETATION ===
+// Plant cover and flowering plants per tile. Flowers only spread when insects pollinate them,
+// and the insects in turn feed on the flowers, so the two populations rise and fall together.
+#[derive(Resource)]
+pub struct Vegetation {
+    pub cover: Vec<f32>, // Ground plant cover (standing biomass) 0.0..=1.0, indexed [x * WORLD_SIZE + y]
+    pub flowers: Vec<FlowerState>, // Indexed like `cover`
+    pub wear: Vec<f32>, // Trampled or dug-up ground 0.0..=1.0; caps cover and slows regrowth
+    pub grazing: Vec<f32>, // Recent grazing pressure, decaying every tick
+    pub ticks: u64, // Completed vegetation ticks; lets renderers notice regrowth cheaply
+    pub rng: StdRng,
+}
+
+impl Vegetation {
+    pub fn from_world_map(world_map: &WorldMap) -> Self {
+        let mut rng = StdRng::seed_from_u64(world_map.seed as u64 ^ 0x564547);
+        let mut cover = vec![0.0; WORLD_SIZE * WORLD_SIZE];
+        let mut flowers = vec![FlowerState::None; WORLD_SIZE * WORLD_SIZE];
+        for (x, column) in world_map.tiles.iter().enumerate() {
+            for (y, tile) in column.iter().enumerate() {
+                if COVER_BIOMES.contains(&tile.biome) {
+                    cover[x * WORLD_SIZE + y] = 1.0;
+                }
+                if Self::flower_chance(tile.biome).is_some_and(|chance| rng.gen_bool(chance)) {
+                    flowers[x * WORLD_SIZE + y] = FlowerState::Blooming;
+                }
+            }
+        }
+        let wear = vec![0.0; WORLD_SIZE * WORLD_SIZE];
+        let grazing = vec![0.0; WORLD_SIZE * WORLD_SIZE];
+        Self { cover, flowers, wear, grazing, ticks: 0, rng }
+    }
+
+    pub fn cover(&self, x: usize, y: usize) -> f32 {
+        self.cover[x * WORLD_SIZE + y]
+    }
+
+    // Eats away plant cover; flowers do not survive being stripped
+    pub fn strip(&mut self, x: usize, y: usize, amount: f32) -> f32 {
+        let index = x * WORLD_SIZE + y;
+        let eaten = self.cover[index].min(amount);
+        self.cover[index] -= eaten;
+        if eaten > 0.0 {
+            self.flowers[index] = FlowerState::None;
+        }
+        eaten
+    }
+
+    // Cover biomes only offer plant food while some cover is left
+    pub fn is_grazed_out(&self, x: usize, y: usize, biome: BiomeType) -> bool {
+        COVER_BIOMES.contains(&biome) && self.cover(x, y) < FORAGE_COVER
+    }
+
+    // Worn ground loses its cover and flowers right away
+    pub fn add_wear(&mut self, x: usize, y: usize, amount: f32) {
+        let index = x * WORLD_SIZE + y;
+        self.wear[index] = (self.wear[index] + amount).min(1.0);
+        self.cover[index] = self.cover[index].min(1.0 - self.wear[index]);
+        if self.wear[index] >= 1.0 {
+            self.flowers[index] = FlowerState::None;
+        }
+    }
+
+    fn flower_chance(biome: BiomeType) -> Option<f64> {
+        FLOWER_BIOMES.iter().find(|(b, _)| *b == biome).map(|(_, chance)| *chance)
+    }
+
+    pub fn is_blooming(&self, x: usize, y: usize) -> bool {
+        self.flowers[x * WORLD_SIZE + y] != FlowerState::None
+    }
+}
+
+fn init_vegetation(mut commands: Commands, world_map: Res<WorldMap>) {
+    commands.insert_resource(Vegetation::from_world_map(&world_map));
+}
+
+fn pollinate_flowers(
+    registry: Res<SpeciesRegistry>,
+    mut vegetation: ResMut<Vegetation>,
+    creatures: Query<(&Transform, &Creature)>,
+) {
+    for (transform, creature) in creatures.iter() {
+        if registry.get(creature.species).diet != Diet::Nectarivore {
+            continue;
+        }
+        let Some((x, y)) = world_to_tile(transform.translation.truncate()) else { continue };
+        let flower = &mut vegetation.flowers[x * WORLD_SIZE + y];
+        if *flower == FlowerState::Blooming {
+            *flower = FlowerState::Pollinated;
+        }
+    }
+}
+
+// === GRAZING ===
+// Plant eaters crop the cover of the tile they feed on. Pressure builds where many graze, and
+// ground that stays overgrazed is worn down, so it regrows slower than lightly grazed pasture.
+fn graze(
+    time: Res<Time>,
+    world_map: Res<WorldMap>,
+    registry: Res<SpeciesRegistry>,
+    mut vegetation: ResMut<Vegetation>,
+    creatures: Query<(&Transform, &Creature)>,
+) {
+    let dt = time.delta_seconds();
+    for (transform, creature) in creatures.iter() {
+        let diet = registry.get(creature.species).diet;
+        if !matches!(diet, Diet::Herbivore | Diet::Omnivore) {
+            continue;
+        }
+        let Some((x, y)) = world_to_tile(transform.translation.truncate()) else { continue };
+        let tile = &world_map.tiles[x][y];
+        if !COVER_BIOMES.contains(&tile.biome) || !tile.resources.iter().any(|r| diet.edible_resources().contains(r)) {
+            continue;
+        }
+        let eaten = vegetation.strip(x, y, GRAZE_RATE * dt);
+        vegetation.grazing[x * WORLD_SIZE + y] += eaten;
+    }
+}
+
+// === TRAMPLING AND DIGGING ===
+// Every walking creature wears down the ground it stands on, scaled by its size, so busy routes
+// turn into bare paths that only grow back once traffic stops. Burrowers dig bare patches outright.
+fn wear_ground(
+    time: Res<Time>,
+    world_map: Res<WorldMap>,
+    registry: Res<SpeciesRegistry>,
+    mut vegetation: ResMut<Vegetation>,
+    creatures: Query<(&Transform, &Creature)>,
+) {
+    let dt = time.delta_seconds();
+    for (transform, creature) in creatures.iter() {
+        let species = registry.get(creature.species);
+        if species.movement != MovementMode::Walk {
+            continue;
+        }
+        let Some((x, y)) = world_to_tile(transform.translation.truncate()) else { continue };
+        if !COVER_BIOMES.contains(&world_map.tiles[x][y].biome) {
+            continue;
+        }
+        vegetation.add_wear(x, y, TRAMPLE_RATE * species.size / REFERENCE_SIZE * dt);
+        if species.burrower && vegetation.rng.gen_bool(DIG_CHANCE * dt as f64) {
+            vegetation.add_wear(x, y, 1.0);
+        }
+    }
+}
+
+fn vegetation_tick(
+    time: Res<Time>,
+    mut tick_timer: Local<f32>,
+    world_map: Res<WorldMap>,
+    tides: Res<Tides>,
+    extremes: Option<Res<ExtremeWeather>>,
+    mut vegetation: ResMut<Vegetation>,
+) {
+    *tick_timer += time.delta_seconds();
+    if *tick_timer < VEGETATION_TICK_SECONDS {
+        return;
+    }
+    *tick_timer = 0.0;
+
+    let vegetation = &mut *vegetation;
+    vegetation.ticks += 1;
+
+    for (x, column) in world_map.tiles.iter().enumerate() {
+        for (y, tile) in column.iter().enumerate() {
+            if COVER_BIOMES.contains(&tile.biome) {
+                let index = x * WORLD_SIZE + y;
+                if vegetation.grazing[index] > OVERGRAZE_PRESSURE {
+                    vegetation.add_wear(x, y, OVERGRAZE_WEAR);
+                }
+                vegetation.grazing[index] *= GRAZING_DECAY;
+                let wear = &mut vegetation.wear[index];
+                *wear = (*wear - WEAR_RECOVERY).max(0.0);
+                let fertility = 1.0 - *wear;
+                let weather = extremes.as_ref().map_or(1.0, |extremes| extremes.regrowth_factor(x, y));
+                let cover = &mut vegetation.cover[index];
+                *cover = (*cover + COVER_REGROWTH * fertility * weather).min(fertility);
+            }
+        }
+    }
+
+    for index in 0..vegetation.flowers.len() {
+        match vegetation.flowers[index] {
+            FlowerState::None => {}
+            FlowerState::Blooming => {
+                if vegetation.rng.gen_bool(WITHER_CHANCE) {
+                    vegetation.flowers[index] = FlowerState::None;
+                }
+            }
+            FlowerState::Pollinated => {
+                vegetation.flowers[index] = FlowerState::Blooming;
+
+                // Drop one seed nearby; it takes if the ground suits flowers
+                let (x, y) = ((index / WORLD_SIZE) as i32, (index % WORLD_SIZE) as i32);
+                let tx = x + vegetation.rng.gen_range(-SEED_SPREAD_RADIUS..=SEED_SPREAD_RADIUS);
+                let ty = y + vegetation.rng.gen_range(-SEED_SPREAD_RADIUS..=SEED_SPREAD_RADIUS);
+                if tx < 0 || ty < 0 || tx >= WORLD_SIZE as i32 || ty >= WORLD_SIZE as i32 {
+                    continue;
+                }
+                let tile = &world_map.tiles[tx as usize][ty as usize];
+                let target = tx as usize * WORLD_SIZE + ty as usize;
+                if Vegetation::flower_chance(tile.biome).is_some() && !tides.is_underwater(tile)
+                    && vegetation.flowers[target] == FlowerState::None {
+                    vegetation.flowers[target] = FlowerState::Blooming;
+                }
+            }
+        }
+    }
+}
+
+// Flowers change too often for per-tile sprites, so the ones near the camera are drawn as gizmos
+fn draw_flowers(
+    mut gizmos: Gizmos,
+    vegetation: Res<Vegetation>,
+    cameras: Query<&Transform, With<MainCamera>>,
+) {
+    let Ok(camera_transform) = cameras.get_single() else { return };
+    let Some((cx, cy)) = world_to_tile(camera_transform.translation.truncate()) else { return };
+
+    let radius = (FLOWER_DRAW_DISTANCE / TILE_SIZE) as usize;
+    let color = EnvironmentType::Flower.get_color();
+    let size = EnvironmentType::Flower.get_size();
+    for x in cx.saturating_sub(radius)..(cx + radius).min(WORLD_SIZE) {
+        for y in cy.saturating_sub(radius)..(cy + radius).min(WORLD_SIZE) {
+            if vegetation.is_blooming(x, y) {
+                gizmos.rect_2d(tile_to_world(x, y), 0.0, size, color);
+            }
+        }
+    }
+}
+
+const DRY_GRASS: Color = Color::srgb(0.72, 0.62, 0.35);
+const BARE_GROUND: Color = Color::srgb(0.55, 0.45, 0.3);
+
+// Grass browns as its cell dries out, and any vegetated tile fades to bare ground when stripped,
+// trampled or dug up.
+// Runs for newly loaded chunks, after hydrology ticks and after vegetation ticks.
+fn tint_vegetated_tiles(
+    vegetation: Res<Vegetation>,
+    hydrology: Option<Res<Hydrology>>,
+    world_map: Res<WorldMap>,
+    mut last_tick: Local<u64>,
+    mut tiles: Query<(Ref<WorldTile>, &mut Sprite)>,
+) {
+    let recolor_all = *last_tick != vegetation.ticks || hydrology.as_ref().is_some_and(|h| h.is_changed());
+    *last_tick = vegetation.ticks;
+
+    for (tile, mut sprite) in tiles.iter_mut() {
+        if !recolor_all && !tile.is_added() {
+            continue;
+        }
+        let biome = world_map.tiles[tile.x][tile.y].biome;
+        if !COVER_BIOMES.contains(&biome) {
+            continue;
+        }
+        let mut color = biome.get_color();
+        if matches!(biome, BiomeType::Grasslands | BiomeType::Savanna) {
+            let dryness = hydrology.as_ref().map_or(0.0, |h| h.dryness(tile.x, tile.y));
+            color = color.mix(&DRY_GRASS, dryness * 0.8);
+        }
+        color = color.mix(&BARE_GROUND, (1.0 - vegetation.cover(tile.x, tile.y)) * 0.9);
+        if sprite.color != color {
+            sprite.color = color;
+        }
+    }
+}
+
+// Grass and bushes vanish from grazed-out or trampled tiles and come back with the cover
+fn hide_grazed_elements(
+    vegetation: Res<Vegetation>,
+    mut last_tick: Local<u64>,
+    mut elements: Query<(Ref<EnvironmentSprite>, &mut Visibility)>,
+) {
+    let refresh_all = *last_tick != vegetation.ticks;
+    *last_tick = vegetation.ticks;
+
+    for (element, mut visibility) in elements.iter_mut() {
+        if !matches!(element.element_type, EnvironmentType::Grass | EnvironmentType::Bush)
+            || (!refresh_all && !element.is_added()) {
+            continue;
+        }
+        let wanted = if vegetation.cover(element.x, element.y) < SPARSE_ELEMENT_COVER {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+        if *visibility != wanted {
+            *visibility = wanted;
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::f32::consts::FRAC_PI_4;
+use crate::coords::WORLD_EXTENT;
+use crate::creature::{Creature, CREATURE_Z, FLYING_Z};
+use crate::environment::{EnvironmentSprite, SwayAnimation};
+use crate::settings::Settings;
+use crate::species::{MovementMode, SpeciesRegistry};
+
+const ISOMETRIC_ANGLE: f32 = FRAC_PI_4; // Camera turn, so the map's diagonals run up and across the screen
+const ISOMETRIC_SQUASH: f32 = 2.0; // World units per screen unit up the screen, against across it
+const Y_SORT_SPAN: f32 = 0.4; // Depth range sorting spreads a layer over, across the whole map
+
+pub struct ViewModePlugin;
+
+impl Plugin for ViewModePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(Update, stand_sprites_upright.run_if(isometric_view))
+            .add_systems(PostUpdate, y_sort_creatures
+                .run_if(isometric_view)
+                .before(TransformSystem::TransformPropagate));
+    }
+}
+
+// === VIEW MODES ===
+// How the world is presented, picked at startup (Settings::view, --isometric). The isometric view
+// turns the main camera by 45° and squashes the picture to half height; the ground turns with it,
+// while creatures and props are turned and stretched back so they stand upright, and sorted so
+// those lower on screen are drawn in front. Cursor picking goes through the camera's transform,
+// so it needs nothing more; panning goes along the screen rather than the map.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ViewMode {
+    #[default]
+    TopDown,
+    Isometric,
+}
+
+impl ViewMode {
+    pub fn parse(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "top-down" | "topdown" => Some(ViewMode::TopDown),
+            "isometric" | "iso" => Some(ViewMode::Isometric),
+            _ => None,
+        }
+    }
+
+    // Turn and stretch of cameras, and of anything standing on the ground
+    pub fn rotation(&self) -> Quat {
+        match self {
+            ViewMode::TopDown => Quat::IDENTITY,
+            ViewMode::Isometric => Quat::from_rotation_z(ISOMETRIC_ANGLE),
+        }
+    }
+
+    pub fn stretch(&self) -> Vec3 {
+        match self {
+            ViewMode::TopDown => Vec3::ONE,
+            ViewMode::Isometric => Vec3::new(1.0, ISOMETRIC_SQUASH, 1.0),
+        }
+    }
+
+    pub fn orient_camera(&self, transform: Transform) -> Transform {
+        Transform { rotation: self.rotation(), scale: self.stretch(), ..transform }
+    }
+
+    // World-space offset of a step across or up the screen
+    pub fn screen_to_world(&self, direction: Vec2) -> Vec2 {
+        (self.rotation() * (direction.extend(0.0) * self.stretch())).truncate()
+    }
+
+    // How far up the screen a world position is drawn
+    pub fn screen_height(&self, position: Vec2) -> f32 {
+        (self.rotation().inverse() * position.extend(0.0)).y / self.stretch().y
+    }
+}
+
+fn isometric_view(settings: Res<Settings>) -> bool {
+    settings.view == ViewMode::Isometric
+}
+
+// Depth offset placing a sprite in front of those drawn above it on screen
+fn y_sort_offset(view: ViewMode, position: Vec2) -> f32 {
+    -view.screen_height(position) / WORLD_EXTENT * Y_SORT_SPAN
+}
+
+type NewSprite = Or<(Added<Creature>, Added<EnvironmentSprite>)>;
+
+// Once, when spawned; gait.rs keeps creatures upright as it animates them. Props do not move, so
+// they are sorted here too.
+fn stand_sprites_upright(
+    settings: Res<Settings>,
+    mut sprites: Query<(&mut Transform, Has<EnvironmentSprite>, Option<&mut SwayAnimation>), NewSprite>,
+) {
+    let view = settings.view;
+    for (mut transform, prop, sway) in sprites.iter_mut() {
+        transform.rotation = view.rotation() * transform.rotation;
+        transform.scale *= view.stretch();
+        if let Some(mut sway) = sway {
+            sway.original_rotation += ISOMETRIC_ANGLE;
+        }
+        if prop {
+            transform.translation.z += y_sort_offset(view, transform.translation.truncate());
+        }
+    }
+}
+
+fn y_sort_creatures(
+    settings: Res<Settings>,
+    registry: Res<SpeciesRegistry>,
+    mut creatures: Query<(&mut Transform, &Creature), Changed<Transform>>,
+) {
+    for (mut transform, creature) in creatures.iter_mut() {
+        let layer = match registry.get(creature.species).movement {
+            MovementMode::Walk => CREATURE_Z,
+            MovementMode::Fly => FLYING_Z,
+        };
+        let z = layer + y_sort_offset(settings.view, transform.translation.truncate());
+        if transform.translation.z != z {
+            transform.translation.z = z;
+        }
+    }
+}
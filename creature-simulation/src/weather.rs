@@ -0,0 +1,312 @@
+use bevy::prelude::*;
+use bevy::time::common_conditions::on_timer;
+use std::time::Duration;
+use crate::camera::MainCamera;
+use crate::coords::world_to_tile;
+use crate::keybindings::{Action, Controls};
+use crate::calendar::{seasonal_temperature_offset, Calendar};
+use crate::extreme_weather::ExtremeWeather;
+use crate::hydrology::{Hydrology, FORECAST_DAYS};
+use crate::localization::Localization;
+use crate::names::region_name;
+use crate::save::SaveViewExt;
+use crate::simulation::SECONDS_PER_DAY;
+use crate::telemetry::RingBuffer;
+use crate::text_input::text_input_inactive;
+use crate::world::{WorldMap, WORLD_SIZE};
+
+const SAMPLES_PER_DAY: f64 = 4.0;
+const HISTORY_SAMPLES: usize = 48; // Twelve days at four samples a day
+const PANEL_REFRESH_SECONDS: f32 = 1.0;
+const GRAPH_HEIGHT: f32 = 40.0; // Pixels
+const BAR_WIDTH: f32 = 4.0;
+const REGIONS_PER_SIDE: usize = 8; // Forecast regions are an 8x8 grid over the world
+const REGION_TILES: usize = WORLD_SIZE / REGIONS_PER_SIDE;
+const REGION_SAMPLE_TILES: usize = 25; // Spacing of the tiles averaged into a region's forecast
+const FORECAST_REGIONS: usize = 9; // The camera's region and the eight around it
+const TEMPERATURE_COLOR: Color = Color::srgb(0.95, 0.55, 0.3);
+const RAIN_COLOR: Color = Color::srgb(0.35, 0.6, 0.95);
+const WARNING_COLOR: Color = Color::srgb(1.0, 0.75, 0.3);
+
+pub struct WeatherPlugin;
+
+impl Plugin for WeatherPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(ClimateHistory { samples: RingBuffer::new(HISTORY_SAMPLES), location: None })
+            .save_visibility::<WeatherPanel>("weather")
+            .add_systems(Startup, spawn_weather_panel)
+            .add_systems(FixedUpdate, sample_local_climate
+                .run_if(resource_exists::<Hydrology>)
+                .run_if(on_timer(Duration::from_secs_f64(SECONDS_PER_DAY / SAMPLES_PER_DAY))))
+            .add_systems(Update, (
+                toggle_weather_panel.run_if(text_input_inactive),
+                refresh_weather_panel
+                    .run_if(resource_exists::<Hydrology>)
+                    .run_if(on_timer(Duration::from_secs_f32(PANEL_REFRESH_SECONDS))),
+            ).chain());
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct ClimateSample {
+    pub temperature: f32, // Tile temperature including the season, 0.0..=1.0
+    pub rainfall: f32, // Rain reaching the tile; 1.0 = normal
+}
+
+// Climate at the camera's location over the last HISTORY_SAMPLES samples
+#[derive(Resource)]
+pub struct ClimateHistory {
+    pub samples: RingBuffer<ClimateSample>,
+    pub location: Option<(usize, usize)>, // Tile the latest sample was taken at
+}
+
+impl ClimateHistory {
+    // Mean of the samples taken over the last day
+    pub fn last_day(&self) -> Option<ClimateSample> {
+        let samples: Vec<&ClimateSample> = self.samples.iter().collect();
+        let day = &samples[samples.len().saturating_sub(SAMPLES_PER_DAY as usize)..];
+        let count = day.len() as f32;
+        (count > 0.0).then(|| ClimateSample {
+            temperature: day.iter().map(|sample| sample.temperature).sum::<f32>() / count,
+            rainfall: day.iter().map(|sample| sample.rainfall).sum::<f32>() / count,
+        })
+    }
+}
+
+#[derive(Component)]
+pub struct WeatherPanel;
+
+// Tile temperatures are normalized; shown as degrees for readability
+pub fn celsius(temperature: f32) -> f32 {
+    temperature * 60.0 - 20.0
+}
+
+pub fn rain_label_key(rainfall: f32) -> &'static str {
+    match rainfall {
+        r if r < 0.3 => "weather.dry",
+        r if r < 0.8 => "weather.showers",
+        r if r < 1.5 => "weather.rain",
+        _ => "weather.storm",
+    }
+}
+
+fn sample_local_climate(
+    world_map: Res<WorldMap>,
+    hydrology: Res<Hydrology>,
+    calendar: Res<Calendar>,
+    cameras: Query<&Transform, With<MainCamera>>,
+    mut history: ResMut<ClimateHistory>,
+) {
+    let Ok(camera_transform) = cameras.get_single() else { return };
+    let Some((x, y)) = world_to_tile(camera_transform.translation.truncate()) else { return };
+    history.samples.push(ClimateSample {
+        temperature: world_map.tiles[x][y].temperature + seasonal_temperature_offset(calendar.day, y, &world_map.params),
+        rainfall: hydrology.rainfall_here(x, y),
+    });
+    history.location = Some((x, y));
+}
+
+// Text of the panel, filled in by refresh_weather_panel
+#[derive(Component, Clone, Copy)]
+enum WeatherText {
+    Title,
+    Dates,
+    Region(usize), // Nth of the FORECAST_REGIONS, the camera's own first
+    Warnings,
+    History,
+}
+
+#[derive(Component)]
+struct HistoryBar {
+    graph: usize,
+    sample: usize,
+}
+
+// Built once; refreshes only change text and bar heights
+fn spawn_weather_panel(mut commands: Commands) {
+    let text_style = TextStyle { font_size: 14.0, color: Color::srgb(0.9, 0.9, 0.9), ..default() };
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(70.0),
+                left: Val::Px(10.0),
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(8.0)),
+                row_gap: Val::Px(4.0),
+                ..default()
+            },
+            background_color: Color::srgba(0.05, 0.05, 0.1, 0.8).into(),
+            visibility: Visibility::Hidden,
+            ..default()
+        },
+        WeatherPanel,
+    )).with_children(|parent| {
+        let texts = [WeatherText::Title, WeatherText::Dates]
+            .into_iter()
+            .chain((0..FORECAST_REGIONS).map(WeatherText::Region))
+            .chain([WeatherText::Warnings, WeatherText::History]);
+        for text in texts {
+            let style = match text {
+                WeatherText::Warnings => TextStyle { color: WARNING_COLOR, ..text_style.clone() },
+                _ => text_style.clone(),
+            };
+            parent.spawn((TextBundle::from_section("", style), text));
+        }
+
+        for (graph, (_, color)) in HISTORY_GRAPHS.iter().enumerate() {
+            parent.spawn(NodeBundle {
+                style: Style {
+                    width: Val::Px(BAR_WIDTH * HISTORY_SAMPLES as f32),
+                    height: Val::Px(GRAPH_HEIGHT),
+                    align_items: AlignItems::FlexEnd,
+                    ..default()
+                },
+                background_color: Color::srgba(0.0, 0.0, 0.0, 0.3).into(),
+                ..default()
+            }).with_children(|bars| {
+                for sample in 0..HISTORY_SAMPLES {
+                    bars.spawn((
+                        NodeBundle {
+                            style: Style { width: Val::Px(BAR_WIDTH), height: Val::Px(0.0), ..default() },
+                            background_color: (*color).into(),
+                            ..default()
+                        },
+                        HistoryBar { graph, sample },
+                    ));
+                }
+            });
+        }
+    });
+}
+
+fn toggle_weather_panel(controls: Controls, mut panels: Query<&mut Visibility, With<WeatherPanel>>) {
+    if !controls.just_pressed(Action::ToggleWeather) {
+        return;
+    }
+    for mut visibility in panels.iter_mut() {
+        *visibility = match *visibility {
+            Visibility::Hidden => Visibility::Visible,
+            _ => Visibility::Hidden,
+        };
+    }
+}
+
+// Which value of a sample a history graph plots, and in what colour
+type HistoryGraph = (fn(&ClimateSample) -> f32, Color);
+
+const HISTORY_GRAPHS: [HistoryGraph; 2] = [
+    (|sample| sample.temperature, TEMPERATURE_COLOR),
+    (|sample| sample.rainfall / 2.0, RAIN_COLOR),
+];
+
+// Mean forecast over a region's sample tiles for each of the next FORECAST_DAYS days, as
+// (temperature, rainfall)
+fn region_forecast(
+    (rx, ry): (usize, usize),
+    world_map: &WorldMap,
+    hydrology: &Hydrology,
+    extremes: Option<&ExtremeWeather>,
+    today: u32,
+) -> Vec<(f32, f32)> {
+    let tiles: Vec<(usize, usize)> = (0..REGION_TILES).step_by(REGION_SAMPLE_TILES)
+        .flat_map(|dx| (0..REGION_TILES).step_by(REGION_SAMPLE_TILES).map(move |dy| (dx, dy)))
+        .map(|(dx, dy)| (rx * REGION_TILES + dx + REGION_SAMPLE_TILES / 2, ry * REGION_TILES + dy + REGION_SAMPLE_TILES / 2))
+        .collect();
+    let count = tiles.len() as f32;
+
+    hydrology.forecast.iter().take(FORECAST_DAYS).enumerate().map(|(offset, rainfall)| {
+        let day = today + offset as u32 + 1;
+        let (mut temperature, mut rain) = (0.0, 0.0);
+        for &(x, y) in &tiles {
+            let mut tile_rain = hydrology.local_rainfall(*rainfall, x, y);
+            let mut tile_temperature = world_map.tiles[x][y].temperature + seasonal_temperature_offset(day, y, &world_map.params);
+            if let Some(extremes) = extremes {
+                // Tiles already carry today's extremes
+                tile_rain *= extremes.rain_factor(x, y, day);
+                tile_temperature += extremes.temperature_offset(x, y, day) - extremes.temperature_offset(x, y, today);
+            }
+            temperature += tile_temperature;
+            rain += tile_rain;
+        }
+        (temperature / count, rain / count)
+    }).collect()
+}
+
+// Forecasts for the camera's region and the regions around it, extreme weather warnings for the
+// camera's location, then bar graphs of its sampled history
+fn refresh_weather_panel(
+    localization: Res<Localization>,
+    (world_map, extremes): (Res<WorldMap>, Option<Res<ExtremeWeather>>),
+    (hydrology, calendar): (Res<Hydrology>, Res<Calendar>),
+    history: Res<ClimateHistory>,
+    panels: Query<&Visibility, With<WeatherPanel>>,
+    mut texts: Query<(&WeatherText, &mut Text, &mut Style)>,
+    mut bars: Query<(&HistoryBar, &mut Style), Without<WeatherText>>,
+) {
+    let Ok(visibility) = panels.get_single() else { return };
+    if *visibility == Visibility::Hidden {
+        return;
+    }
+    let Some((x, y)) = history.location else { return };
+
+    let (rx, ry) = (x / REGION_TILES, y / REGION_TILES);
+    let mut regions = vec![(rx, ry)];
+    for (dx, dy) in [(-1, -1), (-1, 0), (-1, 1), (0, -1), (0, 1), (1, -1), (1, 0), (1, 1)] {
+        let (nx, ny) = (rx as i32 + dx, ry as i32 + dy);
+        if (0..REGIONS_PER_SIDE as i32).contains(&nx) && (0..REGIONS_PER_SIDE as i32).contains(&ny) {
+            regions.push((nx as usize, ny as usize));
+        }
+    }
+
+    let dates: Vec<String> = (1..=FORECAST_DAYS as u32).map(|offset| {
+        let date = Calendar::from_day(calendar.day + offset);
+        localization.format("weather.date", &[
+            ("season", localization.get(date.season.label_key()).to_string()),
+            ("day", date.day_of_season.to_string()),
+        ])
+    }).collect();
+    let warnings: Vec<String> = extremes.iter().flat_map(|extremes| extremes.affecting(x, y)).map(|event| {
+        let (key, days) = if event.is_active(calendar.day) {
+            ("weather.extreme_active", event.end_day - calendar.day)
+        } else {
+            ("weather.extreme_expected", event.start_day.saturating_sub(calendar.day))
+        };
+        localization.format(key, &[
+            ("event", localization.get(event.kind.label_key()).to_string()),
+            ("days", days.to_string()),
+        ])
+    }).collect();
+
+    for (text, mut contents, mut style) in texts.iter_mut() {
+        let value = match *text {
+            WeatherText::Title => localization.get("weather.title").to_string(),
+            WeatherText::Dates => dates.join(" · "),
+            WeatherText::Region(index) => regions.get(index).map_or_else(String::new, |&(rx, ry)| {
+                let (cx, cy) = (rx * REGION_TILES + REGION_TILES / 2, ry * REGION_TILES + REGION_TILES / 2);
+                let name = region_name(((rx as u64) << 32 | ry as u64) ^ world_map.seed as u64, world_map.tiles[cx][cy].biome);
+                let days: Vec<String> = region_forecast((rx, ry), &world_map, &hydrology, extremes.as_deref(), calendar.day)
+                    .into_iter()
+                    .map(|(temperature, rainfall)| localization.format("weather.day", &[
+                        ("sky", localization.get(rain_label_key(rainfall)).to_string()),
+                        ("temperature", format!("{:.0}", celsius(temperature))),
+                    ]))
+                    .collect();
+                localization.format("weather.region", &[("region", name), ("forecast", days.join(" · "))])
+            }),
+            WeatherText::Warnings => warnings.join("\n"),
+            WeatherText::History => localization.get("weather.history").to_string(),
+        };
+        style.display = if value.is_empty() { Display::None } else { Display::Flex };
+        contents.sections[0].value = value;
+    }
+
+    // Oldest sample on the left; bars past the samples taken so far stay empty
+    let samples: Vec<&ClimateSample> = history.samples.iter().collect();
+    for (bar, mut style) in bars.iter_mut() {
+        let (value, _) = HISTORY_GRAPHS[bar.graph];
+        let height = samples.get(bar.sample).map_or(0.0, |sample| value(sample).clamp(0.0, 1.0));
+        style.height = Val::Px(height * GRAPH_HEIGHT);
+    }
+}
@@ -1,11 +1,25 @@
 use bevy::prelude::*;
 use noise::{NoiseFn, Perlin, Seedable};
 use rand::Rng;
+use std::collections::HashMap;
+use std::f64::consts::TAU;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use crate::biome::{BiomeType, ResourceType};
+use crate::chunk_cache::ChunkCache;
+use crate::coords::chunk_tile_bounds;
+use crate::crash_report::Recovering;
+use crate::environment::EnvironmentType;
+use crate::errors::{panic_message, SimError};
+use crate::gpu_noise::{Climate, GpuNoise};
 
 pub const WORLD_SIZE: usize = 1000;
+pub const SEA_LEVEL: f32 = 0.3; // Elevation below which tiles are ocean
+pub const CLIFF_SLOPE: f32 = 0.018; // Elevation step between neighbouring tiles that walkers cannot climb
+const CLIFF_MARGIN: f32 = 0.1; // Cliffs only form this far below the mountain line and higher
 pub const CHUNK_SIZE: usize = 16;
 
 #[derive(Component, Debug, Clone)]
@@ -21,13 +35,139 @@ pub struct Tile {
 pub struct WorldMap {
     pub tiles: Vec<Vec<Tile>>,
     pub seed: u32,
+    pub params: GenerationParams, // Together with the seed, reproduces the map
+    pub props: HashMap<(usize, usize), Vec<EnvironmentType>>, // Editor overrides of the generated props
 }
 
+impl WorldMap {
+    // Whether the edge between two neighbouring tiles is too steep to walk over. Only rocky high
+    // ground forms cliffs; a diagonal step is blocked when both ways around the corner are.
+    pub fn is_cliff(&self, from: (usize, usize), to: (usize, usize)) -> bool {
+        if from.0 != to.0 && from.1 != to.1 {
+            let blocked = |corner| self.is_cliff(from, corner) || self.is_cliff(corner, to);
+            return blocked((to.0, from.1)) && blocked((from.0, to.1));
+        }
+        let (a, b) = (self.tiles[from.0][from.1].elevation, self.tiles[to.0][to.1].elevation);
+        let (low, high) = (a.min(b), a.max(b));
+        high - low > CLIFF_SLOPE && high > self.params.mountain_elevation - CLIFF_MARGIN && low >= SEA_LEVEL
+    }
+}
+
+// === GENERATION PARAMETERS ===
+// Tunable from the generation panel (generation_panel.rs). Sea level shifts elevation rather than
+// moving the ocean threshold, so stored elevations stay relative to SEA_LEVEL for tides and rivers.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GenerationParams {
+    pub sea_level: f32,
+    pub elevation_scale: f32, // Noise frequencies; smaller values give larger features
+    pub temperature_scale: f32,
+    pub moisture_scale: f32,
+    pub mountain_elevation: f32, // Mountains, alpine and volcanic terrain above this
+    pub hot_temperature: f32, // Deserts, savanna and rainforest above this
+    pub cold_temperature: f32, // Tundra and alpine below this
+    pub axial_tilt: f32, // Degrees; how much seasons swing away from the equator (see calendar.rs)
+    pub topology: Topology,
+}
+
+impl Default for GenerationParams {
+    fn default() -> Self {
+        Self {
+            sea_level: SEA_LEVEL,
+            elevation_scale: 0.01,
+            temperature_scale: 0.005,
+            moisture_scale: 0.008,
+            mountain_elevation: 0.8,
+            hot_temperature: 0.7,
+            cold_temperature: 0.3,
+            axial_tilt: 23.5,
+            topology: Topology::Flat,
+        }
+    }
+}
+
+impl GenerationParams {
+    // Settings files and the panel can hand over values the generator cannot work with
+    pub fn validate(&self) -> Result<(), SimError> {
+        let values = [
+            self.sea_level, self.elevation_scale, self.temperature_scale, self.moisture_scale,
+            self.mountain_elevation, self.hot_temperature, self.cold_temperature, self.axial_tilt,
+        ];
+        if values.iter().any(|value| !value.is_finite()) {
+            return Err(SimError::Generation(format!("generation parameters must be numbers, got {:?}", self)));
+        }
+        let scales = [self.elevation_scale, self.temperature_scale, self.moisture_scale];
+        if scales.iter().any(|scale| *scale <= 0.0) {
+            return Err(SimError::Generation(format!("noise scales must be positive, got {:?}", scales)));
+        }
+        if !(0.0..=90.0).contains(&self.axial_tilt) {
+            return Err(SimError::Generation(format!("axial tilt must be 0-90 degrees, got {}", self.axial_tilt)));
+        }
+        if self.cold_temperature > self.hot_temperature {
+            return Err(SimError::Generation(format!(
+                "cold temperature {} is above hot temperature {}", self.cold_temperature, self.hot_temperature
+            )));
+        }
+        Ok(())
+    }
+}
+
+// -1.0 at the southern map edge, 0.0 on the equator across the middle, 1.0 at the northern edge
+pub fn latitude(y: usize) -> f32 {
+    (y as f32 + 0.5) / WORLD_SIZE as f32 * 2.0 - 1.0
+}
+
+// === TOPOLOGY ===
+// Which map edges join up. A cylinder wraps east to west, so migrations can circle the globe; a
+// torus also joins north to south. Part of the generation parameters because the noise has to
+// tile seamlessly across the joined edges.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Topology {
+    #[default]
+    Flat,
+    Cylinder,
+    Torus,
+}
+
+impl Topology {
+    pub const ALL: [Topology; 3] = [Topology::Flat, Topology::Cylinder, Topology::Torus];
+
+    pub fn wraps_x(self) -> bool {
+        self != Topology::Flat
+    }
+
+    pub fn wraps_y(self) -> bool {
+        self == Topology::Torus
+    }
+
+    pub fn label_key(self) -> &'static str {
+        match self {
+            Topology::Flat => "generation.topology.flat",
+            Topology::Cylinder => "generation.topology.cylinder",
+            Topology::Torus => "generation.topology.torus",
+        }
+    }
+
+    // Tile at possibly out-of-range coordinates, across wrapped edges; None past a hard edge
+    pub fn wrap_tile(self, x: i32, y: i32) -> Option<(usize, usize)> {
+        let size = WORLD_SIZE as i32;
+        let x = if self.wraps_x() { x.rem_euclid(size) } else { x };
+        let y = if self.wraps_y() { y.rem_euclid(size) } else { y };
+        (x >= 0 && y >= 0 && x < size && y < size).then_some((x as usize, y as usize))
+    }
+}
+
+// Called from the worker threads with the share of chunks done and a stage label
+pub type ProgressCallback = Box<dyn Fn(f32, &str) + Send + Sync>;
+
 pub struct WorldGenerator {
     elevation_noise: Perlin,
     temperature_noise: Perlin,
     moisture_noise: Perlin,
     seed: u32,
+    params: GenerationParams,
+    chunk_cache_mb: u32, // 0 = generate every chunk
+    gpu_noise: Option<Arc<GpuNoise>>, // Evaluate the climate noise in a compute shader (see gpu_noise.rs)
 }
 
 impl WorldGenerator {
@@ -37,29 +177,60 @@ impl WorldGenerator {
         let mut elevation_noise = Perlin::new(seed);
         elevation_noise = elevation_noise.set_seed(seed);
         
-        let mut temperature_noise = Perlin::new(seed + 1);
-        temperature_noise = temperature_noise.set_seed(seed + 1);
+        let mut temperature_noise = Perlin::new(seed.wrapping_add(1));
+        temperature_noise = temperature_noise.set_seed(seed.wrapping_add(1));
         
-        let mut moisture_noise = Perlin::new(seed + 2);
-        moisture_noise = moisture_noise.set_seed(seed + 2);
+        let mut moisture_noise = Perlin::new(seed.wrapping_add(2));
+        moisture_noise = moisture_noise.set_seed(seed.wrapping_add(2));
 
         Self {
             elevation_noise,
             temperature_noise,
             moisture_noise,
             seed,
+            params: GenerationParams::default(),
+            chunk_cache_mb: 0,
+            gpu_noise: None,
         }
     }
 
-    pub fn generate_world(&self) -> WorldMap {
-        self.generate_world_with_progress(None)
+    pub fn with_params(mut self, params: GenerationParams) -> Self {
+        self.params = params;
+        self
     }
 
-    pub fn generate_world_with_progress(&self, progress_callback: Option<Box<dyn Fn(f32, &str) + Send + Sync>>) -> WorldMap {
-        use std::time::Instant;
-        use std::sync::{Arc, Mutex};
-        
-        let generation_start = Instant::now();
+    // Read and write generated chunks through the disk cache (see chunk_cache.rs)
+    pub fn with_chunk_cache(mut self, cap_mb: u32) -> Self {
+        self.chunk_cache_mb = cap_mb;
+        self
+    }
+
+    // None keeps the noise on the CPU
+    pub fn with_gpu_noise(mut self, gpu_noise: Option<Arc<GpuNoise>>) -> Self {
+        self.gpu_noise = gpu_noise;
+        self
+    }
+
+    pub fn seed(&self) -> u32 {
+        self.seed
+    }
+
+    pub fn params(&self) -> &GenerationParams {
+        &self.params
+    }
+
+    pub fn generate_world(&self) -> Result<WorldMap, SimError> {
+        self.generate_world_with_progress(None, None)
+    }
+
+    // Fails with SimError::Cancelled if `cancel` was raised before all chunks were generated, and
+    // with SimError::Generation for invalid parameters or a panicking worker thread
+    pub fn generate_world_with_progress(
+        &self,
+        progress_callback: Option<ProgressCallback>,
+        cancel: Option<Arc<AtomicBool>>,
+    ) -> Result<WorldMap, SimError> {
+        self.params.validate()?;
         
         // Pre-allocate with default values for better memory performance
         let mut tiles = vec![vec![Tile {
@@ -70,40 +241,31 @@ impl WorldGenerator {
             resources: vec![],
         }; WORLD_SIZE]; WORLD_SIZE];
 
-        let total_tiles = WORLD_SIZE * WORLD_SIZE;
-        
-        // Progress tracking with minimum visible duration for each stage (1.5s total for good UX)
-        let stage_info = [
-            ("🏔️ Raising mountains from the depths...", 0.15),      // ~225ms
-            ("🌋 Sculpting volcanic peaks...", 0.12),               // ~180ms  
-            ("🌡️ Setting perfect temperatures...", 0.1),           // ~150ms
-            ("❄️ Adding arctic chill to the north...", 0.1),       // ~150ms
-            ("💧 Distributing moisture across lands...", 0.12),     // ~180ms
-            ("🌊 Filling rivers and lakes...", 0.1),               // ~150ms
-            ("🌍 Shaping diverse biomes...", 0.1),                 // ~150ms
-            ("🌿 Planting forests and grasslands...", 0.08),       // ~120ms
-            ("💎 Scattering precious minerals...", 0.08),          // ~120ms
-            ("🍄 Growing mushrooms in caves...", 0.05),            // ~75ms
-            ("✨ Adding final magical touches...", 0.1),           // ~150ms
+        // Progress is the share of completed chunks; the stage labels are just flavour text picked
+        // by progress. They are localization keys, translated on the main thread.
+        const STAGE_LABELS: [&str; 11] = [
+            "worldgen.stage.mountains",
+            "worldgen.stage.volcanoes",
+            "worldgen.stage.temperature",
+            "worldgen.stage.arctic",
+            "worldgen.stage.moisture",
+            "worldgen.stage.rivers",
+            "worldgen.stage.biomes",
+            "worldgen.stage.forests",
+            "worldgen.stage.minerals",
+            "worldgen.stage.mushrooms",
+            "worldgen.stage.final",
         ];
-        
-        let total_target_time: f32 = stage_info.iter().map(|(_, duration)| duration).sum();
-        let mut cumulative_times = Vec::new();
-        let mut cumulative = 0.0;
-        for (_, duration) in &stage_info {
-            cumulative += duration;
-            cumulative_times.push(cumulative / total_target_time);
-        }
-        
-        // Wrap noise generators in Arc for multi-threading
-        let elevation_noise = Arc::new(self.elevation_noise);
-        let temperature_noise = Arc::new(self.temperature_noise);
-        let moisture_noise = Arc::new(self.moisture_noise);
-        let seed = self.seed;
-        
-        // Progress tracking for multi-threaded environment
-        let progress_tracker = Arc::new(Mutex::new((0, generation_start)));
-        let current_stage = Arc::new(Mutex::new(0));
+
+        // With the GPU backend the whole climate grid is one dispatch and the chunks below only
+        // classify it, which is quicker than reading them back from the chunk cache
+        let gpu_climate = self.gpu_climate(&(0..WORLD_SIZE).flat_map(|x| (0..WORLD_SIZE).map(move |y| (x, y))).collect::<Vec<_>>());
+        let cache = (self.chunk_cache_mb > 0 && gpu_climate.is_none())
+            .then(|| ChunkCache::new(self.seed, &self.params, self.chunk_cache_mb));
+        let cached_chunks = AtomicUsize::new(0);
+
+        // Chunks finished so far, shared by the worker threads
+        let completed_chunks = Arc::new(AtomicUsize::new(0));
         let callback_arc = progress_callback.map(Arc::new);
         
         // Multi-threaded generation using parallel chunks
@@ -112,7 +274,14 @@ impl WorldGenerator {
         let total_chunks = chunks_per_side * chunks_per_side;
         
         // Generate chunks in parallel
-        let chunk_results: Vec<_> = (0..total_chunks).into_par_iter().map(|chunk_idx| {
+        let is_cancelled = || cancel.as_ref().is_some_and(|flag| flag.load(Ordering::Relaxed));
+        // A panicking chunk fails this world with SimError::Generation rather than the app
+        let recovering = Recovering::enter();
+        let chunk_results: Result<Vec<_>, _> = panic::catch_unwind(AssertUnwindSafe(|| (0..total_chunks).into_par_iter().map(|chunk_idx| {
+            // Cooperative cancellation: remaining chunks are skipped, running ones finish
+            if is_cancelled() {
+                return Vec::new();
+            }
             let chunk_x = chunk_idx % chunks_per_side;
             let chunk_y = chunk_idx / chunks_per_side;
             
@@ -121,100 +290,43 @@ impl WorldGenerator {
             let end_x = (start_x + chunk_size).min(WORLD_SIZE);
             let end_y = (start_y + chunk_size).min(WORLD_SIZE);
             
-            let mut chunk_tiles = Vec::new();
-            
-            // Pre-compute constants to avoid repeated calculations
-            let world_size_f32 = WORLD_SIZE as f32;
-            
-            for x in start_x..end_x {
-                for y in start_y..end_y {
-                    // Optimized noise generation with fewer function calls
-                    let x_f64 = x as f64;
-                    let y_f64 = y as f64;
-                    
-                    // Inline elevation generation for speed
-                    let elevation = {
-                        const SCALE: f64 = 0.01;
-                        let mut elev = 0.0;
-                        let mut amplitude = 1.0;
-                        let mut frequency = SCALE;
-                        
-                        // Reduced octaves for speed (4 -> 2)
-                        for _ in 0..2 {
-                            elev += elevation_noise.get([x_f64 * frequency, y_f64 * frequency]) as f32 * amplitude;
-                            amplitude *= 0.5;
-                            frequency *= 2.0;
-                        }
-                        (elev + 1.0) / 2.0
-                    };
-                    
-                    // Optimized temperature generation
-                    let temperature = {
-                        const SCALE: f64 = 0.005;
-                        let latitude_effect = 1.0 - (y as f32 / world_size_f32);
-                        let noise_value = temperature_noise.get([x_f64 * SCALE, y_f64 * SCALE]) as f32;
-                        (latitude_effect + noise_value * 0.3).clamp(0.0, 1.0)
-                    };
-                    
-                    // Optimized moisture generation
-                    let moisture = {
-                        const SCALE: f64 = 0.008;
-                        let noise_value = moisture_noise.get([x_f64 * SCALE, y_f64 * SCALE]) as f32;
-                        (noise_value + 1.0) / 2.0
-                    };
-                    
-                    let biome = Self::determine_biome_fast(elevation, temperature, moisture);
-                    let resources = Self::generate_resources_fast(&biome, seed, x, y);
-                    
-                    chunk_tiles.push((x, y, Tile {
-                        biome,
-                        elevation,
-                        temperature,
-                        moisture,
-                        resources,
-                    }));
-                }
+            let coords = || (start_x..end_x).flat_map(move |x| (start_y..end_y).map(move |y| (x, y)));
+            let cached = cache.as_ref().and_then(|cache| cache.load(chunk_x, chunk_y, coords().count()));
+            if cached.is_some() {
+                cached_chunks.fetch_add(1, Ordering::Relaxed);
             }
-            
-            // Update progress periodically
-            if let Ok(mut tracker) = progress_tracker.try_lock() {
-                tracker.0 += chunk_tiles.len();
-                let progress = tracker.0 as f32 / total_tiles as f32;
-                
-                if let Some(ref callback) = callback_arc {
-                    let elapsed = tracker.1.elapsed().as_secs_f32();
-                    if elapsed >= 0.05 { // Update every 50ms for better responsiveness
-                        if let Ok(mut stage) = current_stage.try_lock() {
-                            // Use time-based stage progression for better UX
-                            let elapsed_total = generation_start.elapsed().as_secs_f32();
-                            let target_elapsed = elapsed_total.max(progress * 1.5); // Minimum 1.5s total
-                            
-                            // Determine stage based on target elapsed time
-                            let mut new_stage = 0;
-                            let mut cumulative_target = 0.0;
-                            for (i, (_, duration)) in stage_info.iter().enumerate() {
-                                cumulative_target += duration;
-                                if target_elapsed >= cumulative_target {
-                                    new_stage = (i + 1).min(stage_info.len() - 1);
-                                } else {
-                                    break;
-                                }
-                            }
-                            
-                            *stage = new_stage;
-                            let (stage_message, _) = stage_info[*stage];
-                            
-                            // Scale progress to match time-based progression
-                            let time_progress = (target_elapsed / total_target_time).min(0.7); // Cap at 70% for world gen
-                            callback(time_progress, stage_message);
-                            tracker.1 = Instant::now();
-                        }
-                    }
+            let tiles = cached.unwrap_or_else(|| {
+                let tiles: Vec<Tile> = coords().map(|(x, y)| match &gpu_climate {
+                    Some(climate) => self.tile_from_climate(x, y, climate[x * WORLD_SIZE + y]),
+                    None => self.generate_tile(x, y),
+                }).collect();
+                if let Some(cache) = &cache {
+                    cache.store(chunk_x, chunk_y, &tiles);
                 }
+                tiles
+            });
+            let chunk_tiles: Vec<_> = coords().zip(tiles).map(|((x, y), tile)| (x, y, tile)).collect();
+            
+            // Report after every chunk; there are only a few hundred of them
+            let completed = completed_chunks.fetch_add(1, Ordering::Relaxed) + 1;
+            if let Some(ref callback) = callback_arc {
+                let progress = completed as f32 / total_chunks as f32;
+                let stage = ((progress * STAGE_LABELS.len() as f32) as usize).min(STAGE_LABELS.len() - 1);
+                callback(progress, STAGE_LABELS[stage]);
             }
             
             chunk_tiles
-        }).collect();
+        }).collect())).map_err(|panic| SimError::Generation(panic_message(&*panic)));
+        drop(recovering);
+        let chunk_results = chunk_results?;
+        
+        if is_cancelled() {
+            return Err(SimError::Cancelled);
+        }
+        if let Some(cache) = &cache {
+            info!("🗃️ {} of {} chunks loaded from the chunk cache", cached_chunks.load(Ordering::Relaxed), total_chunks);
+            cache.finish();
+        }
         
         // Assemble results back into the tiles array
         for chunk_tiles in chunk_results {
@@ -225,29 +337,145 @@ impl WorldGenerator {
         
         // Final progress update
         if let Some(ref callback) = callback_arc {
-            callback(1.0, "✨ Adding final magical touches...");
+            callback(1.0, "worldgen.stage.final");
         }
 
-        WorldMap { tiles, seed: self.seed }
+        Ok(WorldMap { tiles, seed: self.seed, params: self.params, props: HashMap::new() })
+    }
+    
+    // One tile as full generation produces it
+    pub fn generate_tile(&self, x: usize, y: usize) -> Tile {
+        let climate = Self::sample_climate(
+            &self.elevation_noise, &self.temperature_noise, &self.moisture_noise, &self.params, x, y,
+        );
+        self.tile_from_climate(x, y, climate)
+    }
+
+    fn tile_from_climate(&self, x: usize, y: usize, (elevation, temperature, moisture): Climate) -> Tile {
+        let biome = Self::determine_biome_fast(elevation, temperature, moisture, &self.params);
+        let resources = Self::generate_resources_fast(&biome, self.seed, x, y);
+        Tile { biome, elevation, temperature, moisture, resources }
+    }
+
+    // The tiles of one render chunk (see coords.rs), without generating the rest of the world
+    pub fn generate_chunk(&self, chunk_x: i32, chunk_y: i32) -> Vec<(usize, usize, Tile)> {
+        let (start_x, start_y, end_x, end_y) = chunk_tile_bounds(chunk_x, chunk_y);
+        let tiles: Vec<_> = (start_x..end_x).flat_map(|x| (start_y..end_y).map(move |y| (x, y))).collect();
+        tiles.iter().zip(self.climate_at(&tiles))
+            .map(|(&(x, y), climate)| (x, y, self.tile_from_climate(x, y, climate)))
+            .collect()
+    }
+
+    // Biomes of the given tiles, matching what full generation produces, in one GPU dispatch when
+    // the backend is on (used for previews)
+    pub fn biomes_at(&self, tiles: &[(usize, usize)]) -> Vec<BiomeType> {
+        self.climate_at(tiles).into_iter()
+            .map(|(elevation, temperature, moisture)| Self::determine_biome_fast(elevation, temperature, moisture, &self.params))
+            .collect()
+    }
+
+    fn climate_at(&self, tiles: &[(usize, usize)]) -> Vec<Climate> {
+        self.gpu_climate(tiles).unwrap_or_else(|| {
+            tiles.iter()
+                .map(|&(x, y)| Self::sample_climate(
+                    &self.elevation_noise, &self.temperature_noise, &self.moisture_noise, &self.params, x, y,
+                ))
+                .collect()
+        })
+    }
+
+    // None without the GPU backend or when its dispatch fails, leaving the tiles to the CPU
+    fn gpu_climate(&self, tiles: &[(usize, usize)]) -> Option<Vec<Climate>> {
+        let gpu_noise = self.gpu_noise.as_ref()?;
+        gpu_noise.sample_climate(self.seed, &self.params, tiles)
+            .inspect_err(|err| warn!("GPU noise failed, generating on the CPU: {}", err))
+            .ok()
+    }
+
+    // (elevation, temperature, moisture) of a tile, each in 0-1
+    #[inline]
+    fn sample_climate(
+        elevation_noise: &Perlin,
+        temperature_noise: &Perlin,
+        moisture_noise: &Perlin,
+        params: &GenerationParams,
+        x: usize,
+        y: usize,
+    ) -> (f32, f32, f32) {
+        let x_f64 = x as f64;
+        let y_f64 = y as f64;
+        let sample = |noise: &Perlin, frequency: f64| Self::sample_noise(noise, params.topology, x_f64, y_f64, frequency);
+        
+        let elevation = {
+            let mut elev = 0.0;
+            let mut amplitude = 1.0;
+            let mut frequency = params.elevation_scale as f64;
+            
+            // Reduced octaves for speed (4 -> 2)
+            for _ in 0..2 {
+                elev += sample(elevation_noise, frequency) as f32 * amplitude;
+                amplitude *= 0.5;
+                frequency *= 2.0;
+            }
+            ((elev + 1.0) / 2.0 + SEA_LEVEL - params.sea_level).clamp(0.0, 1.0)
+        };
+        
+        let temperature = {
+            // Hot band along the equator, caps at both poles (one band where they meet on a torus)
+            let latitude_effect = 1.0 - latitude(y).abs();
+            let noise_value = sample(temperature_noise, params.temperature_scale as f64) as f32;
+            (latitude_effect + noise_value * 0.3).clamp(0.0, 1.0)
+        };
+        
+        let moisture = {
+            let noise_value = sample(moisture_noise, params.moisture_scale as f64) as f32;
+            (noise_value + 1.0) / 2.0
+        };
+        
+        (elevation, temperature, moisture)
+    }
+
+    // Noise at a tile. A wrapped axis is sampled around a circle as long as the flat map is wide,
+    // so features keep their size and the opposite edges line up.
+    #[inline]
+    fn sample_noise(noise: &Perlin, topology: Topology, x: f64, y: f64, frequency: f64) -> f64 {
+        let radius = WORLD_SIZE as f64 * frequency / TAU;
+        let around = |t: f64| {
+            let angle = t / WORLD_SIZE as f64 * TAU;
+            (radius * angle.cos(), radius * angle.sin())
+        };
+        match topology {
+            Topology::Flat => noise.get([x * frequency, y * frequency]),
+            Topology::Cylinder => {
+                let (x0, x1) = around(x);
+                noise.get([x0, x1, y * frequency])
+            }
+            Topology::Torus => {
+                let ((x0, x1), (y0, y1)) = (around(x), around(y));
+                noise.get([x0, x1, y0, y1])
+            }
+        }
     }
     
-    // Fast biome determination without method call overhead
-    fn determine_biome_fast(elevation: f32, temperature: f32, moisture: f32) -> BiomeType {
+    // Fast biome determination without method call overhead; also reclassifies tiles as the climate changes
+    pub fn determine_biome_fast(elevation: f32, temperature: f32, moisture: f32, params: &GenerationParams) -> BiomeType {
+        let (hot, cold) = (params.hot_temperature, params.cold_temperature);
+
         // Ocean level
-        if elevation < 0.3 {
+        if elevation < SEA_LEVEL {
             return BiomeType::Ocean;
         }
         
         // Coastal areas
-        if elevation < 0.35 {
+        if elevation < SEA_LEVEL + 0.05 {
             return BiomeType::Coastal;
         }
 
         // High elevation biomes
-        if elevation > 0.8 {
-            if temperature < 0.3 {
+        if elevation > params.mountain_elevation {
+            if temperature < cold {
                 return BiomeType::Alpine;
-            } else if temperature < 0.7 {
+            } else if temperature < hot {
                 return BiomeType::Mountain;
             } else {
                 return BiomeType::Volcanic;
@@ -262,19 +490,19 @@ impl WorldGenerator {
         // Temperature and moisture based biomes
         match (temperature, moisture) {
             // Hot and dry
-            (t, m) if t > 0.7 && m < 0.3 => BiomeType::Desert,
+            (t, m) if t > hot && m < 0.3 => BiomeType::Desert,
             // Hot and moderate moisture
-            (t, m) if t > 0.7 && m < 0.6 => BiomeType::Savanna,
+            (t, m) if t > hot && m < 0.6 => BiomeType::Savanna,
             // Hot and wet
-            (t, m) if t > 0.7 && m >= 0.6 => BiomeType::TropicalRainforest,
+            (t, m) if t > hot && m >= 0.6 => BiomeType::TropicalRainforest,
             // Moderate temperature, very wet
-            (t, m) if t > 0.3 && t <= 0.7 && m > 0.8 => BiomeType::Wetlands,
+            (t, m) if t > cold && t <= hot && m > 0.8 => BiomeType::Wetlands,
             // Moderate temperature, moderate moisture
-            (t, m) if t > 0.3 && t <= 0.7 && m > 0.4 => BiomeType::Forest,
+            (t, m) if t > cold && t <= hot && m > 0.4 => BiomeType::Forest,
             // Moderate temperature, low moisture
-            (t, m) if t > 0.3 && t <= 0.7 && m <= 0.4 => BiomeType::Grasslands,
+            (t, m) if t > cold && t <= hot && m <= 0.4 => BiomeType::Grasslands,
             // Cold
-            (t, _) if t <= 0.3 => BiomeType::Tundra,
+            (t, _) if t <= cold => BiomeType::Tundra,
             // Extreme conditions
             (t, m) if t > 0.8 && m < 0.2 => BiomeType::Badlands,
             // Default fallback
@@ -283,7 +511,7 @@ impl WorldGenerator {
     }
     
     // Fast resource generation without allocations when possible
-    fn generate_resources_fast(biome: &BiomeType, seed: u32, x: usize, y: usize) -> Vec<ResourceType> {
+    pub fn generate_resources_fast(biome: &BiomeType, seed: u32, x: usize, y: usize) -> Vec<ResourceType> {
         // Use position-based deterministic generation instead of thread_rng
         let hash = (seed as u64)
             .wrapping_mul(6364136223846793005)
@@ -300,96 +528,4 @@ impl WorldGenerator {
         
         available_resources.into_iter().take(resource_count).collect()
     }
-
-    fn generate_elevation(&self, x: usize, y: usize) -> f32 {
-        let scale = 0.01;
-        let octaves = 4;
-        let mut elevation = 0.0;
-        let mut amplitude = 1.0;
-        let mut frequency = scale;
-
-        for _ in 0..octaves {
-            elevation += self.elevation_noise.get([x as f64 * frequency, y as f64 * frequency]) as f32 * amplitude;
-            amplitude *= 0.5;
-            frequency *= 2.0;
-        }
-
-        (elevation + 1.0) / 2.0 // Normalize to 0-1
-    }
-
-    fn generate_temperature(&self, x: usize, y: usize) -> f32 {
-        let scale = 0.005;
-        let latitude_effect = 1.0 - (y as f32 / WORLD_SIZE as f32);
-        let noise_value = self.temperature_noise.get([x as f64 * scale, y as f64 * scale]) as f32;
-        
-        (latitude_effect + noise_value * 0.3).clamp(0.0, 1.0)
-    }
-
-    fn generate_moisture(&self, x: usize, y: usize) -> f32 {
-        let scale = 0.008;
-        let noise_value = self.moisture_noise.get([x as f64 * scale, y as f64 * scale]) as f32;
-        
-        (noise_value + 1.0) / 2.0
-    }
-
-    fn determine_biome(&self, elevation: f32, temperature: f32, moisture: f32) -> BiomeType {
-        // Ocean level
-        if elevation < 0.3 {
-            return BiomeType::Ocean;
-        }
-        
-        // Coastal areas
-        if elevation < 0.35 {
-            return BiomeType::Coastal;
-        }
-
-        // High elevation biomes
-        if elevation > 0.8 {
-            if temperature < 0.3 {
-                return BiomeType::Alpine;
-            } else if temperature < 0.7 {
-                return BiomeType::Mountain;
-            } else {
-                return BiomeType::Volcanic;
-            }
-        }
-
-        // Very high elevation or extreme cold
-        if elevation > 0.9 || temperature < 0.1 {
-            return BiomeType::Tundra;
-        }
-
-        // Temperature and moisture based biomes
-        match (temperature, moisture) {
-            // Hot and dry
-            (t, m) if t > 0.7 && m < 0.3 => BiomeType::Desert,
-            // Hot and moderate moisture
-            (t, m) if t > 0.7 && m < 0.6 => BiomeType::Savanna,
-            // Hot and wet
-            (t, m) if t > 0.7 && m >= 0.6 => BiomeType::TropicalRainforest,
-            // Moderate temperature, very wet
-            (t, m) if t > 0.3 && t <= 0.7 && m > 0.8 => BiomeType::Wetlands,
-            // Moderate temperature, moderate moisture
-            (t, m) if t > 0.3 && t <= 0.7 && m > 0.4 => BiomeType::Forest,
-            // Moderate temperature, low moisture
-            (t, m) if t > 0.3 && t <= 0.7 && m <= 0.4 => BiomeType::Grasslands,
-            // Cold
-            (t, _) if t <= 0.3 => BiomeType::Tundra,
-            // Extreme conditions
-            (t, m) if t > 0.8 && m < 0.2 => BiomeType::Badlands,
-            // Default fallback
-            _ => BiomeType::Grasslands,
-        }
-    }
-
-    fn generate_resources(&self, biome: &BiomeType) -> Vec<ResourceType> {
-        let mut rng = rand::thread_rng();
-        let available_resources = biome.get_resources();
-        let resource_count = rng.gen_range(1..=3.min(available_resources.len()));
-        
-        available_resources
-            .into_iter()
-            .take(resource_count)
-            .collect()
-    }
 }
\ No newline at end of file
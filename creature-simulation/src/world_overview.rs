@@ -0,0 +1,356 @@
+use bevy::prelude::*;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::ui::RelativeCursorPosition;
+use crate::biome::BiomeType;
+use crate::camera::{CameraPan, MainCamera, MainCameraQuery};
+use crate::coords::{tile_to_world, TILE_SIZE};
+use crate::creature::Creature;
+use crate::keybindings::{Action, Controls, Keybindings};
+use crate::localization::Localization;
+use crate::optimization::CompressedWorldData;
+use crate::species::SpeciesRegistry;
+use crate::text_input::{text_input_inactive, TextPrompt};
+use crate::world::WORLD_SIZE;
+
+const OVERVIEW_SIDE: usize = 500; // Image pixels across; one per STEP tiles
+const STEP: usize = WORLD_SIZE / OVERVIEW_SIDE;
+const RELIEF_STRENGTH: f32 = 12.0; // Shading per unit of elevation drop towards the light
+const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const BUTTON_ON: Color = Color::srgb(0.25, 0.35, 0.5);
+const BUTTON_OFF: Color = Color::srgb(0.2, 0.2, 0.25);
+
+pub struct WorldOverviewPlugin;
+
+impl Plugin for WorldOverviewPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<PauseMenu>()
+            .add_systems(Startup, create_overview_image)
+            .add_systems(Update, (
+                // Not when Escape has just cancelled a prompt
+                toggle_pause_menu.run_if(text_input_inactive.and_then(not(resource_changed::<TextPrompt>))),
+                handle_pause_buttons,
+                jump_from_overview,
+                show_pause_screen.run_if(resource_changed::<PauseMenu>),
+                paint_world_overview.run_if(resource_changed::<PauseMenu>),
+            ).chain());
+    }
+}
+
+// === PAUSE MENU ===
+// Escape pauses the simulation behind a small menu, and Escape or Resume carry on. Only a pause
+// the menu made itself is undone, as with the error screen. The screens are rebuilt whenever the
+// menu changes, which only happens on a key press or click.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PauseScreen {
+    Menu,
+    Overview,
+}
+
+// Layers drawn over the biomes of the overview
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OverviewLayer {
+    Relief,
+    Creatures,
+    View, // Outline of what the main camera shows
+}
+
+impl OverviewLayer {
+    const ALL: [OverviewLayer; 3] = [OverviewLayer::Relief, OverviewLayer::Creatures, OverviewLayer::View];
+
+    fn label_key(&self) -> &'static str {
+        match self {
+            OverviewLayer::Relief => "overview.layer.relief",
+            OverviewLayer::Creatures => "overview.layer.creatures",
+            OverviewLayer::View => "overview.layer.view",
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct PauseMenu {
+    pub screen: Option<PauseScreen>,
+    pub layers: [bool; OverviewLayer::ALL.len()],
+    paused_simulation: bool, // Only undo a pause we caused ourselves
+}
+
+impl Default for PauseMenu {
+    fn default() -> Self {
+        Self { screen: None, layers: [true; OverviewLayer::ALL.len()], paused_simulation: false }
+    }
+}
+
+impl PauseMenu {
+    fn open(&mut self, virtual_time: &mut Time<Virtual>) {
+        if !virtual_time.is_paused() {
+            virtual_time.pause();
+            self.paused_simulation = true;
+        }
+        self.screen = Some(PauseScreen::Menu);
+    }
+
+    fn close(&mut self, virtual_time: &mut Time<Virtual>) {
+        if self.paused_simulation {
+            virtual_time.unpause();
+            self.paused_simulation = false;
+        }
+        self.screen = None;
+    }
+}
+
+#[derive(Component)]
+struct PauseRoot;
+
+#[derive(Component, Clone, Copy)]
+enum PauseButton {
+    Resume,
+    Overview,
+    Back,
+    Layer(OverviewLayer),
+}
+
+// Escape steps back: from the overview to the menu, from the menu to the game
+fn toggle_pause_menu(controls: Controls, mut menu: ResMut<PauseMenu>, mut virtual_time: ResMut<Time<Virtual>>) {
+    if !controls.just_pressed(Action::TogglePauseMenu) {
+        return;
+    }
+    match menu.screen {
+        None => menu.open(&mut virtual_time),
+        Some(PauseScreen::Menu) => menu.close(&mut virtual_time),
+        Some(PauseScreen::Overview) => menu.screen = Some(PauseScreen::Menu),
+    }
+}
+
+fn handle_pause_buttons(
+    buttons: Query<(&Interaction, &PauseButton), Changed<Interaction>>,
+    mut menu: ResMut<PauseMenu>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+) {
+    let Some((_, button)) = buttons.iter().find(|(interaction, _)| **interaction == Interaction::Pressed) else { return };
+    match button {
+        PauseButton::Resume => menu.close(&mut virtual_time),
+        PauseButton::Overview => menu.screen = Some(PauseScreen::Overview),
+        PauseButton::Back => menu.screen = Some(PauseScreen::Menu),
+        PauseButton::Layer(layer) => menu.layers[*layer as usize] ^= true,
+    }
+}
+
+fn spawn_button(parent: &mut ChildBuilder, button: PauseButton, label: &str, color: Color) {
+    parent.spawn((
+        ButtonBundle {
+            style: Style {
+                margin: UiRect::all(Val::Px(4.0)),
+                padding: UiRect::axes(Val::Px(16.0), Val::Px(6.0)),
+                ..default()
+            },
+            background_color: color.into(),
+            ..default()
+        },
+        button,
+    )).with_children(|text| {
+        text.spawn(TextBundle::from_section(label, TextStyle { font_size: 18.0, color: TEXT_COLOR, ..default() }));
+    });
+}
+
+fn show_pause_screen(
+    mut commands: Commands,
+    menu: Res<PauseMenu>,
+    localization: Res<Localization>,
+    bindings: Res<Keybindings>,
+    overview: Res<OverviewImage>,
+    roots: Query<Entity, With<PauseRoot>>,
+) {
+    for root in roots.iter() {
+        commands.entity(root).despawn_recursive();
+    }
+    let Some(screen) = menu.screen else { return };
+
+    let title = TextStyle { font_size: 32.0, color: TEXT_COLOR, ..default() };
+    let small = TextStyle { font_size: 14.0, color: TEXT_COLOR, ..default() };
+    commands.spawn((
+        NodeBundle {
+            style: Style {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                flex_direction: if screen == PauseScreen::Menu { FlexDirection::Column } else { FlexDirection::Row },
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                column_gap: Val::Px(20.0),
+                ..default()
+            },
+            background_color: Color::srgba(0.03, 0.04, 0.08, 0.92).into(),
+            // Under the error screen
+            z_index: ZIndex::Global(50),
+            ..default()
+        },
+        PauseRoot,
+    )).with_children(|root| match screen {
+        PauseScreen::Menu => {
+            root.spawn(TextBundle::from_section(localization.get("pause.title"), title.clone()));
+            spawn_button(root, PauseButton::Resume, localization.get("pause.resume"), BUTTON_OFF);
+            spawn_button(root, PauseButton::Overview, localization.get("pause.overview"), BUTTON_OFF);
+            root.spawn(TextBundle::from_section(
+                localization.format("pause.hint", &[("key", bindings.label(Action::TogglePauseMenu))]),
+                small.clone(),
+            ));
+        }
+        PauseScreen::Overview => {
+            root.spawn((
+                ImageBundle {
+                    style: Style {
+                        height: Val::Vh(90.0),
+                        aspect_ratio: Some(1.0),
+                        ..default()
+                    },
+                    image: UiImage::new(overview.0.clone()),
+                    ..default()
+                },
+                Interaction::default(),
+                RelativeCursorPosition::default(),
+                OverviewMap,
+            ));
+            root.spawn(NodeBundle {
+                style: Style { flex_direction: FlexDirection::Column, row_gap: Val::Px(4.0), ..default() },
+                ..default()
+            }).with_children(|side| {
+                side.spawn(TextBundle::from_section(localization.get("overview.title"), title.clone()));
+                side.spawn(TextBundle::from_section(localization.get("overview.hint"), small.clone()));
+                for biome in BiomeType::ALL {
+                    side.spawn(NodeBundle {
+                        style: Style { align_items: AlignItems::Center, column_gap: Val::Px(6.0), ..default() },
+                        ..default()
+                    }).with_children(|row| {
+                        row.spawn(NodeBundle {
+                            style: Style { width: Val::Px(14.0), height: Val::Px(14.0), ..default() },
+                            background_color: biome.get_color().into(),
+                            ..default()
+                        });
+                        row.spawn(TextBundle::from_section(localization.get(&biome.name_key()), small.clone()));
+                    });
+                }
+                for layer in OverviewLayer::ALL {
+                    let color = if menu.layers[layer as usize] { BUTTON_ON } else { BUTTON_OFF };
+                    spawn_button(side, PauseButton::Layer(layer), localization.get(layer.label_key()), color);
+                }
+                spawn_button(side, PauseButton::Back, localization.get("overview.back"), BUTTON_OFF);
+            });
+        }
+    });
+}
+
+// === WORLD OVERVIEW ===
+// The whole map on one screen, painted from the compressed world data kept since generation
+// rather than the rendered chunks, most of which are never loaded. Repainted each time it is
+// opened or a layer is switched; the simulation is paused meanwhile, so nothing goes stale.
+// Clicking the map moves the camera there and resumes.
+#[derive(Resource)]
+struct OverviewImage(Handle<Image>);
+
+#[derive(Component)]
+struct OverviewMap;
+
+fn create_overview_image(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let image = Image::new_fill(
+        Extent3d { width: OVERVIEW_SIDE as u32, height: OVERVIEW_SIDE as u32, depth_or_array_layers: 1 },
+        TextureDimension::D2,
+        &[0, 0, 0, 255],
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    );
+    commands.insert_resource(OverviewImage(images.add(image)));
+}
+
+// Image pixel showing a world position, which may lie off the map
+fn overview_pixel(position: Vec2) -> Vec2 {
+    let tile = (position - tile_to_world(0, 0)) / TILE_SIZE + 0.5;
+    Vec2::new(tile.x, WORLD_SIZE as f32 - tile.y) / STEP as f32
+}
+
+fn paint_world_overview(
+    menu: Res<PauseMenu>,
+    compressed: Option<Res<CompressedWorldData>>,
+    registry: Res<SpeciesRegistry>,
+    creatures: Query<(&Transform, &Creature)>,
+    (windows, cameras): (Query<&Window>, MainCameraQuery),
+    overview: Res<OverviewImage>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    if menu.screen != Some(PauseScreen::Overview) {
+        return;
+    }
+    let Some(compressed) = compressed else { return };
+    let Some(image) = images.get_mut(&overview.0) else { return };
+    let mut put = |pixel: Vec2, color: [u8; 4]| {
+        if pixel.x >= 0.0 && pixel.y >= 0.0 && (pixel.x as usize) < OVERVIEW_SIDE && (pixel.y as usize) < OVERVIEW_SIDE {
+            let index = (pixel.y as usize * OVERVIEW_SIDE + pixel.x as usize) * 4;
+            image.data[index..index + 4].copy_from_slice(&color);
+        }
+    };
+
+    let relief = menu.layers[OverviewLayer::Relief as usize];
+    for px in 0..OVERVIEW_SIDE {
+        for py in 0..OVERVIEW_SIDE {
+            let (x, y) = (px * STEP, (OVERVIEW_SIDE - 1 - py) * STEP);
+            let mut color = BiomeType::from_id(compressed.get_biome(x, y)).get_color().to_srgba();
+            // Lit from the top left: slopes facing it brighten, those facing away darken
+            if relief {
+                let towards_light = compressed.get_elevation(x.saturating_sub(STEP), (y + STEP).min(WORLD_SIZE - 1));
+                let shade = (1.0 + (compressed.get_elevation(x, y) - towards_light) * RELIEF_STRENGTH).clamp(0.6, 1.4);
+                color = Srgba::new(color.red * shade, color.green * shade, color.blue * shade, 1.0);
+            }
+            put(Vec2::new(px as f32, py as f32), color.to_u8_array());
+        }
+    }
+
+    if menu.layers[OverviewLayer::Creatures as usize] {
+        for (transform, creature) in creatures.iter() {
+            let (r, g, b) = registry.get(creature.species).color;
+            let color = [(r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8, 255];
+            let pixel = overview_pixel(transform.translation.truncate());
+            for offset in [Vec2::ZERO, Vec2::X, Vec2::Y, Vec2::ONE] {
+                put(pixel + offset, color);
+            }
+        }
+    }
+
+    // The window's corners as seen through the main camera, turned or not
+    if menu.layers[OverviewLayer::View as usize] {
+        let (Ok(window), Ok((camera, camera_transform))) = (windows.get_single(), cameras.get_single()) else { return };
+        let corners: Vec<Vec2> = [Vec2::ZERO, Vec2::new(window.width(), 0.0), window.size(), Vec2::new(0.0, window.height())]
+            .into_iter()
+            .filter_map(|corner| camera.viewport_to_world_2d(camera_transform, corner))
+            .map(overview_pixel)
+            .collect();
+        for (index, start) in corners.iter().enumerate() {
+            let end = corners[(index + 1) % corners.len()];
+            let steps = start.distance(end).ceil() as usize;
+            for step in 0..=steps {
+                put(start.lerp(end, step as f32 / steps.max(1) as f32), [255, 255, 255, 255]);
+            }
+        }
+    }
+}
+
+type OverviewClicks = (Changed<Interaction>, With<OverviewMap>);
+
+// Jumps rather than pans, since the camera may be anywhere on the map
+fn jump_from_overview(
+    maps: Query<(&Interaction, &RelativeCursorPosition), OverviewClicks>,
+    mut cameras: Query<&mut Transform, With<MainCamera>>,
+    (mut pan, mut menu, mut virtual_time): (ResMut<CameraPan>, ResMut<PauseMenu>, ResMut<Time<Virtual>>),
+) {
+    let Some(normalized) = maps.iter()
+        .filter(|(interaction, _)| **interaction == Interaction::Pressed)
+        .find_map(|(_, cursor)| cursor.normalized)
+    else { return };
+    // The cursor position runs from the top left
+    let tile = |share: f32| ((share.clamp(0.0, 1.0) * WORLD_SIZE as f32) as usize).min(WORLD_SIZE - 1);
+    let target = tile_to_world(tile(normalized.x), tile(1.0 - normalized.y));
+    for mut transform in cameras.iter_mut() {
+        transform.translation = target.extend(transform.translation.z);
+    }
+    pan.target = None;
+    menu.close(&mut virtual_time);
+}
@@ -0,0 +1,112 @@
+use bevy::math::Vec2;
+use crate::biome::{BiomeType, ResourceType};
+use crate::coords::{tile_to_world, WorldPos};
+use crate::errors::SimError;
+use crate::world::{GenerationParams, Tile, WorldGenerator, WorldMap, WORLD_SIZE};
+
+// === WORLD QUERIES ===
+// Read-only questions about a world for tools outside the app, such as map analyzers or notebook
+// bindings, which link the library and never start Bevy: the tiles of a region, the climate along
+// a line, the biome at a point and the nearest tile with a resource. Points are in world units,
+// as everywhere else in the simulation; tiles are (x, y) indices into the map. Searches and lines
+// cross the map's wrapped edges where its topology joins them.
+pub struct WorldQuery<'a> {
+    map: &'a WorldMap,
+}
+
+// One point along a line, as sampled by WorldQuery::climate_along
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ClimateSample {
+    pub position: Vec2,
+    pub tile: (usize, usize),
+    pub biome: BiomeType,
+    pub elevation: f32,
+    pub temperature: f32,
+    pub moisture: f32,
+}
+
+impl<'a> WorldQuery<'a> {
+    pub fn new(map: &'a WorldMap) -> Self {
+        Self { map }
+    }
+
+    // Generates the world a seed and parameters give, as the app would, for tools with no map yet
+    pub fn generate(seed: u32, params: GenerationParams) -> Result<WorldMap, SimError> {
+        params.validate()?;
+        WorldGenerator::new(Some(seed)).with_params(params).generate_world()
+    }
+
+    pub fn tile(&self, x: usize, y: usize) -> Option<&'a Tile> {
+        self.map.tiles.get(x)?.get(y)
+    }
+
+    // Every tile from min to max inclusive, clamped to the map, column by column
+    pub fn tiles_in_region(&self, min: (usize, usize), max: (usize, usize)) -> impl Iterator<Item = ((usize, usize), &'a Tile)> + 'a {
+        let map = self.map;
+        let (max_x, max_y) = (max.0.min(WORLD_SIZE - 1), max.1.min(WORLD_SIZE - 1));
+        (min.0..=max_x).flat_map(move |x| (min.1..=max_y).map(move |y| ((x, y), &map.tiles[x][y])))
+    }
+
+    pub fn biome_at(&self, position: Vec2) -> Option<BiomeType> {
+        let (x, y) = self.wrapped_tile(position)?;
+        Some(self.map.tiles[x][y].biome)
+    }
+
+    // `samples` evenly spaced points from one end to the other, both included; points off a hard
+    // edge of the map are left out
+    pub fn climate_along(&self, from: Vec2, to: Vec2, samples: usize) -> Vec<ClimateSample> {
+        let steps = samples.max(2) - 1;
+        (0..=steps)
+            .map(|step| from.lerp(to, step as f32 / steps as f32))
+            .filter_map(|position| {
+                let (x, y) = self.wrapped_tile(position)?;
+                let tile = &self.map.tiles[x][y];
+                Some(ClimateSample {
+                    position,
+                    tile: (x, y),
+                    biome: tile.biome,
+                    elevation: tile.elevation,
+                    temperature: tile.temperature,
+                    moisture: tile.moisture,
+                })
+            })
+            .collect()
+    }
+
+    // Closest tile within max_radius tiles that has the resource, and its distance in tiles.
+    // Searched ring by ring outwards, so the first ring with a match holds the answer.
+    pub fn nearest_resource(&self, from: (usize, usize), resource: ResourceType, max_radius: usize) -> Option<((usize, usize), f32)> {
+        let topology = self.map.params.topology;
+        let (fx, fy) = (from.0 as i32, from.1 as i32);
+        let mut best: Option<((usize, usize), f32)> = None;
+        for radius in 0..=max_radius as i32 {
+            // Tiles on a later ring can still be closer than a corner of this one
+            if best.is_some_and(|(_, distance)| distance <= radius as f32) {
+                break;
+            }
+            for dx in -radius..=radius {
+                for dy in -radius..=radius {
+                    if dx.abs() != radius && dy.abs() != radius {
+                        continue;
+                    }
+                    let Some((x, y)) = topology.wrap_tile(fx + dx, fy + dy) else { continue };
+                    let distance = ((dx * dx + dy * dy) as f32).sqrt();
+                    if best.is_none_or(|(_, closest)| distance < closest) && self.map.tiles[x][y].resources.contains(&resource) {
+                        best = Some(((x, y), distance));
+                    }
+                }
+            }
+        }
+        best
+    }
+
+    // Centre of a tile in world units
+    pub fn tile_position(x: usize, y: usize) -> Vec2 {
+        tile_to_world(x, y)
+    }
+
+    fn wrapped_tile(&self, position: Vec2) -> Option<(usize, usize)> {
+        let tile = WorldPos(position).wrapped(self.map.params.topology).tile()?;
+        Some((tile.x, tile.y))
+    }
+}
@@ -0,0 +1,154 @@
+use bevy::prelude::*;
+use crate::world::{GenerationParams, WorldMap};
+use crate::world_stats::WorldReport;
+use crate::biome::BiomeType;
+use crate::errors::SimFailure;
+use crate::generation_panel::WorldInhabitant;
+use crate::generation_service::{GenerationId, GenerationJob, GenerationOutput, GenerationPriority, GenerationService};
+use crate::optimization::CompressedWorldData;
+use crate::population_pools::PopulationPools;
+
+const MAX_SCRATCH_WORLDS: usize = 2; // A full map is tens of megabytes; the oldest is dropped first
+
+pub struct WorldRegistryPlugin;
+
+impl Plugin for WorldRegistryPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<WorldRegistry>()
+            .add_systems(Update, collect_scratch_worlds);
+    }
+}
+
+// === WORLD REGISTRY ===
+// The active world is always the one in the `WorldMap` resource, so render and creature systems
+// keep reading `Res<WorldMap>`. Scratch worlds are generated alongside it for comparing seeds
+// and generation settings without touching the running simulation, and any of them can be made
+// the active world (see `activate_world`).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WorldId(pub u32);
+
+impl WorldId {
+    pub const MAIN: WorldId = WorldId(0);
+}
+
+pub struct ScratchWorld {
+    pub id: WorldId,
+    pub map: WorldMap,
+    pub report: WorldReport,
+}
+
+#[derive(Resource)]
+pub struct WorldRegistry {
+    pub active: WorldId,
+    pub scratch: Vec<ScratchWorld>,
+    generating: Vec<GenerationId>,
+    next_id: u32,
+}
+
+impl Default for WorldRegistry {
+    fn default() -> Self {
+        Self { active: WorldId::MAIN, scratch: Vec::new(), generating: Vec::new(), next_id: 1 }
+    }
+}
+
+impl WorldRegistry {
+    pub fn get(&self, id: WorldId) -> Option<&ScratchWorld> {
+        self.scratch.iter().find(|world| world.id == id)
+    }
+
+    // Queues a scratch world in the background; it shows up in the registry when done
+    pub fn generate(&mut self, service: &mut GenerationService, seed: u32, params: GenerationParams) {
+        self.generating.push(service.request(GenerationJob::plain_world(seed, params), GenerationPriority::Background));
+        info!("🌍 Generating scratch world with seed {} and {:?}", seed, params);
+    }
+
+    fn insert(&mut self, map: WorldMap, report: WorldReport) -> WorldId {
+        let id = WorldId(self.next_id);
+        self.next_id += 1;
+        if self.scratch.len() >= MAX_SCRATCH_WORLDS {
+            self.scratch.remove(0);
+        }
+        self.scratch.push(ScratchWorld { id, map, report });
+        id
+    }
+}
+
+fn collect_scratch_worlds(
+    mut registry: ResMut<WorldRegistry>,
+    mut service: ResMut<GenerationService>,
+    mut failures: EventWriter<SimFailure>,
+) {
+    let finished: Vec<_> = registry.generating.iter()
+        .filter_map(|id| service.take(*id).map(|result| (*id, result)))
+        .collect();
+    for (id, result) in finished {
+        registry.generating.retain(|generating| *generating != id);
+        let (map, report) = match result {
+            Ok(GenerationOutput::World(generated)) => *generated,
+            Ok(_) => continue,
+            Err(err) => {
+                failures.send(SimFailure::new(err));
+                continue;
+            }
+        };
+
+        let id = registry.insert(map, report);
+        info!("🌍 Scratch world {} ready - compare it with \"world compare {}\"", id.0, id.0);
+    }
+}
+
+// Swaps a scratch world in as the active one. Its map replaces the `WorldMap` resource, which the
+// renderer and simulation pick up like a freshly generated world, and the previous map takes its
+// place among the scratch worlds. Creatures belong to the world they lived in and go with it,
+// as when regenerating from the generation panel.
+pub fn activate_world(world: &mut World, id: WorldId) {
+    let mut registry = world.resource_mut::<WorldRegistry>();
+    let Some(index) = registry.scratch.iter().position(|scratch| scratch.id == id) else {
+        return warn!("No scratch world {}", id.0);
+    };
+    let incoming = registry.scratch.remove(index);
+    let previous = std::mem::replace(&mut registry.active, id);
+
+    if let (Some(map), Some(report)) = (world.remove_resource::<WorldMap>(), world.remove_resource::<WorldReport>()) {
+        world.resource_mut::<WorldRegistry>().scratch.push(ScratchWorld { id: previous, map, report });
+    }
+    let inhabitants: Vec<Entity> = world.query_filtered::<Entity, WorldInhabitant>().iter(world).collect();
+    for entity in inhabitants {
+        world.despawn(entity);
+    }
+    world.resource_mut::<PopulationPools>().chunks.clear();
+
+    info!("🌍 World {} (seed {}) is now active", id.0, incoming.map.seed);
+    world.insert_resource(CompressedWorldData::from_world_map(&incoming.map));
+    world.insert_resource(incoming.map);
+    world.insert_resource(incoming.report);
+}
+
+// === A/B COMPARISON ===
+fn land_share(report: &WorldReport) -> f32 {
+    let ocean: f32 = report.biome_coverage.iter()
+        .filter(|(biome, _)| *biome == BiomeType::Ocean)
+        .map(|(_, share)| share)
+        .sum();
+    100.0 - ocean
+}
+
+// One line per headline statistic, "active -> other", plus how much of the map changed biome
+pub fn compare_worlds(active_map: &WorldMap, active: &WorldReport, other: &ScratchWorld) -> Vec<String> {
+    let changed = active_map.tiles.iter().flatten()
+        .zip(other.map.tiles.iter().flatten())
+        .filter(|(a, b)| a.biome != b.biome)
+        .count();
+    let total = active_map.tiles.iter().map(Vec::len).sum::<usize>().max(1);
+    let report = &other.report;
+    vec![
+        format!("seed: {} -> {}", active.seed, report.seed),
+        format!("land: {:.1}% -> {:.1}%", land_share(active), land_share(report)),
+        format!("continents: {} -> {}", active.continents, report.continents),
+        format!("islands: {} -> {}", active.islands, report.islands),
+        format!("longest river: {} -> {} tiles", active.longest_river, report.longest_river),
+        format!("highest peak: {:.2} -> {:.2}", active.highest_peak.2, report.highest_peak.2),
+        format!("tiles with a different biome: {:.1}%", changed as f32 / total as f32 * 100.0),
+    ]
+}
@@ -0,0 +1,340 @@
+use bevy::prelude::*;
+use serde::Serialize;
+use std::collections::VecDeque;
+use crate::keybindings::{Action, Controls};
+use crate::biome::{BiomeType, ResourceType};
+use crate::text_input::text_input_inactive;
+use crate::world::{WorldMap, WORLD_SIZE};
+use crate::loading::LoadingState;
+use crate::localization::Localization;
+use crate::save::SaveViewExt;
+use crate::telemetry::Telemetry;
+
+pub const CONTINENT_MIN_TILES: usize = 10_000; // Smaller landmasses count as islands
+pub const RIVER_MIN_CATCHMENT: u32 = 400; // Upstream tiles needed before flow counts as a river
+
+pub struct WorldStatsPlugin;
+
+impl Plugin for WorldStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .save_visibility::<WorldReportPanel>("world_report")
+            .add_systems(Update, (
+                record_world_report.run_if(resource_changed::<WorldReport>),
+                spawn_world_report_panel.run_if(resource_changed::<WorldReport>),
+                reveal_world_report_panel,
+                toggle_world_report_panel.run_if(text_input_inactive),
+            ));
+    }
+}
+
+#[derive(Component)]
+pub struct WorldReportPanel {
+    pub revealed: bool,
+}
+
+// === WORLD REPORT ===
+#[derive(Resource, Debug, Clone, Serialize)]
+pub struct WorldReport {
+    pub seed: u32,
+    pub biome_coverage: Vec<(BiomeType, f32)>, // Percentage of all tiles
+    pub continents: usize,
+    pub islands: usize,
+    pub longest_river: usize, // In tiles
+    pub highest_peak: (usize, usize, f32), // (x, y, elevation)
+    pub resource_totals: Vec<(ResourceType, usize)>,
+    pub constraint_failures: Vec<String>, // Rejected seeds and why, when generation constraints are set
+}
+
+impl WorldReport {
+    // Heavy (touches every tile several times) - run on the generation thread, not in a system
+    pub fn from_world_map(world_map: &WorldMap) -> Self {
+        let total_tiles = (WORLD_SIZE * WORLD_SIZE) as f32;
+
+        let mut biome_counts = [0usize; BiomeType::ALL.len()];
+        let mut resource_counts = [0usize; ResourceType::ALL.len()];
+        let mut highest_peak = (0, 0, f32::MIN);
+
+        for (x, column) in world_map.tiles.iter().enumerate() {
+            for (y, tile) in column.iter().enumerate() {
+                biome_counts[tile.biome.to_id() as usize] += 1;
+                for resource in &tile.resources {
+                    if let Some(i) = ResourceType::ALL.iter().position(|r| r == resource) {
+                        resource_counts[i] += 1;
+                    }
+                }
+                if tile.elevation > highest_peak.2 {
+                    highest_peak = (x, y, tile.elevation);
+                }
+            }
+        }
+
+        let biome_coverage = BiomeType::ALL.iter()
+            .map(|biome| (*biome, biome_counts[biome.to_id() as usize] as f32 / total_tiles * 100.0))
+            .collect();
+        let resource_totals = ResourceType::ALL.iter().copied().zip(resource_counts).collect();
+
+        let (continents, islands) = count_landmasses(world_map);
+        let drainage = Drainage::compute(world_map);
+
+        Self {
+            seed: world_map.seed,
+            biome_coverage,
+            continents,
+            islands,
+            longest_river: drainage.longest_river(),
+            highest_peak,
+            resource_totals,
+            constraint_failures: Vec::new(),
+        }
+    }
+}
+
+fn is_land(biome: BiomeType) -> bool {
+    biome != BiomeType::Ocean
+}
+
+// Splits land regions into continents and islands by size
+fn count_landmasses(world_map: &WorldMap) -> (usize, usize) {
+    let sizes = region_sizes(world_map, is_land);
+    let continents = sizes.iter().filter(|size| **size >= CONTINENT_MIN_TILES).count();
+    (continents, sizes.len() - continents)
+}
+
+// Flood-fills 4-connected regions of tiles matching the predicate and returns their sizes
+pub fn region_sizes(world_map: &WorldMap, matches: impl Fn(BiomeType) -> bool) -> Vec<usize> {
+    label_regions(world_map, matches).1
+}
+
+// Like region_sizes, but also returns each tile's region index (x * WORLD_SIZE + y), if any
+pub fn label_regions(world_map: &WorldMap, matches: impl Fn(BiomeType) -> bool) -> (Vec<Option<usize>>, Vec<usize>) {
+    let mut labels = vec![None; WORLD_SIZE * WORLD_SIZE];
+    let mut queue = VecDeque::new();
+    let mut sizes = Vec::new();
+
+    for start_x in 0..WORLD_SIZE {
+        for start_y in 0..WORLD_SIZE {
+            let start = start_x * WORLD_SIZE + start_y;
+            if labels[start].is_some() || !matches(world_map.tiles[start_x][start_y].biome) {
+                continue;
+            }
+
+            let region = sizes.len();
+            labels[start] = Some(region);
+            queue.push_back((start_x, start_y));
+            let mut size = 0;
+
+            while let Some((x, y)) = queue.pop_front() {
+                size += 1;
+                let neighbors = [
+                    (x.wrapping_sub(1), y),
+                    (x + 1, y),
+                    (x, y.wrapping_sub(1)),
+                    (x, y + 1),
+                ];
+                for (nx, ny) in neighbors {
+                    if nx >= WORLD_SIZE || ny >= WORLD_SIZE {
+                        continue;
+                    }
+                    let index = nx * WORLD_SIZE + ny;
+                    if labels[index].is_none() && matches(world_map.tiles[nx][ny].biome) {
+                        labels[index] = Some(region);
+                        queue.push_back((nx, ny));
+                    }
+                }
+            }
+
+            sizes.push(size);
+        }
+    }
+
+    (labels, sizes)
+}
+
+// === DRAINAGE ===
+// Steepest-descent flow routing: every land tile drains into its lowest lower neighbour.
+pub struct Drainage {
+    pub receivers: Vec<Option<usize>>, // Flat index (x * WORLD_SIZE + y) of the downhill neighbour
+    pub accumulation: Vec<u32>, // Number of tiles (including itself) draining through each tile
+    order: Vec<usize>, // Tile indices sorted from highest to lowest elevation
+}
+
+impl Drainage {
+    pub fn compute(world_map: &WorldMap) -> Self {
+        let tile_count = WORLD_SIZE * WORLD_SIZE;
+        let elevation = |index: usize| world_map.tiles[index / WORLD_SIZE][index % WORLD_SIZE].elevation;
+
+        let mut receivers = vec![None; tile_count];
+        for x in 0..WORLD_SIZE {
+            for y in 0..WORLD_SIZE {
+                let tile = &world_map.tiles[x][y];
+                if !is_land(tile.biome) {
+                    continue; // Oceans are sinks
+                }
+
+                let mut lowest = (tile.elevation, None);
+                for dx in -1i32..=1 {
+                    for dy in -1i32..=1 {
+                        let (nx, ny) = (x as i32 + dx, y as i32 + dy);
+                        if (dx, dy) == (0, 0) || nx < 0 || ny < 0 || nx >= WORLD_SIZE as i32 || ny >= WORLD_SIZE as i32 {
+                            continue;
+                        }
+                        let neighbor_elevation = world_map.tiles[nx as usize][ny as usize].elevation;
+                        if neighbor_elevation < lowest.0 {
+                            lowest = (neighbor_elevation, Some(nx as usize * WORLD_SIZE + ny as usize));
+                        }
+                    }
+                }
+                receivers[x * WORLD_SIZE + y] = lowest.1;
+            }
+        }
+
+        let mut order: Vec<usize> = (0..tile_count).collect();
+        order.sort_unstable_by(|a, b| elevation(*b).total_cmp(&elevation(*a)));
+
+        // Receivers are strictly lower, so a high-to-low sweep pushes all upstream flow down
+        let mut accumulation = vec![1u32; tile_count];
+        for &index in &order {
+            if let Some(receiver) = receivers[index] {
+                accumulation[receiver] += accumulation[index];
+            }
+        }
+
+        Self { receivers, accumulation, order }
+    }
+
+    pub fn is_river(&self, index: usize) -> bool {
+        self.receivers[index].is_some() && self.accumulation[index] >= RIVER_MIN_CATCHMENT
+    }
+
+    // Length in tiles of the longest continuous river channel
+    pub fn longest_river(&self) -> usize {
+        let mut length = vec![0usize; self.receivers.len()];
+        let mut longest = 0;
+
+        // Low-to-high sweep so each tile's downstream length is known before its upstream tiles
+        for &index in self.order.iter().rev() {
+            if !self.is_river(index) {
+                continue;
+            }
+            let downstream = self.receivers[index]
+                .filter(|receiver| self.is_river(*receiver))
+                .map_or(0, |receiver| length[receiver]);
+            length[index] = downstream + 1;
+            longest = longest.max(length[index]);
+        }
+
+        longest
+    }
+}
+
+// === REPORT SYSTEMS ===
+fn record_world_report(report: Res<WorldReport>, mut telemetry: ResMut<Telemetry>) {
+    info!(
+        "🌍 World report: {} continents, {} islands, longest river {} tiles, highest peak {:.2}",
+        report.continents, report.islands, report.longest_river, report.highest_peak.2
+    );
+    for failure in &report.constraint_failures {
+        info!("🌍 Generation constraint adjusted the map: {}", failure);
+    }
+    telemetry.record("world_report", &*report);
+}
+
+fn spawn_world_report_panel(
+    mut commands: Commands,
+    report: Res<WorldReport>,
+    localization: Res<Localization>,
+    existing_panels: Query<Entity, With<WorldReportPanel>>,
+) {
+    for entity in existing_panels.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let mut lines = vec![
+        localization.format("report.title", &[("seed", report.seed.to_string())]),
+        localization.format("report.landmasses", &[
+            ("continents", report.continents.to_string()),
+            ("islands", report.islands.to_string()),
+        ]),
+        localization.format("report.longest_river", &[("length", report.longest_river.to_string())]),
+        localization.format("report.highest_peak", &[
+            ("elevation", format!("{:.2}", report.highest_peak.2)),
+            ("x", report.highest_peak.0.to_string()),
+            ("y", report.highest_peak.1.to_string()),
+        ]),
+        String::new(),
+        localization.get("report.biomes").to_string(),
+    ];
+    for (biome, percent) in report.biome_coverage.iter().filter(|(_, percent)| *percent > 0.0) {
+        lines.push(format!("  {}: {:.1}%", localization.get(&biome.name_key()), percent));
+    }
+    lines.push(String::new());
+    lines.push(localization.get("report.resources").to_string());
+    for (resource, count) in report.resource_totals.iter().filter(|(_, count)| *count > 0) {
+        lines.push(format!("  {}: {}", localization.get(&resource.name_key()), count));
+    }
+    if !report.constraint_failures.is_empty() {
+        lines.push(String::new());
+        lines.push(localization.get("report.constraints").to_string());
+        for failure in &report.constraint_failures {
+            lines.push(format!("  {}", failure));
+        }
+    }
+    lines.push(String::new());
+
+    // The second section is the live reserve-vs-wild comparison, filled in by reserves.rs;
+    // mortality.rs adds its charts after it, before the hint
+    let text_style = TextStyle {
+        font_size: 14.0,
+        color: Color::srgb(0.9, 0.9, 0.9),
+        ..default()
+    };
+    commands.spawn((
+        TextBundle::from_sections([
+            TextSection::new(lines.join("\n") + "\n", text_style.clone()),
+            TextSection::new("", text_style.clone()),
+            TextSection::new(localization.get("report.hint"), text_style),
+        ]).with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(10.0),
+            left: Val::Px(10.0),
+            padding: UiRect::all(Val::Px(8.0)),
+            ..default()
+        }).with_background_color(Color::srgba(0.05, 0.05, 0.1, 0.8)),
+        Visibility::Hidden,
+        WorldReportPanel { revealed: false },
+    ));
+}
+
+// Keep the panel hidden behind the loading screen until the world is on screen
+fn reveal_world_report_panel(
+    loading_state: Res<LoadingState>,
+    mut panels: Query<(&mut Visibility, &mut WorldReportPanel)>,
+) {
+    if !loading_state.is_complete {
+        return;
+    }
+    for (mut visibility, mut panel) in panels.iter_mut() {
+        if !panel.revealed {
+            *visibility = Visibility::Visible;
+            panel.revealed = true;
+        }
+    }
+}
+
+fn toggle_world_report_panel(
+    controls: Controls,
+    mut panels: Query<(&mut Visibility, &WorldReportPanel)>,
+) {
+    if !controls.just_pressed(Action::ToggleWorldReport) {
+        return;
+    }
+    for (mut visibility, panel) in panels.iter_mut() {
+        if panel.revealed {
+            *visibility = match *visibility {
+                Visibility::Hidden => Visibility::Visible,
+                _ => Visibility::Hidden,
+            };
+        }
+    }
+}